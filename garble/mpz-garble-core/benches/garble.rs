@@ -33,6 +33,15 @@ fn criterion_benchmark(c: &mut Criterion) {
                 enc_gates.push(gate);
             }
 
+            black_box(gen.outputs().unwrap())
+        })
+    });
+    group.bench_function("aes128_parallel", |b| {
+        b.iter(|| {
+            let mut gen = Generator::new(AES128.clone(), encoder.delta(), &inputs).unwrap();
+
+            black_box(gen.generate_parallel());
+
             black_box(gen.outputs().unwrap())
         })
     });
@@ -0,0 +1,566 @@
+use std::sync::Arc;
+
+use mpz_circuits::{Circuit, Gate};
+use mpz_core::{
+    aes::{FixedKeyAes, FIXED_KEY_AES},
+    Block,
+};
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+
+use super::{AuthError, AuthShare, Opening, Party, Triple};
+use crate::encoding::Label;
+
+/// An authenticated garbled AND gate.
+///
+/// The row indexed by the masked bits of the input wires encrypts the MAC of the garbler's share
+/// of the masked output bit, and the output label masked with the garbler's key for the
+/// evaluator's share of the masked output bit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AuthenticatedGate([[Block; 2]; 4]);
+
+/// Returns the pads of a row of an authenticated garbled gate.
+#[inline]
+fn row_pads(cipher: &FixedKeyAes, gid: usize, row: usize, x: &Label, y: &Label) -> [Block; 2] {
+    let input = Block::sigma(x.to_inner()) ^ y.to_inner();
+    [0u128, 1].map(|k| {
+        let tweak = Block::new(((gid as u128) << 3 | (row as u128) << 1 | k).to_be_bytes());
+        cipher.tccr(tweak, input)
+    })
+}
+
+/// Returns the label encoding `bit`, given the label encoding `0`.
+#[inline]
+pub fn encode_bit(low: &Label, bit: bool, delta: &Block) -> Label {
+    if bit {
+        Label::new(low.to_inner() ^ *delta)
+    } else {
+        *low
+    }
+}
+
+/// Returns the bit encoded by an active label, given the label encoding `0`.
+pub fn decode_label(low: &Label, active: &Label, delta: &Block) -> Result<bool, AuthError> {
+    if active == low {
+        Ok(false)
+    } else if active.to_inner() == low.to_inner() ^ *delta {
+        Ok(true)
+    } else {
+        Err(AuthError::InvalidLabel)
+    }
+}
+
+/// Authenticated shares of the masks of the wires of a circuit.
+///
+/// The masks of the input wires and of the outputs of AND gates are random, while the masks of
+/// the other wires follow from the gates. Before the circuit can be garbled, the shares of the
+/// products of the masks of the inputs of each AND gate must be computed from authenticated
+/// triples, see [`WireMasks::openings`].
+pub struct WireMasks {
+    circ: Arc<Circuit>,
+    party: Party,
+    delta: Block,
+    masks: Vec<AuthShare>,
+}
+
+opaque_debug::implement!(WireMasks);
+
+impl WireMasks {
+    /// Creates the masks of the wires of a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit.
+    /// * `party` - The party holding the shares.
+    /// * `delta` - The global key of this party.
+    /// * `inputs` - The shares of the masks of the input wires.
+    /// * `ands` - The shares of the masks of the outputs of the AND gates.
+    pub fn new(
+        circ: Arc<Circuit>,
+        party: Party,
+        delta: Block,
+        inputs: &[AuthShare],
+        ands: &[AuthShare],
+    ) -> Result<Self, AuthError> {
+        let input_count: usize = circ.inputs().iter().map(|input| input.len()).sum();
+        if inputs.len() != input_count {
+            return Err(AuthError::CountMismatch {
+                expected: input_count,
+                actual: inputs.len(),
+            });
+        }
+
+        if ands.len() != circ.and_count() {
+            return Err(AuthError::CountMismatch {
+                expected: circ.and_count(),
+                actual: ands.len(),
+            });
+        }
+
+        let mut masks = vec![AuthShare::default(); circ.feed_count()];
+        for (node, mask) in circ
+            .inputs()
+            .iter()
+            .flat_map(|input| input.iter())
+            .zip(inputs)
+        {
+            masks[node.id()] = *mask;
+        }
+
+        let mut ands = ands.iter();
+        for gate in circ.gates() {
+            match gate {
+                Gate::Xor { x, y, z } => masks[z.id()] = masks[x.id()] ^ masks[y.id()],
+                Gate::And { z, .. } => {
+                    masks[z.id()] = *ands.next().expect("count was checked");
+                }
+                Gate::Inv { x, z } => {
+                    masks[z.id()] = masks[x.id()].xor_constant(true, party, &delta)
+                }
+            }
+        }
+
+        Ok(Self {
+            circ,
+            party,
+            delta,
+            masks,
+        })
+    }
+
+    /// Returns the shares of the masks of the output wires.
+    pub fn output_masks(&self) -> Vec<Vec<AuthShare>> {
+        output_masks(&self.circ, &self.masks)
+    }
+
+    /// Returns the openings to send to the other party, of the masks of the inputs of each AND
+    /// gate XORed with the `x` and `y` of a triple.
+    pub fn openings(&self, triples: &[Triple]) -> Result<Vec<Opening>, AuthError> {
+        self.check_triples(triples)?;
+
+        Ok(self
+            .and_inputs()
+            .zip(triples)
+            .flat_map(|((x, y), triple)| {
+                [
+                    (self.masks[x] ^ triple.x).opening(),
+                    (self.masks[y] ^ triple.y).opening(),
+                ]
+            })
+            .collect())
+    }
+
+    /// Computes the shares of the products of the masks of the inputs of each AND gate, using
+    /// the openings of the other party.
+    pub fn finish(
+        self,
+        triples: &[Triple],
+        openings: &[Opening],
+    ) -> Result<PreprocessedCircuit, AuthError> {
+        self.check_triples(triples)?;
+        if openings.len() != 2 * triples.len() {
+            return Err(AuthError::CountMismatch {
+                expected: 2 * triples.len(),
+                actual: openings.len(),
+            });
+        }
+
+        let products = self
+            .and_inputs()
+            .zip(triples)
+            .zip(openings.chunks_exact(2))
+            .map(|(((x, y), triple), openings)| {
+                let d = (self.masks[x] ^ triple.x).open(&openings[0], &self.delta)?;
+                let e = (self.masks[y] ^ triple.y).open(&openings[1], &self.delta)?;
+
+                // (x ^ d) & (y ^ e) = z ^ d & y ^ e & x ^ d & e
+                let product = triple.z ^ triple.y.and_constant(d) ^ triple.x.and_constant(e);
+
+                Ok(product.xor_constant(d & e, self.party, &self.delta))
+            })
+            .collect::<Result<Vec<_>, AuthError>>()?;
+
+        Ok(PreprocessedCircuit {
+            circ: self.circ,
+            party: self.party,
+            delta: self.delta,
+            masks: self.masks,
+            products,
+        })
+    }
+
+    fn check_triples(&self, triples: &[Triple]) -> Result<(), AuthError> {
+        if triples.len() != self.circ.and_count() {
+            return Err(AuthError::CountMismatch {
+                expected: self.circ.and_count(),
+                actual: triples.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn and_inputs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.circ.gates().iter().filter_map(|gate| match gate {
+            Gate::And { x, y, .. } => Some((x.id(), y.id())),
+            _ => None,
+        })
+    }
+}
+
+/// A circuit whose wire masks have been preprocessed, ready to be garbled by the garbler or
+/// evaluated by the evaluator.
+pub struct PreprocessedCircuit {
+    circ: Arc<Circuit>,
+    party: Party,
+    delta: Block,
+    masks: Vec<AuthShare>,
+    /// Shares of the products of the masks of the inputs of each AND gate.
+    products: Vec<AuthShare>,
+}
+
+opaque_debug::implement!(PreprocessedCircuit);
+
+impl PreprocessedCircuit {
+    /// Returns the circuit.
+    pub fn circuit(&self) -> &Arc<Circuit> {
+        &self.circ
+    }
+
+    /// Returns the shares of the masks of the output wires.
+    pub fn output_masks(&self) -> Vec<Vec<AuthShare>> {
+        output_masks(&self.circ, &self.masks)
+    }
+
+    /// Returns the share of the masked output bit of an AND gate, for the given masked input
+    /// bits.
+    #[inline]
+    fn row_share(&self, gid: usize, x: usize, y: usize, z: usize, a: bool, b: bool) -> AuthShare {
+        (self.products[gid]
+            ^ self.masks[z]
+            ^ self.masks[y].and_constant(a)
+            ^ self.masks[x].and_constant(b))
+        .xor_constant(a & b, self.party, &self.delta)
+    }
+
+    /// Garbles the circuit, returning the authenticated garbled gates and the labels encoding
+    /// `0` of the output wires.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The labels encoding `0` of the input wires.
+    /// * `rng` - The RNG used to sample the labels of the outputs of AND gates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this party is not the garbler.
+    pub fn garble<R: Rng + CryptoRng + ?Sized>(
+        &self,
+        inputs: &[Label],
+        rng: &mut R,
+    ) -> Result<(Vec<AuthenticatedGate>, Vec<Vec<Label>>), AuthError> {
+        assert_eq!(self.party, Party::Garbler, "only the garbler garbles");
+
+        let mut labels = self.input_labels(inputs)?;
+        let cipher = &(*FIXED_KEY_AES);
+        let delta = self.delta;
+
+        let mut gates = Vec::with_capacity(self.circ.and_count());
+        for gate in self.circ.gates() {
+            match gate {
+                Gate::Xor { x, y, z } => {
+                    labels[z.id()] = labels[x.id()] ^ labels[y.id()];
+                }
+                Gate::Inv { x, z } => labels[z.id()] = labels[x.id()],
+                Gate::And { x, y, z } => {
+                    let gid = gates.len();
+                    let low = Label::new(Block::random(rng));
+                    labels[z.id()] = low;
+
+                    let rows = std::array::from_fn(|row| {
+                        let (a, b) = (row & 2 != 0, row & 1 != 0);
+                        let share = self.row_share(gid, x.id(), y.id(), z.id(), a, b);
+                        let pads = row_pads(
+                            cipher,
+                            gid,
+                            row,
+                            &encode_bit(&labels[x.id()], a, &delta),
+                            &encode_bit(&labels[y.id()], b, &delta),
+                        );
+
+                        [
+                            share.mac() ^ pads[0],
+                            encode_bit(&low, share.bit(), &delta).to_inner()
+                                ^ share.key()
+                                ^ pads[1],
+                        ]
+                    });
+
+                    gates.push(AuthenticatedGate(rows));
+                }
+            }
+        }
+
+        Ok((gates, output_labels(&self.circ, &labels)))
+    }
+
+    /// Evaluates the garbled circuit, returning the masked bits and the active labels of the
+    /// output wires.
+    ///
+    /// # Arguments
+    ///
+    /// * `masked` - The masked bits of the input wires.
+    /// * `inputs` - The active labels of the input wires.
+    /// * `gates` - The authenticated garbled gates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this party is not the evaluator.
+    #[allow(clippy::type_complexity)]
+    pub fn evaluate(
+        &self,
+        masked: &[bool],
+        inputs: &[Label],
+        gates: &[AuthenticatedGate],
+    ) -> Result<(Vec<Vec<bool>>, Vec<Vec<Label>>), AuthError> {
+        assert_eq!(self.party, Party::Evaluator, "only the evaluator evaluates");
+
+        if masked.len() != inputs.len() {
+            return Err(AuthError::CountMismatch {
+                expected: inputs.len(),
+                actual: masked.len(),
+            });
+        }
+
+        if gates.len() != self.circ.and_count() {
+            return Err(AuthError::CountMismatch {
+                expected: self.circ.and_count(),
+                actual: gates.len(),
+            });
+        }
+
+        let mut labels = self.input_labels(inputs)?;
+        let mut bits = vec![false; self.circ.feed_count()];
+        for (node, bit) in self
+            .circ
+            .inputs()
+            .iter()
+            .flat_map(|input| input.iter())
+            .zip(masked)
+        {
+            bits[node.id()] = *bit;
+        }
+
+        let cipher = &(*FIXED_KEY_AES);
+        let mut gates = gates.iter().enumerate();
+        for gate in self.circ.gates() {
+            match gate {
+                Gate::Xor { x, y, z } => {
+                    labels[z.id()] = labels[x.id()] ^ labels[y.id()];
+                    bits[z.id()] = bits[x.id()] ^ bits[y.id()];
+                }
+                Gate::Inv { x, z } => {
+                    labels[z.id()] = labels[x.id()];
+                    bits[z.id()] = bits[x.id()];
+                }
+                Gate::And { x, y, z } => {
+                    let (gid, gate) = gates.next().expect("count was checked");
+                    let (a, b) = (bits[x.id()], bits[y.id()]);
+                    let row = (a as usize) << 1 | b as usize;
+
+                    let share = self.row_share(gid, x.id(), y.id(), z.id(), a, b);
+                    let pads = row_pads(cipher, gid, row, &labels[x.id()], &labels[y.id()]);
+
+                    let mac = gate.0[row][0] ^ pads[0];
+                    let garbler_bit = if mac == share.key() {
+                        false
+                    } else if mac == share.key() ^ self.delta {
+                        true
+                    } else {
+                        return Err(AuthError::InvalidGate(gid));
+                    };
+
+                    labels[z.id()] = Label::new(gate.0[row][1] ^ pads[1] ^ share.mac());
+                    bits[z.id()] = garbler_bit ^ share.bit();
+                }
+            }
+        }
+
+        let masked = self
+            .circ
+            .outputs()
+            .iter()
+            .map(|output| output.iter().map(|node| bits[node.id()]).collect())
+            .collect();
+
+        Ok((masked, output_labels(&self.circ, &labels)))
+    }
+
+    fn input_labels(&self, inputs: &[Label]) -> Result<Vec<Label>, AuthError> {
+        let input_count: usize = self.circ.inputs().iter().map(|input| input.len()).sum();
+        if inputs.len() != input_count {
+            return Err(AuthError::CountMismatch {
+                expected: input_count,
+                actual: inputs.len(),
+            });
+        }
+
+        let mut labels = vec![Label::new(Block::ZERO); self.circ.feed_count()];
+        for (node, label) in self
+            .circ
+            .inputs()
+            .iter()
+            .flat_map(|input| input.iter())
+            .zip(inputs)
+        {
+            labels[node.id()] = *label;
+        }
+
+        Ok(labels)
+    }
+}
+
+fn output_masks(circ: &Circuit, masks: &[AuthShare]) -> Vec<Vec<AuthShare>> {
+    circ.outputs()
+        .iter()
+        .map(|output| output.iter().map(|node| masks[node.id()]).collect())
+        .collect()
+}
+
+fn output_labels(circ: &Circuit, labels: &[Label]) -> Vec<Vec<Label>> {
+    circ.outputs()
+        .iter()
+        .map(|output| output.iter().map(|node| labels[node.id()]).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aes::{
+        cipher::{BlockEncrypt, KeyInit},
+        Aes128,
+    };
+    use itybity::{FromBitIterator, IntoBitIterator};
+    use mpz_circuits::circuits::AES128;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use crate::authenticated::{
+        share::random_shares,
+        triple::{leaky_triples, run},
+    };
+
+    struct Setup {
+        delta_g: Block,
+        delta_e: Block,
+        gen: PreprocessedCircuit,
+        ev: PreprocessedCircuit,
+        inputs_g: Vec<AuthShare>,
+        inputs_e: Vec<AuthShare>,
+    }
+
+    fn setup(rng: &mut ChaCha12Rng) -> Setup {
+        let circ = AES128.clone();
+        let (delta_g, delta_e) = (Block::random(rng), Block::random(rng));
+
+        let (inputs_g, inputs_e) = random_shares(rng, 256, &delta_g, &delta_e);
+        let (ands_g, ands_e) = random_shares(rng, circ.and_count(), &delta_g, &delta_e);
+        let (gen, ev) = leaky_triples(circ.and_count(), delta_g, delta_e);
+        let (triples_g, triples_e) = run(gen, ev).unwrap();
+
+        let gen =
+            WireMasks::new(circ.clone(), Party::Garbler, delta_g, &inputs_g, &ands_g).unwrap();
+        let ev = WireMasks::new(circ, Party::Evaluator, delta_e, &inputs_e, &ands_e).unwrap();
+
+        let openings_g = gen.openings(&triples_g).unwrap();
+        let openings_e = ev.openings(&triples_e).unwrap();
+
+        Setup {
+            delta_g,
+            delta_e,
+            gen: gen.finish(&triples_g, &openings_e).unwrap(),
+            ev: ev.finish(&triples_e, &openings_g).unwrap(),
+            inputs_g,
+            inputs_e,
+        }
+    }
+
+    #[test]
+    fn test_authenticated_garble() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let setup = setup(&mut rng);
+
+        let key = [69u8; 16];
+        let msg = [42u8; 16];
+        let values: Vec<bool> = key.into_iter_lsb0().chain(msg.into_iter_lsb0()).collect();
+
+        // The evaluator learns the masked input bits, and the corresponding active labels.
+        let low: Vec<Label> = (0..256)
+            .map(|_| Label::new(Block::random(&mut rng)))
+            .collect();
+        let masked: Vec<bool> = values
+            .iter()
+            .zip(setup.inputs_g.iter().zip(&setup.inputs_e))
+            .map(|(value, (g, e))| value ^ g.bit() ^ e.bit())
+            .collect();
+        let active: Vec<Label> = low
+            .iter()
+            .zip(&masked)
+            .map(|(low, bit)| encode_bit(low, *bit, &setup.delta_g))
+            .collect();
+
+        let (gates, output_low) = setup.gen.garble(&low, &mut rng).unwrap();
+        let (output_masked, output_active) = setup.ev.evaluate(&masked, &active, &gates).unwrap();
+
+        let output_g = &setup.gen.output_masks()[0];
+        let output_e = &setup.ev.output_masks()[0];
+        let ciphertext = <[u8; 16]>::from_lsb0_iter(
+            output_masked[0]
+                .iter()
+                .zip(output_g.iter().zip(output_e))
+                .zip(output_low[0].iter().zip(&output_active[0]))
+                .map(|((masked, (g, e)), (low, active))| {
+                    assert_eq!(decode_label(low, active, &setup.delta_g).unwrap(), *masked);
+                    masked ^ g.bit() ^ e.bit()
+                }),
+        );
+
+        let mut expected = msg.into();
+        Aes128::new_from_slice(&key)
+            .unwrap()
+            .encrypt_block(&mut expected);
+
+        assert_eq!(ciphertext.to_vec(), expected.to_vec());
+        // The MACs of the shares of the output masks are valid.
+        for (g, e) in output_g.iter().zip(output_e) {
+            g.open(&e.opening(), &setup.delta_g).unwrap();
+            e.open(&g.opening(), &setup.delta_e).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_authenticated_garble_cheat() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let setup = setup(&mut rng);
+
+        let low: Vec<Label> = (0..256)
+            .map(|_| Label::new(Block::random(&mut rng)))
+            .collect();
+        let masked = vec![false; 256];
+
+        let (mut gates, _) = setup.gen.garble(&low, &mut rng).unwrap();
+        // The garbler tampers with the MAC of its share of the masked output bit in every row of
+        // a gate, without knowing the global key of the evaluator.
+        let error = Block::random(&mut rng);
+        for row in gates[3].0.iter_mut() {
+            row[0] ^= error;
+        }
+
+        assert!(matches!(
+            setup.ev.evaluate(&masked, &low, &gates),
+            Err(AuthError::InvalidGate(3))
+        ));
+    }
+}
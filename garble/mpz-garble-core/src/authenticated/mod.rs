@@ -0,0 +1,40 @@
+//! Authenticated garbling, secure against malicious adversaries.
+//!
+//! This module implements the authenticated garbling protocol from [Authenticated Garbling and
+//! Efficient Maliciously Secure Two-Party Computation \[WRK17\]](https://eprint.iacr.org/2017/030).
+//!
+//! Both parties hold [authenticated shares](AuthShare) of the masks of every wire of the circuit,
+//! which are derived from random correlated OTs in both directions. The masks of the outputs of
+//! AND gates are computed using [authenticated AND triples](Triple), which are generated as
+//! [leaky triples](LeakyAnd) and then combined into [buckets](Buckets).
+//!
+//! The garbler then sends an [authenticated garbled gate](AuthenticatedGate) for every AND gate,
+//! each row of which encrypts the garbler's share of the masked output bit together with its
+//! MAC. This allows the evaluator to detect a garbler which garbled the circuit incorrectly,
+//! without revealing anything about the values of the wires.
+
+mod garble;
+mod share;
+mod triple;
+
+pub use garble::{decode_label, encode_bit, AuthenticatedGate, PreprocessedCircuit, WireMasks};
+pub use share::{AuthShare, Opening, Party};
+pub use triple::{
+    bucket_size, Buckets, Correction, LeakyAnd, LeakyAndCheck, LeakyAndDerandomize, Triple,
+};
+
+/// Errors that can occur during authenticated garbling.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum AuthError {
+    #[error("invalid MAC of an authenticated share")]
+    InvalidMac,
+    #[error("check of leaky triples failed")]
+    InvalidTripleCheck,
+    #[error("invalid authenticated gate: {0}")]
+    InvalidGate(usize),
+    #[error("invalid label")]
+    InvalidLabel,
+    #[error("count mismatch: expected {expected}, got {actual}")]
+    CountMismatch { expected: usize, actual: usize },
+}
@@ -0,0 +1,250 @@
+use std::ops::BitXor;
+
+use mpz_core::Block;
+use serde::{Deserialize, Serialize};
+
+use super::AuthError;
+
+/// A party in authenticated garbling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Party {
+    /// The party which garbles the circuits.
+    Garbler,
+    /// The party which evaluates the circuits.
+    Evaluator,
+}
+
+/// An authenticated XOR-share of a bit.
+///
+/// A bit `x = x_g ^ x_e` is shared between the garbler and the evaluator. Each party holds its
+/// share of the bit, a MAC of its share under the global key of the other party, and a key for
+/// the share of the other party under its own global key, such that `mac = key ^ bit * delta`.
+///
+/// Authenticated shares are linear: the XOR of two shares is a share of the XOR of the bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AuthShare {
+    bit: bool,
+    mac: Block,
+    key: Block,
+}
+
+impl AuthShare {
+    /// Creates a new authenticated share.
+    ///
+    /// # Arguments
+    ///
+    /// * `bit` - The share of this party.
+    /// * `mac` - The MAC of the share of this party.
+    /// * `key` - The key for the share of the other party.
+    pub fn new(bit: bool, mac: Block, key: Block) -> Self {
+        Self { bit, mac, key }
+    }
+
+    /// Creates authenticated shares from the outputs of random correlated OTs.
+    ///
+    /// The shares of this party are the choices of the random COTs it received, which
+    /// authenticate them under the global key of the other party. The keys for the shares of
+    /// the other party are the `0`-bit messages of the random COTs this party sent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths of the arguments differ.
+    pub fn from_cots(choices: &[bool], macs: &[Block], keys: &[Block]) -> Vec<Self> {
+        assert_eq!(choices.len(), macs.len());
+        assert_eq!(choices.len(), keys.len());
+
+        choices
+            .iter()
+            .zip(macs)
+            .zip(keys)
+            .map(|((bit, mac), key)| Self::new(*bit, *mac, *key))
+            .collect()
+    }
+
+    /// Returns the share of this party.
+    pub fn bit(&self) -> bool {
+        self.bit
+    }
+
+    /// Returns the MAC of the share of this party.
+    pub fn mac(&self) -> Block {
+        self.mac
+    }
+
+    /// Returns the key for the share of the other party.
+    pub fn key(&self) -> Block {
+        self.key
+    }
+
+    /// Returns the opening of the share of this party, to be sent to the other party.
+    pub fn opening(&self) -> Opening {
+        Opening {
+            bit: self.bit,
+            mac: self.mac,
+        }
+    }
+
+    /// Verifies the opening of the share of the other party, and returns the shared bit.
+    ///
+    /// # Arguments
+    ///
+    /// * `opening` - The opening of the share of the other party.
+    /// * `delta` - The global key of this party.
+    pub fn open(&self, opening: &Opening, delta: &Block) -> Result<bool, AuthError> {
+        Ok(self.bit ^ self.verify(opening, delta)?)
+    }
+
+    /// Verifies the opening of the share of the other party, and returns its share.
+    pub(crate) fn verify(&self, opening: &Opening, delta: &Block) -> Result<bool, AuthError> {
+        let expected = if opening.bit {
+            self.key ^ *delta
+        } else {
+            self.key
+        };
+
+        if opening.mac != expected {
+            return Err(AuthError::InvalidMac);
+        }
+
+        Ok(opening.bit)
+    }
+
+    /// Returns the share of the bit XORed with a public constant.
+    ///
+    /// The garbler flips its share, while the evaluator adjusts its key accordingly.
+    ///
+    /// # Arguments
+    ///
+    /// * `constant` - The public constant.
+    /// * `party` - The party holding this share.
+    /// * `delta` - The global key of this party.
+    pub(crate) fn xor_constant(self, constant: bool, party: Party, delta: &Block) -> Self {
+        if !constant {
+            return self;
+        }
+
+        match party {
+            Party::Garbler => Self {
+                bit: !self.bit,
+                ..self
+            },
+            Party::Evaluator => Self {
+                key: self.key ^ *delta,
+                ..self
+            },
+        }
+    }
+
+    /// Returns the share of the bit ANDed with a public constant.
+    pub(crate) fn and_constant(self, constant: bool) -> Self {
+        if constant {
+            self
+        } else {
+            Self::default()
+        }
+    }
+}
+
+impl BitXor for AuthShare {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self {
+            bit: self.bit ^ rhs.bit,
+            mac: self.mac ^ rhs.mac,
+            key: self.key ^ rhs.key,
+        }
+    }
+}
+
+/// The opening of an authenticated share, ie. the share and its MAC.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Opening {
+    bit: bool,
+    mac: Block,
+}
+
+/// Returns authenticated shares of random bits, as produced by ideal random COTs.
+#[cfg(test)]
+pub(crate) fn random_shares<R: rand::Rng + rand::CryptoRng>(
+    rng: &mut R,
+    count: usize,
+    delta_garbler: &Block,
+    delta_evaluator: &Block,
+) -> (Vec<AuthShare>, Vec<AuthShare>) {
+    (0..count)
+        .map(|_| {
+            let bit_g: bool = rng.gen();
+            let bit_e: bool = rng.gen();
+            let key_g = Block::random(rng);
+            let key_e = Block::random(rng);
+            let mac_g = if bit_g {
+                key_e ^ *delta_evaluator
+            } else {
+                key_e
+            };
+            let mac_e = if bit_e { key_g ^ *delta_garbler } else { key_g };
+
+            (
+                AuthShare::new(bit_g, mac_g, key_g),
+                AuthShare::new(bit_e, mac_e, key_e),
+            )
+        })
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    #[test]
+    fn test_auth_share_linear() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (delta_g, delta_e) = (Block::random(&mut rng), Block::random(&mut rng));
+        let (g, e) = random_shares(&mut rng, 3, &delta_g, &delta_e);
+
+        for constant in [false, true] {
+            let share_g = (g[0] ^ g[1] ^ g[2].and_constant(constant)).xor_constant(
+                true,
+                Party::Garbler,
+                &delta_g,
+            );
+            let share_e = (e[0] ^ e[1] ^ e[2].and_constant(constant)).xor_constant(
+                true,
+                Party::Evaluator,
+                &delta_e,
+            );
+
+            let bits: Vec<bool> = g.iter().zip(&e).map(|(g, e)| g.bit ^ e.bit).collect();
+            let expected = bits[0] ^ bits[1] ^ (bits[2] & constant) ^ true;
+
+            assert_eq!(
+                share_g.open(&share_e.opening(), &delta_g).unwrap(),
+                expected
+            );
+            assert_eq!(
+                share_e.open(&share_g.opening(), &delta_e).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_auth_share_invalid_mac() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (delta_g, delta_e) = (Block::random(&mut rng), Block::random(&mut rng));
+        let (g, e) = random_shares(&mut rng, 1, &delta_g, &delta_e);
+
+        let mut opening = e[0].opening();
+        opening.bit = !opening.bit;
+
+        assert!(matches!(
+            g[0].open(&opening, &delta_g),
+            Err(AuthError::InvalidMac)
+        ));
+    }
+}
@@ -0,0 +1,540 @@
+use mpz_core::{
+    aes::{FixedKeyAes, FIXED_KEY_AES},
+    hash::{Hash, SecureHash},
+    Block,
+};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha12Rng;
+use rand_core::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use super::{AuthError, AuthShare, Opening, Party};
+
+/// Statistical security parameter of the bucketing of leaky triples.
+const STATISTICAL_SECURITY: f64 = 40.0;
+
+/// Domain separator of the tweaks used to generate leaky triples.
+const TRIPLE_DOMAIN: u128 = 1 << 127;
+
+/// Tweak used to compute the cross terms of `z`.
+const HALF_AND: u128 = 0;
+/// Tweak used to compute the cross terms checked by the sender of the correction.
+const CHECK_OWN: u128 = 1;
+/// Tweak used to compute the cross terms checked by the receiver of the correction.
+const CHECK_PEER: u128 = 2;
+
+/// An authenticated AND triple, ie. authenticated shares of bits `x`, `y` and `z = x & y`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triple {
+    pub(crate) x: AuthShare,
+    pub(crate) y: AuthShare,
+    pub(crate) z: AuthShare,
+}
+
+/// The correction sent by a party for a leaky AND triple.
+///
+/// Each correction derandomizes the random COTs which authenticate the `x` share of the other
+/// party, to multiply it with the `y` share of the sender.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Correction {
+    /// Correction used to compute the shares of `z`.
+    bit: bool,
+    /// Correction used to check the triple under the global key of the sender.
+    own: Block,
+    /// Correction used to check the triple under the global key of the receiver.
+    peer: Block,
+}
+
+/// Returns the tweak of the hash of a random COT.
+#[inline]
+fn tweak(sender: Party, index: usize, kind: u128) -> Block {
+    let sender = matches!(sender, Party::Evaluator) as u128;
+    Block::new((TRIPLE_DOMAIN | (index as u128) << 3 | sender << 2 | kind).to_be_bytes())
+}
+
+/// Returns the block if `bit` is set, or zero otherwise.
+#[inline]
+fn select(bit: bool, block: Block) -> Block {
+    if bit {
+        block
+    } else {
+        Block::ZERO
+    }
+}
+
+/// Returns the number of leaky triples combined into each authenticated triple, when
+/// generating `count` triples.
+///
+/// A malicious party which cheats when generating a leaky triple learns the `x` share of the
+/// other party, but is caught with probability 1/2. The bucket size is chosen such that the
+/// probability that every triple of a bucket leaks is at most 2^-40.
+pub fn bucket_size(count: usize) -> usize {
+    fn log2_binomial(n: f64, k: usize) -> f64 {
+        (0..k)
+            .map(|i| ((n - i as f64) / (k - i) as f64).log2())
+            .sum()
+    }
+
+    let count = count.max(1) as f64;
+    (2..)
+        .find(|&size| {
+            let total = count * size as f64;
+            // The probability is maximized when the adversary corrupts at most `2 * size`
+            // triples, as each additional corrupted triple halves it.
+            (size..=2 * size)
+                .map(|corrupted| corrupted as f64)
+                .filter(|&corrupted| corrupted <= total)
+                .all(|corrupted| {
+                    -corrupted + count.log2() + log2_binomial(corrupted, size)
+                        - log2_binomial(total, size)
+                        <= -STATISTICAL_SECURITY
+                })
+        })
+        .expect("bucket size should exist")
+}
+
+/// Generates leaky authenticated AND triples.
+///
+/// The triples are generated in 3 rounds, where both parties exchange their corrections, then
+/// the bits derandomizing their shares of `z`, and finally a digest to check the triples. A
+/// malicious party can guess the `x` share of the other party in a triple, which is why they
+/// must be combined with [`Buckets`].
+pub struct LeakyAnd {
+    party: Party,
+    delta: Block,
+    cipher: &'static FixedKeyAes,
+    x: Vec<AuthShare>,
+    y: Vec<AuthShare>,
+    r: Vec<AuthShare>,
+    corrections: Vec<Correction>,
+    /// Shares of the products of the `x` shares of the other party and the `y` shares of this
+    /// party.
+    products: Vec<(bool, Block, Block)>,
+}
+
+opaque_debug::implement!(LeakyAnd);
+
+impl LeakyAnd {
+    /// Creates a new generator of leaky triples.
+    ///
+    /// # Arguments
+    ///
+    /// * `party` - The party generating the triples.
+    /// * `delta` - The global key of this party.
+    /// * `x` - Random authenticated shares of the `x` bits.
+    /// * `y` - Random authenticated shares of the `y` bits.
+    /// * `r` - Random authenticated shares, used to authenticate the `z` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of shares differ.
+    pub fn new(
+        party: Party,
+        delta: Block,
+        x: Vec<AuthShare>,
+        y: Vec<AuthShare>,
+        r: Vec<AuthShare>,
+    ) -> Self {
+        assert_eq!(x.len(), y.len());
+        assert_eq!(x.len(), r.len());
+
+        let cipher = &(*FIXED_KEY_AES);
+        let (corrections, products) = x
+            .iter()
+            .zip(&y)
+            .enumerate()
+            .map(|(i, (x, y))| {
+                let key = x.key();
+                let [hash_bit, hash_own, hash_peer] =
+                    [HALF_AND, CHECK_OWN, CHECK_PEER].map(|kind| {
+                        let tweak = tweak(party, i, kind);
+                        (cipher.tccr(tweak, key), cipher.tccr(tweak, key ^ delta))
+                    });
+
+                let correction = Correction {
+                    bit: (hash_bit.0.lsb() ^ hash_bit.1.lsb() == 1) ^ y.bit(),
+                    own: hash_own.0 ^ hash_own.1 ^ select(y.bit(), delta) ^ y.key(),
+                    peer: hash_peer.0 ^ hash_peer.1 ^ y.mac(),
+                };
+
+                (correction, (hash_bit.0.lsb() == 1, hash_own.0, hash_peer.0))
+            })
+            .unzip();
+
+        Self {
+            party,
+            delta,
+            cipher,
+            x,
+            y,
+            r,
+            corrections,
+            products,
+        }
+    }
+
+    /// Returns the corrections to send to the other party.
+    pub fn corrections(&self) -> &[Correction] {
+        &self.corrections
+    }
+
+    /// Receives the corrections of the other party, returning the bits to send to the other
+    /// party which derandomize the shares of `z` of this party.
+    pub fn receive_corrections(
+        self,
+        corrections: &[Correction],
+    ) -> Result<(LeakyAndDerandomize, Vec<bool>), AuthError> {
+        if corrections.len() != self.x.len() {
+            return Err(AuthError::CountMismatch {
+                expected: self.x.len(),
+                actual: corrections.len(),
+            });
+        }
+
+        let peer = match self.party {
+            Party::Garbler => Party::Evaluator,
+            Party::Evaluator => Party::Garbler,
+        };
+
+        let mut z = Vec::with_capacity(self.x.len());
+        let mut check_own = Vec::with_capacity(self.x.len());
+        let mut check_peer = Vec::with_capacity(self.x.len());
+        for (i, (((x, y), correction), &(product_bit, product_own, product_peer))) in self
+            .x
+            .iter()
+            .zip(&self.y)
+            .zip(corrections)
+            .zip(&self.products)
+            .enumerate()
+        {
+            let mac = x.mac();
+            let [hash_bit, hash_own, hash_peer] = [HALF_AND, CHECK_OWN, CHECK_PEER]
+                .map(|kind| self.cipher.tccr(tweak(peer, i, kind), mac));
+
+            // Shares of the products of the `x` share of this party with the `y` share of
+            // the other party.
+            let bit = (hash_bit.lsb() == 1) ^ (x.bit() & correction.bit);
+            let peer_own = hash_own ^ select(x.bit(), correction.own);
+            let peer_peer = hash_peer ^ select(x.bit(), correction.peer);
+
+            z.push((x.bit() & y.bit()) ^ product_bit ^ bit);
+            check_own.push(
+                select(x.bit(), select(y.bit(), self.delta) ^ y.key()) ^ product_own ^ peer_peer,
+            );
+            check_peer.push(select(x.bit(), y.mac()) ^ peer_own ^ product_peer);
+        }
+
+        let derandomize = z
+            .iter()
+            .zip(&self.r)
+            .map(|(z, r)| z ^ r.bit())
+            .collect::<Vec<_>>();
+
+        Ok((
+            LeakyAndDerandomize {
+                delta: self.delta,
+                x: self.x,
+                y: self.y,
+                r: self.r,
+                derandomize: derandomize.clone(),
+                check_own,
+                check_peer,
+            },
+            derandomize,
+        ))
+    }
+}
+
+/// Leaky triples waiting for the bits derandomizing the shares of `z` of the other party.
+pub struct LeakyAndDerandomize {
+    delta: Block,
+    x: Vec<AuthShare>,
+    y: Vec<AuthShare>,
+    r: Vec<AuthShare>,
+    derandomize: Vec<bool>,
+    check_own: Vec<Block>,
+    check_peer: Vec<Block>,
+}
+
+opaque_debug::implement!(LeakyAndDerandomize);
+
+impl LeakyAndDerandomize {
+    /// Receives the bits derandomizing the shares of `z` of the other party, returning the
+    /// digest to send to the other party to check the triples.
+    pub fn receive_derandomize(
+        self,
+        derandomize: &[bool],
+    ) -> Result<(LeakyAndCheck, Hash), AuthError> {
+        if derandomize.len() != self.r.len() {
+            return Err(AuthError::CountMismatch {
+                expected: self.r.len(),
+                actual: derandomize.len(),
+            });
+        }
+
+        let z = self
+            .r
+            .iter()
+            .zip(&self.derandomize)
+            .zip(derandomize)
+            .map(|((r, own), peer)| {
+                AuthShare::new(r.bit() ^ own, r.mac(), r.key() ^ select(*peer, self.delta))
+            })
+            .collect::<Vec<_>>();
+
+        // Both parties hold shares of `(x & y ^ z) * delta` under the global key of each party,
+        // which must be zero.
+        let check_own = self
+            .check_own
+            .iter()
+            .zip(&z)
+            .map(|(check, z)| *check ^ select(z.bit(), self.delta) ^ z.key())
+            .collect::<Vec<_>>();
+        let check_peer = self
+            .check_peer
+            .iter()
+            .zip(&z)
+            .map(|(check, z)| *check ^ z.mac())
+            .collect::<Vec<_>>();
+
+        let triples = self
+            .x
+            .into_iter()
+            .zip(self.y)
+            .zip(z)
+            .map(|((x, y), z)| Triple { x, y, z })
+            .collect();
+
+        Ok((
+            LeakyAndCheck {
+                triples,
+                expected: check_own.hash(),
+            },
+            check_peer.hash(),
+        ))
+    }
+}
+
+/// Leaky triples waiting for the digest of the other party.
+pub struct LeakyAndCheck {
+    triples: Vec<Triple>,
+    expected: Hash,
+}
+
+opaque_debug::implement!(LeakyAndCheck);
+
+impl LeakyAndCheck {
+    /// Checks the digest of the other party, returning the leaky triples.
+    pub fn check(self, digest: Hash) -> Result<Vec<Triple>, AuthError> {
+        if digest != self.expected {
+            return Err(AuthError::InvalidTripleCheck);
+        }
+
+        Ok(self.triples)
+    }
+}
+
+/// Combines leaky triples into authenticated triples.
+///
+/// The leaky triples are randomly permuted with a seed sampled jointly by the parties after
+/// the triples are generated, and split into buckets of [`bucket_size`] triples. The triples of
+/// a bucket are combined into a triple whose `x` is the XOR of their `x`, so that it does not
+/// leak unless every triple of the bucket leaks.
+pub struct Buckets {
+    delta: Block,
+    size: usize,
+    triples: Vec<Triple>,
+}
+
+opaque_debug::implement!(Buckets);
+
+impl Buckets {
+    /// Creates new buckets.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The global key of this party.
+    /// * `triples` - The leaky triples.
+    /// * `size` - The size of a bucket.
+    /// * `seed` - The seed used to permute the triples, sampled jointly by the parties.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of triples is not a multiple of the size of a bucket.
+    pub fn new(delta: Block, mut triples: Vec<Triple>, size: usize, seed: [u8; 32]) -> Self {
+        assert!(size > 0);
+        assert_eq!(triples.len() % size, 0);
+
+        triples.shuffle(&mut ChaCha12Rng::from_seed(seed));
+
+        Self {
+            delta,
+            size,
+            triples,
+        }
+    }
+
+    /// Returns the openings to send to the other party, of the XOR of the `y` of the first
+    /// triple of each bucket with the `y` of the other triples.
+    pub fn openings(&self) -> Vec<Opening> {
+        self.triples
+            .chunks(self.size)
+            .flat_map(|bucket| {
+                bucket[1..]
+                    .iter()
+                    .map(|triple| (bucket[0].y ^ triple.y).opening())
+            })
+            .collect()
+    }
+
+    /// Combines the buckets using the openings of the other party.
+    pub fn combine(self, openings: &[Opening]) -> Result<Vec<Triple>, AuthError> {
+        let expected = self.triples.len() / self.size * (self.size - 1);
+        if openings.len() != expected {
+            return Err(AuthError::CountMismatch {
+                expected,
+                actual: openings.len(),
+            });
+        }
+
+        let mut openings = openings.iter();
+        self.triples
+            .chunks(self.size)
+            .map(|bucket| {
+                let mut combined = bucket[0];
+                for triple in &bucket[1..] {
+                    let opening = openings.next().expect("count was checked");
+                    let d = (bucket[0].y ^ triple.y).open(opening, &self.delta)?;
+
+                    // x_j & y_0 = x_j & (y_j ^ d) = z_j ^ x_j & d
+                    combined.x = combined.x ^ triple.x;
+                    combined.z = combined.z ^ triple.z ^ triple.x.and_constant(d);
+                }
+
+                Ok(combined)
+            })
+            .collect()
+    }
+}
+
+/// Returns generators of leaky triples for both parties.
+#[cfg(test)]
+pub(crate) fn leaky_triples(count: usize, delta_g: Block, delta_e: Block) -> (LeakyAnd, LeakyAnd) {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    let (g, e) = super::share::random_shares(&mut rng, 3 * count, &delta_g, &delta_e);
+
+    let gen = LeakyAnd::new(
+        Party::Garbler,
+        delta_g,
+        g[..count].to_vec(),
+        g[count..2 * count].to_vec(),
+        g[2 * count..].to_vec(),
+    );
+    let ev = LeakyAnd::new(
+        Party::Evaluator,
+        delta_e,
+        e[..count].to_vec(),
+        e[count..2 * count].to_vec(),
+        e[2 * count..].to_vec(),
+    );
+
+    (gen, ev)
+}
+
+/// Runs the generation of leaky triples between both parties.
+#[cfg(test)]
+pub(crate) fn run(gen: LeakyAnd, ev: LeakyAnd) -> Result<(Vec<Triple>, Vec<Triple>), AuthError> {
+    let gen_corrections = gen.corrections().to_vec();
+    let ev_corrections = ev.corrections().to_vec();
+
+    let (gen, gen_d) = gen.receive_corrections(&ev_corrections)?;
+    let (ev, ev_d) = ev.receive_corrections(&gen_corrections)?;
+
+    let (gen, gen_digest) = gen.receive_derandomize(&ev_d)?;
+    let (ev, ev_digest) = ev.receive_derandomize(&gen_d)?;
+
+    Ok((gen.check(ev_digest)?, ev.check(gen_digest)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(garbler: &AuthShare, evaluator: &AuthShare) -> bool {
+        garbler.bit() ^ evaluator.bit()
+    }
+
+    fn assert_triples(gen: &[Triple], ev: &[Triple], delta_g: &Block, delta_e: &Block) {
+        for (g, e) in gen.iter().zip(ev) {
+            for (g, e) in [(g.x, e.x), (g.y, e.y), (g.z, e.z)] {
+                // MACs are valid.
+                g.open(&e.opening(), delta_g).unwrap();
+                e.open(&g.opening(), delta_e).unwrap();
+            }
+
+            assert_eq!(open(&g.x, &e.x) & open(&g.y, &e.y), open(&g.z, &e.z));
+        }
+    }
+
+    #[test]
+    fn test_bucket_size() {
+        assert_eq!(bucket_size(1), 40);
+        assert_eq!(bucket_size(1000), 5);
+        assert_eq!(bucket_size(6400), 4);
+        assert_eq!(bucket_size(1 << 20), 3);
+    }
+
+    #[test]
+    fn test_leaky_and() {
+        let mut rng = ChaCha12Rng::seed_from_u64(1);
+        let (delta_g, delta_e) = (Block::random(&mut rng), Block::random(&mut rng));
+        let (gen, ev) = leaky_triples(128, delta_g, delta_e);
+
+        let (gen, ev) = run(gen, ev).unwrap();
+
+        assert_eq!(gen.len(), 128);
+        assert_triples(&gen, &ev, &delta_g, &delta_e);
+    }
+
+    #[test]
+    fn test_leaky_and_cheat() {
+        let mut rng = ChaCha12Rng::seed_from_u64(1);
+        let (delta_g, delta_e) = (Block::random(&mut rng), Block::random(&mut rng));
+        let (gen, ev) = leaky_triples(128, delta_g, delta_e);
+
+        // The garbler flips its share of `z` in a triple.
+        let gen_corrections = gen.corrections().to_vec();
+        let ev_corrections = ev.corrections().to_vec();
+        let (gen, mut gen_d) = gen.receive_corrections(&ev_corrections).unwrap();
+        let (ev, ev_d) = ev.receive_corrections(&gen_corrections).unwrap();
+        gen_d[7] = !gen_d[7];
+
+        let (_, gen_digest) = gen.receive_derandomize(&ev_d).unwrap();
+        let (ev, _) = ev.receive_derandomize(&gen_d).unwrap();
+
+        assert!(matches!(
+            ev.check(gen_digest),
+            Err(AuthError::InvalidTripleCheck)
+        ));
+    }
+
+    #[test]
+    fn test_buckets() {
+        let mut rng = ChaCha12Rng::seed_from_u64(2);
+        let (delta_g, delta_e) = (Block::random(&mut rng), Block::random(&mut rng));
+        let (gen, ev) = leaky_triples(4 * 32, delta_g, delta_e);
+        let (gen, ev) = run(gen, ev).unwrap();
+
+        let gen = Buckets::new(delta_g, gen, 4, [3u8; 32]);
+        let ev = Buckets::new(delta_e, ev, 4, [3u8; 32]);
+
+        let gen_openings = gen.openings();
+        let ev_openings = ev.openings();
+
+        let gen = gen.combine(&ev_openings).unwrap();
+        let ev = ev.combine(&gen_openings).unwrap();
+
+        assert_eq!(gen.len(), 32);
+        assert_triples(&gen, &ev, &delta_g, &delta_e);
+    }
+}
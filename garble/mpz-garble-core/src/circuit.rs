@@ -7,10 +7,8 @@ use crate::EncodingCommitment;
 
 /// Encrypted gate truth table
 ///
-/// For the half-gate garbling scheme a truth table will typically have 2 rows, except for in
-/// privacy-free garbling mode where it will be reduced to 1.
-///
-/// We do not yet support privacy-free garbling.
+/// For the half-gate garbling scheme a truth table has 2 rows, see [`PrivacyFreeGate`] for the
+/// single row used in privacy-free garbling mode.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EncryptedGate(#[serde(with = "serde_arrays")] pub(crate) [Block; 2]);
 
@@ -35,6 +33,23 @@ impl Index<usize> for EncryptedGate {
     }
 }
 
+/// Encrypted gate truth table of a privacy-free garbled circuit
+///
+/// Privacy-free garbling is only secure if the evaluator knows the values of all the wires, so
+/// the truth table is reduced to a single row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyFreeGate(pub(crate) Block);
+
+impl PrivacyFreeGate {
+    pub(crate) fn new(inner: Block) -> Self {
+        Self(inner)
+    }
+
+    pub(crate) fn to_bytes(&self) -> [u8; 16] {
+        self.0.to_bytes()
+    }
+}
+
 /// A garbled circuit
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GarbledCircuit {
@@ -105,6 +105,11 @@ impl Encoder for ChaChaEncoder {
             ValueType::U32 => self.encode::<u32>(id).into(),
             ValueType::U64 => self.encode::<u64>(id).into(),
             ValueType::U128 => self.encode::<u128>(id).into(),
+            ValueType::I8 => self.encode::<i8>(id).into(),
+            ValueType::I16 => self.encode::<i16>(id).into(),
+            ValueType::I32 => self.encode::<i32>(id).into(),
+            ValueType::I64 => self.encode::<i64>(id).into(),
+            ValueType::I128 => self.encode::<i128>(id).into(),
             ValueType::Array(_, _) => {
                 let mut rng = self.get_rng(id);
 
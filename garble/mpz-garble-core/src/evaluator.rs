@@ -2,9 +2,16 @@ use std::sync::Arc;
 
 use blake3::Hasher;
 
+cfg_if::cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        use rayon::prelude::*;
+    }
+}
+
 use crate::{
     circuit::EncryptedGate,
     encoding::{state, EncodedValue, Label},
+    generator::tweaks,
 };
 use mpz_circuits::{types::TypeError, Circuit, CircuitError, Gate};
 use mpz_core::{
@@ -23,8 +30,13 @@ pub enum EvaluatorError {
     CircuitError(#[from] CircuitError),
     #[error("evaluator not finished")]
     NotFinished,
+    #[error("invalid number of encrypted gates: expected {0}, got {1}")]
+    InvalidGateCount(usize, usize),
 }
 
+/// Number of AND gates which are evaluated with a single call to the cipher.
+const AND_BATCH: usize = 8;
+
 /// Evaluates half-gate garbled AND gate
 #[inline]
 pub(crate) fn and_gate(
@@ -36,16 +48,57 @@ pub(crate) fn and_gate(
 ) -> Label {
     let x = x.to_inner();
     let y = y.to_inner();
-
-    let s_a = x.lsb();
-    let s_b = y.lsb();
-
-    let j = Block::new((gid as u128).to_be_bytes());
-    let k = Block::new(((gid + 1) as u128).to_be_bytes());
+    let (j, k) = tweaks(gid);
 
     let mut h = [x, y];
     cipher.tccr_many(&[j, k], &mut h);
 
+    half_gates(h, x, y, encrypted_gate)
+}
+
+/// Evaluates a batch of at most `AND_BATCH` half-gate garbled AND gates.
+///
+/// Each gate is given by its active input labels, its encrypted gate and its gate id.
+#[inline]
+fn and_gates(cipher: &FixedKeyAes, gates: &[(Label, Label, &EncryptedGate, usize)]) -> Vec<Label> {
+    if gates.len() < AND_BATCH {
+        return gates
+            .iter()
+            .map(|(x, y, encrypted_gate, gid)| and_gate(cipher, x, y, encrypted_gate, *gid))
+            .collect();
+    }
+
+    let mut tweaks_batch = [Block::ZERO; 2 * AND_BATCH];
+    let mut h = [Block::ZERO; 2 * AND_BATCH];
+    for (i, (x, y, _, gid)) in gates.iter().enumerate() {
+        let (j, k) = tweaks(*gid);
+
+        tweaks_batch[2 * i..2 * i + 2].copy_from_slice(&[j, k]);
+        h[2 * i..2 * i + 2].copy_from_slice(&[x.to_inner(), y.to_inner()]);
+    }
+
+    cipher.tccr_many(&tweaks_batch, &mut h);
+
+    gates
+        .iter()
+        .zip(h.chunks_exact(2))
+        .map(|((x, y, encrypted_gate, _), h)| {
+            half_gates(
+                h.try_into().unwrap(),
+                x.to_inner(),
+                y.to_inner(),
+                encrypted_gate,
+            )
+        })
+        .collect()
+}
+
+/// Evaluates the half-gates from the hashes of the active input labels.
+#[inline]
+fn half_gates(h: [Block; 2], x: Block, y: Block, encrypted_gate: &EncryptedGate) -> Label {
+    let s_a = x.lsb();
+    let s_b = y.lsb();
+
     let [hx, hy] = h;
 
     let w_g = hx ^ (encrypted_gate[0] & Block::SELECT_MASK[s_a]);
@@ -195,6 +248,124 @@ impl Evaluator {
         self.complete = true;
     }
 
+    /// Evaluates all remaining encrypted gates of the circuit.
+    ///
+    /// Rather than evaluating one gate at a time, the AND gates are evaluated layer by layer
+    /// (see [`Circuit::and_layers`]) in batches, which are spread across threads if the `rayon`
+    /// feature is enabled. The result is identical to evaluating the gates with
+    /// [`Evaluator::evaluate`].
+    ///
+    /// If the evaluator has already been partially evaluated, the remaining gates are evaluated
+    /// sequentially.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_gates` - All remaining encrypted gates of the circuit.
+    pub fn evaluate_parallel(
+        &mut self,
+        encrypted_gates: &[EncryptedGate],
+    ) -> Result<(), EvaluatorError> {
+        let remaining = self.circ.and_count() - (self.gid - 1) / 2;
+        if encrypted_gates.len() != remaining {
+            return Err(EvaluatorError::InvalidGateCount(
+                remaining,
+                encrypted_gates.len(),
+            ));
+        }
+
+        if self.pos > 0 || self.complete {
+            self.evaluate(encrypted_gates.iter());
+            return Ok(());
+        }
+
+        if let Some(hasher) = &mut self.hasher {
+            for encrypted_gate in encrypted_gates {
+                hasher.update(&encrypted_gate.to_bytes());
+            }
+        }
+
+        let circ = self.circ.clone();
+        let cipher = self.cipher;
+        let labels = &mut self.active_labels;
+
+        // The index of each AND gate, by gate index.
+        let mut and_idx = 0;
+        let and_indices: Vec<usize> = circ
+            .gates()
+            .iter()
+            .map(|gate| {
+                let idx = and_idx;
+                if matches!(gate, Gate::And { .. }) {
+                    and_idx += 1;
+                }
+                idx
+            })
+            .collect();
+
+        for layer in circ.and_layers() {
+            let (and_gates_idx, free_gates_idx): (Vec<usize>, Vec<usize>) = layer
+                .into_iter()
+                .partition(|&idx| matches!(circ.gates()[idx], Gate::And { .. }));
+
+            let inputs: Vec<(Label, Label, &EncryptedGate, usize)> = and_gates_idx
+                .iter()
+                .map(|&idx| {
+                    let gate = &circ.gates()[idx];
+                    let y = gate.y().expect("AND gate should have two inputs");
+                    let and_idx = and_indices[idx];
+                    (
+                        labels[gate.x().id()].expect("feed should be initialized"),
+                        labels[y.id()].expect("feed should be initialized"),
+                        &encrypted_gates[and_idx],
+                        self.gid + 2 * and_idx,
+                    )
+                })
+                .collect();
+
+            let evaluate = |chunk| and_gates(cipher, chunk);
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "rayon")] {
+                    let outputs: Vec<_> =
+                        inputs.par_chunks(AND_BATCH).flat_map_iter(evaluate).collect();
+                } else {
+                    let outputs: Vec<_> = inputs.chunks(AND_BATCH).flat_map(evaluate).collect();
+                }
+            }
+
+            for (idx, z) in and_gates_idx.into_iter().zip(outputs) {
+                labels[circ.gates()[idx].z().id()] = Some(z);
+            }
+
+            for idx in free_gates_idx {
+                match &circ.gates()[idx] {
+                    Gate::Inv {
+                        x: node_x,
+                        z: node_z,
+                    } => {
+                        let x = labels[node_x.id()].expect("feed should be initialized");
+                        labels[node_z.id()] = Some(x);
+                    }
+                    Gate::Xor {
+                        x: node_x,
+                        y: node_y,
+                        z: node_z,
+                    } => {
+                        let x = labels[node_x.id()].expect("feed should be initialized");
+                        let y = labels[node_y.id()].expect("feed should be initialized");
+                        labels[node_z.id()] = Some(x ^ y);
+                    }
+                    Gate::And { .. } => unreachable!("AND gates are evaluated in batches"),
+                }
+            }
+        }
+
+        self.pos = circ.gates().len();
+        self.gid += 2 * encrypted_gates.len();
+        self.complete = true;
+
+        Ok(())
+    }
+
     /// Returns whether the evaluator has finished evaluating the circuit.
     pub fn is_complete(&self) -> bool {
         self.complete
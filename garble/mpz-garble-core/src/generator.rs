@@ -2,6 +2,12 @@ use std::sync::Arc;
 
 use blake3::Hasher;
 
+cfg_if::cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        use rayon::prelude::*;
+    }
+}
+
 use crate::{
     circuit::EncryptedGate,
     encoding::{state, Delta, EncodedValue, Label},
@@ -25,6 +31,9 @@ pub enum GeneratorError {
     NotFinished,
 }
 
+/// Number of AND gates which are garbled with a single call to the cipher.
+const AND_BATCH: usize = 8;
+
 /// Computes half-gate garbled AND gate
 #[inline]
 pub(crate) fn and_gate(
@@ -36,17 +45,67 @@ pub(crate) fn and_gate(
 ) -> (Label, EncryptedGate) {
     let delta = delta.into_inner();
     let x_0 = x_0.to_inner();
-    let x_1 = x_0 ^ delta;
     let y_0 = y_0.to_inner();
-    let y_1 = y_0 ^ delta;
+    let (j, k) = tweaks(gid);
+
+    let mut h = [x_0, y_0, x_0 ^ delta, y_0 ^ delta];
+    cipher.tccr_many(&[j, k, j, k], &mut h);
+
+    half_gates(h, x_0, y_0, delta)
+}
 
+/// Computes a batch of at most `AND_BATCH` half-gate garbled AND gates.
+///
+/// Each gate is given by its 0 bit input labels and its gate id.
+#[inline]
+fn and_gates(
+    cipher: &FixedKeyAes,
+    gates: &[(Label, Label, usize)],
+    delta: &Delta,
+) -> Vec<(Label, EncryptedGate)> {
+    if gates.len() < AND_BATCH {
+        return gates
+            .iter()
+            .map(|(x_0, y_0, gid)| and_gate(cipher, x_0, y_0, delta, *gid))
+            .collect();
+    }
+
+    let delta = delta.into_inner();
+    let mut tweaks_batch = [Block::ZERO; 4 * AND_BATCH];
+    let mut h = [Block::ZERO; 4 * AND_BATCH];
+    for (i, (x_0, y_0, gid)) in gates.iter().enumerate() {
+        let (x_0, y_0) = (x_0.to_inner(), y_0.to_inner());
+        let (j, k) = tweaks(*gid);
+
+        tweaks_batch[4 * i..4 * i + 4].copy_from_slice(&[j, k, j, k]);
+        h[4 * i..4 * i + 4].copy_from_slice(&[x_0, y_0, x_0 ^ delta, y_0 ^ delta]);
+    }
+
+    cipher.tccr_many(&tweaks_batch, &mut h);
+
+    gates
+        .iter()
+        .zip(h.chunks_exact(4))
+        .map(|((x_0, y_0, _), h)| {
+            half_gates(h.try_into().unwrap(), x_0.to_inner(), y_0.to_inner(), delta)
+        })
+        .collect()
+}
+
+/// Returns the tweaks of the AND gate with the given gate id.
+#[inline]
+pub(crate) fn tweaks(gid: usize) -> (Block, Block) {
+    (
+        Block::new((gid as u128).to_be_bytes()),
+        Block::new(((gid + 1) as u128).to_be_bytes()),
+    )
+}
+
+/// Computes the half-gates from the hashes of the 0 and 1 bit input labels.
+#[inline]
+fn half_gates(h: [Block; 4], x_0: Block, y_0: Block, delta: Block) -> (Label, EncryptedGate) {
     let p_a = x_0.lsb();
     let p_b = y_0.lsb();
-    let j = Block::new((gid as u128).to_be_bytes());
-    let k = Block::new(((gid + 1) as u128).to_be_bytes());
-
-    let mut h = [x_0, y_0, x_1, y_1];
-    cipher.tccr_many(&[j, k, j, k], &mut h);
 
     let [hx_0, hy_0, hx_1, hy_1] = h;
 
@@ -182,6 +241,107 @@ impl Generator {
             .collect())
     }
 
+    /// Generates all remaining encrypted gates of the circuit.
+    ///
+    /// Rather than garbling one gate at a time, the AND gates are garbled layer by layer
+    /// (see [`Circuit::and_layers`]) in batches, which are spread across threads if the `rayon`
+    /// feature is enabled. The encrypted gates are identical to those yielded by the iterator.
+    ///
+    /// If the generator has already been partially iterated, the remaining gates are garbled
+    /// sequentially.
+    pub fn generate_parallel(&mut self) -> Vec<EncryptedGate> {
+        if self.pos > 0 {
+            return self.by_ref().collect();
+        }
+
+        let circ = self.circ.clone();
+        let cipher = self.cipher;
+        let delta = self.delta;
+        let low_labels = &mut self.low_labels;
+
+        // The gate id of each AND gate, by gate index.
+        let mut gid = self.gid;
+        let gids: Vec<usize> = circ
+            .gates()
+            .iter()
+            .map(|gate| {
+                let id = gid;
+                if matches!(gate, Gate::And { .. }) {
+                    gid += 2;
+                }
+                id
+            })
+            .collect();
+
+        let mut encrypted_gates = vec![EncryptedGate::new([Block::ZERO; 2]); circ.and_count()];
+        for layer in circ.and_layers() {
+            let (and_gates_idx, free_gates_idx): (Vec<usize>, Vec<usize>) = layer
+                .into_iter()
+                .partition(|&idx| matches!(circ.gates()[idx], Gate::And { .. }));
+
+            let inputs: Vec<(Label, Label, usize)> = and_gates_idx
+                .iter()
+                .map(|&idx| {
+                    let gate = &circ.gates()[idx];
+                    let y = gate.y().expect("AND gate should have two inputs");
+                    (
+                        low_labels[gate.x().id()].expect("feed should be initialized"),
+                        low_labels[y.id()].expect("feed should be initialized"),
+                        gids[idx],
+                    )
+                })
+                .collect();
+
+            let garble = |chunk| and_gates(cipher, chunk, &delta);
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "rayon")] {
+                    let outputs: Vec<_> =
+                        inputs.par_chunks(AND_BATCH).flat_map_iter(garble).collect();
+                } else {
+                    let outputs: Vec<_> = inputs.chunks(AND_BATCH).flat_map(garble).collect();
+                }
+            }
+
+            for (idx, (z_0, encrypted_gate)) in and_gates_idx.into_iter().zip(outputs) {
+                low_labels[circ.gates()[idx].z().id()] = Some(z_0);
+                encrypted_gates[(gids[idx] - self.gid) / 2] = encrypted_gate;
+            }
+
+            for idx in free_gates_idx {
+                match &circ.gates()[idx] {
+                    Gate::Inv {
+                        x: node_x,
+                        z: node_z,
+                    } => {
+                        let x_0 = low_labels[node_x.id()].expect("feed should be initialized");
+                        low_labels[node_z.id()] = Some(x_0 ^ delta);
+                    }
+                    Gate::Xor {
+                        x: node_x,
+                        y: node_y,
+                        z: node_z,
+                    } => {
+                        let x_0 = low_labels[node_x.id()].expect("feed should be initialized");
+                        let y_0 = low_labels[node_y.id()].expect("feed should be initialized");
+                        low_labels[node_z.id()] = Some(x_0 ^ y_0);
+                    }
+                    Gate::And { .. } => unreachable!("AND gates are garbled in batches"),
+                }
+            }
+        }
+
+        if let Some(hasher) = &mut self.hasher {
+            for encrypted_gate in &encrypted_gates {
+                hasher.update(&encrypted_gate.to_bytes());
+            }
+        }
+
+        self.pos = circ.gates().len();
+        self.gid = gid;
+
+        encrypted_gates
+    }
+
     /// Returns the hash of the encrypted gates.
     pub fn hash(&self) -> Option<Hash> {
         self.hasher.as_ref().map(|hasher| {
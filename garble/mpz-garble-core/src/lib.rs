@@ -1,6 +1,10 @@
 //! Core components used to implement garbled circuit protocols
 //!
-//! This module implements "half-gate" garbled circuits from the [Two Halves Make a Whole \[ZRE15\]](https://eprint.iacr.org/2014/756) paper.
+//! This module implements "half-gate" garbled circuits from the [Two Halves Make a Whole \[ZRE15\]](https://eprint.iacr.org/2014/756) paper,
+//! including its privacy-free variant, see [`PrivacyFreeGenerator`] and [`PrivacyFreeEvaluator`].
+//!
+//! The [`authenticated`] module implements authenticated garbling, which is secure against
+//! malicious adversaries.
 //!
 //! # Example
 //!
@@ -53,19 +57,22 @@
 #![deny(missing_docs, unreachable_pub, unused_must_use)]
 #![deny(clippy::all)]
 
+pub mod authenticated;
 pub(crate) mod circuit;
 pub mod encoding;
 mod evaluator;
 mod generator;
 pub mod msg;
+mod privacy_free;
 
-pub use circuit::{EncryptedGate, GarbledCircuit};
+pub use circuit::{EncryptedGate, GarbledCircuit, PrivacyFreeGate};
 pub use encoding::{
     state as encoding_state, ChaChaEncoder, Decoding, Delta, Encode, EncodedValue, Encoder,
     EncodingCommitment, EqualityCheck, Label, ValueError,
 };
 pub use evaluator::{Evaluator, EvaluatorError};
 pub use generator::{Generator, GeneratorError};
+pub use privacy_free::{PrivacyFreeEvaluator, PrivacyFreeGenerator};
 
 #[cfg(test)]
 mod tests {
@@ -164,4 +171,84 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_garble_parallel() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let key = [69u8; 16];
+        let msg = [42u8; 16];
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = AES128
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select(key).unwrap(),
+            full_inputs[1].clone().select(msg).unwrap(),
+        ];
+
+        let mut gen =
+            Generator::new_with_hasher(AES128.clone(), encoder.delta(), &full_inputs).unwrap();
+        let enc_gates: Vec<EncryptedGate> = gen.by_ref().collect();
+
+        let mut gen_parallel =
+            Generator::new_with_hasher(AES128.clone(), encoder.delta(), &full_inputs).unwrap();
+        let enc_gates_parallel = gen_parallel.generate_parallel();
+
+        assert!(gen_parallel.is_complete());
+        assert_eq!(enc_gates_parallel, enc_gates);
+        assert_eq!(gen_parallel.hash(), gen.hash());
+        assert_eq!(gen_parallel.outputs().unwrap(), gen.outputs().unwrap());
+
+        let mut ev = Evaluator::new_with_hasher(AES128.clone(), &active_inputs).unwrap();
+        ev.evaluate(enc_gates.iter());
+
+        let mut ev_parallel = Evaluator::new_with_hasher(AES128.clone(), &active_inputs).unwrap();
+        ev_parallel.evaluate_parallel(&enc_gates).unwrap();
+
+        assert!(ev_parallel.is_complete());
+        assert_eq!(ev_parallel.hash(), ev.hash());
+        assert_eq!(ev_parallel.outputs().unwrap(), ev.outputs().unwrap());
+    }
+
+    #[test]
+    fn test_garble_parallel_partial() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = AES128
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select([0u8; 16]).unwrap(),
+            full_inputs[1].clone().select([0u8; 16]).unwrap(),
+        ];
+
+        let mut gen = Generator::new(AES128.clone(), encoder.delta(), &full_inputs).unwrap();
+        let enc_gates: Vec<EncryptedGate> = gen.by_ref().collect();
+
+        let mut gen_partial =
+            Generator::new(AES128.clone(), encoder.delta(), &full_inputs).unwrap();
+        let mut enc_gates_partial: Vec<EncryptedGate> = gen_partial.by_ref().take(100).collect();
+        enc_gates_partial.extend(gen_partial.generate_parallel());
+
+        assert_eq!(enc_gates_partial, enc_gates);
+
+        let mut ev = Evaluator::new(AES128.clone(), &active_inputs).unwrap();
+        ev.evaluate(enc_gates[..100].iter());
+
+        assert!(matches!(
+            ev.evaluate_parallel(&enc_gates[101..]),
+            Err(EvaluatorError::InvalidGateCount(..))
+        ));
+
+        ev.evaluate_parallel(&enc_gates[100..]).unwrap();
+
+        assert!(ev.is_complete());
+    }
 }
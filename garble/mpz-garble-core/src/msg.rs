@@ -1,11 +1,19 @@
 //! Messages used in garbled circuit protocols.
 
-use mpz_core::{commit::Decommitment, hash::Hash};
+use mpz_core::{
+    cointoss::msgs::{
+        ReceiverPayload as CointossReceiverPayload, SenderCommitment,
+        SenderPayload as CointossSenderPayload,
+    },
+    commit::Decommitment,
+    hash::Hash,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    circuit::EncryptedGate, encoding_state, Decoding, Delta, EncodedValue, EncodingCommitment,
-    EqualityCheck,
+    authenticated::{AuthenticatedGate, Correction, Opening},
+    circuit::{EncryptedGate, PrivacyFreeGate},
+    encoding_state, Decoding, Delta, EncodedValue, EncodingCommitment, EqualityCheck, Label,
 };
 
 /// Top-level message type encapsulating all messages used in garbled circuit protocols.
@@ -26,4 +34,15 @@ pub enum GarbleMessage {
     ProofDecommitments(Vec<Decommitment<Hash>>),
     Delta(Delta),
     EncoderSeed(Vec<u8>),
+    PrivacyFreeGates(Vec<PrivacyFreeGate>),
+    CointossCommit(SenderCommitment),
+    CointossReceiverPayload(CointossReceiverPayload),
+    CointossSenderPayload(CointossSenderPayload),
+    Openings(Vec<Opening>),
+    LeakyAndCorrections(Vec<Correction>),
+    Bits(Vec<bool>),
+    TripleCheck(Hash),
+    Labels(Vec<Label>),
+    AuthenticatedGates(Vec<AuthenticatedGate>),
+    ActiveValuesDecommitment(Decommitment<Vec<EncodedValue<encoding_state::Active>>>),
 }
@@ -0,0 +1,580 @@
+//! Privacy-free garbling.
+//!
+//! This module implements the privacy-free variant of half-gates from the [Two Halves Make a Whole \[ZRE15\]](https://eprint.iacr.org/2014/756) paper,
+//! where each AND gate is garbled with a single row. It is only secure when the evaluator knows
+//! the values of all the inputs, eg. when the evaluator is proving a statement about its own
+//! inputs.
+//!
+//! The labels are canonicalized such that the pointer bit of a label is the value it encodes,
+//! which lets the evaluator select the row of an AND gate without further information.
+
+use std::sync::Arc;
+
+use blake3::Hasher;
+use itybity::IntoBits;
+
+use crate::{
+    circuit::PrivacyFreeGate,
+    encoding::{state, Delta, EncodedValue, Label},
+    generator::tweaks,
+    EvaluatorError, GeneratorError,
+};
+use mpz_circuits::{
+    types::{TypeError, Value},
+    Circuit, CircuitError, Gate,
+};
+use mpz_core::{
+    aes::{FixedKeyAes, FIXED_KEY_AES},
+    hash::Hash,
+    Block,
+};
+
+/// Returns the label with its pointer bit set to `value`.
+#[inline]
+fn with_pointer_bit(label: Label, value: bool) -> Label {
+    let mut block = label.to_inner();
+    if block.lsb() != value as usize {
+        block ^= Block::ONE;
+    }
+    Label::new(block)
+}
+
+/// Computes a privacy-free garbled AND gate
+#[inline]
+pub(crate) fn and_gate_gen(
+    cipher: &FixedKeyAes,
+    x_0: &Label,
+    y_0: &Label,
+    delta: &Delta,
+    gid: usize,
+) -> (Label, PrivacyFreeGate) {
+    let x_0 = x_0.to_inner();
+    let x_1 = x_0 ^ delta.into_inner();
+    let (j, _) = tweaks(gid);
+
+    let mut h = [x_0, x_1];
+    cipher.tccr_many(&[j, j], &mut h);
+
+    let [hx_0, hx_1] = h;
+
+    let z_0 = with_pointer_bit(Label::new(hx_0), false);
+
+    (z_0, PrivacyFreeGate::new(hx_0 ^ hx_1 ^ y_0.to_inner()))
+}
+
+/// Evaluates a privacy-free garbled AND gate
+#[inline]
+pub(crate) fn and_gate_ev(
+    cipher: &FixedKeyAes,
+    x: &Label,
+    y: &Label,
+    encrypted_gate: &PrivacyFreeGate,
+    gid: usize,
+) -> Label {
+    let (j, _) = tweaks(gid);
+    let x_value = x.pointer_bit();
+    let y_value = y.pointer_bit();
+
+    let mut w = cipher.tccr(j, x.to_inner());
+    if x_value {
+        w ^= encrypted_gate.0 ^ y.to_inner();
+    }
+
+    with_pointer_bit(Label::new(w), x_value & y_value)
+}
+
+/// Core generator type used to generate privacy-free garbled circuits.
+///
+/// A generator is to be used as an iterator of encrypted gates. Each
+/// iteration will return the next encrypted gate in the circuit until the
+/// entire garbled circuit has been yielded.
+pub struct PrivacyFreeGenerator {
+    /// Cipher to use to encrypt the gates
+    cipher: &'static FixedKeyAes,
+    /// Circuit to generate a garbled circuit for
+    circ: Arc<Circuit>,
+    /// Delta value to use while generating the circuit
+    delta: Delta,
+    /// The 0 bit labels for the garbled circuit
+    low_labels: Vec<Option<Label>>,
+    /// Current position in the circuit
+    pos: usize,
+    /// Current gate id
+    gid: usize,
+    /// Hasher to use to hash the encrypted gates
+    hasher: Option<Hasher>,
+}
+
+impl PrivacyFreeGenerator {
+    /// Creates a new generator for the given circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to generate a garbled circuit for.
+    /// * `delta` - The delta value to use.
+    /// * `inputs` - The inputs to the circuit.
+    pub fn new(
+        circ: Arc<Circuit>,
+        delta: Delta,
+        inputs: &[EncodedValue<state::Full>],
+    ) -> Result<Self, GeneratorError> {
+        Self::new_with(circ, delta, inputs, None)
+    }
+
+    /// Creates a new generator for the given circuit. Generator will compute a hash
+    /// of the encrypted gates while they are produced.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to generate a garbled circuit for.
+    /// * `delta` - The delta value to use.
+    /// * `inputs` - The inputs to the circuit.
+    pub fn new_with_hasher(
+        circ: Arc<Circuit>,
+        delta: Delta,
+        inputs: &[EncodedValue<state::Full>],
+    ) -> Result<Self, GeneratorError> {
+        Self::new_with(circ, delta, inputs, Some(Hasher::new()))
+    }
+
+    fn new_with(
+        circ: Arc<Circuit>,
+        delta: Delta,
+        inputs: &[EncodedValue<state::Full>],
+        hasher: Option<Hasher>,
+    ) -> Result<Self, GeneratorError> {
+        if inputs.len() != circ.inputs().len() {
+            return Err(CircuitError::InvalidInputCount(
+                circ.inputs().len(),
+                inputs.len(),
+            ))?;
+        }
+
+        let mut low_labels: Vec<Option<Label>> = vec![None; circ.feed_count()];
+        for (encoded, input) in inputs.iter().zip(circ.inputs()) {
+            if encoded.value_type() != input.value_type() {
+                return Err(TypeError::UnexpectedType {
+                    expected: input.value_type(),
+                    actual: encoded.value_type(),
+                })?;
+            }
+
+            for (label, node) in encoded.iter().zip(input.iter()) {
+                low_labels[node.id()] = Some(with_pointer_bit(*label, false));
+            }
+        }
+
+        Ok(Self {
+            cipher: &(*FIXED_KEY_AES),
+            circ,
+            delta,
+            low_labels,
+            pos: 0,
+            gid: 1,
+            hasher,
+        })
+    }
+
+    /// Returns whether the generator has finished generating the circuit.
+    pub fn is_complete(&self) -> bool {
+        self.pos >= self.circ.gates().len()
+    }
+
+    /// Returns the encoded outputs of the circuit.
+    pub fn outputs(&self) -> Result<Vec<EncodedValue<state::Full>>, GeneratorError> {
+        if !self.is_complete() {
+            return Err(GeneratorError::NotFinished);
+        }
+
+        Ok(self
+            .circ
+            .outputs()
+            .iter()
+            .map(|output| {
+                let labels: Vec<Label> = output
+                    .iter()
+                    .map(|node| self.low_labels[node.id()].expect("feed should be initialized"))
+                    .collect();
+
+                EncodedValue::<state::Full>::from_labels(output.value_type(), self.delta, &labels)
+                    .expect("encoding should be correct")
+            })
+            .collect())
+    }
+
+    /// Returns the hash of the encrypted gates.
+    pub fn hash(&self) -> Option<Hash> {
+        self.hasher.as_ref().map(|hasher| {
+            let hash: [u8; 32] = hasher.finalize().into();
+            Hash::from(hash)
+        })
+    }
+}
+
+impl Iterator for PrivacyFreeGenerator {
+    type Item = PrivacyFreeGate;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let low_labels = &mut self.low_labels;
+        while let Some(gate) = self.circ.gates().get(self.pos) {
+            self.pos += 1;
+            match gate {
+                Gate::Inv {
+                    x: node_x,
+                    z: node_z,
+                } => {
+                    // Flipping the pointer bit keeps the 0 bit label canonical.
+                    let x_0 = low_labels[node_x.id()].expect("feed should be initialized");
+                    low_labels[node_z.id()] = Some(x_0 ^ self.delta ^ Label::new(Block::ONE));
+                }
+                Gate::Xor {
+                    x: node_x,
+                    y: node_y,
+                    z: node_z,
+                } => {
+                    let x_0 = low_labels[node_x.id()].expect("feed should be initialized");
+                    let y_0 = low_labels[node_y.id()].expect("feed should be initialized");
+                    low_labels[node_z.id()] = Some(x_0 ^ y_0);
+                }
+                Gate::And {
+                    x: node_x,
+                    y: node_y,
+                    z: node_z,
+                } => {
+                    let x_0 = low_labels[node_x.id()].expect("feed should be initialized");
+                    let y_0 = low_labels[node_y.id()].expect("feed should be initialized");
+                    let (z_0, encrypted_gate) =
+                        and_gate_gen(self.cipher, &x_0, &y_0, &self.delta, self.gid);
+                    low_labels[node_z.id()] = Some(z_0);
+                    self.gid += 1;
+
+                    if let Some(hasher) = &mut self.hasher {
+                        hasher.update(&encrypted_gate.to_bytes());
+                    }
+
+                    return Some(encrypted_gate);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Core evaluator type for evaluating a privacy-free garbled circuit.
+pub struct PrivacyFreeEvaluator {
+    /// Cipher to use to encrypt the gates
+    cipher: &'static FixedKeyAes,
+    /// Circuit to evaluate
+    circ: Arc<Circuit>,
+    /// Active label state
+    active_labels: Vec<Option<Label>>,
+    /// Current position in the circuit
+    pos: usize,
+    /// Current gate id
+    gid: usize,
+    /// Whether the evaluator is finished
+    complete: bool,
+    /// Hasher to use to hash the encrypted gates
+    hasher: Option<Hasher>,
+}
+
+impl PrivacyFreeEvaluator {
+    /// Creates a new evaluator for the given circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to evaluate.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `values` - The values of the inputs.
+    pub fn new(
+        circ: Arc<Circuit>,
+        inputs: &[EncodedValue<state::Active>],
+        values: &[Value],
+    ) -> Result<Self, EvaluatorError> {
+        Self::new_with(circ, inputs, values, None)
+    }
+
+    /// Creates a new evaluator for the given circuit. Evaluator will compute
+    /// a hash of the encrypted gates while they are evaluated.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to evaluate.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `values` - The values of the inputs.
+    pub fn new_with_hasher(
+        circ: Arc<Circuit>,
+        inputs: &[EncodedValue<state::Active>],
+        values: &[Value],
+    ) -> Result<Self, EvaluatorError> {
+        Self::new_with(circ, inputs, values, Some(Hasher::new()))
+    }
+
+    fn new_with(
+        circ: Arc<Circuit>,
+        inputs: &[EncodedValue<state::Active>],
+        values: &[Value],
+        hasher: Option<Hasher>,
+    ) -> Result<Self, EvaluatorError> {
+        if inputs.len() != circ.inputs().len() {
+            return Err(CircuitError::InvalidInputCount(
+                circ.inputs().len(),
+                inputs.len(),
+            ))?;
+        } else if values.len() != circ.inputs().len() {
+            return Err(CircuitError::InvalidInputCount(
+                circ.inputs().len(),
+                values.len(),
+            ))?;
+        }
+
+        let mut active_labels: Vec<Option<Label>> = vec![None; circ.feed_count()];
+        for ((encoded, value), input) in inputs.iter().zip(values).zip(circ.inputs()) {
+            for ty in [encoded.value_type(), value.value_type()] {
+                if ty != input.value_type() {
+                    return Err(TypeError::UnexpectedType {
+                        expected: input.value_type(),
+                        actual: ty,
+                    })?;
+                }
+            }
+
+            for ((label, bit), node) in encoded
+                .iter()
+                .zip(value.clone().into_iter_lsb0())
+                .zip(input.iter())
+            {
+                active_labels[node.id()] = Some(with_pointer_bit(*label, bit));
+            }
+        }
+
+        let mut ev = Self {
+            cipher: &(*FIXED_KEY_AES),
+            circ,
+            active_labels,
+            pos: 0,
+            gid: 1,
+            complete: false,
+            hasher,
+        };
+
+        // If circuit has no AND gates we can evaluate it immediately for cheap
+        if ev.circ.and_count() == 0 {
+            ev.evaluate(std::iter::empty());
+        }
+
+        Ok(ev)
+    }
+
+    /// Evaluates the next batch of encrypted gates.
+    #[inline]
+    pub fn evaluate<'a>(&mut self, mut encrypted_gates: impl Iterator<Item = &'a PrivacyFreeGate>) {
+        let labels = &mut self.active_labels;
+
+        // Process gates until we run out of encrypted gates
+        while self.pos < self.circ.gates().len() {
+            match &self.circ.gates()[self.pos] {
+                Gate::Inv {
+                    x: node_x,
+                    z: node_z,
+                } => {
+                    let x = labels[node_x.id()].expect("feed should be initialized");
+                    labels[node_z.id()] = Some(x ^ Label::new(Block::ONE));
+                }
+                Gate::Xor {
+                    x: node_x,
+                    y: node_y,
+                    z: node_z,
+                } => {
+                    let x = labels[node_x.id()].expect("feed should be initialized");
+                    let y = labels[node_y.id()].expect("feed should be initialized");
+                    labels[node_z.id()] = Some(x ^ y);
+                }
+                Gate::And {
+                    x: node_x,
+                    y: node_y,
+                    z: node_z,
+                } => {
+                    if let Some(encrypted_gate) = encrypted_gates.next() {
+                        if let Some(hasher) = &mut self.hasher {
+                            hasher.update(&encrypted_gate.to_bytes());
+                        }
+
+                        let x = labels[node_x.id()].expect("feed should be initialized");
+                        let y = labels[node_y.id()].expect("feed should be initialized");
+                        let z = and_gate_ev(self.cipher, &x, &y, encrypted_gate, self.gid);
+                        labels[node_z.id()] = Some(z);
+                        self.gid += 1;
+                    } else {
+                        // We ran out of encrypted gates, so we return until we get more
+                        return;
+                    }
+                }
+            }
+            self.pos += 1;
+        }
+
+        self.complete = true;
+    }
+
+    /// Returns whether the evaluator has finished evaluating the circuit.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Returns the active encoded outputs of the circuit.
+    pub fn outputs(&self) -> Result<Vec<EncodedValue<state::Active>>, EvaluatorError> {
+        if !self.is_complete() {
+            return Err(EvaluatorError::NotFinished);
+        }
+
+        Ok(self
+            .circ
+            .outputs()
+            .iter()
+            .map(|output| {
+                let labels: Vec<Label> = output
+                    .iter()
+                    .map(|node| self.active_labels[node.id()].expect("feed should be initialized"))
+                    .collect();
+
+                EncodedValue::<state::Active>::from_labels(output.value_type(), &labels)
+                    .expect("encoding should be correct")
+            })
+            .collect())
+    }
+
+    /// Returns the hash of the encrypted gates.
+    pub fn hash(&self) -> Option<Hash> {
+        self.hasher.as_ref().map(|hasher| {
+            let hash: [u8; 32] = hasher.finalize().into();
+            Hash::from(hash)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aes::{
+        cipher::{BlockEncrypt, KeyInit},
+        Aes128,
+    };
+    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, types::ValueType, CircuitBuilder};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use crate::{ChaChaEncoder, Encoder};
+
+    use super::*;
+
+    #[test]
+    fn test_and_gate() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let cipher = &(*FIXED_KEY_AES);
+
+        let delta = Delta::random(&mut rng);
+        let x_0 = with_pointer_bit(Label::random(&mut rng), false);
+        let x_1 = x_0 ^ delta;
+        let y_0 = with_pointer_bit(Label::random(&mut rng), false);
+        let y_1 = y_0 ^ delta;
+        let gid: usize = 1;
+
+        let (z_0, encrypted_gate) = and_gate_gen(cipher, &x_0, &y_0, &delta, gid);
+        let z_1 = z_0 ^ delta;
+
+        assert!(!z_0.pointer_bit());
+        assert_eq!(and_gate_ev(cipher, &x_0, &y_0, &encrypted_gate, gid), z_0);
+        assert_eq!(and_gate_ev(cipher, &x_0, &y_1, &encrypted_gate, gid), z_0);
+        assert_eq!(and_gate_ev(cipher, &x_1, &y_0, &encrypted_gate, gid), z_0);
+        assert_eq!(and_gate_ev(cipher, &x_1, &y_1, &encrypted_gate, gid), z_1);
+    }
+
+    fn garble(circ: Arc<Circuit>, values: &[Value]) -> Vec<Value> {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let full_inputs: Vec<EncodedValue<state::Full>> = circ
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<state::Active>> = full_inputs
+            .iter()
+            .zip(values)
+            .map(|(full, value)| full.clone().select(value.clone()).unwrap())
+            .collect();
+
+        let mut gen =
+            PrivacyFreeGenerator::new_with_hasher(circ.clone(), encoder.delta(), &full_inputs)
+                .unwrap();
+        let mut ev =
+            PrivacyFreeEvaluator::new_with_hasher(circ.clone(), &active_inputs, values).unwrap();
+
+        let gates: Vec<PrivacyFreeGate> = gen.by_ref().collect();
+        ev.evaluate(gates.iter());
+
+        assert!(ev.is_complete());
+        assert_eq!(gates.len(), circ.and_count());
+        assert_eq!(gen.hash(), ev.hash());
+
+        ev.outputs()
+            .unwrap()
+            .iter()
+            .zip(gen.outputs().unwrap())
+            .map(|(active, full)| active.decode(&full.decoding()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_garble_aes() {
+        let key = [69u8; 16];
+        let msg = [42u8; 16];
+
+        let expected: [u8; 16] = {
+            let cipher = Aes128::new_from_slice(&key).unwrap();
+            let mut out = msg.into();
+            cipher.encrypt_block(&mut out);
+            out.into()
+        };
+
+        let outputs = garble(AES128.clone(), &[key.into(), msg.into()]);
+        let actual: [u8; 16] = outputs[0].clone().try_into().unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_garble_inv() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(!(a.wrapping_add(!b)));
+        let circ = Arc::new(builder.build().unwrap());
+
+        for (a, b) in [(0u8, 0u8), (1, 2), (200, 100), (255, 255)] {
+            let outputs = garble(circ.clone(), &[a.into(), b.into()]);
+            let actual: u8 = outputs[0].clone().try_into().unwrap();
+
+            assert_eq!(actual, !(a.wrapping_add(!b)));
+        }
+    }
+
+    #[test]
+    fn test_evaluator_invalid_input_count() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let full = encoder.encode_by_type(0, &ValueType::Array(Box::new(ValueType::U8), 16));
+        let active = full.select([1u8; 16]).unwrap();
+
+        let err = PrivacyFreeEvaluator::new(AES128.clone(), &[active], &[[1u8; 16].into()]);
+
+        assert!(matches!(
+            err,
+            Err(EvaluatorError::CircuitError(
+                CircuitError::InvalidInputCount(2, 1)
+            ))
+        ));
+    }
+}
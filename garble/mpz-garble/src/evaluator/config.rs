@@ -12,6 +12,9 @@ pub struct EvaluatorConfig {
     /// Whether to log decodings.
     #[builder(default = "false", setter(custom))]
     pub(crate) log_decodings: bool,
+    /// Whether to expect privacy-free garbled circuits.
+    #[builder(default = "false", setter(custom))]
+    pub(crate) privacy_free: bool,
 }
 
 impl EvaluatorConfig {
@@ -39,4 +42,12 @@ impl EvaluatorConfigBuilder {
         self.log_decodings = Some(true);
         self
     }
+
+    /// Enable privacy-free garbling.
+    ///
+    /// This requires the generator to reveal the values of the inputs to the evaluator.
+    pub fn privacy_free(&mut self) -> &mut Self {
+        self.privacy_free = Some(true);
+        self
+    }
 }
@@ -17,7 +17,7 @@ use mpz_circuits::{
 use mpz_core::hash::Hash;
 use mpz_garble_core::{
     encoding_state, msg::GarbleMessage, Decoding, EncodedValue, Evaluator as EvaluatorCore,
-    GarbledCircuit,
+    GarbledCircuit, PrivacyFreeEvaluator as PrivacyFreeEvaluatorCore,
 };
 use utils::iter::FilterDrain;
 use utils_aio::{
@@ -28,6 +28,7 @@ use utils_aio::{
 use crate::{
     memory::EncodingMemory,
     ot::{OTReceiveEncoding, OTVerifyEncoding},
+    preprocessed::{EvaluatorPreprocessed, StoredGarbledCircuit},
     value::{CircuitRefs, ValueId, ValueRef},
     AssignedValues, Generator, GeneratorConfigBuilder,
 };
@@ -87,6 +88,55 @@ impl Evaluator {
         self.state.lock().unwrap()
     }
 
+    /// Creates an evaluator from a preprocessed state.
+    ///
+    /// See [`export_preprocessed`](Self::export_preprocessed).
+    pub fn from_preprocessed(
+        config: EvaluatorConfig,
+        preprocessed: EvaluatorPreprocessed,
+    ) -> Result<Self, EvaluatorError> {
+        let mut garbled_circuits = HashMap::with_capacity(preprocessed.garbled.len());
+        for StoredGarbledCircuit {
+            refs,
+            gates,
+            commitments,
+        } in preprocessed.garbled
+        {
+            if garbled_circuits
+                .insert(refs.into(), GarbledCircuit { gates, commitments })
+                .is_some()
+            {
+                return Err(EvaluatorError::DuplicateCircuit);
+            }
+        }
+
+        Ok(Self {
+            config,
+            state: Mutex::new(State {
+                garbled_circuits,
+                ..Default::default()
+            }),
+        })
+    }
+
+    /// Exports the garbled circuits which have been received but not evaluated yet, so that
+    /// they can be used after the evaluator is restored with
+    /// [`from_preprocessed`](Self::from_preprocessed).
+    pub fn export_preprocessed(&self) -> EvaluatorPreprocessed {
+        EvaluatorPreprocessed {
+            garbled: self
+                .state()
+                .garbled_circuits
+                .iter()
+                .map(|(refs, circuit)| StoredGarbledCircuit {
+                    refs: refs.into(),
+                    gates: circuit.gates.clone(),
+                    commitments: circuit.commitments.clone(),
+                })
+                .collect(),
+        }
+    }
+
     /// Sets a value as decoded.
     ///
     /// # Errors
@@ -358,6 +408,23 @@ impl Evaluator {
         inputs: &[ValueRef],
         outputs: &[ValueRef],
         stream: &mut S,
+    ) -> Result<Vec<EncodedValue<encoding_state::Active>>, EvaluatorError> {
+        self.evaluate_with(circ, inputs, outputs, stream, self.config.privacy_free)
+            .await
+    }
+
+    /// Evaluate a circuit, which is expected to be garbled privacy-free if `privacy_free` is set.
+    ///
+    /// A garbled circuit which has already been received is never privacy-free.
+    pub(crate) async fn evaluate_with<
+        S: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    >(
+        &self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        stream: &mut S,
+        privacy_free: bool,
     ) -> Result<Vec<EncodedValue<encoding_state::Active>>, EvaluatorError> {
         let refs = CircuitRefs {
             inputs: inputs.to_vec(),
@@ -377,19 +444,15 @@ impl Evaluator {
                 .collect::<Result<Vec<_>, _>>()?
         };
 
-        let mut ev = if self.config.log_circuits {
-            EvaluatorCore::new_with_hasher(circ.clone(), &encoded_inputs)?
-        } else {
-            EvaluatorCore::new(circ.clone(), &encoded_inputs)?
-        };
-
         let existing_garbled_circuit = self.state().garbled_circuits.remove(&refs);
+        let privacy_free = privacy_free && existing_garbled_circuit.is_none();
 
         // If we've already received the garbled circuit, we evaluate it, otherwise we stream the encrypted gates
         // from the generator.
-        let encoded_outputs = if let Some(GarbledCircuit { gates, commitments }) =
+        let (encoded_outputs, hash) = if let Some(GarbledCircuit { gates, commitments }) =
             existing_garbled_circuit
         {
+            let mut ev = self.new_evaluator(circ.clone(), &encoded_inputs)?;
             ev = Backend::spawn(move || {
                 ev.evaluate(gates.iter());
                 ev
@@ -406,18 +469,25 @@ impl Evaluator {
                 }
             }
 
-            encoded_outputs
+            (encoded_outputs, ev.hash())
         } else {
-            while !ev.is_complete() {
-                let gates = expect_msg_or_err!(stream, GarbleMessage::EncryptedGates)?;
-                ev = Backend::spawn(move || {
-                    ev.evaluate(gates.iter());
-                    ev
-                })
-                .await;
-            }
+            let (encoded_outputs, hash) = if privacy_free {
+                self.evaluate_privacy_free(circ.clone(), inputs, &encoded_inputs, stream)
+                    .await?
+            } else {
+                let mut ev = self.new_evaluator(circ.clone(), &encoded_inputs)?;
+                while !ev.is_complete() {
+                    let gates = expect_msg_or_err!(stream, GarbleMessage::EncryptedGates)?;
+                    ev = Backend::spawn(move || {
+                        ev.evaluate(gates.iter());
+                        ev
+                    })
+                    .await;
+                }
+
+                (ev.outputs()?, ev.hash())
+            };
 
-            let encoded_outputs = ev.outputs()?;
             if self.config.encoding_commitments {
                 let commitments = expect_msg_or_err!(stream, GarbleMessage::EncodingCommitments)?;
 
@@ -434,7 +504,7 @@ impl Evaluator {
                 }
             }
 
-            encoded_outputs
+            (encoded_outputs, hash)
         };
 
         // Add the output encodings to the memory.
@@ -445,18 +515,84 @@ impl Evaluator {
 
         // If configured, log the circuit evaluation
         if self.config.log_circuits {
-            let hash = ev.hash().unwrap();
             state.circuit_logs.push(EvaluatorLog::new(
                 inputs.to_vec(),
                 outputs.to_vec(),
                 circ,
-                hash,
+                hash.unwrap(),
+                privacy_free,
             ));
         }
 
         Ok(encoded_outputs)
     }
 
+    /// Creates a core evaluator, which hashes the encrypted gates if circuits are logged.
+    fn new_evaluator(
+        &self,
+        circ: Arc<Circuit>,
+        encoded_inputs: &[EncodedValue<encoding_state::Active>],
+    ) -> Result<EvaluatorCore, EvaluatorError> {
+        Ok(if self.config.log_circuits {
+            EvaluatorCore::new_with_hasher(circ, encoded_inputs)?
+        } else {
+            EvaluatorCore::new(circ, encoded_inputs)?
+        })
+    }
+
+    /// Evaluates a privacy-free garbled circuit streamed from the generator.
+    ///
+    /// The generator first sends the decodings of the inputs, which reveals their values. If
+    /// decodings are logged, they are verified along with the circuit.
+    async fn evaluate_privacy_free<
+        S: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    >(
+        &self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        encoded_inputs: &[EncodedValue<encoding_state::Active>],
+        stream: &mut S,
+    ) -> Result<(Vec<EncodedValue<encoding_state::Active>>, Option<Hash>), EvaluatorError> {
+        let decodings = expect_msg_or_err!(stream, GarbleMessage::ValueDecodings)?;
+
+        // Make sure the generator sent the expected number of decodings.
+        if decodings.len() != inputs.len() {
+            return Err(EvaluatorError::IncorrectValueCount {
+                expected: inputs.len(),
+                actual: decodings.len(),
+            });
+        }
+
+        let values = encoded_inputs
+            .iter()
+            .zip(decodings.iter())
+            .map(|(encoding, decoding)| encoding.decode(decoding))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if self.config.log_decodings {
+            for (input, decoding) in inputs.iter().zip(decodings) {
+                self.add_decoding_log(input, decoding);
+            }
+        }
+
+        let mut ev = if self.config.log_circuits {
+            PrivacyFreeEvaluatorCore::new_with_hasher(circ, encoded_inputs, &values)?
+        } else {
+            PrivacyFreeEvaluatorCore::new(circ, encoded_inputs, &values)?
+        };
+
+        while !ev.is_complete() {
+            let gates = expect_msg_or_err!(stream, GarbleMessage::PrivacyFreeGates)?;
+            ev = Backend::spawn(move || {
+                ev.evaluate(gates.iter());
+                ev
+            })
+            .await;
+        }
+
+        Ok((ev.outputs()?, ev.hash()))
+    }
+
     /// Receive decoding information for a set of values from the generator
     /// and decode them.
     ///
@@ -566,7 +702,7 @@ impl Evaluator {
                 .map(|log| async {
                     // Compute the garbled circuit digest
                     let (_, digest) = gen
-                        .generate(
+                        .generate_with(
                             log.circ.clone(),
                             &log.inputs,
                             &log.outputs,
@@ -574,6 +710,7 @@ impl Evaluator {
                                 std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "")
                             }),
                             true,
+                            log.privacy_free,
                         )
                         .await
                         .map_err(VerificationError::from)?;
@@ -610,6 +747,7 @@ pub(crate) struct EvaluatorLog {
     outputs: Vec<ValueRef>,
     circ: Arc<Circuit>,
     hash: Hash,
+    privacy_free: bool,
 }
 
 impl EvaluatorLog {
@@ -618,12 +756,14 @@ impl EvaluatorLog {
         outputs: Vec<ValueRef>,
         circ: Arc<Circuit>,
         digest: Hash,
+        privacy_free: bool,
     ) -> Self {
         Self {
             inputs,
             outputs,
             circ,
             hash: digest,
+            privacy_free,
         }
     }
 }
@@ -9,6 +9,9 @@ pub struct GeneratorConfig {
     /// The batch size for encrypted gates sent to the evaluator.
     #[builder(default = "1024")]
     pub(crate) batch_size: usize,
+    /// Whether to garble circuits privacy-free.
+    #[builder(default = "false", setter(custom))]
+    pub(crate) privacy_free: bool,
 }
 
 impl GeneratorConfig {
@@ -24,6 +27,15 @@ impl GeneratorConfigBuilder {
         self.encoding_commitments = Some(true);
         self
     }
+
+    /// Enable privacy-free garbling.
+    ///
+    /// Privacy-free garbled circuits are half the size, but are only secure if the evaluator
+    /// knows the values of all the inputs, which are revealed to it.
+    pub fn privacy_free(&mut self) -> &mut Self {
+        self.privacy_free = Some(true);
+        self
+    }
 }
 
 impl Default for GeneratorConfig {
@@ -16,14 +16,16 @@ use mpz_circuits::{
 };
 use mpz_core::hash::Hash;
 use mpz_garble_core::{
-    encoding_state, msg::GarbleMessage, ChaChaEncoder, EncodedValue, Encoder,
-    Generator as GeneratorCore,
+    encoding_state, msg::GarbleMessage, ChaChaEncoder, Delta, EncodedValue, Encoder,
+    Generator as GeneratorCore, PrivacyFreeGenerator as PrivacyFreeGeneratorCore,
 };
+use rand::{thread_rng, Rng};
 use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
 
 use crate::{
     memory::EncodingMemory,
     ot::OTSendEncoding,
+    preprocessed::{ConsumedExports, GeneratorPreprocessed, PreprocessedError},
     value::{CircuitRefs, ValueId, ValueRef},
     AssignedValues,
 };
@@ -66,6 +68,73 @@ impl Generator {
         }
     }
 
+    /// Creates a generator from a preprocessed state, recording its ID in `consumed`.
+    ///
+    /// See [`export_preprocessed`](Self::export_preprocessed).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the preprocessed state has already been restored, ie. its ID is in
+    /// `consumed`.
+    pub fn from_preprocessed(
+        config: GeneratorConfig,
+        preprocessed: GeneratorPreprocessed,
+        consumed: &mut ConsumedExports,
+    ) -> Result<Self, PreprocessedError> {
+        let GeneratorPreprocessed {
+            id,
+            encoder_seed,
+            encodings,
+            active,
+            garbled,
+        } = preprocessed;
+
+        consumed.consume(id)?;
+
+        Ok(Self {
+            config,
+            state: Mutex::new(State {
+                encoder: ChaChaEncoder::new(encoder_seed),
+                memory: EncodingMemory::import(encodings),
+                garbled: garbled
+                    .into_iter()
+                    .map(|(refs, hash)| (refs.into(), hash))
+                    .collect(),
+                active: active.iter().map(|id| ValueId::new(id)).collect(),
+            }),
+        })
+    }
+
+    /// Exports the state of the generator, so that the circuits which have already been garbled
+    /// can be used after the generator is restored with
+    /// [`from_preprocessed`](Self::from_preprocessed).
+    ///
+    /// This consumes the generator, as the export must be the only user of its encodings. See
+    /// the security notes of [`GeneratorPreprocessed`].
+    pub fn export_preprocessed(self) -> GeneratorPreprocessed {
+        let state = self.state();
+
+        GeneratorPreprocessed {
+            id: thread_rng().gen(),
+            encoder_seed: state
+                .encoder
+                .seed()
+                .try_into()
+                .expect("encoder seed is 32 bytes"),
+            encodings: state.memory.export(),
+            active: state
+                .active
+                .iter()
+                .map(|id| id.as_ref().to_string())
+                .collect(),
+            garbled: state
+                .garbled
+                .iter()
+                .map(|(refs, hash)| (refs.into(), *hash))
+                .collect(),
+        }
+    }
+
     /// Convenience method for grabbing a lock to the state.
     fn state(&self) -> impl DerefMut<Target = State> + '_ {
         self.state.lock().unwrap()
@@ -260,6 +329,22 @@ impl Generator {
         outputs: &[ValueRef],
         sink: &mut S,
         hash: bool,
+    ) -> Result<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>), GeneratorError> {
+        self.generate_with(circ, inputs, outputs, sink, hash, self.config.privacy_free)
+            .await
+    }
+
+    /// Generate a garbled circuit, which is garbled privacy-free if `privacy_free` is set.
+    ///
+    /// A circuit which has already been garbled is not garbled again.
+    pub(crate) async fn generate_with<S: Sink<GarbleMessage, Error = std::io::Error> + Unpin>(
+        &self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        sink: &mut S,
+        hash: bool,
+        privacy_free: bool,
     ) -> Result<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>), GeneratorError> {
         let refs = CircuitRefs {
             inputs: inputs.to_vec(),
@@ -298,30 +383,34 @@ impl Generator {
             (delta, inputs)
         };
 
-        let mut gen = if hash {
-            GeneratorCore::new_with_hasher(circ.clone(), delta, &inputs)?
+        let (encoded_outputs, hash) = if privacy_free {
+            self.generate_privacy_free(circ, delta, &inputs, sink, hash)
+                .await?
         } else {
-            GeneratorCore::new(circ.clone(), delta, &inputs)?
-        };
-
-        let mut batch: Vec<_>;
-        let batch_size = self.config.batch_size;
-        while !gen.is_complete() {
-            // Move the generator to another thread to produce the next batch
-            // then send it back
-            (gen, batch) = Backend::spawn(move || {
-                let batch = gen.by_ref().take(batch_size).collect();
-                (gen, batch)
-            })
-            .await;
+            let mut gen = if hash {
+                GeneratorCore::new_with_hasher(circ.clone(), delta, &inputs)?
+            } else {
+                GeneratorCore::new(circ.clone(), delta, &inputs)?
+            };
+
+            let mut batch: Vec<_>;
+            let batch_size = self.config.batch_size;
+            while !gen.is_complete() {
+                // Move the generator to another thread to produce the next batch
+                // then send it back
+                (gen, batch) = Backend::spawn(move || {
+                    let batch = gen.by_ref().take(batch_size).collect();
+                    (gen, batch)
+                })
+                .await;
 
-            if !batch.is_empty() {
-                sink.send(GarbleMessage::EncryptedGates(batch)).await?;
+                if !batch.is_empty() {
+                    sink.send(GarbleMessage::EncryptedGates(batch)).await?;
+                }
             }
-        }
 
-        let encoded_outputs = gen.outputs()?;
-        let hash = gen.hash();
+            (gen.outputs()?, gen.hash())
+        };
 
         if self.config.encoding_commitments {
             let commitments = encoded_outputs
@@ -347,6 +436,49 @@ impl Generator {
         Ok((encoded_outputs, hash))
     }
 
+    /// Generates a privacy-free garbled circuit, streaming the encrypted gates to the evaluator
+    /// in batches.
+    ///
+    /// The decodings of the inputs are sent to the evaluator first, as it needs to know their
+    /// values to evaluate the circuit.
+    async fn generate_privacy_free<S: Sink<GarbleMessage, Error = std::io::Error> + Unpin>(
+        &self,
+        circ: Arc<Circuit>,
+        delta: Delta,
+        inputs: &[EncodedValue<encoding_state::Full>],
+        sink: &mut S,
+        hash: bool,
+    ) -> Result<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>), GeneratorError> {
+        let mut gen = if hash {
+            PrivacyFreeGeneratorCore::new_with_hasher(circ, delta, inputs)?
+        } else {
+            PrivacyFreeGeneratorCore::new(circ, delta, inputs)?
+        };
+
+        sink.send(GarbleMessage::ValueDecodings(
+            inputs.iter().map(|input| input.decoding()).collect(),
+        ))
+        .await?;
+
+        let mut batch: Vec<_>;
+        let batch_size = self.config.batch_size;
+        while !gen.is_complete() {
+            // Move the generator to another thread to produce the next batch
+            // then send it back
+            (gen, batch) = Backend::spawn(move || {
+                let batch = gen.by_ref().take(batch_size).collect();
+                (gen, batch)
+            })
+            .await;
+
+            if !batch.is_empty() {
+                sink.send(GarbleMessage::PrivacyFreeGates(batch)).await?;
+            }
+        }
+
+        Ok((gen.outputs()?, gen.hash()))
+    }
+
     /// Send value decoding information to the evaluator.
     ///
     /// # Arguments
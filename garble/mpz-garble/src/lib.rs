@@ -20,6 +20,7 @@ pub(crate) mod generator;
 pub(crate) mod internal_circuits;
 pub(crate) mod memory;
 pub mod ot;
+pub mod preprocessed;
 pub mod protocol;
 mod threadpool;
 pub mod value;
@@ -27,6 +28,9 @@ pub mod value;
 pub use evaluator::{Evaluator, EvaluatorConfig, EvaluatorConfigBuilder, EvaluatorError};
 pub use generator::{Generator, GeneratorConfig, GeneratorConfigBuilder, GeneratorError};
 pub use memory::{AssignedValues, ValueMemory};
+pub use preprocessed::{
+    ConsumedExports, EvaluatorPreprocessed, GeneratorPreprocessed, PreprocessedError,
+};
 pub use threadpool::ThreadPool;
 
 use utils::id::NestedId;
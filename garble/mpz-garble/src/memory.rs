@@ -444,6 +444,24 @@ where
     pub(crate) fn contains(&self, id: &ValueId) -> bool {
         self.encodings.contains_key(&id.to_u64().into())
     }
+
+    /// Returns all the encodings, keyed by the `u64` representation of their value id.
+    pub(crate) fn export(&self) -> Vec<(u64, EncodedValue<T>)> {
+        self.encodings
+            .iter()
+            .map(|(id, encoding)| (id.0, encoding.clone()))
+            .collect()
+    }
+
+    /// Creates a memory from encodings which were previously exported.
+    pub(crate) fn import(encodings: Vec<(u64, EncodedValue<T>)>) -> Self {
+        Self {
+            encodings: encodings
+                .into_iter()
+                .map(|(id, encoding)| (EncodingId::new(id), encoding))
+                .collect(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,400 @@
+//! Persistent storage of preprocessed garbled circuits.
+//!
+//! Circuits can be garbled before their inputs are known, with [`Generator::generate`] and
+//! [`Evaluator::receive_garbled_circuit`]. The resulting state of each party can be exported
+//! with [`Generator::export_preprocessed`] and [`Evaluator::export_preprocessed`], serialized
+//! with `to_bytes`, and restored in another process with [`Generator::from_preprocessed`] and
+//! [`Evaluator::from_preprocessed`].
+//!
+//! Values are identified by their IDs, so the restored parties must use the same value IDs as
+//! when the circuits were garbled.
+//!
+//! A generator export must only be restored once, which is enforced by recording its ID in
+//! [`ConsumedExports`] when it is restored.
+//!
+//! # Format
+//!
+//! The serialized state starts with a 4 byte magic, followed by the format version as a little
+//! endian `u16` and a byte identifying the party. The rest is the state encoded with `bincode`.
+//! The format version is incremented whenever the encoding of the state changes.
+
+use std::collections::HashSet;
+
+use mpz_core::hash::Hash;
+use mpz_garble_core::{encoding_state, EncodedValue, EncodingCommitment, EncryptedGate};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::value::{ArrayRef, CircuitRefs, ValueId, ValueRef};
+
+#[cfg(doc)]
+use crate::{Evaluator, Generator};
+
+/// The magic bytes at the start of the serialized state.
+const MAGIC: [u8; 4] = *b"MPZG";
+
+/// The version of the serialized format.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The length of the header of the serialized state.
+const HEADER_LEN: usize = MAGIC.len() + 3;
+
+/// Errors that can occur when deserializing preprocessed state.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum PreprocessedError {
+    #[error("invalid format: {0}")]
+    InvalidFormat(String),
+    #[error("unsupported format version: expected {FORMAT_VERSION}, got {0}")]
+    UnsupportedVersion(u16),
+    #[error("the export {0:?} has already been restored")]
+    ConsumedExport([u8; 16]),
+    #[error(transparent)]
+    SerializationError(#[from] bincode::Error),
+}
+
+/// The party which exported a preprocessed state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+enum Party {
+    Generator = 0,
+    Evaluator = 1,
+}
+
+/// The preprocessed state of a [`Generator`].
+///
+/// # Security
+///
+/// This contains the encoder seed of the generator, from which all of its encodings can be
+/// derived. It must be kept secret from the evaluator.
+///
+/// Each export must be restored at most once. The generator only sends the active encoding of
+/// each input once, but it has no way to know whether another generator restored from the same
+/// export already did. Restoring an export twice and evaluating the same circuit on different
+/// inputs reveals both labels of an input, and with them the global offset of every encoding.
+///
+/// [`Generator::export_preprocessed`] consumes the generator, and every export has a random
+/// [`id`](Self::id) which [`Generator::from_preprocessed`] records in [`ConsumedExports`], so
+/// that an export is not restored again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratorPreprocessed {
+    pub(crate) id: [u8; 16],
+    pub(crate) encoder_seed: [u8; 32],
+    pub(crate) encodings: Vec<(u64, EncodedValue<encoding_state::Full>)>,
+    pub(crate) active: Vec<String>,
+    pub(crate) garbled: Vec<(StoredCircuitRefs, Option<Hash>)>,
+}
+
+impl GeneratorPreprocessed {
+    /// Returns the unique ID of this export.
+    pub fn id(&self) -> [u8; 16] {
+        self.id
+    }
+
+    /// Returns the number of garbled circuits.
+    pub fn len(&self) -> usize {
+        self.garbled.len()
+    }
+
+    /// Returns whether there are no garbled circuits.
+    pub fn is_empty(&self) -> bool {
+        self.garbled.is_empty()
+    }
+
+    /// Serializes the preprocessed state.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(Party::Generator, self)
+    }
+
+    /// Deserializes a preprocessed state.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PreprocessedError> {
+        let preprocessed: Self = from_bytes(Party::Generator, bytes)?;
+        for (refs, _) in &preprocessed.garbled {
+            refs.validate()?;
+        }
+
+        Ok(preprocessed)
+    }
+}
+
+/// The IDs of the [`GeneratorPreprocessed`] exports which have been restored.
+///
+/// [`Generator::from_preprocessed`] rejects the exports whose ID is already recorded. The IDs
+/// must be persisted along with the exports, eg. with `serde`, so that an export is not restored
+/// again after a restart.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ConsumedExports {
+    ids: HashSet<[u8; 16]>,
+}
+
+impl ConsumedExports {
+    /// Returns whether the export with the given ID has been restored.
+    pub fn contains(&self, id: &[u8; 16]) -> bool {
+        self.ids.contains(id)
+    }
+
+    /// Records the export with the given ID as restored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the export has already been restored.
+    pub(crate) fn consume(&mut self, id: [u8; 16]) -> Result<(), PreprocessedError> {
+        if !self.ids.insert(id) {
+            return Err(PreprocessedError::ConsumedExport(id));
+        }
+
+        Ok(())
+    }
+}
+
+/// The preprocessed state of an [`Evaluator`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluatorPreprocessed {
+    pub(crate) garbled: Vec<StoredGarbledCircuit>,
+}
+
+impl EvaluatorPreprocessed {
+    /// Returns the number of garbled circuits.
+    pub fn len(&self) -> usize {
+        self.garbled.len()
+    }
+
+    /// Returns whether there are no garbled circuits.
+    pub fn is_empty(&self) -> bool {
+        self.garbled.is_empty()
+    }
+
+    /// Serializes the preprocessed state.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(Party::Evaluator, self)
+    }
+
+    /// Deserializes a preprocessed state.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PreprocessedError> {
+        let preprocessed: Self = from_bytes(Party::Evaluator, bytes)?;
+        for circuit in &preprocessed.garbled {
+            circuit.refs.validate()?;
+        }
+
+        Ok(preprocessed)
+    }
+}
+
+/// A garbled circuit received by the evaluator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct StoredGarbledCircuit {
+    pub(crate) refs: StoredCircuitRefs,
+    pub(crate) gates: Vec<EncryptedGate>,
+    pub(crate) commitments: Option<Vec<EncodingCommitment>>,
+}
+
+/// References to the inputs and outputs of a circuit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct StoredCircuitRefs {
+    inputs: Vec<StoredValueRef>,
+    outputs: Vec<StoredValueRef>,
+}
+
+impl StoredCircuitRefs {
+    /// Checks that the references are valid, ie. that every array has at least one value.
+    fn validate(&self) -> Result<(), PreprocessedError> {
+        if self
+            .inputs
+            .iter()
+            .chain(&self.outputs)
+            .any(|value| matches!(value, StoredValueRef::Array(ids) if ids.is_empty()))
+        {
+            return Err(PreprocessedError::InvalidFormat(
+                "empty array reference".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&CircuitRefs> for StoredCircuitRefs {
+    fn from(refs: &CircuitRefs) -> Self {
+        Self {
+            inputs: refs.inputs.iter().map(StoredValueRef::from).collect(),
+            outputs: refs.outputs.iter().map(StoredValueRef::from).collect(),
+        }
+    }
+}
+
+impl From<StoredCircuitRefs> for CircuitRefs {
+    fn from(refs: StoredCircuitRefs) -> Self {
+        Self {
+            inputs: refs.inputs.into_iter().map(ValueRef::from).collect(),
+            outputs: refs.outputs.into_iter().map(ValueRef::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum StoredValueRef {
+    Value(String),
+    Array(Vec<String>),
+}
+
+impl From<&ValueRef> for StoredValueRef {
+    fn from(value: &ValueRef) -> Self {
+        match value {
+            ValueRef::Value { id } => StoredValueRef::Value(id.as_ref().to_string()),
+            ValueRef::Array(array) => StoredValueRef::Array(
+                array
+                    .ids()
+                    .iter()
+                    .map(|id| id.as_ref().to_string())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<StoredValueRef> for ValueRef {
+    fn from(value: StoredValueRef) -> Self {
+        match value {
+            StoredValueRef::Value(id) => ValueRef::Value {
+                id: ValueId::new(&id),
+            },
+            StoredValueRef::Array(ids) => ValueRef::Array(ArrayRef::new(
+                ids.iter().map(|id| ValueId::new(id)).collect(),
+            )),
+        }
+    }
+}
+
+fn to_bytes<T: Serialize>(party: Party, state: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN);
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.push(party as u8);
+
+    bincode::serialize_into(&mut bytes, state).expect("state should serialize");
+
+    bytes
+}
+
+fn from_bytes<T: DeserializeOwned>(party: Party, bytes: &[u8]) -> Result<T, PreprocessedError> {
+    if bytes.len() < HEADER_LEN || bytes[..MAGIC.len()] != MAGIC {
+        return Err(PreprocessedError::InvalidFormat(
+            "missing header".to_string(),
+        ));
+    }
+
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != FORMAT_VERSION {
+        return Err(PreprocessedError::UnsupportedVersion(version));
+    }
+
+    if bytes[6] != party as u8 {
+        return Err(PreprocessedError::InvalidFormat(format!(
+            "expected the state of the {:?}",
+            party
+        )));
+    }
+
+    Ok(bincode::deserialize(&bytes[HEADER_LEN..])?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Generator, GeneratorConfigBuilder};
+
+    fn evaluator_preprocessed() -> EvaluatorPreprocessed {
+        EvaluatorPreprocessed {
+            garbled: vec![StoredGarbledCircuit {
+                refs: StoredCircuitRefs {
+                    inputs: vec![StoredValueRef::Array(vec!["a/0".to_string()])],
+                    outputs: vec![StoredValueRef::Value("b".to_string())],
+                },
+                gates: Vec::new(),
+                commitments: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_preprocessed_round_trip() {
+        let bytes = evaluator_preprocessed().to_bytes();
+        let preprocessed = EvaluatorPreprocessed::from_bytes(&bytes).unwrap();
+
+        assert_eq!(preprocessed.len(), 1);
+        assert_eq!(preprocessed.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_preprocessed_invalid_header() {
+        let mut bytes = evaluator_preprocessed().to_bytes();
+
+        assert!(matches!(
+            GeneratorPreprocessed::from_bytes(&bytes).unwrap_err(),
+            PreprocessedError::InvalidFormat(_)
+        ));
+
+        bytes[4] = 42;
+        assert!(matches!(
+            EvaluatorPreprocessed::from_bytes(&bytes).unwrap_err(),
+            PreprocessedError::UnsupportedVersion(42)
+        ));
+
+        bytes[0] = 0;
+        assert!(matches!(
+            EvaluatorPreprocessed::from_bytes(&bytes).unwrap_err(),
+            PreprocessedError::InvalidFormat(_)
+        ));
+    }
+
+    #[test]
+    fn test_generator_preprocessed_id() {
+        let export = || {
+            Generator::new(
+                GeneratorConfigBuilder::default().build().unwrap(),
+                [0u8; 32],
+            )
+            .export_preprocessed()
+        };
+
+        let preprocessed = export();
+        let restored = GeneratorPreprocessed::from_bytes(&preprocessed.to_bytes()).unwrap();
+
+        assert_eq!(restored.id(), preprocessed.id());
+        assert_ne!(export().id(), preprocessed.id());
+    }
+
+    #[test]
+    fn test_generator_preprocessed_consumed() {
+        let config = || GeneratorConfigBuilder::default().build().unwrap();
+        let bytes = Generator::new(config(), [0u8; 32])
+            .export_preprocessed()
+            .to_bytes();
+
+        let mut consumed = ConsumedExports::default();
+        let preprocessed = GeneratorPreprocessed::from_bytes(&bytes).unwrap();
+        let id = preprocessed.id();
+        assert!(Generator::from_preprocessed(config(), preprocessed, &mut consumed).is_ok());
+        assert!(consumed.contains(&id));
+
+        // The consumed IDs are persisted, so the export is rejected after a restart.
+        let mut consumed: ConsumedExports =
+            bincode::deserialize(&bincode::serialize(&consumed).unwrap()).unwrap();
+        let preprocessed = GeneratorPreprocessed::from_bytes(&bytes).unwrap();
+        assert!(matches!(
+            Generator::from_preprocessed(config(), preprocessed, &mut consumed),
+            Err(PreprocessedError::ConsumedExport(consumed_id)) if consumed_id == id
+        ));
+    }
+
+    #[test]
+    fn test_preprocessed_empty_array() {
+        let mut preprocessed = evaluator_preprocessed();
+        preprocessed.garbled[0].refs.inputs = vec![StoredValueRef::Array(Vec::new())];
+
+        assert!(matches!(
+            EvaluatorPreprocessed::from_bytes(&preprocessed.to_bytes()).unwrap_err(),
+            PreprocessedError::InvalidFormat(_)
+        ));
+    }
+}
@@ -0,0 +1,45 @@
+use mpz_circuits::types::TypeError;
+use mpz_garble_core::authenticated::AuthError;
+
+use crate::{
+    value::{ValueId, ValueRef},
+    DecodeError, ExecutionError,
+};
+
+/// Errors that can occur during the authenticated garbling protocol.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum AuthenticatedError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+    #[error(transparent)]
+    CointossError(#[from] mpz_core::cointoss::CointossError),
+    #[error(transparent)]
+    AuthError(#[from] AuthError),
+    #[error("value is not assigned: {0:?}")]
+    UnassignedValue(ValueId),
+    #[error("value has not been computed: {0:?}")]
+    MissingValue(ValueRef),
+    #[error(transparent)]
+    TypeError(#[from] TypeError),
+}
+
+impl From<AuthenticatedError> for ExecutionError {
+    fn from(err: AuthenticatedError) -> Self {
+        match err {
+            AuthenticatedError::IOError(err) => ExecutionError::IOError(err),
+            err => ExecutionError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<AuthenticatedError> for DecodeError {
+    fn from(err: AuthenticatedError) -> Self {
+        match err {
+            AuthenticatedError::IOError(err) => DecodeError::IOError(err),
+            err => DecodeError::ProtocolError(Box::new(err)),
+        }
+    }
+}
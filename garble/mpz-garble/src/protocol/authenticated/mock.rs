@@ -0,0 +1,52 @@
+//! Mocked authenticated garbling VMs for testing
+
+use mpz_core::Block;
+use mpz_ot::ideal::{
+    ideal_random_cot_shared_pair, IdealSharedRandomCOTReceiver, IdealSharedRandomCOTSender,
+};
+use rand::{thread_rng, Rng};
+use utils_aio::mux::mock::MockMuxChannelFactory;
+
+use crate::config::Role;
+
+use super::{vm::AuthenticatedVm, AuthenticatedThread};
+
+/// Mock authenticated garbling Leader VM.
+pub type MockLeader = AuthenticatedVm<IdealSharedRandomCOTSender, IdealSharedRandomCOTReceiver>;
+/// Mock authenticated garbling Leader thread.
+pub type MockLeaderThread =
+    AuthenticatedThread<IdealSharedRandomCOTSender, IdealSharedRandomCOTReceiver>;
+/// Mock authenticated garbling Follower VM.
+pub type MockFollower = AuthenticatedVm<IdealSharedRandomCOTSender, IdealSharedRandomCOTReceiver>;
+/// Mock authenticated garbling Follower thread.
+pub type MockFollowerThread =
+    AuthenticatedThread<IdealSharedRandomCOTSender, IdealSharedRandomCOTReceiver>;
+
+/// Create a pair of mocked authenticated garbling VMs
+pub async fn create_mock_authenticated_vm(id: &str) -> (MockLeader, MockFollower) {
+    let mux_factory = MockMuxChannelFactory::new();
+    let mut rng = thread_rng();
+
+    let (leader_cot_send, follower_cot_recv) =
+        ideal_random_cot_shared_pair(rng.gen(), Block::random(&mut rng));
+    let (follower_cot_send, leader_cot_recv) =
+        ideal_random_cot_shared_pair(rng.gen(), Block::random(&mut rng));
+
+    let leader = AuthenticatedVm::new(
+        id,
+        Role::Leader,
+        Box::new(mux_factory.clone()),
+        leader_cot_send,
+        leader_cot_recv,
+    );
+
+    let follower = AuthenticatedVm::new(
+        id,
+        Role::Follower,
+        Box::new(mux_factory),
+        follower_cot_send,
+        follower_cot_recv,
+    );
+
+    (leader, follower)
+}
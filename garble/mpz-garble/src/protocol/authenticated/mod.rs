@@ -0,0 +1,711 @@
+//! An implementation of authenticated garbling, secure against malicious adversaries.
+//!
+//! The leader garbles the circuits and the follower evaluates them, following the authenticated
+//! garbling protocol from [WRK17](https://eprint.iacr.org/2017/030), see
+//! [`mpz_garble_core::authenticated`]. The authenticated shares of the wire masks are derived
+//! from random correlated OTs in both directions, eg. KOS or Ferret, where the global key of each
+//! party is the delta of its random COT sender.
+//!
+//! Unlike [DEAP](crate::protocol::deap), the protocol does not leak any bit of the inputs of an
+//! honest party to a malicious party, at the cost of generating authenticated AND triples for
+//! every executed circuit.
+
+mod error;
+mod memory;
+pub mod mock;
+mod vm;
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+};
+
+use futures::{Sink, SinkExt, Stream};
+use itybity::IntoBits;
+use mpz_circuits::{
+    types::{BinaryRepr, Value},
+    Circuit,
+};
+use mpz_core::{cointoss, Block};
+use mpz_garble_core::{
+    authenticated::{
+        bucket_size, decode_label, encode_bit, AuthError, AuthShare, Buckets, LeakyAnd, Party,
+        Triple, WireMasks,
+    },
+    msg::GarbleMessage,
+    Label,
+};
+use mpz_ot::{RandomCOTReceiverShared, RandomCOTSenderShared};
+use rand::thread_rng;
+use utils_aio::expect_msg_or_err;
+
+use crate::{
+    config::Role,
+    memory::{AssignedValues, ValueMemory},
+    value::{ValueId, ValueRef},
+};
+
+pub use error::AuthenticatedError;
+pub use vm::{AuthenticatedThread, AuthenticatedVm};
+
+/// Sends a message to the other party while receiving the same kind of message from it.
+macro_rules! exchange {
+    ($sink:expr, $stream:expr, $variant:path, $msg:expr) => {
+        futures::try_join!($sink.send($variant($msg)), async {
+            expect_msg_or_err!($stream, $variant)
+        })
+        .map(|(_, msg)| msg)
+    };
+}
+
+/// The authenticated garbling protocol.
+pub struct Authenticated {
+    role: Role,
+    delta: Block,
+    state: Mutex<State>,
+}
+
+opaque_debug::implement!(Authenticated);
+
+#[derive(Default)]
+struct State {
+    memory: ValueMemory,
+    /// Wires of the values which have been set up or computed.
+    wires: HashMap<ValueId, Wires>,
+    /// Binary representations of the values, from the circuits they are used in.
+    reprs: HashMap<ValueId, BinaryRepr>,
+}
+
+impl State {
+    /// Records the binary representation of each element of a value.
+    fn insert_repr(&mut self, value: &ValueRef, repr: &BinaryRepr) {
+        match (value, repr) {
+            (ValueRef::Array(_), BinaryRepr::Array(elems)) => {
+                for (id, elem) in value.iter().zip(elems) {
+                    self.reprs.insert(id.clone(), elem.clone());
+                }
+            }
+            _ => {
+                for id in value.iter() {
+                    self.reprs.insert(id.clone(), repr.clone());
+                }
+            }
+        }
+    }
+
+    /// Returns the binary representation of a value.
+    fn get_repr(&self, value: &ValueRef) -> Option<BinaryRepr> {
+        match value {
+            ValueRef::Value { id } => self.reprs.get(id).cloned(),
+            ValueRef::Array(_) => value
+                .iter()
+                .map(|id| self.reprs.get(id).cloned())
+                .collect::<Option<Vec<_>>>()
+                .map(BinaryRepr::Array),
+        }
+    }
+}
+
+/// The wires of a value.
+#[derive(Debug, Clone, Default)]
+struct Wires {
+    /// Shares of the masks of the wires.
+    masks: Vec<AuthShare>,
+    /// The labels encoding `0` for the garbler, or the active labels for the evaluator.
+    labels: Vec<Label>,
+    /// The masked bits of the wires, only known to the evaluator.
+    masked: Vec<bool>,
+}
+
+impl Wires {
+    fn extend(&mut self, other: &Wires) {
+        self.masks.extend_from_slice(&other.masks);
+        self.labels.extend_from_slice(&other.labels);
+        self.masked.extend_from_slice(&other.masked);
+    }
+
+    /// Returns the wires in the provided range.
+    fn slice(&self, range: std::ops::Range<usize>) -> Wires {
+        Wires {
+            masks: self.masks[range.clone()].to_vec(),
+            labels: self.labels[range.clone()].to_vec(),
+            masked: self.masked.get(range).unwrap_or_default().to_vec(),
+        }
+    }
+}
+
+/// An input value which has not been set up yet.
+enum Input {
+    Public(Vec<bool>),
+    /// A private input of this party.
+    Private(Vec<bool>),
+    /// A private input of the other party, with its length in bits.
+    Blind(usize),
+}
+
+impl Input {
+    fn len(&self) -> usize {
+        match self {
+            Input::Public(bits) | Input::Private(bits) => bits.len(),
+            Input::Blind(len) => *len,
+        }
+    }
+}
+
+impl Authenticated {
+    /// Creates a new authenticated garbling protocol instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party, the leader is the garbler.
+    /// * `delta` - The global key of this party, ie. the delta of its random COT sender.
+    pub fn new(role: Role, delta: Block) -> Self {
+        Self {
+            role,
+            delta,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> impl DerefMut<Target = State> + '_ {
+        self.state.lock().unwrap()
+    }
+
+    fn party(&self) -> Party {
+        match self.role {
+            Role::Leader => Party::Garbler,
+            Role::Follower => Party::Evaluator,
+        }
+    }
+
+    /// Executes a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the circuit.
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs to the circuit.
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    /// * `cot_send` - The random COT sender.
+    /// * `cot_recv` - The random COT receiver.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute<T, U, COTS, COTR>(
+        &self,
+        id: &str,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+        cot_send: &COTS,
+        cot_recv: &COTR,
+    ) -> Result<(), AuthenticatedError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        COTS: RandomCOTSenderShared<Block>,
+        COTR: RandomCOTReceiverShared<bool, Block>,
+    {
+        let new_inputs = self.new_inputs(inputs)?;
+        let fresh_count = new_inputs
+            .iter()
+            .filter(|(_, input)| !matches!(input, Input::Public(_)))
+            .map(|(_, input)| input.len())
+            .sum();
+
+        let (fresh_masks, and_masks, triples) = self
+            .preprocess(
+                id,
+                fresh_count,
+                circ.and_count(),
+                sink,
+                stream,
+                cot_send,
+                cot_recv,
+            )
+            .await?;
+
+        let new_wires = self
+            .setup_inputs(new_inputs, fresh_masks, sink, stream)
+            .await?;
+
+        let input_wires = {
+            let mut state = self.state();
+            state.wires.extend(new_wires);
+            for (value, repr) in inputs
+                .iter()
+                .zip(circ.inputs())
+                .chain(outputs.iter().zip(circ.outputs()))
+            {
+                state.insert_repr(value, repr);
+            }
+
+            let mut input_wires = Wires::default();
+            for id in inputs.iter().flat_map(|value| value.iter()) {
+                input_wires.extend(state.wires.get(id).expect("input wires are set up"));
+            }
+            input_wires
+        };
+
+        let masks = WireMasks::new(
+            circ.clone(),
+            self.party(),
+            self.delta,
+            &input_wires.masks,
+            &and_masks,
+        )?;
+
+        let openings = exchange!(
+            sink,
+            stream,
+            GarbleMessage::Openings,
+            masks.openings(&triples)?
+        )?;
+        let circ = masks.finish(&triples, &openings)?;
+
+        let output_masks = circ.output_masks();
+        let (output_labels, output_masked) = match self.role {
+            Role::Leader => {
+                let (gates, labels) = circ.garble(&input_wires.labels, &mut thread_rng())?;
+                sink.send(GarbleMessage::AuthenticatedGates(gates)).await?;
+
+                (labels, vec![Vec::new(); output_masks.len()])
+            }
+            Role::Follower => {
+                let gates = expect_msg_or_err!(stream, GarbleMessage::AuthenticatedGates)?;
+                let (masked, labels) =
+                    circ.evaluate(&input_wires.masked, &input_wires.labels, &gates)?;
+
+                (labels, masked)
+            }
+        };
+
+        let mut state = self.state();
+        for (((value, masks), labels), masked) in outputs
+            .iter()
+            .zip(output_masks)
+            .zip(output_labels)
+            .zip(output_masked)
+        {
+            let wires = Wires {
+                masks,
+                labels,
+                masked,
+            };
+            let len = wires.masks.len() / value.len();
+            for (i, id) in value.iter().enumerate() {
+                state
+                    .wires
+                    .insert(id.clone(), wires.slice(i * len..(i + 1) * len));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the provided values, revealing the plaintext values to both parties.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to decode.
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    pub async fn decode<T, U>(
+        &self,
+        values: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<Vec<Value>, AuthenticatedError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        let (reprs, wires) = {
+            let state = self.state();
+            let mut wires = Wires::default();
+            let mut reprs = Vec::with_capacity(values.len());
+            for value in values {
+                for id in value.iter() {
+                    wires.extend(
+                        state
+                            .wires
+                            .get(id)
+                            .ok_or_else(|| AuthenticatedError::MissingValue(value.clone()))?,
+                    );
+                }
+                reprs.push(
+                    state
+                        .get_repr(value)
+                        .ok_or_else(|| AuthenticatedError::MissingValue(value.clone()))?,
+                );
+            }
+
+            (reprs, wires)
+        };
+
+        let bits = match self.role {
+            Role::Leader => {
+                let openings = expect_msg_or_err!(stream, GarbleMessage::Openings)?;
+                let active = expect_msg_or_err!(stream, GarbleMessage::Labels)?;
+                check_count(wires.masks.len(), openings.len())?;
+                check_count(wires.masks.len(), active.len())?;
+
+                let bits = wires
+                    .masks
+                    .iter()
+                    .zip(&openings)
+                    .zip(wires.labels.iter().zip(&active))
+                    .map(|((mask, opening), (low, active))| {
+                        Ok(mask.open(opening, &self.delta)?
+                            ^ decode_label(low, active, &self.delta)?)
+                    })
+                    .collect::<Result<Vec<_>, AuthError>>()?;
+
+                sink.send(GarbleMessage::Openings(
+                    wires.masks.iter().map(AuthShare::opening).collect(),
+                ))
+                .await?;
+
+                bits
+            }
+            Role::Follower => {
+                sink.send(GarbleMessage::Openings(
+                    wires.masks.iter().map(AuthShare::opening).collect(),
+                ))
+                .await?;
+                sink.send(GarbleMessage::Labels(wires.labels)).await?;
+
+                let openings = expect_msg_or_err!(stream, GarbleMessage::Openings)?;
+                check_count(wires.masks.len(), openings.len())?;
+
+                wires
+                    .masks
+                    .iter()
+                    .zip(&openings)
+                    .zip(&wires.masked)
+                    .map(|((mask, opening), masked)| Ok(mask.open(opening, &self.delta)? ^ masked))
+                    .collect::<Result<Vec<_>, AuthError>>()?
+            }
+        };
+
+        let mut bits = bits.as_slice();
+        Ok(reprs
+            .iter()
+            .map(|repr| {
+                let (value, rest) = bits.split_at(repr.len());
+                bits = rest;
+                repr.from_bin_repr(value)
+            })
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Returns the inputs which have not been set up yet, in order.
+    fn new_inputs(&self, inputs: &[ValueRef]) -> Result<Vec<(ValueId, Input)>, AuthenticatedError> {
+        let mut state = self.state();
+        let AssignedValues {
+            public,
+            private,
+            blind,
+        } = state.memory.drain_assigned(inputs);
+
+        let mut assigned = public
+            .into_iter()
+            .map(|(id, value)| (id, Input::Public(value.into_lsb0_vec())))
+            .chain(
+                private
+                    .into_iter()
+                    .map(|(id, value)| (id, Input::Private(value.into_lsb0_vec()))),
+            )
+            .chain(
+                blind
+                    .into_iter()
+                    .map(|(id, typ)| (id, Input::Blind(typ.len()))),
+            )
+            .collect::<HashMap<_, _>>();
+
+        let mut new_inputs = Vec::new();
+        let mut new_ids = HashSet::new();
+        for id in inputs.iter().flat_map(|value| value.iter()) {
+            if state.wires.contains_key(id) || new_ids.contains(id) {
+                continue;
+            }
+
+            let input = assigned
+                .remove(id)
+                .ok_or_else(|| AuthenticatedError::UnassignedValue(id.clone()))?;
+            new_ids.insert(id.clone());
+            new_inputs.push((id.clone(), input));
+        }
+
+        Ok(new_inputs)
+    }
+
+    /// Generates the authenticated shares of the masks of the new private inputs and of the
+    /// outputs of the AND gates, and the authenticated triples.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    async fn preprocess<T, U, COTS, COTR>(
+        &self,
+        id: &str,
+        input_count: usize,
+        and_count: usize,
+        sink: &mut T,
+        stream: &mut U,
+        cot_send: &COTS,
+        cot_recv: &COTR,
+    ) -> Result<(Vec<AuthShare>, Vec<AuthShare>, Vec<Triple>), AuthenticatedError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        COTS: RandomCOTSenderShared<Block>,
+        COTR: RandomCOTReceiverShared<bool, Block>,
+    {
+        let bucket_size = bucket_size(and_count);
+        let leaky_count = bucket_size * and_count;
+        let count = input_count + and_count + 3 * leaky_count;
+
+        let (send_id, recv_id) = match self.role {
+            Role::Leader => (format!("{id}/0"), format!("{id}/1")),
+            Role::Follower => (format!("{id}/1"), format!("{id}/0")),
+        };
+
+        let (keys, (bits, macs)) = futures::try_join!(
+            cot_send.send_random_correlated(&send_id, count),
+            cot_recv.receive_random_correlated(&recv_id, count)
+        )?;
+
+        let mut shares = AuthShare::from_cots(&bits, &macs, &keys);
+        let r = shares.split_off(input_count + and_count + 2 * leaky_count);
+        let y = shares.split_off(input_count + and_count + leaky_count);
+        let x = shares.split_off(input_count + and_count);
+        let and_masks = shares.split_off(input_count);
+        let input_masks = shares;
+
+        // Generate the leaky triples.
+        let leaky_and = LeakyAnd::new(self.party(), self.delta, x, y, r);
+        let corrections = exchange!(
+            sink,
+            stream,
+            GarbleMessage::LeakyAndCorrections,
+            leaky_and.corrections().to_vec()
+        )?;
+        let (leaky_and, derandomize) = leaky_and.receive_corrections(&corrections)?;
+        let derandomize = exchange!(sink, stream, GarbleMessage::Bits, derandomize)?;
+        let (leaky_and, digest) = leaky_and.receive_derandomize(&derandomize)?;
+        let digest = exchange!(sink, stream, GarbleMessage::TripleCheck, digest)?;
+        let leaky_triples = leaky_and.check(digest)?;
+
+        // The leaky triples must be permuted after they are generated.
+        let seed = self.cointoss(sink, stream).await?;
+
+        let buckets = Buckets::new(self.delta, leaky_triples, bucket_size, seed);
+        let openings = exchange!(sink, stream, GarbleMessage::Openings, buckets.openings())?;
+        let triples = buckets.combine(&openings)?;
+
+        Ok((input_masks, and_masks, triples))
+    }
+
+    /// Samples a random seed jointly with the other party.
+    async fn cointoss<T, U>(
+        &self,
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<[u8; 32], AuthenticatedError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        let seeds = vec![
+            Block::random(&mut thread_rng()),
+            Block::random(&mut thread_rng()),
+        ];
+
+        let blocks = match self.role {
+            Role::Leader => {
+                let (sender, commitment) = cointoss::Sender::new(seeds).send();
+                sink.send(GarbleMessage::CointossCommit(commitment)).await?;
+                let payload = expect_msg_or_err!(stream, GarbleMessage::CointossReceiverPayload)?;
+                let (blocks, payload) = sender.finalize(payload)?;
+                sink.send(GarbleMessage::CointossSenderPayload(payload))
+                    .await?;
+
+                blocks
+            }
+            Role::Follower => {
+                let commitment = expect_msg_or_err!(stream, GarbleMessage::CointossCommit)?;
+                let (receiver, payload) = cointoss::Receiver::new(seeds).reveal(commitment)?;
+                sink.send(GarbleMessage::CointossReceiverPayload(payload))
+                    .await?;
+                let payload = expect_msg_or_err!(stream, GarbleMessage::CointossSenderPayload)?;
+
+                receiver.finalize(payload)?
+            }
+        };
+
+        let mut seed = [0u8; 32];
+        seed[..16].copy_from_slice(&blocks[0].to_bytes());
+        seed[16..].copy_from_slice(&blocks[1].to_bytes());
+
+        Ok(seed)
+    }
+
+    /// Sets up the wires of the new inputs.
+    ///
+    /// The masked bits of the private inputs are revealed to the evaluator by opening the masks
+    /// to their owner, and the garbler sends the active labels of every new input.
+    async fn setup_inputs<T, U>(
+        &self,
+        inputs: Vec<(ValueId, Input)>,
+        masks: Vec<AuthShare>,
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<Vec<(ValueId, Wires)>, AuthenticatedError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        let mut masks = masks.into_iter();
+        let inputs = inputs
+            .into_iter()
+            .map(|(id, input)| {
+                let input_masks = match &input {
+                    Input::Public(bits) => vec![AuthShare::default(); bits.len()],
+                    input => masks.by_ref().take(input.len()).collect(),
+                };
+                (id, input, input_masks)
+            })
+            .collect::<Vec<_>>();
+
+        let private_masks = inputs
+            .iter()
+            .filter(|(_, input, _)| matches!(input, Input::Private(_)))
+            .flat_map(|(_, _, masks)| masks)
+            .copied()
+            .collect::<Vec<_>>();
+        let blind_openings = inputs
+            .iter()
+            .filter(|(_, input, _)| matches!(input, Input::Blind(_)))
+            .flat_map(|(_, _, masks)| masks.iter().map(AuthShare::opening))
+            .collect::<Vec<_>>();
+
+        let blind_count = blind_openings.len();
+
+        // The evaluator opens its masks of the inputs of the garbler first.
+        let openings = match self.role {
+            Role::Leader => {
+                let openings = expect_msg_or_err!(stream, GarbleMessage::Openings)?;
+                sink.send(GarbleMessage::Openings(blind_openings)).await?;
+                openings
+            }
+            Role::Follower => {
+                sink.send(GarbleMessage::Openings(blind_openings)).await?;
+                expect_msg_or_err!(stream, GarbleMessage::Openings)?
+            }
+        };
+        check_count(private_masks.len(), openings.len())?;
+
+        let private_values = inputs
+            .iter()
+            .filter_map(|(_, input, _)| match input {
+                Input::Private(bits) => Some(bits),
+                _ => None,
+            })
+            .flatten();
+        let private_masked = private_values
+            .zip(private_masks.iter().zip(&openings))
+            .map(|(bit, (mask, opening))| Ok(bit ^ mask.open(opening, &self.delta)?))
+            .collect::<Result<Vec<_>, AuthError>>()?;
+
+        let blind_masked = match self.role {
+            Role::Leader => {
+                let blind_masked = expect_msg_or_err!(stream, GarbleMessage::Bits)?;
+                sink.send(GarbleMessage::Bits(private_masked.clone()))
+                    .await?;
+                blind_masked
+            }
+            Role::Follower => {
+                sink.send(GarbleMessage::Bits(private_masked.clone()))
+                    .await?;
+                expect_msg_or_err!(stream, GarbleMessage::Bits)?
+            }
+        };
+        check_count(blind_count, blind_masked.len())?;
+
+        // The masked bits of every new input, in order.
+        let mut private_masked = private_masked.into_iter();
+        let mut blind_masked = blind_masked.into_iter();
+        let masked = inputs
+            .iter()
+            .map(|(_, input, _)| match input {
+                Input::Public(bits) => bits.clone(),
+                Input::Private(bits) => private_masked.by_ref().take(bits.len()).collect(),
+                Input::Blind(len) => blind_masked.by_ref().take(*len).collect(),
+            })
+            .collect::<Vec<Vec<bool>>>();
+
+        let labels = match self.role {
+            Role::Leader => {
+                let low = masked
+                    .iter()
+                    .map(|masked| {
+                        masked
+                            .iter()
+                            .map(|_| Label::new(Block::random(&mut thread_rng())))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+
+                let active = low
+                    .iter()
+                    .flatten()
+                    .zip(masked.iter().flatten())
+                    .map(|(low, bit)| encode_bit(low, *bit, &self.delta))
+                    .collect();
+                sink.send(GarbleMessage::Labels(active)).await?;
+
+                low
+            }
+            Role::Follower => {
+                let active = expect_msg_or_err!(stream, GarbleMessage::Labels)?;
+                check_count(masked.iter().map(Vec::len).sum(), active.len())?;
+
+                let mut active = active.into_iter();
+                masked
+                    .iter()
+                    .map(|masked| active.by_ref().take(masked.len()).collect())
+                    .collect()
+            }
+        };
+
+        Ok(inputs
+            .into_iter()
+            .zip(masked)
+            .zip(labels)
+            .map(|(((id, _, masks), masked), labels)| {
+                let masked = match self.role {
+                    Role::Leader => Vec::new(),
+                    Role::Follower => masked,
+                };
+
+                (
+                    id,
+                    Wires {
+                        masks,
+                        labels,
+                        masked,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+fn check_count(expected: usize, actual: usize) -> Result<(), AuthError> {
+    if expected != actual {
+        return Err(AuthError::CountMismatch { expected, actual });
+    }
+
+    Ok(())
+}
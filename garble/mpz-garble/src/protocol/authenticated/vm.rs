@@ -0,0 +1,397 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Weak},
+};
+
+use async_trait::async_trait;
+use futures::{
+    stream::{SplitSink, SplitStream},
+    StreamExt, TryFutureExt,
+};
+
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_core::Block;
+use mpz_garble_core::msg::GarbleMessage;
+use mpz_ot::{RandomCOTReceiverShared, RandomCOTSenderShared};
+use utils::id::NestedId;
+use utils_aio::{duplex::Duplex, mux::MuxChannel};
+
+use crate::{
+    config::{Role, Visibility},
+    value::ValueRef,
+    Decode, DecodeError, Execute, ExecutionError, Memory, MemoryError, Thread, Vm, VmError,
+};
+
+use super::Authenticated;
+
+type ChannelFactory = Box<dyn MuxChannel<GarbleMessage> + Send + 'static>;
+type GarbleChannel = Box<dyn Duplex<GarbleMessage>>;
+
+/// An authenticated garbling Vm.
+pub struct AuthenticatedVm<COTS, COTR> {
+    /// The id of the vm.
+    id: NestedId,
+    /// Channel factory used to create new channels for new threads.
+    channel_factory: ChannelFactory,
+    /// The random COT sender.
+    cot_send: Arc<COTS>,
+    /// The random COT receiver.
+    cot_recv: Arc<COTR>,
+    /// The protocol instance.
+    ///
+    /// The vm is the only owner of a strong reference to the instance.
+    protocol: Arc<Authenticated>,
+    /// The set of threads spawned by this vm.
+    threads: HashSet<NestedId>,
+}
+
+impl<COTS, COTR> AuthenticatedVm<COTS, COTR>
+where
+    COTS: RandomCOTSenderShared<Block>,
+    COTR: RandomCOTReceiverShared<bool, Block>,
+{
+    /// Create a new authenticated garbling Vm.
+    ///
+    /// The global key of this party is the delta of its random COT sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the vm.
+    /// * `role` - The role of the vm, the leader is the garbler.
+    /// * `channel_factory` - The channel factory used to create new channels for new threads.
+    /// * `cot_send` - The random COT sender.
+    /// * `cot_recv` - The random COT receiver.
+    pub fn new(
+        id: &str,
+        role: Role,
+        channel_factory: ChannelFactory,
+        cot_send: COTS,
+        cot_recv: COTR,
+    ) -> Self {
+        let delta = cot_send.delta();
+
+        Self {
+            id: NestedId::new(id),
+            channel_factory,
+            cot_send: Arc::new(cot_send),
+            cot_recv: Arc::new(cot_recv),
+            protocol: Arc::new(Authenticated::new(role, delta)),
+            threads: HashSet::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<COTS, COTR> Vm for AuthenticatedVm<COTS, COTR>
+where
+    COTS: RandomCOTSenderShared<Block> + Send + Sync + 'static,
+    COTR: RandomCOTReceiverShared<bool, Block> + Send + Sync + 'static,
+{
+    type Thread = AuthenticatedThread<COTS, COTR>;
+
+    async fn new_thread(&mut self, id: &str) -> Result<AuthenticatedThread<COTS, COTR>, VmError> {
+        let thread_id = self.id.append_string(id);
+
+        if !self.threads.insert(thread_id.clone()) {
+            return Err(VmError::ThreadAlreadyExists(thread_id.to_string()));
+        }
+
+        let channel = self
+            .channel_factory
+            .get_channel(&thread_id.to_string())
+            .await?;
+
+        Ok(AuthenticatedThread::new(
+            thread_id,
+            channel,
+            Arc::downgrade(&self.protocol),
+            self.cot_send.clone(),
+            self.cot_recv.clone(),
+        ))
+    }
+}
+
+/// An authenticated garbling thread.
+pub struct AuthenticatedThread<COTS, COTR> {
+    /// The thread id.
+    _id: NestedId,
+    /// The current operation id.
+    op_id: NestedId,
+    /// Reference to the protocol instance.
+    protocol: Weak<Authenticated>,
+    /// The random COT sender.
+    cot_send: Arc<COTS>,
+    /// The random COT receiver.
+    cot_recv: Arc<COTR>,
+    /// The duplex channel sink to the peer.
+    sink: SplitSink<GarbleChannel, GarbleMessage>,
+    /// The duplex channel stream from the peer.
+    stream: SplitStream<GarbleChannel>,
+}
+
+impl<COTS, COTR> AuthenticatedThread<COTS, COTR> {
+    fn new(
+        id: NestedId,
+        channel: GarbleChannel,
+        protocol: Weak<Authenticated>,
+        cot_send: Arc<COTS>,
+        cot_recv: Arc<COTR>,
+    ) -> Self {
+        let (sink, stream) = channel.split();
+        let op_id = id.append_counter();
+        Self {
+            _id: id,
+            op_id,
+            protocol,
+            cot_send,
+            cot_recv,
+            sink,
+            stream,
+        }
+    }
+
+    fn protocol(&self) -> Arc<Authenticated> {
+        self.protocol
+            .upgrade()
+            .expect("instance should not be dropped")
+    }
+}
+
+impl<COTS, COTR> Thread for AuthenticatedThread<COTS, COTR> {}
+
+impl<COTS, COTR> Memory for AuthenticatedThread<COTS, COTR> {
+    fn new_input_with_type(
+        &self,
+        id: &str,
+        typ: ValueType,
+        visibility: Visibility,
+    ) -> Result<ValueRef, MemoryError> {
+        self.protocol().new_input_with_type(id, typ, visibility)
+    }
+
+    fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
+        self.protocol().new_output_with_type(id, typ)
+    }
+
+    fn assign(&self, value_ref: &ValueRef, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol().assign(value_ref, value)
+    }
+
+    fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol().assign_by_id(id, value)
+    }
+
+    fn get_value(&self, id: &str) -> Option<ValueRef> {
+        self.protocol().get_value(id)
+    }
+
+    fn get_value_type(&self, value_ref: &ValueRef) -> ValueType {
+        self.protocol().get_value_type(value_ref)
+    }
+
+    fn get_value_type_by_id(&self, id: &str) -> Option<ValueType> {
+        self.protocol().get_value_type_by_id(id)
+    }
+}
+
+#[async_trait]
+impl<COTS, COTR> Execute for AuthenticatedThread<COTS, COTR>
+where
+    COTS: RandomCOTSenderShared<Block> + Send + Sync,
+    COTR: RandomCOTReceiverShared<bool, Block> + Send + Sync,
+{
+    async fn execute(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), ExecutionError> {
+        self.protocol()
+            .execute(
+                &self.op_id.increment_in_place().to_string(),
+                circ,
+                inputs,
+                outputs,
+                &mut self.sink,
+                &mut self.stream,
+                &*self.cot_send,
+                &*self.cot_recv,
+            )
+            .map_err(ExecutionError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<COTS, COTR> Decode for AuthenticatedThread<COTS, COTR>
+where
+    COTS: Send + Sync,
+    COTR: Send + Sync,
+{
+    async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol()
+            .decode(values, &mut self.sink, &mut self.stream)
+            .map_err(DecodeError::from)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::circuits::AES128;
+
+    use crate::protocol::authenticated::mock::create_mock_authenticated_vm;
+
+    #[tokio::test]
+    async fn test_vm() {
+        let (mut leader_vm, mut follower_vm) = create_mock_authenticated_vm("test_vm").await;
+
+        let mut leader_thread = leader_vm.new_thread("test_thread").await.unwrap();
+        let mut follower_thread = follower_vm.new_thread("test_thread").await.unwrap();
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader_thread.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader_thread.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader_thread.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader_thread.assign(&key_ref, key).unwrap();
+
+            async move {
+                leader_thread
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        std::slice::from_ref(&ciphertext_ref),
+                    )
+                    .await
+                    .unwrap();
+
+                leader_thread.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower_thread.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower_thread
+                .new_private_input::<[u8; 16]>("msg")
+                .unwrap();
+            let ciphertext_ref = follower_thread
+                .new_output::<[u8; 16]>("ciphertext")
+                .unwrap();
+
+            follower_thread.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower_thread
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        std::slice::from_ref(&ciphertext_ref),
+                    )
+                    .await
+                    .unwrap();
+
+                follower_thread.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        let expected: [u8; 16] = {
+            use aes::{
+                cipher::{BlockEncrypt, KeyInit},
+                Aes128,
+            };
+
+            let mut msg = msg.into();
+            Aes128::new_from_slice(&key)
+                .unwrap()
+                .encrypt_block(&mut msg);
+            msg.into()
+        };
+
+        assert_eq!(leader_result, follower_result);
+        assert_eq!(leader_result, vec![Value::from(expected)]);
+    }
+
+    #[tokio::test]
+    async fn test_vm_chained() {
+        let (mut leader_vm, mut follower_vm) = create_mock_authenticated_vm("test_vm").await;
+
+        let mut leader_thread = leader_vm.new_thread("test_thread").await.unwrap();
+        let mut follower_thread = follower_vm.new_thread("test_thread").await.unwrap();
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        // Encrypts the message twice, the second time with the ciphertext as the key and a
+        // public message.
+        async fn run<T: Thread + Execute + Decode>(thread: &mut T, key: Option<[u8; 16]>) -> Value {
+            let key_ref = if key.is_some() {
+                thread.new_private_input::<[u8; 16]>("key").unwrap()
+            } else {
+                thread.new_blind_input::<[u8; 16]>("key").unwrap()
+            };
+            let msg_ref = thread.new_public_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = thread.new_output::<[u8; 16]>("ciphertext").unwrap();
+            let output_ref = thread.new_output::<[u8; 16]>("output").unwrap();
+
+            if let Some(key) = key {
+                thread.assign(&key_ref, key).unwrap();
+            }
+            thread.assign(&msg_ref, [69u8; 16]).unwrap();
+
+            thread
+                .execute(
+                    AES128.clone(),
+                    &[key_ref, msg_ref.clone()],
+                    std::slice::from_ref(&ciphertext_ref),
+                )
+                .await
+                .unwrap();
+            thread
+                .execute(
+                    AES128.clone(),
+                    &[ciphertext_ref, msg_ref],
+                    std::slice::from_ref(&output_ref),
+                )
+                .await
+                .unwrap();
+
+            thread.decode(&[output_ref]).await.unwrap().pop().unwrap()
+        }
+
+        let (leader_result, follower_result) = futures::join!(
+            run(&mut leader_thread, Some(key)),
+            run(&mut follower_thread, None)
+        );
+
+        let expected: [u8; 16] = {
+            use aes::{
+                cipher::{BlockEncrypt, KeyInit},
+                Aes128,
+            };
+
+            let mut ciphertext = msg.into();
+            Aes128::new_from_slice(&key)
+                .unwrap()
+                .encrypt_block(&mut ciphertext);
+            let mut output = msg.into();
+            Aes128::new_from_slice(&ciphertext)
+                .unwrap()
+                .encrypt_block(&mut output);
+            output.into()
+        };
+
+        assert_eq!(leader_result, follower_result);
+        assert_eq!(leader_result, Value::from(expected));
+    }
+}
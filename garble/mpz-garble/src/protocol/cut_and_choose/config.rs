@@ -0,0 +1,93 @@
+use derive_builder::Builder;
+
+/// The default number of copies of each circuit which are garbled.
+const DEFAULT_COPIES: usize = 125;
+/// The default number of copies which are opened and checked.
+const DEFAULT_CHECKED: usize = 66;
+/// The default number of shares each bit of the evaluator's inputs is split into.
+const DEFAULT_INPUT_SHARES: usize = 40;
+
+/// Cut-and-choose configuration.
+///
+/// Both parties must use the same configuration.
+///
+/// The defaults garble 125 copies, check 66 of them and evaluate the remaining 59, for which a
+/// cheating generator changes the output with probability at most `2^-40`, see the
+/// [module documentation](super#security).
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct CutAndChooseConfig {
+    /// The number of copies of each circuit which are garbled.
+    #[builder(default = "DEFAULT_COPIES")]
+    copies: usize,
+    /// The number of copies which are opened and checked, the remaining copies are evaluated.
+    #[builder(default = "DEFAULT_CHECKED")]
+    checked: usize,
+    /// The number of shares each bit of the evaluator's inputs is split into.
+    ///
+    /// A selective failure attack on the oblivious transfer reveals an input bit of the evaluator
+    /// with probability at most `2^-input_shares`.
+    #[builder(default = "DEFAULT_INPUT_SHARES")]
+    input_shares: usize,
+}
+
+impl CutAndChooseConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let copies = self.copies.unwrap_or(DEFAULT_COPIES);
+        let checked = self.checked.unwrap_or(DEFAULT_CHECKED);
+        let input_shares = self.input_shares.unwrap_or(DEFAULT_INPUT_SHARES);
+
+        if checked == 0 {
+            return Err(
+                "invalid cut-and-choose parameters: checked should be at least 1".to_string(),
+            );
+        }
+
+        if checked >= copies {
+            return Err(format!(
+                "invalid cut-and-choose parameters: checked = {checked} should be less than copies = {copies}"
+            ));
+        }
+
+        if input_shares == 0 {
+            return Err(
+                "invalid cut-and-choose parameters: input_shares should be at least 1".to_string(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl CutAndChooseConfig {
+    /// Creates a new builder for the cut-and-choose configuration.
+    pub fn builder() -> CutAndChooseConfigBuilder {
+        CutAndChooseConfigBuilder::default()
+    }
+
+    /// Returns the number of copies of each circuit which are garbled.
+    pub fn copies(&self) -> usize {
+        self.copies
+    }
+
+    /// Returns the number of copies which are opened and checked.
+    pub fn checked(&self) -> usize {
+        self.checked
+    }
+
+    /// Returns the number of shares each bit of the evaluator's inputs is split into.
+    pub fn input_shares(&self) -> usize {
+        self.input_shares
+    }
+
+    /// Returns the number of copies which are evaluated.
+    pub fn evaluated(&self) -> usize {
+        self.copies - self.checked
+    }
+}
+
+impl Default for CutAndChooseConfig {
+    fn default() -> Self {
+        CutAndChooseConfigBuilder::default().build().unwrap()
+    }
+}
@@ -0,0 +1,46 @@
+use mpz_garble_core::ValueError;
+
+use crate::ExecutionError;
+
+/// Errors that can occur during the cut-and-choose protocol.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum CutAndChooseError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    GeneratorError(#[from] crate::generator::GeneratorError),
+    #[error(transparent)]
+    EvaluatorError(#[from] crate::evaluator::EvaluatorError),
+    #[error(transparent)]
+    CointossError(#[from] mpz_core::cointoss::CointossError),
+    #[error(transparent)]
+    ValueError(#[from] ValueError),
+    #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+    #[error(transparent)]
+    CommitmentError(#[from] mpz_core::commit::CommitmentError),
+    #[error("invalid encoder seed")]
+    InvalidEncoderSeed,
+    #[error("an opened copy was not generated from its encoder seed")]
+    InvalidCopy,
+    #[error("the generator sent input encodings of an unexpected length")]
+    InvalidInputEncodings,
+    #[error("the generator used inconsistent inputs in the evaluated copies")]
+    InconsistentInputs,
+    #[error("the generator used an incorrect public input")]
+    InvalidPublicInput,
+    #[error("none of the evaluated copies could be evaluated successfully")]
+    NoValidCopy,
+    #[error("the output encodings do not match the committed output encodings")]
+    InvalidOutput,
+}
+
+impl From<CutAndChooseError> for ExecutionError {
+    fn from(err: CutAndChooseError) -> Self {
+        match err {
+            CutAndChooseError::IOError(err) => ExecutionError::IOError(err),
+            err => ExecutionError::ProtocolError(Box::new(err)),
+        }
+    }
+}
@@ -0,0 +1,1421 @@
+//! A cut-and-choose protocol for maliciously secure two-party computation.
+//!
+//! The leader acts as the generator and the follower acts as the evaluator. For every
+//! execution, the generator garbles many copies of the circuit, each from a fresh encoder seed,
+//! and sends them to the evaluator together with [commitments](mpz_garble_core::EncodingCommitment)
+//! to the output encodings and the output decodings of every copy.
+//!
+//! The parties then use a coin toss to select a random subset of the copies which are opened:
+//! the generator reveals their encoder seeds and the evaluator checks them with
+//! [`Evaluator::verify`]. The remaining copies are evaluated on the real inputs, and the
+//! evaluator takes the majority output.
+//!
+//! # Outputs
+//!
+//! Before the copies to open are chosen, the generator also commits to an encoding of the outputs
+//! which is the same in every copy. In every evaluated copy, it encrypts the labels of this
+//! encoding under the output labels of the copy, so the evaluator translates the active output
+//! labels of a copy and checks the result against the commitments. Finally, the evaluator sends
+//! the translated encodings of the majority output to the generator, which authenticates them
+//! without learning which copy they come from.
+//!
+//! # Inputs
+//!
+//! Before the copies to open are chosen, the generator commits to the active encodings of its
+//! inputs in every copy, along with a random 64-bit mask `r`. The parties then toss a random
+//! binary matrix `M`, and the generator sends the decoding of `M·x ⊕ r` in every copy, which the
+//! evaluator computes on the labels thanks to free-XOR. The decodings of the opened copies are
+//! checked against their encoder seed, and the evaluator aborts unless every evaluated copy
+//! decodes to the same hash.
+//!
+//! Every bit of the evaluator's inputs is split into random XOR shares, which the evaluator
+//! receives keys for with a single oblivious transfer. The generator then encrypts the labels of
+//! the shares in every copy under these keys before the copies to open are chosen, so the
+//! evaluator uses the same input in every copy and the labels of a bit are recovered from the
+//! shares with free-XOR. For the opened copies, the generator reveals the hashes of both keys of
+//! every share which the labels are encrypted with, and the evaluator checks them against the
+//! keys it received and the encodings of the copy.
+//!
+//! # Security
+//!
+//! A generator which garbles a copy incorrectly is caught unless that copy is not opened, and
+//! it must cheat in at least half of the evaluated copies to change the output. With `s` copies
+//! of which `c` are checked, `e = s - c` are evaluated, and a generator which cheats in `b`
+//! copies escapes when all of them are evaluated, which happens with probability
+//! `C(e, b) / C(s, b)`. This is largest for the smallest `b = ceil(e / 2)`, which gives
+//! `C(59, 30) / C(125, 30) ≈ 2^-40.08` for the default 125 copies with 66 checked.
+//!
+//! A generator which uses different inputs in two evaluated copies is caught unless it sent an
+//! incorrect hash decoding in a copy which is not opened, or with probability `2^-64`.
+//!
+//! A selective failure attack on the evaluator's inputs only reveals shares of its input bits,
+//! which are uniformly random unless every share of a bit is attacked, revealing that bit with
+//! probability `2^-input_shares`, see [`CutAndChooseConfig::input_shares`]. A generator which
+//! encrypts the labels of a share incorrectly is also caught with probability 1/2 if the copy is
+//! opened.
+//!
+//! The evaluator discards the evaluated copies which fail to evaluate or to translate their
+//! outputs, and the outputs it returns to the generator are the same for every copy with the
+//! majority output, so the generator does not learn which copies were discarded.
+
+mod config;
+mod error;
+mod memory;
+
+use std::{
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+};
+
+use futures::{Sink, SinkExt, Stream};
+use itybity::IntoBits;
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_core::{aes::FIXED_KEY_AES, cointoss, commit::Decommitment, Block};
+use mpz_garble_core::{
+    encoding_state::{self, LabelState},
+    msg::GarbleMessage,
+    ChaChaEncoder, EncodedValue, Encoder, EncodingCommitment, Label,
+};
+use rand::{seq::index::sample, thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use utils_aio::expect_msg_or_err;
+
+use crate::{
+    config::Role,
+    evaluator::{Evaluator, EvaluatorConfigBuilder},
+    generator::{Generator, GeneratorConfigBuilder},
+    memory::{AssignedValues, ValueMemory},
+    ot::{OTSendEncoding, VerifiableOTReceiveEncoding},
+    value::{ValueId, ValueRef},
+};
+
+pub use config::{CutAndChooseConfig, CutAndChooseConfigBuilder};
+pub use error::CutAndChooseError;
+
+/// The number of bits of the hash which checks the consistency of the generator's inputs.
+const HASH_BITS: usize = 64;
+
+/// The tweak domain of the encrypted labels of the shares of the evaluator's inputs.
+const SHARES_DOMAIN: u8 = 1;
+
+/// The tweak domain of the translations of the output labels.
+const OUTPUTS_DOMAIN: u8 = 2;
+
+/// The cut-and-choose protocol.
+#[derive(Debug)]
+pub struct CutAndChoose {
+    role: Role,
+    config: CutAndChooseConfig,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    memory: ValueMemory,
+}
+
+impl CutAndChoose {
+    /// Creates a new cut-and-choose protocol instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of the party, the leader is the generator.
+    /// * `config` - The protocol configuration.
+    pub fn new(role: Role, config: CutAndChooseConfig) -> Self {
+        Self {
+            role,
+            config,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> impl DerefMut<Target = State> + '_ {
+        self.state.lock().unwrap()
+    }
+
+    /// Executes a circuit, revealing the outputs to both parties.
+    ///
+    /// All the inputs must be assigned, as the encodings of every copy are only used for a
+    /// single execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the circuit.
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs to the circuit.
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    /// * `ot_send` - The OT sender, used by the leader.
+    /// * `ot_recv` - The OT receiver, used by the follower.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute<T, U, OTS, OTR>(
+        &self,
+        id: &str,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+        ot_send: &OTS,
+        ot_recv: &OTR,
+    ) -> Result<Vec<Value>, CutAndChooseError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OTS: OTSendEncoding,
+        OTR: VerifiableOTReceiveEncoding,
+    {
+        let (assigned_values, input_types) = {
+            let mut state = self.state();
+            let input_types = inputs
+                .iter()
+                .map(|input| state.memory.get_value_type(input))
+                .collect::<Vec<_>>();
+
+            (state.memory.drain_assigned(inputs), input_types)
+        };
+
+        match self.role {
+            Role::Leader => {
+                self.execute_generator(
+                    id,
+                    circ,
+                    inputs,
+                    &input_types,
+                    outputs,
+                    &assigned_values,
+                    |_| assigned_values.private.clone(),
+                    sink,
+                    stream,
+                    ot_send,
+                )
+                .await
+            }
+            Role::Follower => {
+                self.execute_evaluator(
+                    id,
+                    circ,
+                    inputs,
+                    &input_types,
+                    outputs,
+                    &assigned_values,
+                    sink,
+                    stream,
+                    ot_recv,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Executes the generator, using the private inputs returned by `copy_inputs` in each copy.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_generator<T, U, OTS>(
+        &self,
+        id: &str,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        input_types: &[ValueType],
+        outputs: &[ValueRef],
+        assigned_values: &AssignedValues,
+        copy_inputs: impl Fn(usize) -> Vec<(ValueId, Value)>,
+        sink: &mut T,
+        stream: &mut U,
+        ot_send: &OTS,
+    ) -> Result<Vec<Value>, CutAndChooseError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OTS: OTSendEncoding,
+    {
+        let config = GeneratorConfigBuilder::default()
+            .encoding_commitments()
+            .build()
+            .expect("config should be valid");
+
+        let public = sorted(assigned_values.public.clone());
+        let public_ids = public.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        let blind_ids = sorted(assigned_values.blind.clone())
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        let mask_id = ValueId::new(&format!("{id}/mask"));
+        let hash_ids = sorted(assigned_values.private.clone())
+            .into_iter()
+            .map(|(id, _)| id)
+            .chain([mask_id.clone()])
+            .collect::<Vec<_>>();
+        let mask = Value::U64(thread_rng().gen());
+
+        // Garble every copy from a fresh seed, committing to the output encodings, the active
+        // encodings of the private inputs and revealing the output and public input decodings
+        // before the copies to open are chosen.
+        let mut copies = Vec::with_capacity(self.config.copies());
+        for idx in 0..self.config.copies() {
+            let seed: [u8; 32] = thread_rng().gen();
+            let gen = Generator::new(config.clone(), seed);
+            for (input, typ) in inputs.iter().zip(input_types) {
+                gen.generate_input_encoding(input, typ);
+            }
+            gen.generate_input_encodings_by_id(&[(mask_id.clone(), ValueType::U64)]);
+
+            gen.generate(circ.clone(), inputs, outputs, sink, false)
+                .await?;
+            gen.decode(outputs, sink).await?;
+
+            let public_decodings = encodings(&gen, &public_ids)
+                .iter()
+                .map(|encoding| encoding.decoding())
+                .collect();
+            sink.send(GarbleMessage::ValueDecodings(public_decodings))
+                .await?;
+
+            let values = sorted(copy_inputs(idx))
+                .into_iter()
+                .map(|(_, value)| value)
+                .chain([mask.clone()]);
+            let active = encodings(&gen, &hash_ids)
+                .into_iter()
+                .zip(values)
+                .map(|(encoding, value)| encoding.select(value))
+                .collect::<Result<Vec<_>, _>>()?;
+            let decommitment = Decommitment::new(active);
+            sink.send(GarbleMessage::HashCommitment(decommitment.commit()))
+                .await?;
+
+            copies.push((seed, gen, decommitment));
+        }
+
+        // Transfer the keys of the shares of the evaluator's inputs once, and encrypt the labels
+        // of the shares of every copy under them before the copies to open are chosen.
+        let keys = if blind_ids.is_empty() {
+            Vec::new()
+        } else {
+            let encoder = ChaChaEncoder::new(thread_rng().gen());
+            let keys = encodings(&copies[0].1, &blind_ids)
+                .iter()
+                .flat_map(|encoding| {
+                    std::iter::repeat_n(encoding.value_type().len(), self.config.input_shares())
+                })
+                .enumerate()
+                .map(|(idx, len)| {
+                    encoder.encode_by_type(
+                        idx as u64,
+                        &ValueType::Array(Box::new(ValueType::Bit), len),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            ot_send.send(id, keys.clone()).await?;
+
+            for (idx, (_, gen, _)) in copies.iter().enumerate() {
+                let ciphertexts = encrypt_shares(
+                    idx,
+                    &keys,
+                    &encodings(gen, &blind_ids),
+                    self.config.input_shares(),
+                );
+                sink.send(GarbleMessage::Labels(ciphertexts)).await?;
+            }
+
+            keys
+        };
+
+        // Commit to an encoding of the outputs which is the same in every copy, so that the
+        // evaluator returns the outputs without revealing which copy it used.
+        let output_encoder = ChaChaEncoder::new(thread_rng().gen());
+        let output_encodings = copies[0]
+            .1
+            .get_encodings(outputs)?
+            .iter()
+            .enumerate()
+            .map(|(idx, encoding)| {
+                output_encoder.encode_by_type(idx as u64, &encoding.value_type())
+            })
+            .collect::<Vec<_>>();
+        sink.send(GarbleMessage::EncodingCommitments(
+            output_encodings
+                .iter()
+                .map(|encoding| encoding.commit())
+                .collect(),
+        ))
+        .await?;
+
+        // Reveal the decodings of the hash of the inputs of every copy.
+        let hash_seed = self.cointoss(sink, stream).await?;
+        let hash_decodings = copies
+            .iter()
+            .flat_map(|(_, gen, _)| hash(hash_seed, &pointer_bits(&encodings(gen, &hash_ids))))
+            .collect();
+        sink.send(GarbleMessage::Bits(hash_decodings)).await?;
+
+        let (checked, evaluated) = self.cut(sink, stream).await?;
+
+        for idx in checked {
+            let (seed, _, _) = &copies[idx];
+            sink.send(GarbleMessage::EncoderSeed(seed.to_vec())).await?;
+
+            if !keys.is_empty() {
+                sink.send(GarbleMessage::Labels(pads(idx, &keys))).await?;
+            }
+        }
+
+        for &idx in &evaluated {
+            let (_, gen, decommitment) = &copies[idx];
+            sink.send(GarbleMessage::ActiveValuesDecommitment(
+                decommitment.clone(),
+            ))
+            .await?;
+
+            let active = encodings(gen, &public_ids)
+                .into_iter()
+                .zip(&public)
+                .map(|(encoding, (_, value))| encoding.select(value.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            sink.send(GarbleMessage::ActiveValues(active)).await?;
+
+            let translations =
+                translate_outputs(idx, &gen.get_encodings(outputs)?, &output_encodings);
+            sink.send(GarbleMessage::Labels(translations)).await?;
+        }
+
+        // The evaluator returns the outputs in the encoding which is the same in every copy.
+        let active = expect_msg_or_err!(stream, GarbleMessage::ActiveValues)?;
+        if active.len() != outputs.len() {
+            return Err(CutAndChooseError::InvalidOutput);
+        }
+
+        output_encodings
+            .iter()
+            .zip(&active)
+            .map(|(full, active)| full.decode(active))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| CutAndChooseError::InvalidOutput)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_evaluator<T, U, OTR>(
+        &self,
+        id: &str,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        input_types: &[ValueType],
+        outputs: &[ValueRef],
+        assigned_values: &AssignedValues,
+        sink: &mut T,
+        stream: &mut U,
+        ot_recv: &OTR,
+    ) -> Result<Vec<Value>, CutAndChooseError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OTR: VerifiableOTReceiveEncoding,
+    {
+        let config = EvaluatorConfigBuilder::default()
+            .encoding_commitments()
+            .log_circuits()
+            .log_decodings()
+            .build()
+            .expect("config should be valid");
+
+        let public = sorted(assigned_values.public.clone());
+        let public_ids = public.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        let private = sorted(assigned_values.private.clone());
+        let private_ids = private.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        let blind = sorted(assigned_values.blind.clone());
+        let mask_id = ValueId::new(&format!("{id}/mask"));
+        let hash_ids = blind
+            .iter()
+            .map(|(id, _)| id.clone())
+            .chain([mask_id.clone()])
+            .collect::<Vec<_>>();
+
+        let mut copies = Vec::with_capacity(self.config.copies());
+        for _ in 0..self.config.copies() {
+            let ev = Evaluator::new(config.clone());
+            ev.receive_garbled_circuit(circ.clone(), inputs, outputs, stream)
+                .await?;
+            let decodings = expect_msg_or_err!(stream, GarbleMessage::ValueDecodings)?;
+            let public_decodings = expect_msg_or_err!(stream, GarbleMessage::ValueDecodings)?;
+            let commitment = expect_msg_or_err!(stream, GarbleMessage::HashCommitment)?;
+
+            if public_decodings.len() != public.len() {
+                return Err(CutAndChooseError::InvalidInputEncodings);
+            }
+
+            copies.push((ev, decodings, public_decodings, commitment));
+        }
+
+        // Receive the keys of the shares of the inputs once, and the labels of the shares of every
+        // copy encrypted under them before the copies to open are chosen.
+        let shares = private
+            .iter()
+            .flat_map(|(_, value)| share(value, self.config.input_shares()))
+            .collect::<Vec<_>>();
+        let (keys, ciphertexts) = if private.is_empty() {
+            (Vec::new(), Vec::new())
+        } else {
+            let keys = ot_recv
+                .receive(id, shares.iter().cloned().map(Value::from).collect())
+                .await?;
+
+            let mut ciphertexts = Vec::with_capacity(self.config.copies());
+            for _ in 0..self.config.copies() {
+                ciphertexts.push(expect_msg_or_err!(stream, GarbleMessage::Labels)?);
+            }
+
+            (keys, ciphertexts)
+        };
+
+        let output_commitments = expect_msg_or_err!(stream, GarbleMessage::EncodingCommitments)?;
+        if output_commitments.len() != outputs.len() {
+            return Err(CutAndChooseError::InvalidOutput);
+        }
+
+        let hash_seed = self.cointoss(sink, stream).await?;
+        let hash_decodings = expect_msg_or_err!(stream, GarbleMessage::Bits)?;
+        if hash_decodings.len() != self.config.copies() * HASH_BITS {
+            return Err(CutAndChooseError::InvalidInputEncodings);
+        }
+        let hash_decodings = hash_decodings.chunks(HASH_BITS).collect::<Vec<_>>();
+
+        let (checked, evaluated) = self.cut(sink, stream).await?;
+
+        // Verify the checked copies by regenerating their input encodings and decodings from their
+        // seed, then evaluating them on random inputs, which also logs the output decodings.
+        let dummy_values = dummy_values(inputs, input_types);
+        let dummy_ids = dummy_values
+            .iter()
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        for &idx in &checked {
+            let seed = expect_msg_or_err!(stream, GarbleMessage::EncoderSeed)?;
+            let seed: [u8; 32] = seed
+                .try_into()
+                .map_err(|_| CutAndChooseError::InvalidEncoderSeed)?;
+
+            let gen = Generator::new(
+                GeneratorConfigBuilder::default()
+                    .build()
+                    .expect("config should be valid"),
+                seed,
+            );
+            gen.generate_input_encodings_by_id(&dummy_values);
+            gen.generate_input_encodings_by_id(&[(mask_id.clone(), ValueType::U64)]);
+
+            let (ev, decodings, public_decodings, _) = &mut copies[idx];
+            if hash(hash_seed, &pointer_bits(&encodings(&gen, &hash_ids))) != hash_decodings[idx]
+                || encodings(&gen, &public_ids)
+                    .iter()
+                    .map(|encoding| encoding.decoding())
+                    .ne(public_decodings.iter().cloned())
+            {
+                return Err(CutAndChooseError::InvalidCopy);
+            }
+
+            let active = encodings(&gen, &dummy_ids)
+                .into_iter()
+                .zip(&dummy_values)
+                .map(|(encoding, (_, typ))| encoding.select(Value::random(&mut thread_rng(), typ)))
+                .collect::<Result<Vec<_>, _>>()?;
+            ev.direct_receive_active_encodings(
+                &dummy_values,
+                &mut message_stream(GarbleMessage::ActiveValues(active)),
+            )
+            .await?;
+            ev.evaluate(circ.clone(), inputs, outputs, stream).await?;
+            ev.decode(
+                outputs,
+                &mut message_stream(GarbleMessage::ValueDecodings(decodings.clone())),
+            )
+            .await?;
+            ev.verify(seed, ot_recv).await?;
+
+            if !private.is_empty() {
+                let pads = expect_msg_or_err!(stream, GarbleMessage::Labels)?;
+                if !check_shares(
+                    idx,
+                    &keys,
+                    &shares,
+                    &encodings(&gen, &private_ids),
+                    &ciphertexts[idx],
+                    &pads,
+                    self.config.input_shares(),
+                ) {
+                    return Err(CutAndChooseError::InvalidCopy);
+                }
+            }
+        }
+
+        // Check that the generator used the same inputs in every evaluated copy before using
+        // them, so that aborting does not depend on the inputs of the evaluator.
+        let mut expected_hash = None;
+        let mut translations = Vec::with_capacity(evaluated.len());
+        for &idx in &evaluated {
+            let (ev, _, public_decodings, commitment) = &copies[idx];
+
+            let decommitment = expect_msg_or_err!(stream, GarbleMessage::ActiveValuesDecommitment)?;
+            decommitment.verify(commitment)?;
+            let mut active = decommitment.into_inner();
+
+            let bits = pointer_bits(&active);
+            if active.len() != hash_ids.len()
+                || bits.len() != blind.iter().map(|(_, typ)| typ.len()).sum::<usize>() + HASH_BITS
+            {
+                return Err(CutAndChooseError::InvalidInputEncodings);
+            }
+
+            let hash = hash(hash_seed, &bits)
+                .into_iter()
+                .zip(hash_decodings[idx])
+                .map(|(bit, decoding)| bit ^ decoding)
+                .collect::<Vec<_>>();
+            if expected_hash.get_or_insert_with(|| hash.clone()) != &hash {
+                return Err(CutAndChooseError::InconsistentInputs);
+            }
+
+            let public_active = expect_msg_or_err!(stream, GarbleMessage::ActiveValues)?;
+            if public_active.len() != public.len() {
+                return Err(CutAndChooseError::InvalidInputEncodings);
+            }
+            for ((active, decoding), (_, value)) in
+                public_active.iter().zip(public_decodings).zip(&public)
+            {
+                if &active.decode(decoding)? != value {
+                    return Err(CutAndChooseError::InvalidPublicInput);
+                }
+            }
+
+            // Drop the mask, which is not an input of the circuit.
+            active.pop();
+            active.extend(public_active);
+            let values = blind
+                .iter()
+                .cloned()
+                .chain(
+                    public
+                        .iter()
+                        .map(|(id, value)| (id.clone(), value.value_type())),
+                )
+                .collect::<Vec<_>>();
+            ev.direct_receive_active_encodings(
+                &values,
+                &mut message_stream(GarbleMessage::ActiveValues(active)),
+            )
+            .await?;
+
+            translations.push(expect_msg_or_err!(stream, GarbleMessage::Labels)?);
+        }
+
+        if !private.is_empty() {
+            let types = private
+                .iter()
+                .map(|(_, value)| value.value_type())
+                .collect::<Vec<_>>();
+            let values = private
+                .iter()
+                .map(|(id, value)| (id.clone(), value.value_type()))
+                .collect::<Vec<_>>();
+            for &idx in &evaluated {
+                let (ev, _, _, _) = &copies[idx];
+                let active = decrypt_shares(
+                    idx,
+                    &keys,
+                    &shares,
+                    &types,
+                    &ciphertexts[idx],
+                    self.config.input_shares(),
+                )?;
+                ev.direct_receive_active_encodings(
+                    &values,
+                    &mut message_stream(GarbleMessage::ActiveValues(active)),
+                )
+                .await?;
+            }
+        }
+
+        // A copy which fails to evaluate was garbled incorrectly, so it is discarded instead of
+        // aborting, which would reveal whether the generator cheated.
+        let mut results = Vec::with_capacity(evaluated.len());
+        for (idx, translations) in evaluated.into_iter().zip(translations) {
+            let (ev, decodings, _, _) = &copies[idx];
+            if ev
+                .evaluate(circ.clone(), inputs, outputs, stream)
+                .await
+                .is_err()
+            {
+                continue;
+            }
+
+            if let Ok(values) = ev
+                .decode(
+                    outputs,
+                    &mut message_stream(GarbleMessage::ValueDecodings(decodings.clone())),
+                )
+                .await
+            {
+                // A copy which does not translate its outputs to the committed encoding is
+                // discarded as well.
+                if let Ok(active) = translate_active(
+                    idx,
+                    &ev.get_encodings(outputs)?,
+                    &values,
+                    &translations,
+                    &output_commitments,
+                ) {
+                    results.push((active, values));
+                }
+            }
+        }
+
+        // Every copy which outputs the majority translates it to the same encoding, so the
+        // generator does not learn which copy was used.
+        let (active, values) = majority(results).ok_or(CutAndChooseError::NoValidCopy)?;
+        sink.send(GarbleMessage::ActiveValues(active)).await?;
+
+        Ok(values)
+    }
+
+    /// Tosses a random seed with the other party.
+    async fn cointoss<T, U>(
+        &self,
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<[u8; 32], CutAndChooseError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        let seeds = vec![
+            Block::random(&mut thread_rng()),
+            Block::random(&mut thread_rng()),
+        ];
+
+        let blocks = match self.role {
+            Role::Leader => {
+                let (sender, commitment) = cointoss::Sender::new(seeds).send();
+                sink.send(GarbleMessage::CointossCommit(commitment)).await?;
+                let payload = expect_msg_or_err!(stream, GarbleMessage::CointossReceiverPayload)?;
+                let (blocks, payload) = sender.finalize(payload)?;
+                sink.send(GarbleMessage::CointossSenderPayload(payload))
+                    .await?;
+
+                blocks
+            }
+            Role::Follower => {
+                let commitment = expect_msg_or_err!(stream, GarbleMessage::CointossCommit)?;
+                let (receiver, payload) = cointoss::Receiver::new(seeds).reveal(commitment)?;
+                sink.send(GarbleMessage::CointossReceiverPayload(payload))
+                    .await?;
+                let payload = expect_msg_or_err!(stream, GarbleMessage::CointossSenderPayload)?;
+
+                receiver.finalize(payload)?
+            }
+        };
+
+        let mut seed = [0u8; 32];
+        seed[..16].copy_from_slice(&blocks[0].to_bytes());
+        seed[16..].copy_from_slice(&blocks[1].to_bytes());
+
+        Ok(seed)
+    }
+
+    /// Chooses the copies to open with the other party, returning the indices of the checked
+    /// and evaluated copies in ascending order.
+    async fn cut<T, U>(
+        &self,
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<(Vec<usize>, Vec<usize>), CutAndChooseError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        let seed = self.cointoss(sink, stream).await?;
+
+        let mut checked = sample(
+            &mut ChaCha12Rng::from_seed(seed),
+            self.config.copies(),
+            self.config.checked(),
+        )
+        .into_vec();
+        checked.sort_unstable();
+
+        let evaluated = (0..self.config.copies())
+            .filter(|idx| checked.binary_search(idx).is_err())
+            .collect();
+
+        Ok((checked, evaluated))
+    }
+}
+
+/// Returns the values sorted by ID, so that both parties agree on their order.
+fn sorted<T>(mut values: Vec<(ValueId, T)>) -> Vec<(ValueId, T)> {
+    values.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
+    values
+}
+
+/// Returns the encodings of the values, which must have been generated.
+fn encodings(gen: &Generator, ids: &[ValueId]) -> Vec<EncodedValue<encoding_state::Full>> {
+    gen.get_encodings_by_id(ids)
+        .expect("encodings should be generated")
+}
+
+/// Returns the IDs and types of the values of the inputs.
+fn dummy_values(inputs: &[ValueRef], input_types: &[ValueType]) -> Vec<(ValueId, ValueType)> {
+    inputs
+        .iter()
+        .zip(input_types)
+        .flat_map(|(input, typ)| {
+            let typ = match typ {
+                ValueType::Array(elem, _) => (**elem).clone(),
+                typ => typ.clone(),
+            };
+            input.iter().map(move |id| (id.clone(), typ.clone()))
+        })
+        .collect()
+}
+
+/// Returns the pointer bits of the labels of the encodings, which are the bits of the low labels
+/// for full encodings.
+fn pointer_bits<S: LabelState>(encodings: &[EncodedValue<S>]) -> Vec<bool> {
+    encodings
+        .iter()
+        .flat_map(|encoding| encoding.iter().map(|label| label.as_ref().lsb() == 1))
+        .collect()
+}
+
+/// Computes `M·x ⊕ r` over GF(2), where `bits` are the bits of `x` followed by the
+/// [`HASH_BITS`] bits of the mask `r`, and `M` is a random binary matrix derived from `seed`.
+fn hash(seed: [u8; 32], bits: &[bool]) -> Vec<bool> {
+    let mut rng = ChaCha12Rng::from_seed(seed);
+    let (x, mask) = bits.split_at(bits.len() - HASH_BITS);
+
+    mask.iter()
+        .map(|&bit| x.iter().fold(bit, |acc, &x| acc ^ (rng.gen::<bool>() & x)))
+        .collect()
+}
+
+/// Splits the bits of a value into random XOR shares.
+fn share(value: &Value, count: usize) -> Vec<Vec<bool>> {
+    let mut rng = thread_rng();
+    let mut last = value.clone().into_iter_lsb0().collect::<Vec<_>>();
+    let mut shares = (1..count)
+        .map(|_| {
+            (0..last.len())
+                .map(|i| {
+                    let bit = rng.gen();
+                    last[i] ^= bit;
+                    bit
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    shares.push(last);
+    shares
+}
+
+/// Returns the tweak of the `idx`-th label of a copy in a domain.
+///
+/// The domain is the most significant byte, which is zero in the tweaks of the garbled gates.
+fn tweak(domain: u8, copy: usize, idx: usize) -> Block {
+    Block::new((((domain as u128) << 120) | ((copy as u128) << 64) | idx as u128).to_be_bytes())
+}
+
+/// Returns the pads which encrypt the labels of the shares of the evaluator's inputs in a copy,
+/// ie. the hashes of both keys of every bit of the shares.
+fn pads(copy: usize, keys: &[EncodedValue<encoding_state::Full>]) -> Vec<Label> {
+    keys.iter()
+        .flat_map(|key| key.iter_blocks())
+        .enumerate()
+        .flat_map(|(idx, [low, high])| {
+            let tweak = tweak(SHARES_DOMAIN, copy, 2 * idx);
+            [
+                Label::new(FIXED_KEY_AES.tccr(tweak, low)),
+                Label::new(FIXED_KEY_AES.tccr(tweak, high)),
+            ]
+        })
+        .collect()
+}
+
+/// Encrypts the labels of the shares of the evaluator's inputs in a copy under the keys of the
+/// shares, such that the labels of the shares of a bit XOR to its label in the copy.
+fn encrypt_shares(
+    copy: usize,
+    keys: &[EncodedValue<encoding_state::Full>],
+    inputs: &[EncodedValue<encoding_state::Full>],
+    count: usize,
+) -> Vec<Label> {
+    let mut rng = thread_rng();
+    let mut pads = pads(copy, keys).into_iter();
+    let mut ciphertexts = Vec::new();
+    for input in inputs {
+        let delta = input.delta();
+        let mut last = input.iter().copied().collect::<Vec<_>>();
+        let mut shares = (1..count)
+            .map(|_| {
+                last.iter_mut()
+                    .map(|last| {
+                        let label = Label::new(Block::random(&mut rng));
+                        *last = *last ^ label;
+                        label
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        shares.push(last);
+
+        for label in shares.into_iter().flatten() {
+            let pad = pads
+                .next()
+                .expect("a key should be present for every share");
+            ciphertexts.push(pad ^ label);
+            let pad = pads
+                .next()
+                .expect("a key should be present for every share");
+            ciphertexts.push(pad ^ label ^ delta);
+        }
+    }
+
+    ciphertexts
+}
+
+/// Decrypts the labels of the shares of the evaluator's inputs in a copy, returning the active
+/// encodings of the inputs.
+fn decrypt_shares(
+    copy: usize,
+    keys: &[EncodedValue<encoding_state::Active>],
+    shares: &[Vec<bool>],
+    types: &[ValueType],
+    ciphertexts: &[Label],
+    count: usize,
+) -> Result<Vec<EncodedValue<encoding_state::Active>>, CutAndChooseError> {
+    if ciphertexts.len() != 2 * shares.iter().map(Vec::len).sum::<usize>() {
+        return Err(CutAndChooseError::InvalidInputEncodings);
+    }
+
+    let mut idx = 0;
+    types
+        .iter()
+        .zip(keys.chunks(count).zip(shares.chunks(count)))
+        .map(|(typ, (keys, shares))| {
+            let mut labels = vec![Label::new(Block::ZERO); typ.len()];
+            for (key, share) in keys.iter().zip(shares) {
+                for ((label, key), &bit) in labels.iter_mut().zip(key.iter()).zip(share) {
+                    let ciphertext = ciphertexts[idx + bit as usize];
+                    *label = *label
+                        ^ Label::new(
+                            FIXED_KEY_AES.tccr(tweak(SHARES_DOMAIN, copy, idx), *key.as_ref()),
+                        )
+                        ^ ciphertext;
+                    idx += 2;
+                }
+            }
+
+            Ok(EncodedValue::<encoding_state::Active>::from_labels(
+                typ.clone(),
+                &labels,
+            )?)
+        })
+        .collect()
+}
+
+/// Checks that the labels of the shares of the evaluator's inputs in an opened copy were
+/// encrypted correctly, given the pads of both values of every share.
+///
+/// The pads of the chosen values are checked against the keys received from the oblivious
+/// transfer, so a generator which sends an incorrect pad is caught with probability 1/2 as it
+/// does not know the shares.
+fn check_shares(
+    copy: usize,
+    keys: &[EncodedValue<encoding_state::Active>],
+    shares: &[Vec<bool>],
+    inputs: &[EncodedValue<encoding_state::Full>],
+    ciphertexts: &[Label],
+    pads: &[Label],
+    count: usize,
+) -> bool {
+    if ciphertexts.len() != 2 * shares.iter().map(Vec::len).sum::<usize>()
+        || pads.len() != ciphertexts.len()
+    {
+        return false;
+    }
+
+    let mut idx = 0;
+    for (key, share) in keys.iter().zip(shares) {
+        for (key, &bit) in key.iter().zip(share) {
+            let pad = FIXED_KEY_AES.tccr(tweak(SHARES_DOMAIN, copy, idx), *key.as_ref());
+            if *pads[idx + bit as usize].as_ref() != pad {
+                return false;
+            }
+            idx += 2;
+        }
+    }
+
+    let mut labels = ciphertexts
+        .iter()
+        .zip(pads)
+        .map(|(ciphertext, pad)| *ciphertext ^ *pad);
+    for input in inputs {
+        let delta = input.delta();
+        let mut low = vec![Label::new(Block::ZERO); input.value_type().len()];
+        for _ in 0..count {
+            for low in low.iter_mut() {
+                let (Some(zero), Some(one)) = (labels.next(), labels.next()) else {
+                    return false;
+                };
+
+                if zero ^ delta != one {
+                    return false;
+                }
+                *low = *low ^ zero;
+            }
+        }
+
+        if input.iter().ne(low.iter()) {
+            return false;
+        }
+    }
+
+    labels.next().is_none()
+}
+
+/// Encrypts the labels of the outputs of a copy under its output labels, such that the
+/// evaluator recovers the labels of the same values in every copy.
+fn translate_outputs(
+    copy: usize,
+    outputs: &[EncodedValue<encoding_state::Full>],
+    encodings: &[EncodedValue<encoding_state::Full>],
+) -> Vec<Label> {
+    outputs
+        .iter()
+        .flat_map(|output| output.iter_blocks())
+        .zip(encodings.iter().flat_map(|encoding| encoding.iter_blocks()))
+        .enumerate()
+        .flat_map(|(idx, ([low, high], [zero, one]))| {
+            let tweak = tweak(OUTPUTS_DOMAIN, copy, 2 * idx);
+            [
+                Label::new(FIXED_KEY_AES.tccr(tweak, low) ^ zero),
+                Label::new(FIXED_KEY_AES.tccr(tweak, high) ^ one),
+            ]
+        })
+        .collect()
+}
+
+/// Translates the active output labels of a copy with the output values they decode to,
+/// returning the active encodings of the outputs which are the same in every copy.
+fn translate_active(
+    copy: usize,
+    outputs: &[EncodedValue<encoding_state::Active>],
+    values: &[Value],
+    translations: &[Label],
+    commitments: &[EncodingCommitment],
+) -> Result<Vec<EncodedValue<encoding_state::Active>>, CutAndChooseError> {
+    if translations.len()
+        != 2 * values
+            .iter()
+            .map(|value| value.value_type().len())
+            .sum::<usize>()
+    {
+        return Err(CutAndChooseError::InvalidOutput);
+    }
+
+    let mut idx = 0;
+    outputs
+        .iter()
+        .zip(values)
+        .zip(commitments)
+        .map(|((output, value), commitment)| {
+            let labels = output
+                .iter()
+                .zip(value.clone().into_iter_lsb0())
+                .map(|(label, bit)| {
+                    let pad = FIXED_KEY_AES.tccr(tweak(OUTPUTS_DOMAIN, copy, idx), *label.as_ref());
+                    let label = translations[idx + bit as usize] ^ Label::new(pad);
+                    idx += 2;
+                    label
+                })
+                .collect::<Vec<_>>();
+
+            let active =
+                EncodedValue::<encoding_state::Active>::from_labels(value.value_type(), &labels)?;
+            commitment.verify(&active)?;
+
+            Ok::<_, CutAndChooseError>(active)
+        })
+        .collect()
+}
+
+/// Returns a stream which yields a single message, to reuse the methods of the generator and
+/// evaluator which receive it from the other party.
+fn message_stream(
+    msg: GarbleMessage,
+) -> impl Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin {
+    futures::stream::iter([Ok(msg)])
+}
+
+/// Returns the most common output of the evaluated copies, along with the data of the first
+/// copy which output it.
+fn majority<T>(results: Vec<(T, Vec<Value>)>) -> Option<(T, Vec<Value>)> {
+    let counts = results
+        .iter()
+        .map(|(_, values)| results.iter().filter(|(_, other)| other == values).count())
+        .collect::<Vec<_>>();
+
+    let max = counts.iter().copied().max()?;
+    let position = counts
+        .iter()
+        .position(|&count| count == max)
+        .expect("max count is present");
+
+    results.into_iter().nth(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::{future::Either, StreamExt};
+    use mpz_circuits::circuits::AES128;
+    use mpz_ot::ideal::ideal_ot_shared_pair;
+    use utils_aio::duplex::MemoryDuplex;
+
+    use crate::Memory;
+
+    #[test]
+    fn test_config_validation() {
+        assert!(CutAndChooseConfig::builder()
+            .copies(4)
+            .checked(4)
+            .build()
+            .is_err());
+        assert!(CutAndChooseConfig::builder()
+            .copies(4)
+            .checked(2)
+            .build()
+            .is_ok());
+        assert!(CutAndChooseConfig::builder()
+            .copies(4)
+            .checked(0)
+            .build()
+            .is_err());
+        assert!(CutAndChooseConfig::builder()
+            .input_shares(0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_majority() {
+        let results = vec![
+            (0, vec![Value::from(1u8)]),
+            (2, vec![Value::from(2u8)]),
+            (3, vec![Value::from(2u8)]),
+        ];
+
+        assert_eq!(majority(results), Some((2, vec![Value::from(2u8)])));
+        assert_eq!(majority(Vec::new()), None);
+    }
+
+    #[test]
+    fn test_check_shares() {
+        let encoder = ChaChaEncoder::new([0u8; 32]);
+        let count = 3;
+        let inputs = vec![encoder.encode_by_type(0, &ValueType::U8)];
+        let keys = (0..count)
+            .map(|idx| {
+                encoder.encode_by_type(
+                    1 + idx as u64,
+                    &ValueType::Array(Box::new(ValueType::Bit), 8),
+                )
+            })
+            .collect::<Vec<_>>();
+        let shares = share(&Value::U8(42), count);
+        let active_keys = keys
+            .iter()
+            .zip(&shares)
+            .map(|(key, share)| key.select(Value::from(share.clone())).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut ciphertexts = encrypt_shares(1, &keys, &inputs, count);
+        let mut pads = pads(1, &keys);
+        assert!(check_shares(
+            1,
+            &active_keys,
+            &shares,
+            &inputs,
+            &ciphertexts,
+            &pads,
+            count
+        ));
+
+        let active = decrypt_shares(
+            1,
+            &active_keys,
+            &shares,
+            &[ValueType::U8],
+            &ciphertexts,
+            count,
+        )
+        .unwrap();
+        assert_eq!(
+            active[0].decode(&inputs[0].decoding()).unwrap(),
+            Value::U8(42)
+        );
+
+        // The pads are specific to the copy.
+        assert!(!check_shares(
+            0,
+            &active_keys,
+            &shares,
+            &inputs,
+            &ciphertexts,
+            &pads,
+            count
+        ));
+
+        // An incorrect ciphertext is detected with the pads of the keys.
+        let chosen = shares[0][0] as usize;
+        ciphertexts[chosen] = ciphertexts[chosen] ^ Label::new(Block::ONES);
+        assert!(!check_shares(
+            1,
+            &active_keys,
+            &shares,
+            &inputs,
+            &ciphertexts,
+            &pads,
+            count
+        ));
+
+        // And a pad which hides it does not match the key of the chosen share.
+        pads[chosen] = pads[chosen] ^ Label::new(Block::ONES);
+        assert!(!check_shares(
+            1,
+            &active_keys,
+            &shares,
+            &inputs,
+            &ciphertexts,
+            &pads,
+            count
+        ));
+    }
+
+    #[test]
+    fn test_translate_outputs() {
+        let encoder = ChaChaEncoder::new([0u8; 32]);
+        let outputs = vec![
+            encoder.encode_by_type(0, &ValueType::U8),
+            encoder.encode_by_type(1, &ValueType::Array(Box::new(ValueType::U8), 2)),
+        ];
+        let values = vec![Value::U8(42), Value::from([1u8, 2u8])];
+        let active = outputs
+            .iter()
+            .zip(&values)
+            .map(|(output, value)| output.select(value.clone()).unwrap())
+            .collect::<Vec<_>>();
+
+        let output_encoder = ChaChaEncoder::new([1u8; 32]);
+        let encodings = outputs
+            .iter()
+            .enumerate()
+            .map(|(idx, output)| output_encoder.encode_by_type(idx as u64, &output.value_type()))
+            .collect::<Vec<_>>();
+        let commitments = encodings
+            .iter()
+            .map(|encoding| encoding.commit())
+            .collect::<Vec<_>>();
+
+        let mut translations = translate_outputs(3, &outputs, &encodings);
+        let translated =
+            translate_active(3, &active, &values, &translations, &commitments).unwrap();
+        let decoded = encodings
+            .iter()
+            .zip(&translated)
+            .map(|(encoding, active)| encoding.decode(active).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, values);
+
+        // The translations are specific to the copy.
+        assert!(translate_active(2, &active, &values, &translations, &commitments).is_err());
+
+        // An incorrect translation does not match the commitments.
+        translations[0] = translations[0] ^ Label::new(Block::ONES);
+        translations[1] = translations[1] ^ Label::new(Block::ONES);
+        assert!(translate_active(3, &active, &values, &translations, &commitments).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cut_and_choose() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (mut leader_sink, mut leader_stream) = leader_channel.split();
+        let (mut follower_sink, mut follower_stream) = follower_channel.split();
+        let (ot_send, ot_recv) = ideal_ot_shared_pair();
+
+        let config = CutAndChooseConfig::builder()
+            .copies(5)
+            .checked(3)
+            .build()
+            .unwrap();
+        let leader = CutAndChoose::new(Role::Leader, config.clone());
+        let follower = CutAndChoose::new(Role::Follower, config);
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = async {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            leader
+                .execute(
+                    "test",
+                    AES128.clone(),
+                    &[key_ref, msg_ref],
+                    &[ciphertext_ref],
+                    &mut leader_sink,
+                    &mut leader_stream,
+                    &ot_send,
+                    &ot_recv,
+                )
+                .await
+                .unwrap()
+        };
+
+        let follower_fut = async {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            follower
+                .execute(
+                    "test",
+                    AES128.clone(),
+                    &[key_ref, msg_ref],
+                    &[ciphertext_ref],
+                    &mut follower_sink,
+                    &mut follower_stream,
+                    &ot_send,
+                    &ot_recv,
+                )
+                .await
+                .unwrap()
+        };
+
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        let expected: [u8; 16] = {
+            use aes::{
+                cipher::{BlockEncrypt, KeyInit},
+                Aes128,
+            };
+
+            let mut msg = msg.into();
+            Aes128::new_from_slice(&key)
+                .unwrap()
+                .encrypt_block(&mut msg);
+            msg.into()
+        };
+
+        assert_eq!(leader_result, follower_result);
+        assert_eq!(leader_result, vec![Value::from(expected)]);
+    }
+
+    #[tokio::test]
+    async fn test_cut_and_choose_inconsistent_inputs() {
+        let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
+        let (mut leader_sink, mut leader_stream) = leader_channel.split();
+        let (mut follower_sink, mut follower_stream) = follower_channel.split();
+        let (ot_send, ot_recv) = ideal_ot_shared_pair();
+
+        let config = CutAndChooseConfig::builder()
+            .copies(5)
+            .checked(3)
+            .build()
+            .unwrap();
+        let leader = CutAndChoose::new(Role::Leader, config.clone());
+        let follower = CutAndChoose::new(Role::Follower, config);
+
+        let leader_fut = async {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, [42u8; 16]).unwrap();
+
+            let inputs = [key_ref, msg_ref];
+            let (assigned_values, input_types) = {
+                let mut state = leader.state();
+                let input_types = inputs
+                    .iter()
+                    .map(|input| state.memory.get_value_type(input))
+                    .collect::<Vec<_>>();
+
+                (state.memory.drain_assigned(&inputs), input_types)
+            };
+
+            // The generator uses a different key in every copy.
+            leader
+                .execute_generator(
+                    "test",
+                    AES128.clone(),
+                    &inputs,
+                    &input_types,
+                    &[ciphertext_ref],
+                    &assigned_values,
+                    |idx| {
+                        assigned_values
+                            .private
+                            .iter()
+                            .map(|(id, _)| (id.clone(), Value::U8(idx as u8)))
+                            .collect()
+                    },
+                    &mut leader_sink,
+                    &mut leader_stream,
+                    &ot_send,
+                )
+                .await
+        };
+
+        let follower_fut = async {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, [69u8; 16]).unwrap();
+
+            follower
+                .execute(
+                    "test",
+                    AES128.clone(),
+                    &[key_ref, msg_ref],
+                    &[ciphertext_ref],
+                    &mut follower_sink,
+                    &mut follower_stream,
+                    &ot_send,
+                    &ot_recv,
+                )
+                .await
+        };
+
+        // The generator waits for the evaluator's output, which it never receives.
+        let follower_result =
+            match futures::future::select(Box::pin(leader_fut), Box::pin(follower_fut)).await {
+                Either::Left(_) => panic!("the generator should not complete"),
+                Either::Right((result, _)) => result,
+            };
+
+        assert!(matches!(
+            follower_result,
+            Err(CutAndChooseError::InconsistentInputs)
+        ));
+    }
+}
@@ -31,7 +31,7 @@ use crate::{
     evaluator::{Evaluator, EvaluatorConfigBuilder},
     generator::{Generator, GeneratorConfigBuilder},
     internal_circuits::{build_otp_circuit, build_otp_shared_circuit},
-    memory::ValueMemory,
+    memory::{AssignedValues, ValueMemory},
     ot::{OTReceiveEncoding, OTSendEncoding, OTVerifyEncoding},
     value::ValueRef,
 };
@@ -265,6 +265,7 @@ impl DEAP {
         }
 
         let assigned_values = self.state().memory.drain_assigned(inputs);
+        let privacy_free = is_known_to_leader(inputs, &assigned_values, &assigned_values.blind);
 
         // The prover only acts as the evaluator for ZKPs instead of
         // dual-execution.
@@ -274,7 +275,7 @@ impl DEAP {
             .await?;
 
         self.ev
-            .evaluate(circ, inputs, outputs, stream)
+            .evaluate_with(circ, inputs, outputs, stream, privacy_free)
             .map_err(DEAPError::from)
             .await?;
 
@@ -316,6 +317,7 @@ impl DEAP {
         }
 
         let assigned_values = self.state().memory.drain_assigned(inputs);
+        let privacy_free = is_known_to_leader(inputs, &assigned_values, &assigned_values.private);
 
         // The verifier only acts as the generator for ZKPs instead of
         // dual-execution.
@@ -325,7 +327,7 @@ impl DEAP {
             .await?;
 
         self.gen
-            .generate(circ.clone(), inputs, outputs, sink, false)
+            .generate_with(circ.clone(), inputs, outputs, sink, false, privacy_free)
             .map_err(DEAPError::from)
             .await?;
 
@@ -886,6 +888,24 @@ impl State {
     }
 }
 
+/// Returns whether the leader knows all the inputs of a proof, in which case the circuit can be
+/// garbled privacy-free.
+///
+/// Both parties must come to the same conclusion, so this only depends on which inputs are
+/// assigned in this call and on the values which are private to the follower, ie. the blind
+/// values of the leader or the private values of the follower.
+fn is_known_to_leader<T>(
+    inputs: &[ValueRef],
+    assigned_values: &AssignedValues,
+    follower_private: &[T],
+) -> bool {
+    let assigned_count =
+        assigned_values.public.len() + assigned_values.private.len() + assigned_values.blind.len();
+
+    follower_private.is_empty()
+        && assigned_count == inputs.iter().map(|input| input.len()).sum::<usize>()
+}
+
 #[cfg(test)]
 mod tests {
     use mpz_circuits::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
@@ -1346,6 +1366,36 @@ mod tests {
             [
                 235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 75,
             ],
+            false,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_deap_zk_privacy_free_pass() {
+        // The leader knows all the inputs, so the circuit is garbled privacy-free.
+        run_zk(
+            [42u8; 16],
+            [69u8; 16],
+            [
+                235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 75,
+            ],
+            true,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_deap_zk_privacy_free_fail() {
+        run_zk(
+            [42u8; 16],
+            [69u8; 16],
+            // wrong ciphertext
+            [
+                235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 76,
+            ],
+            true,
         )
         .await;
     }
@@ -1360,11 +1410,12 @@ mod tests {
             [
                 235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 76,
             ],
+            false,
         )
         .await;
     }
 
-    async fn run_zk(key: [u8; 16], msg: [u8; 16], expected_ciphertext: [u8; 16]) {
+    async fn run_zk(key: [u8; 16], msg: [u8; 16], expected_ciphertext: [u8; 16], public_msg: bool) {
         let (leader_channel, follower_channel) = MemoryDuplex::<GarbleMessage>::new();
         let (_, follower_ot_recv) = ideal_ot_shared_pair();
         let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
@@ -1375,7 +1426,13 @@ mod tests {
         let leader_fut = {
             let (mut sink, mut stream) = leader_channel.split();
             let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let msg_ref = if public_msg {
+                let msg_ref = leader.new_public_input::<[u8; 16]>("msg").unwrap();
+                leader.assign(&msg_ref, msg).unwrap();
+                msg_ref
+            } else {
+                leader.new_blind_input::<[u8; 16]>("msg").unwrap()
+            };
             let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
 
             leader.assign(&key_ref, key).unwrap();
@@ -1408,7 +1465,11 @@ mod tests {
         let follower_fut = {
             let (mut sink, mut stream) = follower_channel.split();
             let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let msg_ref = if public_msg {
+                follower.new_public_input::<[u8; 16]>("msg").unwrap()
+            } else {
+                follower.new_private_input::<[u8; 16]>("msg").unwrap()
+            };
             let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
 
             follower.assign(&msg_ref, msg).unwrap();
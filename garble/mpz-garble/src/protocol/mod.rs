@@ -1,3 +1,6 @@
 //! Implementations of garbled circuit protocols
 
+pub mod authenticated;
+pub mod cut_and_choose;
 pub mod deap;
+pub mod semihonest;
@@ -0,0 +1,46 @@
+use mpz_garble_core::ValueError;
+
+use crate::{DecodeError, ExecutionError, LoadError};
+
+/// Errors that can occur during the semi-honest protocol.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SemiHonestError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    GeneratorError(#[from] crate::generator::GeneratorError),
+    #[error(transparent)]
+    EvaluatorError(#[from] crate::evaluator::EvaluatorError),
+    #[error(transparent)]
+    ValueError(#[from] ValueError),
+    #[error("incorrect number of values: expected {expected}, got {actual}")]
+    IncorrectValueCount { expected: usize, actual: usize },
+}
+
+impl From<SemiHonestError> for LoadError {
+    fn from(err: SemiHonestError) -> Self {
+        match err {
+            SemiHonestError::IOError(err) => LoadError::IOError(err),
+            err => LoadError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<SemiHonestError> for ExecutionError {
+    fn from(err: SemiHonestError) -> Self {
+        match err {
+            SemiHonestError::IOError(err) => ExecutionError::IOError(err),
+            err => ExecutionError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<SemiHonestError> for DecodeError {
+    fn from(err: SemiHonestError) -> Self {
+        match err {
+            SemiHonestError::IOError(err) => DecodeError::IOError(err),
+            err => DecodeError::ProtocolError(Box::new(err)),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Mocked semi-honest VMs for testing
+
+use mpz_ot::ideal::{ideal_ot_shared_pair, IdealSharedOTReceiver, IdealSharedOTSender};
+use utils_aio::mux::mock::MockMuxChannelFactory;
+
+use crate::config::Role;
+
+use super::{vm::SemiHonestVm, SemiHonestThread};
+
+/// Mock semi-honest Leader VM.
+pub type MockLeader = SemiHonestVm<IdealSharedOTSender, IdealSharedOTReceiver>;
+/// Mock semi-honest Leader thread.
+pub type MockLeaderThread = SemiHonestThread<IdealSharedOTSender, IdealSharedOTReceiver>;
+/// Mock semi-honest Follower VM.
+pub type MockFollower = SemiHonestVm<IdealSharedOTSender, IdealSharedOTReceiver>;
+/// Mock semi-honest Follower thread.
+pub type MockFollowerThread = SemiHonestThread<IdealSharedOTSender, IdealSharedOTReceiver>;
+
+/// Create a pair of mocked semi-honest VMs
+pub async fn create_mock_semihonest_vm(id: &str) -> (MockLeader, MockFollower) {
+    let mux_factory = MockMuxChannelFactory::new();
+    let (leader_ot_send, follower_ot_recv) = ideal_ot_shared_pair();
+    let (follower_ot_send, leader_ot_recv) = ideal_ot_shared_pair();
+
+    let leader = SemiHonestVm::new(
+        id,
+        Role::Leader,
+        [42u8; 32],
+        Box::new(mux_factory.clone()),
+        leader_ot_send,
+        leader_ot_recv,
+    );
+
+    let follower = SemiHonestVm::new(
+        id,
+        Role::Follower,
+        [69u8; 32],
+        Box::new(mux_factory),
+        follower_ot_send,
+        follower_ot_recv,
+    );
+
+    (leader, follower)
+}
@@ -0,0 +1,375 @@
+//! A semi-honest implementation of Yao's garbled circuit protocol.
+//!
+//! The leader acts as the generator and the follower acts as the evaluator. Unlike
+//! [DEAP](crate::protocol::deap), circuits are only garbled once, so this protocol provides
+//! no security against a malicious generator. It is intended for settings where both parties
+//! are trusted to follow the protocol, or for benchmarking.
+
+mod error;
+mod memory;
+pub mod mock;
+mod vm;
+
+use std::{
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+};
+
+use futures::{Sink, SinkExt, Stream};
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_garble_core::msg::GarbleMessage;
+use rand::thread_rng;
+use utils_aio::expect_msg_or_err;
+
+use crate::{
+    config::{Role, Visibility},
+    evaluator::{Evaluator, EvaluatorConfigBuilder},
+    generator::{Generator, GeneratorConfigBuilder},
+    internal_circuits::build_otp_circuit,
+    memory::ValueMemory,
+    ot::{OTReceiveEncoding, OTSendEncoding},
+    value::ValueRef,
+};
+
+pub use error::SemiHonestError;
+pub use vm::{SemiHonestThread, SemiHonestVm};
+
+/// The semi-honest garbled circuit protocol.
+#[derive(Debug)]
+pub struct SemiHonest {
+    backend: Backend,
+    state: Mutex<State>,
+}
+
+/// The garbled circuit backend of a party.
+#[derive(Debug)]
+enum Backend {
+    /// The leader garbles the circuits.
+    Generator(Generator),
+    /// The follower evaluates the circuits.
+    Evaluator(Evaluator),
+}
+
+#[derive(Debug, Default)]
+struct State {
+    memory: ValueMemory,
+}
+
+impl SemiHonest {
+    /// Creates a new semi-honest protocol instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of the party, the leader is the generator.
+    /// * `encoder_seed` - The seed of the encoder, only used by the leader.
+    pub fn new(role: Role, encoder_seed: [u8; 32]) -> Self {
+        let backend = match role {
+            Role::Leader => {
+                let config = GeneratorConfigBuilder::default()
+                    .build()
+                    .expect("config should be valid");
+                Backend::Generator(Generator::new(config, encoder_seed))
+            }
+            Role::Follower => {
+                let config = EvaluatorConfigBuilder::default()
+                    .build()
+                    .expect("config should be valid");
+                Backend::Evaluator(Evaluator::new(config))
+            }
+        };
+
+        Self {
+            backend,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> impl DerefMut<Target = State> + '_ {
+        self.state.lock().unwrap()
+    }
+
+    /// Performs pre-processing for executing the provided circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to load.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    pub async fn load<T, U>(
+        &self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<(), SemiHonestError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        match &self.backend {
+            Backend::Generator(gen) => {
+                _ = gen.generate(circ, inputs, outputs, sink, false).await?;
+            }
+            Backend::Evaluator(ev) => {
+                ev.receive_garbled_circuit(circ, inputs, outputs, stream)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the circuit.
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs to the circuit.
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    /// * `ot_send` - The OT sender, used by the leader.
+    /// * `ot_recv` - The OT receiver, used by the follower.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute<T, U, OTS, OTR>(
+        &self,
+        id: &str,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+        ot_send: &OTS,
+        ot_recv: &OTR,
+    ) -> Result<(), SemiHonestError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OTS: OTSendEncoding,
+        OTR: OTReceiveEncoding,
+    {
+        let assigned_values = self.state().memory.drain_assigned(inputs);
+
+        match &self.backend {
+            Backend::Generator(gen) => {
+                gen.setup_assigned_values(id, &assigned_values, sink, ot_send)
+                    .await?;
+                // Drop the encoded outputs, we don't need them here
+                _ = gen.generate(circ, inputs, outputs, sink, false).await?;
+            }
+            Backend::Evaluator(ev) => {
+                ev.setup_assigned_values(id, &assigned_values, stream, ot_recv)
+                    .await?;
+                _ = ev.evaluate(circ, inputs, outputs, stream).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the provided values, revealing the plaintext value to both parties.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to decode
+    /// * `sink` - The sink to send messages to.
+    /// * `stream` - The stream to receive messages from.
+    pub async fn decode<T, U>(
+        &self,
+        values: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+    ) -> Result<Vec<Value>, SemiHonestError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        match &self.backend {
+            Backend::Generator(gen) => {
+                gen.decode(values, sink).await?;
+                self.receive_active(gen, values, stream).await
+            }
+            Backend::Evaluator(ev) => {
+                let decoded = ev.decode(values, stream).await?;
+                sink.send(GarbleMessage::ActiveValues(ev.get_encodings(values)?))
+                    .await?;
+                Ok(decoded)
+            }
+        }
+    }
+
+    /// Decodes the provided values, revealing the plaintext value only to this party.
+    pub(crate) async fn decode_private<U>(
+        &self,
+        values: &[ValueRef],
+        stream: &mut U,
+    ) -> Result<Vec<Value>, SemiHonestError>
+    where
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        match &self.backend {
+            Backend::Generator(gen) => self.receive_active(gen, values, stream).await,
+            Backend::Evaluator(ev) => Ok(ev.decode(values, stream).await?),
+        }
+    }
+
+    /// Decodes the provided values, revealing the plaintext value only to the peer.
+    pub(crate) async fn decode_blind<T>(
+        &self,
+        values: &[ValueRef],
+        sink: &mut T,
+    ) -> Result<(), SemiHonestError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+    {
+        match &self.backend {
+            Backend::Generator(gen) => gen.decode(values, sink).await?,
+            Backend::Evaluator(ev) => {
+                sink.send(GarbleMessage::ActiveValues(ev.get_encodings(values)?))
+                    .await?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the provided values into additive (XOR) shares held by each party.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn decode_shared<T, U, OTS, OTR>(
+        &self,
+        id: &str,
+        values: &[ValueRef],
+        sink: &mut T,
+        stream: &mut U,
+        ot_send: &OTS,
+        ot_recv: &OTR,
+    ) -> Result<Vec<Value>, SemiHonestError>
+    where
+        T: Sink<GarbleMessage, Error = std::io::Error> + Unpin,
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+        OTS: OTSendEncoding,
+        OTR: OTReceiveEncoding,
+    {
+        // The leader masks the values with a one-time pad which serves as its share.
+        let (((otp_refs, otp_typs), otp_values), mask_refs): (((Vec<_>, Vec<_>), Vec<_>), Vec<_>) = {
+            let mut state = self.state();
+
+            values
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| {
+                    let otp_id = format!("{id}/{idx}/otp");
+                    let (otp_ref, otp_typ, otp_value) = match &self.backend {
+                        Backend::Generator(gen) => {
+                            let (otp_ref, otp_value) = state.new_private_otp(&otp_id, value);
+                            let otp_typ = otp_value.value_type();
+                            gen.generate_input_encoding(&otp_ref, &otp_typ);
+                            (otp_ref, otp_typ, Some(otp_value))
+                        }
+                        Backend::Evaluator(_) => {
+                            let (otp_ref, otp_typ) = state.new_blind_otp(&otp_id, value);
+                            (otp_ref, otp_typ, None)
+                        }
+                    };
+                    let mask_ref = state.new_output_mask(&format!("{id}/{idx}/mask"), value);
+                    (((otp_ref, otp_typ), otp_value), mask_ref)
+                })
+                .unzip()
+        };
+
+        // Apply OTPs to values
+        let circ = build_otp_circuit(&otp_typs);
+
+        let inputs = values
+            .iter()
+            .zip(otp_refs.iter())
+            .flat_map(|(value, otp)| [value, otp])
+            .cloned()
+            .collect::<Vec<_>>();
+
+        self.execute(
+            id, circ, &inputs, &mask_refs, sink, stream, ot_send, ot_recv,
+        )
+        .await?;
+
+        match &self.backend {
+            Backend::Generator(gen) => {
+                // Reveal the masked values to the follower, keeping the OTPs as shares
+                gen.decode(&mask_refs, sink).await?;
+                Ok(otp_values
+                    .into_iter()
+                    .map(|otp| otp.expect("leader sets otp values"))
+                    .collect())
+            }
+            Backend::Evaluator(ev) => Ok(ev.decode(&mask_refs, stream).await?),
+        }
+    }
+
+    /// Receives the active encodings of the provided values from the follower and decodes them.
+    async fn receive_active<U>(
+        &self,
+        gen: &Generator,
+        values: &[ValueRef],
+        stream: &mut U,
+    ) -> Result<Vec<Value>, SemiHonestError>
+    where
+        U: Stream<Item = Result<GarbleMessage, std::io::Error>> + Unpin,
+    {
+        let full = gen.get_encodings(values)?;
+        let active = expect_msg_or_err!(stream, GarbleMessage::ActiveValues)?;
+
+        if active.len() != full.len() {
+            return Err(SemiHonestError::IncorrectValueCount {
+                expected: full.len(),
+                actual: active.len(),
+            });
+        }
+
+        Ok(active
+            .iter()
+            .zip(full)
+            .map(|(active, full)| full.decode(active))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+impl State {
+    fn new_private_otp(&mut self, id: &str, value_ref: &ValueRef) -> (ValueRef, Value) {
+        let typ = self.memory.get_value_type(value_ref);
+        let value = Value::random(&mut thread_rng(), &typ);
+
+        let value_ref = self
+            .memory
+            .new_input(id, typ, Visibility::Private)
+            .expect("otp id is unique");
+
+        self.memory
+            .assign(&value_ref, value.clone())
+            .expect("value should assign");
+
+        (value_ref, value)
+    }
+
+    fn new_blind_otp(&mut self, id: &str, value_ref: &ValueRef) -> (ValueRef, ValueType) {
+        let typ = self.memory.get_value_type(value_ref);
+
+        (
+            self.memory
+                .new_input(id, typ.clone(), Visibility::Blind)
+                .expect("otp id is unique"),
+            typ,
+        )
+    }
+
+    fn new_output_mask(&mut self, id: &str, value_ref: &ValueRef) -> ValueRef {
+        let typ = self.memory.get_value_type(value_ref);
+        self.memory.new_output(id, typ).expect("mask id is unique")
+    }
+}
@@ -0,0 +1,464 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Weak},
+};
+
+use async_trait::async_trait;
+use futures::{
+    stream::{SplitSink, SplitStream},
+    StreamExt, TryFutureExt,
+};
+
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_garble_core::msg::GarbleMessage;
+use utils::id::NestedId;
+use utils_aio::{duplex::Duplex, mux::MuxChannel};
+
+use crate::{
+    config::{Role, Visibility},
+    ot::{OTReceiveEncoding, OTSendEncoding},
+    value::ValueRef,
+    Decode, DecodeError, DecodePrivate, Execute, ExecutionError, Load, LoadError, Memory,
+    MemoryError, Thread, Vm, VmError,
+};
+
+use super::SemiHonest;
+
+type ChannelFactory = Box<dyn MuxChannel<GarbleMessage> + Send + 'static>;
+type GarbleChannel = Box<dyn Duplex<GarbleMessage>>;
+
+/// A semi-honest garbled circuit Vm.
+pub struct SemiHonestVm<OTS, OTR> {
+    /// The id of the vm.
+    id: NestedId,
+    /// Channel factory used to create new channels for new threads.
+    channel_factory: ChannelFactory,
+    /// The OT sender, used by the leader.
+    ot_send: Arc<OTS>,
+    /// The OT receiver, used by the follower.
+    ot_recv: Arc<OTR>,
+    /// The protocol instance.
+    ///
+    /// The vm is the only owner of a strong reference to the instance.
+    protocol: Arc<SemiHonest>,
+    /// The set of threads spawned by this vm.
+    threads: HashSet<NestedId>,
+}
+
+impl<OTS, OTR> SemiHonestVm<OTS, OTR>
+where
+    OTS: OTSendEncoding,
+    OTR: OTReceiveEncoding,
+{
+    /// Create a new semi-honest Vm.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the vm.
+    /// * `role` - The role of the vm, the leader is the generator.
+    /// * `encoder_seed` - The seed of the encoder, only used by the leader.
+    /// * `channel_factory` - The channel factory used to create new channels for new threads.
+    /// * `ot_send` - The OT sender, used by the leader.
+    /// * `ot_recv` - The OT receiver, used by the follower.
+    pub fn new(
+        id: &str,
+        role: Role,
+        encoder_seed: [u8; 32],
+        channel_factory: ChannelFactory,
+        ot_send: OTS,
+        ot_recv: OTR,
+    ) -> Self {
+        Self {
+            id: NestedId::new(id),
+            channel_factory,
+            ot_send: Arc::new(ot_send),
+            ot_recv: Arc::new(ot_recv),
+            protocol: Arc::new(SemiHonest::new(role, encoder_seed)),
+            threads: HashSet::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<OTS, OTR> Vm for SemiHonestVm<OTS, OTR>
+where
+    OTS: OTSendEncoding + Send + Sync + 'static,
+    OTR: OTReceiveEncoding + Send + Sync + 'static,
+{
+    type Thread = SemiHonestThread<OTS, OTR>;
+
+    async fn new_thread(&mut self, id: &str) -> Result<SemiHonestThread<OTS, OTR>, VmError> {
+        let thread_id = self.id.append_string(id);
+
+        if !self.threads.insert(thread_id.clone()) {
+            return Err(VmError::ThreadAlreadyExists(thread_id.to_string()));
+        }
+
+        let channel = self
+            .channel_factory
+            .get_channel(&thread_id.to_string())
+            .await?;
+
+        Ok(SemiHonestThread::new(
+            thread_id,
+            channel,
+            Arc::downgrade(&self.protocol),
+            self.ot_send.clone(),
+            self.ot_recv.clone(),
+        ))
+    }
+}
+
+/// A semi-honest garbled circuit thread.
+pub struct SemiHonestThread<OTS, OTR> {
+    /// The thread id.
+    _id: NestedId,
+    /// The current operation id.
+    op_id: NestedId,
+    /// Reference to the protocol instance.
+    protocol: Weak<SemiHonest>,
+    /// The OT sender.
+    ot_send: Arc<OTS>,
+    /// The OT receiver.
+    ot_recv: Arc<OTR>,
+    /// The duplex channel sink to the peer.
+    sink: SplitSink<GarbleChannel, GarbleMessage>,
+    /// The duplex channel stream from the peer.
+    stream: SplitStream<GarbleChannel>,
+}
+
+impl<OTS, OTR> SemiHonestThread<OTS, OTR> {
+    fn new(
+        id: NestedId,
+        channel: GarbleChannel,
+        protocol: Weak<SemiHonest>,
+        ot_send: Arc<OTS>,
+        ot_recv: Arc<OTR>,
+    ) -> Self {
+        let (sink, stream) = channel.split();
+        let op_id = id.append_counter();
+        Self {
+            _id: id,
+            op_id,
+            protocol,
+            ot_send,
+            ot_recv,
+            sink,
+            stream,
+        }
+    }
+
+    fn protocol(&self) -> Arc<SemiHonest> {
+        self.protocol
+            .upgrade()
+            .expect("instance should not be dropped")
+    }
+}
+
+impl<OTS, OTR> Thread for SemiHonestThread<OTS, OTR> {}
+
+impl<OTS, OTR> Memory for SemiHonestThread<OTS, OTR> {
+    fn new_input_with_type(
+        &self,
+        id: &str,
+        typ: ValueType,
+        visibility: Visibility,
+    ) -> Result<ValueRef, MemoryError> {
+        self.protocol().new_input_with_type(id, typ, visibility)
+    }
+
+    fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
+        self.protocol().new_output_with_type(id, typ)
+    }
+
+    fn assign(&self, value_ref: &ValueRef, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol().assign(value_ref, value)
+    }
+
+    fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol().assign_by_id(id, value)
+    }
+
+    fn get_value(&self, id: &str) -> Option<ValueRef> {
+        self.protocol().get_value(id)
+    }
+
+    fn get_value_type(&self, value_ref: &ValueRef) -> ValueType {
+        self.protocol().get_value_type(value_ref)
+    }
+
+    fn get_value_type_by_id(&self, id: &str) -> Option<ValueType> {
+        self.protocol().get_value_type_by_id(id)
+    }
+}
+
+#[async_trait]
+impl<OTS, OTR> Load for SemiHonestThread<OTS, OTR>
+where
+    OTS: Send + Sync,
+    OTR: Send + Sync,
+{
+    async fn load(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), LoadError> {
+        self.protocol()
+            .load(circ, inputs, outputs, &mut self.sink, &mut self.stream)
+            .map_err(LoadError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<OTS, OTR> Execute for SemiHonestThread<OTS, OTR>
+where
+    OTS: OTSendEncoding + Send + Sync,
+    OTR: OTReceiveEncoding + Send + Sync,
+{
+    async fn execute(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), ExecutionError> {
+        self.protocol()
+            .execute(
+                &self.op_id.increment_in_place().to_string(),
+                circ,
+                inputs,
+                outputs,
+                &mut self.sink,
+                &mut self.stream,
+                &*self.ot_send,
+                &*self.ot_recv,
+            )
+            .map_err(ExecutionError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<OTS, OTR> Decode for SemiHonestThread<OTS, OTR>
+where
+    OTS: Send + Sync,
+    OTR: Send + Sync,
+{
+    async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol()
+            .decode(values, &mut self.sink, &mut self.stream)
+            .map_err(DecodeError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<OTS, OTR> DecodePrivate for SemiHonestThread<OTS, OTR>
+where
+    OTS: OTSendEncoding + Send + Sync,
+    OTR: OTReceiveEncoding + Send + Sync,
+{
+    async fn decode_private(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol()
+            .decode_private(values, &mut self.stream)
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    async fn decode_blind(&mut self, values: &[ValueRef]) -> Result<(), DecodeError> {
+        self.protocol()
+            .decode_blind(values, &mut self.sink)
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    async fn decode_shared(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol()
+            .decode_shared(
+                &self.op_id.increment_in_place().to_string(),
+                values,
+                &mut self.sink,
+                &mut self.stream,
+                &*self.ot_send,
+                &*self.ot_recv,
+            )
+            .map_err(DecodeError::from)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::circuits::AES128;
+
+    use crate::protocol::semihonest::mock::create_mock_semihonest_vm;
+
+    fn aes128(key: [u8; 16], msg: [u8; 16]) -> [u8; 16] {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+        Aes128::new_from_slice(&key)
+            .unwrap()
+            .encrypt_block(&mut msg);
+        msg.into()
+    }
+
+    #[tokio::test]
+    async fn test_vm() {
+        let (mut leader_vm, mut follower_vm) = create_mock_semihonest_vm("test_vm").await;
+
+        let mut leader_thread = leader_vm.new_thread("test_thread").await.unwrap();
+        let mut follower_thread = follower_vm.new_thread("test_thread").await.unwrap();
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader_thread.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader_thread.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader_thread.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader_thread.assign(&key_ref, key).unwrap();
+
+            async move {
+                leader_thread
+                    .load(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        std::slice::from_ref(&ciphertext_ref),
+                    )
+                    .await
+                    .unwrap();
+
+                leader_thread
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        std::slice::from_ref(&ciphertext_ref),
+                    )
+                    .await
+                    .unwrap();
+
+                leader_thread.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower_thread.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower_thread
+                .new_private_input::<[u8; 16]>("msg")
+                .unwrap();
+            let ciphertext_ref = follower_thread
+                .new_output::<[u8; 16]>("ciphertext")
+                .unwrap();
+
+            follower_thread.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower_thread
+                    .load(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        std::slice::from_ref(&ciphertext_ref),
+                    )
+                    .await
+                    .unwrap();
+
+                follower_thread
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        std::slice::from_ref(&ciphertext_ref),
+                    )
+                    .await
+                    .unwrap();
+
+                follower_thread.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_result, follower_result);
+        assert_eq!(leader_result, vec![Value::from(aes128(key, msg))]);
+    }
+
+    #[tokio::test]
+    async fn test_vm_decode_private() {
+        let (mut leader_vm, mut follower_vm) = create_mock_semihonest_vm("test_vm").await;
+
+        let mut leader_thread = leader_vm.new_thread("test_thread").await.unwrap();
+        let mut follower_thread = follower_vm.new_thread("test_thread").await.unwrap();
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        // Encrypts the message, revealing the ciphertext to the leader, to the follower and
+        // as shares to both parties.
+        async fn run<T: Thread + Execute + DecodePrivate>(
+            thread: &mut T,
+            key: Option<[u8; 16]>,
+            leader: bool,
+        ) -> Vec<Value> {
+            let key_ref = if key.is_some() {
+                thread.new_private_input::<[u8; 16]>("key").unwrap()
+            } else {
+                thread.new_blind_input::<[u8; 16]>("key").unwrap()
+            };
+            let msg_ref = thread.new_public_input::<[u8; 16]>("msg").unwrap();
+            let outputs = ["leader", "follower", "shared"]
+                .map(|id| thread.new_output::<[u8; 16]>(id).unwrap());
+
+            if let Some(key) = key {
+                thread.assign(&key_ref, key).unwrap();
+            }
+            thread.assign(&msg_ref, [69u8; 16]).unwrap();
+
+            for output in &outputs {
+                thread
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        std::slice::from_ref(output),
+                    )
+                    .await
+                    .unwrap();
+            }
+
+            let [leader_ref, follower_ref, shared_ref] = outputs;
+            let mut values = Vec::new();
+            if leader {
+                values.extend(thread.decode_private(&[leader_ref]).await.unwrap());
+                thread.decode_blind(&[follower_ref]).await.unwrap();
+            } else {
+                thread.decode_blind(&[leader_ref]).await.unwrap();
+                values.extend(thread.decode_private(&[follower_ref]).await.unwrap());
+            }
+            values.extend(thread.decode_shared(&[shared_ref]).await.unwrap());
+            values
+        }
+
+        let (leader_result, follower_result) = futures::join!(
+            run(&mut leader_thread, Some(key), true),
+            run(&mut follower_thread, None, false)
+        );
+
+        let expected = Value::from(aes128(key, msg));
+
+        assert_eq!(leader_result[0], expected);
+        assert_eq!(follower_result[0], expected);
+        assert_eq!(
+            (leader_result[1].clone() ^ follower_result[1].clone()).unwrap(),
+            expected
+        );
+    }
+}
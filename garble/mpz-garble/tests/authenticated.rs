@@ -0,0 +1,151 @@
+use aes::{
+    cipher::{BlockEncrypt, KeyInit},
+    Aes128,
+};
+use futures::{
+    stream::{SplitSink, SplitStream},
+    StreamExt,
+};
+use mpz_circuits::{circuits::AES128, types::Value};
+use mpz_core::Block;
+use mpz_garble::{
+    config::Role, protocol::authenticated::AuthenticatedVm, Decode, Execute, Memory, Vm,
+};
+use mpz_ot::{
+    ferret::{
+        msgs::Message, params::LPN_PARAMETERS_REGULAR_2_20, FerretConfig, Receiver, Sender,
+        SharedReceiver, SharedSender,
+    },
+    ideal::{ideal_ot_pair, IdealOTReceiver, IdealOTSender},
+    OTSetup,
+};
+use utils_aio::{duplex::MemoryDuplex, mux::mock::MockMuxChannelFactory};
+
+type FerretChannel = MemoryDuplex<Message<()>>;
+type FerretSender = SharedSender<
+    IdealOTSender<Block>,
+    SplitSink<FerretChannel, Message<()>>,
+    SplitStream<FerretChannel>,
+>;
+type FerretReceiver = SharedReceiver<
+    IdealOTReceiver<Block>,
+    SplitSink<FerretChannel, Message<()>>,
+    SplitStream<FerretChannel>,
+>;
+
+/// Sets up a pair of shared Ferret COT sender and receiver.
+async fn ferret_pair() -> (FerretSender, FerretReceiver) {
+    let (sender_channel, receiver_channel) = FerretChannel::new();
+
+    let (mut sender_sink, mut sender_stream) = sender_channel.split();
+    let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+    let config = FerretConfig::builder()
+        .lpn_parameters(LPN_PARAMETERS_REGULAR_2_20)
+        .build()
+        .unwrap();
+
+    let (base_sender, base_receiver) = ideal_ot_pair();
+
+    let mut sender = Sender::new(config.clone(), base_sender);
+    let mut receiver = Receiver::new(config, base_receiver);
+
+    let (sender_res, receiver_res) = tokio::join!(
+        sender.setup(&mut sender_sink, &mut sender_stream),
+        receiver.setup(&mut receiver_sink, &mut receiver_stream)
+    );
+
+    sender_res.unwrap();
+    receiver_res.unwrap();
+
+    (
+        SharedSender::new(sender, sender_sink, sender_stream).unwrap(),
+        SharedReceiver::new(receiver, receiver_sink, receiver_stream),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_authenticated_ferret() {
+    let mux_factory = MockMuxChannelFactory::new();
+
+    let (leader_cot_send, follower_cot_recv) = ferret_pair().await;
+    let (follower_cot_send, leader_cot_recv) = ferret_pair().await;
+
+    let mut leader_vm = AuthenticatedVm::new(
+        "test_vm",
+        Role::Leader,
+        Box::new(mux_factory.clone()),
+        leader_cot_send,
+        leader_cot_recv,
+    );
+    let mut follower_vm = AuthenticatedVm::new(
+        "test_vm",
+        Role::Follower,
+        Box::new(mux_factory),
+        follower_cot_send,
+        follower_cot_recv,
+    );
+
+    let mut leader_thread = leader_vm.new_thread("test_thread").await.unwrap();
+    let mut follower_thread = follower_vm.new_thread("test_thread").await.unwrap();
+
+    let key = [42u8; 16];
+    let msg = [69u8; 16];
+
+    let leader_fut = {
+        let key_ref = leader_thread.new_private_input::<[u8; 16]>("key").unwrap();
+        let msg_ref = leader_thread.new_blind_input::<[u8; 16]>("msg").unwrap();
+        let ciphertext_ref = leader_thread.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+        leader_thread.assign(&key_ref, key).unwrap();
+
+        async move {
+            leader_thread
+                .execute(
+                    AES128.clone(),
+                    &[key_ref, msg_ref],
+                    std::slice::from_ref(&ciphertext_ref),
+                )
+                .await
+                .unwrap();
+
+            leader_thread.decode(&[ciphertext_ref]).await.unwrap()
+        }
+    };
+
+    let follower_fut = {
+        let key_ref = follower_thread.new_blind_input::<[u8; 16]>("key").unwrap();
+        let msg_ref = follower_thread
+            .new_private_input::<[u8; 16]>("msg")
+            .unwrap();
+        let ciphertext_ref = follower_thread
+            .new_output::<[u8; 16]>("ciphertext")
+            .unwrap();
+
+        follower_thread.assign(&msg_ref, msg).unwrap();
+
+        async move {
+            follower_thread
+                .execute(
+                    AES128.clone(),
+                    &[key_ref, msg_ref],
+                    std::slice::from_ref(&ciphertext_ref),
+                )
+                .await
+                .unwrap();
+
+            follower_thread.decode(&[ciphertext_ref]).await.unwrap()
+        }
+    };
+
+    let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+    let mut expected = msg.into();
+    Aes128::new_from_slice(&key)
+        .unwrap()
+        .encrypt_block(&mut expected);
+    let expected: [u8; 16] = expected.into();
+
+    assert_eq!(leader_result, follower_result);
+    assert_eq!(leader_result, vec![Value::from(expected)]);
+}
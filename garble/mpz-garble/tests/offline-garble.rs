@@ -3,7 +3,10 @@ use mpz_garble_core::msg::GarbleMessage;
 use mpz_ot::ideal::ideal_ot_shared_pair;
 use utils_aio::duplex::MemoryDuplex;
 
-use mpz_garble::{config::Visibility, Evaluator, Generator, GeneratorConfigBuilder, ValueMemory};
+use mpz_garble::{
+    config::Visibility, ConsumedExports, Evaluator, EvaluatorConfigBuilder, EvaluatorPreprocessed,
+    Generator, GeneratorConfigBuilder, GeneratorPreprocessed, ValueMemory,
+};
 
 #[tokio::test]
 async fn test_offline_garble() {
@@ -134,3 +137,154 @@ async fn test_offline_garble() {
 
     assert_eq!(ciphertext, expected)
 }
+
+#[tokio::test]
+async fn test_offline_garble_persistent() {
+    let (mut gen_channel, mut ev_channel) = MemoryDuplex::<GarbleMessage>::new();
+    let (ot_send, ot_recv) = ideal_ot_shared_pair();
+
+    let key = [69u8; 16];
+    let msg = [42u8; 16];
+
+    let typ = <[u8; 16]>::value_type();
+
+    // Both parties must use the same value IDs across restarts.
+    let new_memory = |key_vis, msg_vis| {
+        let mut memory = ValueMemory::default();
+        let key_ref = memory.new_input("key", typ.clone(), key_vis).unwrap();
+        let msg_ref = memory.new_input("msg", typ.clone(), msg_vis).unwrap();
+        let ciphertext_ref = memory.new_output("ciphertext", typ.clone()).unwrap();
+        (memory, key_ref, msg_ref, ciphertext_ref)
+    };
+
+    // Garble the circuit and persist the state of both parties.
+    let (gen_bytes, ev_bytes) = {
+        let gen = Generator::new(
+            GeneratorConfigBuilder::default().build().unwrap(),
+            [0u8; 32],
+        );
+        let ev = Evaluator::default();
+
+        let gen_fut = async {
+            let (_, key_ref, msg_ref, ciphertext_ref) =
+                new_memory(Visibility::Private, Visibility::Blind);
+
+            gen.generate_input_encoding(&key_ref, &typ);
+            gen.generate_input_encoding(&msg_ref, &typ);
+
+            gen.generate(
+                AES128.clone(),
+                &[key_ref, msg_ref],
+                &[ciphertext_ref],
+                &mut gen_channel,
+                false,
+            )
+            .await
+            .unwrap();
+        };
+
+        let ev_fut = async {
+            let (_, key_ref, msg_ref, ciphertext_ref) =
+                new_memory(Visibility::Blind, Visibility::Private);
+
+            ev.receive_garbled_circuit(
+                AES128.clone(),
+                &[key_ref, msg_ref],
+                &[ciphertext_ref],
+                &mut ev_channel,
+            )
+            .await
+            .unwrap();
+        };
+
+        tokio::join!(gen_fut, ev_fut);
+
+        (
+            gen.export_preprocessed().to_bytes(),
+            ev.export_preprocessed().to_bytes(),
+        )
+    };
+
+    // Restore the parties and execute the circuit once the inputs are known.
+    let gen = Generator::from_preprocessed(
+        GeneratorConfigBuilder::default().build().unwrap(),
+        GeneratorPreprocessed::from_bytes(&gen_bytes).unwrap(),
+        &mut ConsumedExports::default(),
+    )
+    .unwrap();
+    let ev = Evaluator::from_preprocessed(
+        EvaluatorConfigBuilder::default().build().unwrap(),
+        EvaluatorPreprocessed::from_bytes(&ev_bytes).unwrap(),
+    )
+    .unwrap();
+
+    let gen_fut = async {
+        let (mut memory, key_ref, msg_ref, ciphertext_ref) =
+            new_memory(Visibility::Private, Visibility::Blind);
+
+        memory.assign(&key_ref, key.into()).unwrap();
+
+        gen.setup_assigned_values(
+            "test",
+            &memory.drain_assigned(&[key_ref, msg_ref]),
+            &mut gen_channel,
+            &ot_send,
+        )
+        .await
+        .unwrap();
+
+        gen.get_encoding(&ciphertext_ref).unwrap()
+    };
+
+    let ev_fut = async {
+        let (mut memory, key_ref, msg_ref, ciphertext_ref) =
+            new_memory(Visibility::Blind, Visibility::Private);
+
+        memory.assign(&msg_ref, msg.into()).unwrap();
+
+        ev.setup_assigned_values(
+            "test",
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ev_channel,
+            &ot_recv,
+        )
+        .await
+        .unwrap();
+
+        _ = ev
+            .evaluate(
+                AES128.clone(),
+                &[key_ref, msg_ref],
+                &[ciphertext_ref.clone()],
+                &mut ev_channel,
+            )
+            .await
+            .unwrap();
+
+        ev.get_encoding(&ciphertext_ref).unwrap()
+    };
+
+    let (ciphertext_full_encoding, ciphertext_active_encoding) = tokio::join!(gen_fut, ev_fut);
+
+    let ciphertext: [u8; 16] = ciphertext_active_encoding
+        .decode(&ciphertext_full_encoding.decoding())
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let expected: [u8; 16] = {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        cipher.encrypt_block(&mut msg);
+
+        msg.into()
+    };
+
+    assert_eq!(ciphertext, expected)
+}
@@ -7,7 +7,7 @@ use syn::{
 
 use crate::{
     traits::IsPrimitiveType,
-    visitors::{CallRename, CallRenameConfig, FnSigTypeReplace},
+    visitors::{CallRename, CallRenameConfig, FnSigTypeReplace, IfSelect},
     DEFAULT_SUFFIX,
 };
 
@@ -94,6 +94,9 @@ pub(crate) fn trace_impl(args: TokenStream, item: TokenStream) -> TokenStream {
         CallRename { config }.visit_item_fn_mut(&mut trace_fn);
     }
 
+    // Replace if expressions with multiplexers
+    IfSelect.visit_block_mut(&mut trace_fn.block);
+
     // insert 'trace lifetime into generics
     trace_fn.sig.generics.params.insert(0, parse_quote!('trace));
 
@@ -215,6 +218,7 @@ pub(crate) fn trace_impl(args: TokenStream, item: TokenStream) -> TokenStream {
 
     let output_expr: Vec<Expr> = if let Type::Tuple(tuple_type) = &return_type {
         (0..tuple_type.elems.len())
+            .map(syn::Index::from)
             .map(|i| parse_quote!(builder.add_output(output.#i)))
             .collect()
     } else {
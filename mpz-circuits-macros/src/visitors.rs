@@ -1,6 +1,11 @@
+use proc_macro2::Span;
 use syn::{
-    parse::Parse, parse_quote, visit_mut::VisitMut, Expr, FnArg, Ident, ItemFn, Meta, Path, Token,
-    Type,
+    parse::Parse,
+    parse_quote, parse_quote_spanned,
+    spanned::Spanned,
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    Expr, ExprIf, FnArg, Ident, ItemFn, Label, Lifetime, Meta, Path, Token, Type,
 };
 
 use crate::map::map_primitive_type;
@@ -129,3 +134,219 @@ impl VisitMut for CallRename {
         }
     }
 }
+
+/// Translates `if` expressions into calls to `Select`, which is a multiplexer if the condition is
+/// traced.
+///
+/// Both branches are only evaluated if the condition is not known while tracing. The branches are
+/// bound once in a closure, so they can not contain control flow which would escape them, such as
+/// `return` or `?`.
+pub struct IfSelect;
+
+impl VisitMut for IfSelect {
+    fn visit_expr_mut(&mut self, i: &mut Expr) {
+        // process nested expressions first
+        visit_mut::visit_expr_mut(self, i);
+
+        let Expr::If(ExprIf {
+            cond,
+            then_branch,
+            else_branch: Some((_, else_branch)),
+            ..
+        }) = i
+        else {
+            return;
+        };
+
+        // `if let` can not be traced
+        if matches!(**cond, Expr::Let(_)) {
+            return;
+        }
+
+        let mut finder = ControlFlowFinder::default();
+        finder.visit_block(then_branch);
+        finder.visit_expr(else_branch);
+
+        if let Some(span) = finder.found {
+            *i = parse_quote_spanned! {span=>
+                compile_error!("control flow can not escape the branches of a traced `if` expression")
+            };
+            return;
+        }
+
+        *i = parse_quote! {
+            {
+                let __trace_cond = #cond;
+                #[allow(unused_mut)]
+                let mut __trace_branch = |__trace_then: bool| {
+                    if __trace_then #then_branch else #else_branch
+                };
+                match ::mpz_circuits::ops::Condition::to_const(&__trace_cond) {
+                    Some(__trace_then) => __trace_branch(__trace_then),
+                    None => {
+                        let __trace_then = __trace_branch(true);
+                        let __trace_else = __trace_branch(false);
+                        ::mpz_circuits::ops::Select::select(__trace_cond, __trace_then, __trace_else)
+                    }
+                }
+            }
+        };
+    }
+}
+
+/// Finds the first control flow expression which would escape the expressions it visits.
+#[derive(Default)]
+struct ControlFlowFinder {
+    /// The labels of the enclosing loops and blocks, `None` for unlabeled loops.
+    labels: Vec<Option<Lifetime>>,
+    found: Option<Span>,
+}
+
+impl ControlFlowFinder {
+    fn found(&mut self, span: Span) {
+        self.found.get_or_insert(span);
+    }
+
+    fn escapes(&self, label: &Option<Lifetime>) -> bool {
+        match label {
+            Some(label) => !self.labels.iter().flatten().any(|inner| inner == label),
+            None => !self.labels.iter().any(Option::is_none),
+        }
+    }
+
+    fn scoped(&mut self, label: &Option<Label>, f: impl FnOnce(&mut Self)) {
+        self.labels
+            .push(label.as_ref().map(|label| label.name.clone()));
+        f(self);
+        self.labels.pop();
+    }
+}
+
+impl<'ast> Visit<'ast> for ControlFlowFinder {
+    fn visit_expr_return(&mut self, i: &'ast syn::ExprReturn) {
+        self.found(i.span());
+    }
+
+    fn visit_expr_try(&mut self, i: &'ast syn::ExprTry) {
+        self.found(i.question_token.span());
+    }
+
+    fn visit_expr_break(&mut self, i: &'ast syn::ExprBreak) {
+        if self.escapes(&i.label) {
+            self.found(i.span());
+        }
+        visit::visit_expr_break(self, i);
+    }
+
+    fn visit_expr_continue(&mut self, i: &'ast syn::ExprContinue) {
+        if self.escapes(&i.label) {
+            self.found(i.span());
+        }
+    }
+
+    fn visit_expr_loop(&mut self, i: &'ast syn::ExprLoop) {
+        self.scoped(&i.label, |this| visit::visit_expr_loop(this, i));
+    }
+
+    fn visit_expr_while(&mut self, i: &'ast syn::ExprWhile) {
+        self.scoped(&i.label, |this| visit::visit_expr_while(this, i));
+    }
+
+    fn visit_expr_for_loop(&mut self, i: &'ast syn::ExprForLoop) {
+        self.scoped(&i.label, |this| visit::visit_expr_for_loop(this, i));
+    }
+
+    fn visit_expr_block(&mut self, i: &'ast syn::ExprBlock) {
+        // Only labeled blocks can be the target of a `break`.
+        if i.label.is_some() {
+            self.labels
+                .push(i.label.as_ref().map(|label| label.name.clone()));
+            visit::visit_expr_block(self, i);
+            self.labels.pop();
+        } else {
+            visit::visit_expr_block(self, i);
+        }
+    }
+
+    // Control flow can not escape closures, async blocks and items.
+    fn visit_expr_closure(&mut self, _: &'ast syn::ExprClosure) {}
+
+    fn visit_expr_async(&mut self, _: &'ast syn::ExprAsync) {}
+
+    fn visit_item(&mut self, _: &'ast syn::Item) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::*;
+
+    fn if_select(expr: Expr) -> String {
+        let mut expr = expr;
+        IfSelect.visit_expr_mut(&mut expr);
+        expr.into_token_stream().to_string()
+    }
+
+    #[test]
+    fn test_if_select_control_flow() {
+        for expr in [
+            parse_quote!(if a {
+                return b;
+            } else {
+                c
+            }),
+            parse_quote!(if a { b? } else { c }),
+            parse_quote!(if a { b } else { break }),
+            parse_quote!(if a {
+                b
+            } else {
+                loop {
+                    continue 'outer;
+                }
+            }),
+        ] {
+            assert!(if_select(expr).contains("compile_error"));
+        }
+
+        for expr in [
+            parse_quote!(if a {
+                loop {
+                    break;
+                }
+            } else {
+                c
+            }),
+            parse_quote!(if a {
+                'inner: loop {
+                    break 'inner b;
+                }
+            } else {
+                c
+            }),
+            parse_quote!(if a { (|| b?)() } else { c }),
+            parse_quote!(if a {
+                b
+            } else {
+                for _ in c {
+                    continue;
+                }
+            }),
+        ] {
+            assert!(!if_select(expr).contains("compile_error"));
+        }
+    }
+
+    #[test]
+    fn test_if_select_branches_bound_once() {
+        let expr: Expr = parse_quote! {
+            if a { b0 } else if c { b1 } else if d { b2 } else if e { b3 } else { b4 }
+        };
+
+        let expanded = if_select(expr);
+
+        for branch in ["b0", "b1", "b2", "b3", "b4"] {
+            assert_eq!(expanded.matches(branch).count(), 1);
+        }
+    }
+}
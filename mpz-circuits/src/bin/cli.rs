@@ -0,0 +1,392 @@
+//! A command line tool for working with circuits.
+//!
+//! Circuits are read and written in the Bristol-fashion format if the file extension is `txt`
+//! or `bristol`, and in the bincode format if it is `bin`.
+
+use std::{
+    error::Error,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{Parser, Subcommand};
+use itybity::IntoBits;
+use mpz_circuits::{types::ValueType, Circuit, Gate};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Inspect, convert and evaluate binary circuits.
+#[derive(Parser)]
+#[clap(name = "mpz-circuits")]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Converts a circuit between the Bristol-fashion and bincode formats.
+    Convert {
+        /// The circuit to convert.
+        input: PathBuf,
+        /// The path to write the converted circuit to.
+        output: PathBuf,
+    },
+    /// Prints statistics about a circuit.
+    Stats {
+        /// The circuit to inspect.
+        circuit: PathBuf,
+    },
+    /// Evaluates a circuit, printing the outputs in hex.
+    ///
+    /// Integers are encoded in big-endian order, bits as a single byte and arrays of bits as a
+    /// big-endian integer. Other arrays are the concatenation of their elements.
+    Eval {
+        /// The circuit to evaluate.
+        circuit: PathBuf,
+        /// The inputs of the circuit in hex.
+        inputs: Vec<String>,
+    },
+    /// Prints a circuit in the Graphviz DOT format.
+    Dot {
+        /// The circuit to print.
+        circuit: PathBuf,
+        /// The path to write to instead of stdout.
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+enum Format {
+    Bristol,
+    Bincode,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("txt" | "bristol") => Ok(Format::Bristol),
+            Some("bin") => Ok(Format::Bincode),
+            _ => Err(format!(
+                "unknown circuit format of {}, expected a .txt, .bristol or .bin file",
+                path.display()
+            )
+            .into()),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Convert { input, output } => {
+            let circ = read_circuit(&input)?;
+            write_circuit(&output, &circ)?;
+        }
+        Command::Stats { circuit } => {
+            print!("{}", stats(&read_circuit(&circuit)?));
+        }
+        Command::Eval { circuit, inputs } => {
+            for output in eval(&read_circuit(&circuit)?, &inputs)? {
+                println!("{output}");
+            }
+        }
+        Command::Dot { circuit, output } => {
+            let dot = dot(&read_circuit(&circuit)?);
+            match output {
+                Some(path) => fs::write(path, dot)?,
+                None => print!("{dot}"),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn read_circuit(path: &Path) -> Result<Circuit> {
+    Ok(match Format::from_path(path)? {
+        Format::Bristol => Circuit::from_bristol(&fs::read_to_string(path)?)?,
+        Format::Bincode => bincode::deserialize(&fs::read(path)?)?,
+    })
+}
+
+fn write_circuit(path: &Path, circ: &Circuit) -> Result<()> {
+    match Format::from_path(path)? {
+        Format::Bristol => fs::write(path, circ.to_bristol())?,
+        Format::Bincode => fs::write(path, bincode::serialize(circ)?)?,
+    }
+
+    Ok(())
+}
+
+/// Returns the statistics of a circuit.
+fn stats(circ: &Circuit) -> String {
+    let types = |values: Vec<ValueType>| {
+        values
+            .iter()
+            .map(|ty| ty.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let inv_count = circ.gates().len() - circ.and_count() - circ.xor_count();
+
+    let mut stats = String::new();
+    let mut line = |name: &str, value: &dyn std::fmt::Display| {
+        writeln!(stats, "{name:<12}{value}").unwrap();
+    };
+
+    line(
+        "inputs",
+        &types(circ.inputs().iter().map(|i| i.value_type()).collect()),
+    );
+    line(
+        "outputs",
+        &types(circ.outputs().iter().map(|o| o.value_type()).collect()),
+    );
+    line("feeds", &circ.feed_count());
+    line("gates", &circ.gates().len());
+    line("and", &circ.and_count());
+    line("xor", &circ.xor_count());
+    line("inv", &inv_count);
+    line("depth", &circ.depth());
+    line("and depth", &circ.and_depth());
+
+    stats
+}
+
+/// Evaluates a circuit on hex encoded inputs, returning the hex encoded outputs.
+fn eval(circ: &Circuit, inputs: &[String]) -> Result<Vec<String>> {
+    if inputs.len() != circ.inputs().len() {
+        return Err(format!(
+            "expected {} inputs, got {}",
+            circ.inputs().len(),
+            inputs.len()
+        )
+        .into());
+    }
+
+    let values = circ
+        .inputs()
+        .iter()
+        .zip(inputs)
+        .map(|(input, hex)| {
+            let bytes = hex::decode(hex.trim_start_matches("0x"))?;
+            let bits = decode_bits(&input.value_type(), &bytes)?;
+
+            Ok(input.from_bin_repr(&bits)?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(circ
+        .evaluate(&values)?
+        .into_iter()
+        .map(|value| {
+            let ty = value.value_type();
+            let bytes = encode_bits(&ty, &value.into_iter_lsb0().collect::<Vec<_>>())?;
+
+            Ok(hex::encode(bytes))
+        })
+        .collect::<Result<Vec<_>>>()?)
+}
+
+/// Returns whether a value of this type is encoded as a single integer.
+fn is_integer(ty: &ValueType) -> bool {
+    match ty {
+        ValueType::Array(ty, _) => **ty == ValueType::Bit,
+        _ => true,
+    }
+}
+
+/// Returns the number of bytes a value of this type is encoded in.
+fn byte_len(ty: &ValueType) -> Result<usize> {
+    match ty {
+        ValueType::Array(elem, len) if !is_integer(ty) => {
+            check_elem(ty, elem)?;
+
+            Ok(byte_len(elem)? * len)
+        }
+        _ => Ok(ty.len().div_ceil(8)),
+    }
+}
+
+/// Returns an error if the elements of an array are zero-width, as they cannot be delimited.
+fn check_elem(ty: &ValueType, elem: &ValueType) -> Result<()> {
+    if elem.len() == 0 {
+        return Err(format!("unsupported zero-width elements in {ty}").into());
+    }
+
+    Ok(())
+}
+
+/// Decodes the bits of a value, in the order of its binary representation.
+fn decode_bits(ty: &ValueType, bytes: &[u8]) -> Result<Vec<bool>> {
+    let len = byte_len(ty)?;
+
+    if bytes.len() != len {
+        return Err(format!("expected {len} bytes for {ty}, got {}", bytes.len()).into());
+    }
+
+    match ty {
+        ValueType::Array(elem, _) if !is_integer(ty) => Ok(bytes
+            .chunks(byte_len(elem)?)
+            .map(|bytes| decode_bits(elem, bytes))
+            .collect::<Result<Vec<_>>>()?
+            .concat()),
+        _ => Ok(bytes
+            .iter()
+            .rev()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+            .take(ty.len())
+            .collect()),
+    }
+}
+
+/// Encodes the bits of a value, in the order of its binary representation.
+fn encode_bits(ty: &ValueType, bits: &[bool]) -> Result<Vec<u8>> {
+    match ty {
+        ValueType::Array(elem, _) if !is_integer(ty) => {
+            check_elem(ty, elem)?;
+
+            Ok(bits
+                .chunks(elem.len())
+                .map(|bits| encode_bits(elem, bits))
+                .collect::<Result<Vec<_>>>()?
+                .concat())
+        }
+        _ => Ok(bits
+            .chunks(8)
+            .rev()
+            .map(|bits| {
+                bits.iter()
+                    .enumerate()
+                    .fold(0u8, |byte, (i, bit)| byte | ((*bit as u8) << i))
+            })
+            .collect()),
+    }
+}
+
+/// Returns the circuit in the Graphviz DOT format.
+fn dot(circ: &Circuit) -> String {
+    let mut dot = String::from("digraph circuit {\n");
+
+    for (i, input) in circ.inputs().iter().enumerate() {
+        for (bit, node) in input.iter().enumerate() {
+            writeln!(dot, "  n{} [shape=box, label=\"in{i}[{bit}]\"];", node.id()).unwrap();
+        }
+    }
+
+    for gate in circ.gates() {
+        let z = gate.z().id();
+        let label = match gate {
+            Gate::Xor { .. } => "XOR",
+            Gate::And { .. } => "AND",
+            Gate::Inv { .. } => "INV",
+        };
+
+        writeln!(dot, "  n{z} [label=\"{label}\"];").unwrap();
+        writeln!(dot, "  n{} -> n{z};", gate.x().id()).unwrap();
+        if let Some(y) = gate.y() {
+            writeln!(dot, "  n{} -> n{z};", y.id()).unwrap();
+        }
+    }
+
+    for (i, output) in circ.outputs().iter().enumerate() {
+        for (bit, node) in output.iter().enumerate() {
+            writeln!(dot, "  out{i}_{bit} [shape=box, label=\"out{i}[{bit}]\"];").unwrap();
+            writeln!(dot, "  n{} -> out{i}_{bit};", node.id()).unwrap();
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::{ops::WrappingAdd, CircuitBuilder};
+
+    use super::*;
+
+    fn adder() -> Circuit {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u16>();
+        let b = builder.add_input::<u16>();
+
+        builder.add_output(a.wrapping_add(b));
+
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_eval() {
+        let outputs = eval(&adder(), &["0x0102".to_string(), "00ff".to_string()]).unwrap();
+
+        assert_eq!(outputs, ["0201"]);
+    }
+
+    #[test]
+    fn test_eval_invalid_input() {
+        let err = eval(&adder(), &["01".to_string(), "00ff".to_string()]).unwrap_err();
+
+        assert_eq!(err.to_string(), "expected 2 bytes for U16, got 1");
+    }
+
+    #[test]
+    fn test_encode_bits() {
+        for (ty, bytes) in [
+            (ValueType::Bit, vec![1]),
+            (ValueType::U32, vec![1, 2, 3, 4]),
+            (
+                ValueType::Array(Box::new(ValueType::Bit), 12),
+                vec![0x0a, 0xbc],
+            ),
+            (
+                ValueType::Array(Box::new(ValueType::U16), 2),
+                vec![1, 2, 3, 4],
+            ),
+            (
+                ValueType::Array(Box::new(ValueType::Array(Box::new(ValueType::Bit), 12)), 2),
+                vec![0x0a, 0xbc, 0x0d, 0xef],
+            ),
+            (
+                ValueType::Array(Box::new(ValueType::Array(Box::new(ValueType::Bit), 4)), 3),
+                vec![1, 2, 3],
+            ),
+        ] {
+            let bits = decode_bits(&ty, &bytes).unwrap();
+
+            assert_eq!(bits.len(), ty.len());
+            assert_eq!(encode_bits(&ty, &bits).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_decode_bits_zero_width() {
+        let ty = ValueType::Array(Box::new(ValueType::Array(Box::new(ValueType::Bit), 0)), 2);
+
+        let err = decode_bits(&ty, &[]).unwrap_err();
+        assert!(err.to_string().contains("zero-width"), "{err}");
+
+        assert!(encode_bits(&ty, &[]).is_err());
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(&adder());
+
+        assert!(stats.contains("inputs      U16, U16\n"));
+        assert!(stats.contains("and         16\n"));
+        assert!(stats.contains("and depth   16\n"));
+    }
+
+    #[test]
+    fn test_dot() {
+        let dot = dot(&adder());
+
+        assert!(dot.starts_with("digraph circuit {\n"));
+        assert_eq!(dot.matches("label=\"AND\"").count(), 16);
+        assert_eq!(dot.matches("shape=box").count(), 48);
+    }
+}
@@ -1,8 +1,8 @@
-use itybity::{BitIterable, IntoBits};
+use itybity::IntoBits;
 
 use crate::{
     components::{Feed, Gate, Node},
-    types::{BinaryLength, BinaryRepr, ToBinaryRepr, ValueType},
+    types::{BinaryLength, BinaryRepr, ToBinaryRepr, Value, ValueType},
     Circuit, Tracer,
 };
 use std::{cell::RefCell, collections::HashMap, mem::discriminant};
@@ -148,7 +148,7 @@ impl CircuitBuilder {
     }
 
     /// Returns a tracer for a constant value
-    pub fn get_constant<T: ToBinaryRepr + BitIterable>(&self, value: T) -> Tracer<'_, T::Repr> {
+    pub fn get_constant<T: ToBinaryRepr>(&self, value: T) -> Tracer<'_, T::Repr> {
         let mut state = self.state.borrow_mut();
 
         let value = state.get_constant(value);
@@ -221,10 +221,11 @@ impl BuilderState {
     /// # Arguments
     ///
     /// * `value` - The value to encode.
-    pub fn get_constant<T: ToBinaryRepr + BitIterable>(&mut self, value: T) -> T::Repr {
+    pub fn get_constant<T: ToBinaryRepr>(&mut self, value: T) -> T::Repr {
         let zero = self.get_const_zero();
         let one = self.get_const_one();
 
+        let value: Value = value.into();
         let nodes: Vec<_> = value
             .into_iter_lsb0()
             .map(|bit| if bit { one } else { zero })
@@ -1,7 +1,9 @@
+use std::fmt::Write;
+
 use itybity::IntoBits;
 
 use crate::{
-    components::Gate,
+    components::{Feed, Gate, Node},
     types::{BinaryRepr, TypeError, Value},
 };
 
@@ -183,6 +185,107 @@ impl Circuit {
     }
 }
 
+impl Circuit {
+    /// Returns the circuit in Bristol-fashion format.
+    ///
+    /// The inputs are the first wires and the outputs are the last wires. An output which is
+    /// also an input, or which appears more than once, is copied to its wire with an `EQW` gate.
+    ///
+    /// See `https://homes.esat.kuleuven.be/~nsmart/MPC/` for more information.
+    pub fn to_bristol(&self) -> String {
+        let input_lens = self
+            .inputs
+            .iter()
+            .map(|input| input.len())
+            .collect::<Vec<_>>();
+        let output_lens = self
+            .outputs
+            .iter()
+            .map(|output| output.len())
+            .collect::<Vec<_>>();
+        let outputs = self
+            .outputs
+            .iter()
+            .flat_map(|output| output.iter().copied())
+            .collect::<Vec<_>>();
+
+        let mut wires: Vec<Option<usize>> = vec![None; self.feed_count];
+        let mut wire_count = 0;
+        for node in self.inputs.iter().flat_map(|input| input.iter()) {
+            wires[node.id] = Some(wire_count);
+            wire_count += 1;
+        }
+
+        // The position of the output which each gate output is assigned to.
+        let mut positions: Vec<Option<usize>> = vec![None; self.feed_count];
+        let mut copies = Vec::new();
+        for (pos, node) in outputs.iter().enumerate() {
+            if wires[node.id].is_none() && positions[node.id].is_none() {
+                positions[node.id] = Some(pos);
+            } else {
+                copies.push((*node, pos));
+            }
+        }
+
+        let gate_count = self.gates.len() + copies.len();
+        let output_offset = wire_count + gate_count - outputs.len();
+
+        let mut bristol = String::new();
+        writeln!(bristol, "{} {}", gate_count, wire_count + gate_count).unwrap();
+        writeln!(bristol, "{}", header_line(&input_lens)).unwrap();
+        writeln!(bristol, "{}", header_line(&output_lens)).unwrap();
+        writeln!(bristol).unwrap();
+
+        let wire = |wires: &[Option<usize>], node: Node<Feed>| {
+            wires[node.id].expect("gates should be topologically sorted")
+        };
+
+        for gate in &self.gates {
+            let z = gate.z();
+            wires[z.id] = Some(match positions[z.id] {
+                Some(pos) => output_offset + pos,
+                None => {
+                    let id = wire_count;
+                    wire_count += 1;
+                    id
+                }
+            });
+
+            let z = wire(&wires, z);
+            match gate {
+                Gate::Xor { x, y, .. } => {
+                    let (x, y) = (wire(&wires, (*x).into()), wire(&wires, (*y).into()));
+                    writeln!(bristol, "2 1 {x} {y} {z} XOR").unwrap();
+                }
+                Gate::And { x, y, .. } => {
+                    let (x, y) = (wire(&wires, (*x).into()), wire(&wires, (*y).into()));
+                    writeln!(bristol, "2 1 {x} {y} {z} AND").unwrap();
+                }
+                Gate::Inv { x, .. } => {
+                    let x = wire(&wires, (*x).into());
+                    writeln!(bristol, "1 1 {x} {z} INV").unwrap();
+                }
+            }
+        }
+
+        for (node, pos) in copies {
+            let x = wire(&wires, node);
+            writeln!(bristol, "1 1 {} {} EQW", x, output_offset + pos).unwrap();
+        }
+
+        bristol
+    }
+}
+
+/// Returns a header line listing the number of wires of each value.
+fn header_line(lens: &[usize]) -> String {
+    std::iter::once(lens.len())
+        .chain(lens.iter().copied())
+        .map(|len| len.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 impl IntoIterator for Circuit {
     type Item = Gate;
     type IntoIter = std::vec::IntoIter<Self::Item>;
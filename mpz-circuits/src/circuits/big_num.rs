@@ -1,13 +1,16 @@
 //! Circuits for arithmetic with large numbers.
+//!
+//! Numbers are encoded as arrays of bytes in big-endian order, and the modulus is a constant
+//! known when the circuit is built.
 
 use std::cell::RefCell;
 
-use itybity::IntoBits;
+use num_bigint::BigUint;
 
 use crate::{
-    ops::binary::{switch_nbit, wrapping_add_nbit, wrapping_sub_nbit},
+    ops::binary::{switch_nbit, wrapping_add_nbit, wrapping_mul_nbit, wrapping_sub_nbit},
     types::U8,
-    BuilderState, Node, Tracer,
+    BuilderState, Feed, Node, Tracer,
 };
 
 /// Add two numbers modulo a constant modulus.
@@ -26,30 +29,13 @@ use crate::{
 /// (a + b) % modulus
 pub fn nbyte_add_mod_trace<'a, const N: usize>(
     state: &'a RefCell<BuilderState>,
-    mut a: [Tracer<'a, U8>; N],
-    mut b: [Tracer<'a, U8>; N],
-    mut modulus: [u8; N],
+    a: [Tracer<'a, U8>; N],
+    b: [Tracer<'a, U8>; N],
+    modulus: [u8; N],
 ) -> [Tracer<'a, U8>; N] {
-    // Reverse the arrays to be little-endian
-    a.reverse();
-    b.reverse();
-    modulus.reverse();
-
-    // NO OPERATIONS USING CONST GENERICS YET :(
-    // Otherwise, we would just use a const generic array here with length (N * 8) + 1
-    let mut a_bits = a
-        .into_iter()
-        .flat_map(|a| a.to_inner().nodes().into_iter())
-        .collect::<Vec<_>>();
-    let mut b_bits = b
-        .into_iter()
-        .flat_map(|b| b.to_inner().nodes().into_iter())
-        .collect::<Vec<_>>();
-    let mut modulus_bits = modulus
-        .into_iter()
-        .flat_map(|m| m.into_iter_lsb0())
-        .map(|bit| Node::new(bit as usize))
-        .collect::<Vec<_>>();
+    let mut a_bits = to_bits(a);
+    let mut b_bits = to_bits(b);
+    let mut modulus_bits = const_bits(&BigUint::from_bytes_be(&modulus), N * 8);
 
     // Tack on an extra bit to absorb overflow
     a_bits.push(Node::new(0));
@@ -58,35 +44,324 @@ pub fn nbyte_add_mod_trace<'a, const N: usize>(
 
     let sum = wrapping_add_nbit(&mut state.borrow_mut(), &a_bits, &b_bits);
 
-    let (rem, underflow) = wrapping_sub_nbit(&mut state.borrow_mut(), &sum, &modulus_bits);
-
     // if sum < modulus { sum } else { sum - modulus }
-    let mut sum_reduced = switch_nbit(&mut state.borrow_mut(), &rem, &sum, underflow);
+    let sum_reduced = reduce_once(&mut state.borrow_mut(), &sum, &modulus_bits);
+
+    from_bits(state, &sum_reduced[..N * 8])
+}
+
+/// Subtract two numbers modulo a constant modulus.
+///
+/// This circuit assumes that the operands are in the range [0, modulus).
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The minuend encoded as an array of bytes in big-endian order.
+/// * `b` - The subtrahend encoded as an array of bytes in big-endian order.
+/// * `modulus` - The modulus encoded as an array of bytes in big-endian order.
+///
+/// # Returns
+///
+/// (a - b) % modulus
+pub fn nbyte_sub_mod_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    a: [Tracer<'a, U8>; N],
+    b: [Tracer<'a, U8>; N],
+    modulus: [u8; N],
+) -> [Tracer<'a, U8>; N] {
+    let a_bits = to_bits(a);
+    let b_bits = to_bits(b);
+    let modulus_bits = const_bits(&BigUint::from_bytes_be(&modulus), N * 8);
+
+    let mut builder_state = state.borrow_mut();
+
+    let (diff, underflow) = wrapping_sub_nbit(&mut builder_state, &a_bits, &b_bits);
+
+    // The sum wraps around to the correct result if the difference underflowed.
+    let sum = wrapping_add_nbit(&mut builder_state, &diff, &modulus_bits);
+
+    // if a < b { a - b + modulus } else { a - b }
+    let diff_reduced = switch_nbit(&mut builder_state, &diff, &sum, underflow);
+
+    drop(builder_state);
+
+    from_bits(state, &diff_reduced)
+}
+
+/// Multiply two numbers modulo a constant modulus.
+///
+/// The product is reduced using Barrett reduction, the constants of which are computed when
+/// the circuit is built.
+///
+/// This circuit assumes that the factors are in the range [0, modulus).
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The first number encoded as an array of bytes in big-endian order.
+/// * `b` - The second number encoded as an array of bytes in big-endian order.
+/// * `modulus` - The modulus encoded as an array of bytes in big-endian order, must be
+///   greater than 1.
+///
+/// # Returns
+///
+/// (a * b) % modulus
+pub fn nbyte_mul_mod_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    a: [Tracer<'a, U8>; N],
+    b: [Tracer<'a, U8>; N],
+    modulus: [u8; N],
+) -> [Tracer<'a, U8>; N] {
+    let modulus = parse_modulus(&modulus);
+    let len = modulus.bits() as usize;
 
-    // Pop off the extra bit
-    sum_reduced.pop();
+    // The factors are less than the modulus, so the high bits are zero.
+    let a_bits = &to_bits(a)[..len];
+    let b_bits = &to_bits(b)[..len];
+
+    let product = mul_mod(&mut state.borrow_mut(), a_bits, b_bits, &modulus);
+
+    from_bits(state, &product)
+}
 
-    let mut sum_reduced: [U8; N] = sum_reduced
+/// Raise a number to a power modulo a constant modulus.
+///
+/// This circuit assumes that the base is in the range [0, modulus). Each bit of the
+/// exponent costs a squaring and a multiplication, see [`nbyte_mul_mod_trace`].
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `base` - The base encoded as an array of bytes in big-endian order.
+/// * `exponent` - The exponent encoded as an array of bytes in big-endian order.
+/// * `modulus` - The modulus encoded as an array of bytes in big-endian order, must be
+///   greater than 1.
+///
+/// # Returns
+///
+/// (base ^ exponent) % modulus
+pub fn nbyte_pow_mod_trace<'a, const N: usize, const M: usize>(
+    state: &'a RefCell<BuilderState>,
+    base: [Tracer<'a, U8>; N],
+    exponent: [Tracer<'a, U8>; M],
+    modulus: [u8; N],
+) -> [Tracer<'a, U8>; N] {
+    let modulus = parse_modulus(&modulus);
+    let len = modulus.bits() as usize;
+
+    let base_bits = &to_bits(base)[..len];
+    let exponent_bits = to_bits(exponent);
+
+    let power = pow_mod(&mut state.borrow_mut(), base_bits, &exponent_bits, &modulus);
+
+    from_bits(state, &power)
+}
+
+/// Invert a number modulo a constant prime modulus.
+///
+/// The inverse is computed as `a ^ (modulus - 2)` using Fermat's little theorem, so the
+/// result is only correct if the modulus is prime. Zero, which has no inverse, maps to zero.
+///
+/// This circuit assumes that the number is in the range [0, modulus).
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The number encoded as an array of bytes in big-endian order.
+/// * `modulus` - The prime modulus encoded as an array of bytes in big-endian order.
+///
+/// # Returns
+///
+/// a^-1 % modulus
+pub fn nbyte_inv_mod_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    a: [Tracer<'a, U8>; N],
+    modulus: [u8; N],
+) -> [Tracer<'a, U8>; N] {
+    let modulus = parse_modulus(&modulus);
+    let len = modulus.bits() as usize;
+
+    let a_bits = &to_bits(a)[..len];
+
+    // The exponent is a constant, so the multiplications by its zero bits are skipped.
+    let exponent = &modulus - 2u8;
+    let exponent_bits = const_bits(&exponent, exponent.bits() as usize);
+
+    let inverse = pow_mod(&mut state.borrow_mut(), a_bits, &exponent_bits, &modulus);
+
+    from_bits(state, &inverse)
+}
+
+/// Parses a modulus encoded in big-endian order, panicking if it is less than 2.
+fn parse_modulus(modulus: &[u8]) -> BigUint {
+    let modulus = BigUint::from_bytes_be(modulus);
+    assert!(
+        modulus > BigUint::from(1u8),
+        "modulus must be greater than 1"
+    );
+    modulus
+}
+
+/// Returns the bits of a number encoded in big-endian order, in little-endian order.
+fn to_bits<const N: usize>(bytes: [Tracer<'_, U8>; N]) -> Vec<Node<Feed>> {
+    bytes
+        .into_iter()
+        .rev()
+        .flat_map(|byte| byte.to_inner().nodes())
+        .collect()
+}
+
+/// Returns a number encoded in big-endian order from its bits in little-endian order.
+///
+/// The number is padded with zeros if there are less than `N * 8` bits.
+fn from_bits<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    bits: &[Node<Feed>],
+) -> [Tracer<'a, U8>; N] {
+    let mut bits = bits.to_vec();
+    bits.resize(N * 8, Node::new(0));
+
+    let mut bytes: [U8; N] = bits
         .chunks(8)
         .map(|chunk| U8::new(chunk.try_into().unwrap()))
         .collect::<Vec<_>>()
         .try_into()
         .unwrap();
 
-    // Reverse the array back to big-endian
-    sum_reduced.reverse();
+    bytes.reverse();
 
-    sum_reduced.map(|v| Tracer::new(state, v))
+    bytes.map(|v| Tracer::new(state, v))
+}
+
+/// Returns the `len` least significant bits of a constant in little-endian order.
+fn const_bits(value: &BigUint, len: usize) -> Vec<Node<Feed>> {
+    (0..len)
+        .map(|i| Node::new(value.bit(i as u64) as usize))
+        .collect()
+}
+
+/// Zero-extends a value to `len` bits.
+fn zero_extend(value: &[Node<Feed>], len: usize) -> Vec<Node<Feed>> {
+    let mut value = value.to_vec();
+    value.resize(len, Node::new(0));
+    value
+}
+
+/// Returns `a - modulus` if `a >= modulus`, otherwise `a`.
+fn reduce_once(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    modulus: &[Node<Feed>],
+) -> Vec<Node<Feed>> {
+    let (diff, underflow) = wrapping_sub_nbit(state, a, modulus);
+    switch_nbit(state, &diff, a, underflow)
+}
+
+/// Reduces `x < modulus^2` modulo a constant modulus using Barrett reduction.
+///
+/// `x` has twice as many bits as the modulus, and the result has as many bits as the modulus.
+fn barrett_reduce(
+    state: &mut BuilderState,
+    x: &[Node<Feed>],
+    modulus: &BigUint,
+) -> Vec<Node<Feed>> {
+    let k = modulus.bits() as usize;
+    assert_eq!(x.len(), 2 * k);
+
+    // μ = ⌊4^k / modulus⌋, which has k + 1 bits.
+    let mu = (BigUint::from(1u8) << (2 * k)) / modulus;
+
+    // q = ⌊⌊x / 2^(k - 1)⌋ * μ / 2^(k + 1)⌋, which underestimates ⌊x / modulus⌋ by at most 2.
+    let len = 2 * k + 2;
+    let q = wrapping_mul_nbit(state, &zero_extend(&x[k - 1..], len), &const_bits(&mu, len));
+    let q = &q[k + 1..];
+
+    // r = x - q * modulus is less than 3 * modulus, so only the low k + 2 bits are needed.
+    let len = k + 2;
+    let modulus = const_bits(modulus, len);
+    let q_modulus = wrapping_mul_nbit(state, &zero_extend(q, len), &modulus);
+    let (r, _) = wrapping_sub_nbit(state, &x[..len], &q_modulus);
+
+    let r = reduce_once(state, &r, &modulus);
+    let r = reduce_once(state, &r, &modulus);
+
+    r[..k].to_vec()
+}
+
+/// Multiplies two values modulo a constant modulus.
+///
+/// The values have as many bits as the modulus.
+fn mul_mod(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+    modulus: &BigUint,
+) -> Vec<Node<Feed>> {
+    let len = 2 * a.len();
+    let product = wrapping_mul_nbit(state, &zero_extend(a, len), &zero_extend(b, len));
+
+    barrett_reduce(state, &product, modulus)
+}
+
+/// Raises a value to a power modulo a constant modulus using left-to-right square-and-multiply.
+///
+/// The base has as many bits as the modulus, the exponent bits are in little-endian order.
+fn pow_mod(
+    state: &mut BuilderState,
+    base: &[Node<Feed>],
+    exponent: &[Node<Feed>],
+    modulus: &BigUint,
+) -> Vec<Node<Feed>> {
+    // Operations on the constant one until the first set bit of the exponent are folded away.
+    let mut acc = const_bits(&BigUint::from(1u8), base.len());
+    for bit in exponent.iter().rev() {
+        acc = mul_mod(state, &acc, &acc, modulus);
+
+        match bit.id() {
+            0 => {}
+            1 => acc = mul_mod(state, &acc, base, modulus),
+            _ => {
+                let product = mul_mod(state, &acc, base, modulus);
+                acc = switch_nbit(state, &acc, &product, *bit);
+            }
+        }
+    }
+
+    acc
 }
 
 #[cfg(test)]
 mod tests {
     use mpz_circuits_macros::evaluate;
+    use rand::{thread_rng, Rng};
 
     use crate::CircuitBuilder;
 
     use super::*;
 
+    /// The modulus of the P-256 base field.
+    const P256_MODULUS: [u8; 32] = [
+        0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff,
+    ];
+
+    /// Returns a random number in the range [0, modulus) encoded in big-endian order.
+    fn random_below<const N: usize>(modulus: &[u8; N]) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        thread_rng().fill(&mut bytes[..]);
+
+        to_bytes(&(BigUint::from_bytes_be(&bytes) % BigUint::from_bytes_be(modulus)))
+    }
+
+    fn to_bytes<const N: usize>(value: &BigUint) -> [u8; N] {
+        let bytes = value.to_bytes_be();
+        let mut out = [0u8; N];
+        out[N - bytes.len()..].copy_from_slice(&bytes);
+        out
+    }
+
     #[test]
     fn test_nbyte_add_mod() {
         let builder = CircuitBuilder::new();
@@ -112,4 +387,145 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_nbyte_sub_mod() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_array_input::<u8, 2>();
+        let b = builder.add_array_input::<u8, 2>();
+        let modulus = [0u8, 239u8];
+
+        let diff = nbyte_sub_mod_trace(builder.state(), a, b, modulus).map(|v| v.to_inner());
+
+        builder.add_output(diff);
+
+        let circ = builder.build().unwrap();
+
+        for a in 0u8..modulus[1] {
+            for b in 0u8..modulus[1] {
+                let expected_diff =
+                    ((a as u16 + modulus[1] as u16 - b as u16) % modulus[1] as u16) as u8;
+
+                let diff: [u8; 2] = evaluate!(circ, fn([0u8, a], [0u8, b]) -> [u8; 2]).unwrap();
+                let diff = u16::from_be_bytes(diff) as u8;
+
+                assert_eq!(diff, expected_diff);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nbyte_mul_mod() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_array_input::<u8, 1>();
+        let b = builder.add_array_input::<u8, 1>();
+        let modulus = [239u8];
+
+        let product = nbyte_mul_mod_trace(builder.state(), a, b, modulus).map(|v| v.to_inner());
+
+        builder.add_output(product);
+
+        let circ = builder.build().unwrap();
+
+        for a in 0u8..modulus[0] {
+            for b in 0u8..modulus[0] {
+                let expected_product = ((a as u16 * b as u16) % modulus[0] as u16) as u8;
+
+                let product: [u8; 1] = evaluate!(circ, fn([a], [b]) -> [u8; 1]).unwrap();
+
+                assert_eq!(product[0], expected_product);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nbyte_mul_mod_p256() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_array_input::<u8, 32>();
+        let b = builder.add_array_input::<u8, 32>();
+
+        let product =
+            nbyte_mul_mod_trace(builder.state(), a, b, P256_MODULUS).map(|v| v.to_inner());
+
+        builder.add_output(product);
+
+        let circ = builder.build().unwrap();
+
+        let modulus = BigUint::from_bytes_be(&P256_MODULUS);
+        let max = to_bytes(&(&modulus - 1u8));
+        for (a, b) in [
+            (max, max),
+            (random_below(&P256_MODULUS), random_below(&P256_MODULUS)),
+        ] {
+            let expected_product =
+                BigUint::from_bytes_be(&a) * BigUint::from_bytes_be(&b) % &modulus;
+
+            let product: [u8; 32] = evaluate!(circ, fn(a, b) -> [u8; 32]).unwrap();
+
+            assert_eq!(BigUint::from_bytes_be(&product), expected_product);
+        }
+    }
+
+    #[test]
+    fn test_nbyte_pow_mod() {
+        let builder = CircuitBuilder::new();
+
+        let base = builder.add_array_input::<u8, 3>();
+        let exponent = builder.add_array_input::<u8, 2>();
+        // Barrett reduction does not require the modulus to be odd.
+        let modulus = [0xb1, 0x23, 0x46];
+
+        let power =
+            nbyte_pow_mod_trace(builder.state(), base, exponent, modulus).map(|v| v.to_inner());
+
+        builder.add_output(power);
+
+        let circ = builder.build().unwrap();
+
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let base = random_below(&modulus);
+            let exponent: [u8; 2] = rng.gen();
+
+            let expected_power = BigUint::from_bytes_be(&base).modpow(
+                &BigUint::from_bytes_be(&exponent),
+                &BigUint::from_bytes_be(&modulus),
+            );
+
+            let power: [u8; 3] = evaluate!(circ, fn(base, exponent) -> [u8; 3]).unwrap();
+
+            assert_eq!(BigUint::from_bytes_be(&power), expected_power);
+        }
+    }
+
+    #[test]
+    fn test_nbyte_inv_mod() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_array_input::<u8, 2>();
+        // The largest prime less than 2^16.
+        let modulus = 65521u16.to_be_bytes();
+
+        let inverse = nbyte_inv_mod_trace(builder.state(), a, modulus).map(|v| v.to_inner());
+
+        builder.add_output(inverse);
+
+        let circ = builder.build().unwrap();
+
+        let inverse: [u8; 2] = evaluate!(circ, fn([0u8, 0u8]) -> [u8; 2]).unwrap();
+        assert_eq!(inverse, [0, 0]);
+
+        let modulus = BigUint::from_bytes_be(&modulus);
+        for a in (1..65521u16).step_by(97).chain([65520]) {
+            let inverse: [u8; 2] = evaluate!(circ, fn(a.to_be_bytes()) -> [u8; 2]).unwrap();
+
+            assert_eq!(
+                BigUint::from(a) * BigUint::from_bytes_be(&inverse) % &modulus,
+                BigUint::from(1u8)
+            );
+        }
+    }
 }
@@ -8,8 +8,10 @@ mod circuit;
 pub mod circuits;
 pub(crate) mod components;
 pub mod ops;
+mod optimize;
 #[cfg(feature = "parse")]
 mod parse;
+mod schedule;
 mod tracer;
 pub mod types;
 
@@ -20,6 +22,8 @@ pub use circuit::{Circuit, CircuitError};
 #[doc(hidden)]
 pub use components::{Feed, Node, Sink};
 pub use components::{Gate, GateType};
+#[cfg(feature = "parse")]
+pub use parse::ParseError;
 pub use tracer::Tracer;
 
 pub use once_cell;
@@ -109,6 +113,40 @@ pub use once_cell;
 ///    assert_eq!(output, bitxor_and(a, b));
 /// }
 /// ```
+///
+/// # If expressions
+///
+/// `if` expressions with an `else` branch are translated into [`Select`](crate::ops::Select),
+/// so the condition may be a traced `bool`. In that case both branches are evaluated and the
+/// result is selected with a multiplexer, otherwise only the taken branch is evaluated.
+///
+/// ```
+/// use mpz_circuits::{trace, evaluate, CircuitBuilder};
+///
+/// #[trace]
+/// fn max(a: u8, b: u8) -> u8 {
+///     if a.lt(&b) {
+///         b
+///     } else {
+///         a
+///     }
+/// }
+///
+/// let builder = CircuitBuilder::new();
+/// let a = builder.add_input::<u8>();
+/// let b = builder.add_input::<u8>();
+///
+/// let c = max_trace(builder.state(), a, b);
+///
+/// builder.add_output(c);
+///
+/// let circ = builder.build().unwrap();
+///
+/// let output = evaluate!(circ, fn(42u8, 69u8) -> u8).unwrap();
+///
+/// assert_eq!(output, 69u8);
+/// ```
+///
 /// # Cache
 ///
 /// The macro can optionally be configured with the `cache` argument which will cache the circuit
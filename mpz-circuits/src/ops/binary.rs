@@ -151,6 +151,8 @@ pub(crate) fn wrapping_sub_nbit(
 /// Switch between two nbit values.
 ///
 /// If `toggle` is 0, the result is `a`, otherwise it is `b`.
+///
+/// Costs a single AND gate per bit.
 pub(crate) fn switch_nbit(
     state: &mut BuilderState,
     a: &[Node<Feed>],
@@ -159,14 +161,13 @@ pub(crate) fn switch_nbit(
 ) -> Vec<Node<Feed>> {
     assert_eq!(a.len(), b.len());
 
-    let not_toggle = state.add_inv_gate(toggle);
-
     a.iter()
         .zip(b)
         .map(|(a, b)| {
-            let a_and_not_toggle = state.add_and_gate(*a, not_toggle);
-            let b_and_toggle = state.add_and_gate(*b, toggle);
-            state.add_xor_gate(a_and_not_toggle, b_and_toggle)
+            // OUT = A ⊕ (TOGGLE ^ (A ⊕ B))
+            let a_b = state.add_xor_gate(*a, *b);
+            let select = state.add_and_gate(a_b, toggle);
+            state.add_xor_gate(*a, select)
         })
         .collect()
 }
@@ -212,6 +213,171 @@ pub(crate) fn inv_nbit<const N: usize>(
     std::array::from_fn(|n| state.add_inv_gate(a[n]))
 }
 
+/// Carry out of a binary full-adder.
+fn carry(state: &mut BuilderState, a: Node<Feed>, b: Node<Feed>, c_in: Node<Feed>) -> Node<Feed> {
+    // C_OUT = C_IN ⊕ ((A ⊕ C_IN) ^ (B ⊕ C_IN))
+    let a_c_in = state.add_xor_gate(a, c_in);
+    let b_c_in = state.add_xor_gate(b, c_in);
+    let and = state.add_and_gate(a_c_in, b_c_in);
+    state.add_xor_gate(and, c_in)
+}
+
+/// Add two nbit values together, wrapping on overflow.
+///
+/// Unlike [`wrapping_add_nbit`], the carry out of the most significant bit is not computed,
+/// which costs `N - 1` AND gates.
+fn wrapping_add_no_carry_nbit(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+) -> Vec<Node<Feed>> {
+    assert_eq!(a.len(), b.len());
+
+    let len = a.len();
+    let mut c_out = state.get_const_zero();
+    a.iter()
+        .zip(b)
+        .enumerate()
+        .map(|(n, (a, b))| {
+            let a_b = state.add_xor_gate(*a, *b);
+            let sum = state.add_xor_gate(a_b, c_out);
+            if n < len - 1 {
+                c_out = carry(state, *a, *b, c_out);
+            }
+            sum
+        })
+        .collect()
+}
+
+/// Multiply two nbit values together, wrapping on overflow.
+///
+/// Only the partial products contributing to the low `N` bits are computed, which costs
+/// `N^2 - N + 1` AND gates. Partial products with a constant zero operand are skipped.
+pub(crate) fn wrapping_mul_nbit(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+) -> Vec<Node<Feed>> {
+    assert_eq!(a.len(), b.len());
+
+    let len = a.len();
+    let mut product: Vec<_> = a.iter().map(|a| state.add_and_gate(*a, b[0])).collect();
+    for (i, b) in b.iter().enumerate().skip(1) {
+        let row: Vec<_> = a[..len - i]
+            .iter()
+            .map(|a| state.add_and_gate(*a, *b))
+            .collect();
+
+        // Nothing to add if the row is constant zero.
+        if row.iter().all(|node| node.id() == 0) {
+            continue;
+        }
+
+        let sum = wrapping_add_no_carry_nbit(state, &product[i..], &row);
+        product[i..].copy_from_slice(&sum);
+    }
+
+    product
+}
+
+/// Divide two nbit values using restoring division.
+///
+/// Returns the quotient and the remainder. Division by zero does not panic, instead the
+/// quotient is all ones and the remainder is the dividend.
+///
+/// Each of the `N` steps costs `2N + 1` AND gates.
+pub(crate) fn div_rem_nbit(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+) -> (Vec<Node<Feed>>, Vec<Node<Feed>>) {
+    assert_eq!(a.len(), b.len());
+
+    let len = a.len();
+    let const_zero = state.get_const_zero();
+    let const_one = state.get_const_one();
+
+    let b_inv = b.iter().map(|b| state.add_inv_gate(*b)).collect::<Vec<_>>();
+
+    let mut quotient = vec![const_zero; len];
+    let mut rem = vec![const_zero; len];
+    for i in (0..len).rev() {
+        // Shift the next bit of the dividend into the partial remainder, the bit shifted out
+        // is kept as the most significant bit.
+        let msb = rem[len - 1];
+        rem.rotate_right(1);
+        rem[0] = a[i];
+
+        // Subtract the divisor, setting the first borrow in to 1 which adds 1 to b_inv.
+        let mut b_out = const_one;
+        let diff = rem
+            .iter()
+            .zip(&b_inv)
+            .map(|(rem, b_inv)| {
+                let (diff_n, b_out_n) = full_adder(state, *rem, *b_inv, b_out);
+                b_out = b_out_n;
+                diff_n
+            })
+            .collect::<Vec<_>>();
+
+        // The divisor fits if the subtraction did not underflow.
+        let fits = carry(state, msb, const_one, b_out);
+        quotient[i] = fits;
+
+        // rem = if fits { diff } else { rem }
+        rem = switch_nbit(state, &rem, &diff, fits);
+    }
+
+    (quotient, rem)
+}
+
+/// Returns whether `a` is less than `b`.
+///
+/// Computes the borrow of `a - b`, which costs `N` AND gates.
+pub(crate) fn lt_nbit(state: &mut BuilderState, a: &[Node<Feed>], b: &[Node<Feed>]) -> Node<Feed> {
+    assert_eq!(a.len(), b.len());
+
+    // Set first b_in to 1, which adds 1 to b_inv.
+    let mut b_out = state.get_const_one();
+    for (a, b) in a.iter().zip(b) {
+        let b_inv = state.add_inv_gate(*b);
+        b_out = carry(state, *a, b_inv, b_out);
+    }
+
+    // underflow occurred if b_out is 0
+    state.add_inv_gate(b_out)
+}
+
+/// Returns whether `a` is equal to `b`.
+///
+/// Reduces the bitwise equality with a tree of AND gates, which costs `N - 1` AND gates with
+/// logarithmic depth.
+pub(crate) fn eq_nbit(state: &mut BuilderState, a: &[Node<Feed>], b: &[Node<Feed>]) -> Node<Feed> {
+    assert_eq!(a.len(), b.len());
+
+    let mut eq = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| {
+            let a_b = state.add_xor_gate(*a, *b);
+            state.add_inv_gate(a_b)
+        })
+        .collect::<Vec<_>>();
+
+    while eq.len() > 1 {
+        eq = eq
+            .chunks(2)
+            .map(|pair| match pair {
+                [x, y] => state.add_and_gate(*x, *y),
+                [x] => *x,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    eq[0]
+}
+
 impl<'a> BitXor for Tracer<'a, Bit> {
     type Output = Tracer<'a, Bit>;
 
@@ -323,6 +489,95 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wrapping_mul() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>().to_inner();
+        let b = builder.add_input::<u8>().to_inner();
+
+        let product = U8::new(
+            wrapping_mul_nbit(&mut builder.state().borrow_mut(), &a.nodes(), &b.nodes())
+                .try_into()
+                .unwrap(),
+        );
+
+        builder.add_output(product);
+
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.and_count(), 8 * 8 - 8 + 1);
+
+        for a in 0u8..=255 {
+            for b in 0u8..=255 {
+                let expected_product = a.wrapping_mul(b);
+
+                let product: u8 = evaluate!(circ, fn(a, b) -> u8).unwrap();
+
+                assert_eq!(product, expected_product);
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>().to_inner();
+        let b = builder.add_input::<u8>().to_inner();
+
+        let (quotient, rem) =
+            div_rem_nbit(&mut builder.state().borrow_mut(), &a.nodes(), &b.nodes());
+
+        builder.add_output(U8::new(quotient.try_into().unwrap()));
+        builder.add_output(U8::new(rem.try_into().unwrap()));
+
+        let circ = builder.build().unwrap();
+
+        for a in 0u8..=255 {
+            let (quotient, rem): (u8, u8) = evaluate!(circ, fn(a, 0u8) -> (u8, u8)).unwrap();
+
+            assert_eq!(quotient, u8::MAX);
+            assert_eq!(rem, a);
+
+            for b in 1u8..=255 {
+                let (quotient, rem): (u8, u8) = evaluate!(circ, fn(a, b) -> (u8, u8)).unwrap();
+
+                assert_eq!(quotient, a / b);
+                assert_eq!(rem, a % b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>().to_inner();
+        let b = builder.add_input::<u8>().to_inner();
+
+        let mut state = builder.state().borrow_mut();
+        let lt = lt_nbit(&mut state, &a.nodes(), &b.nodes());
+        let eq = eq_nbit(&mut state, &a.nodes(), &b.nodes());
+        drop(state);
+
+        builder.add_output(Bit::new([lt]));
+        builder.add_output(Bit::new([eq]));
+
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.and_count(), 8 + 7);
+
+        for a in 0u8..=255 {
+            for b in 0u8..=255 {
+                let (lt, eq): (bool, bool) = evaluate!(circ, fn(a, b) -> (bool, bool)).unwrap();
+
+                assert_eq!(lt, a < b);
+                assert_eq!(eq, a == b);
+            }
+        }
+    }
+
     #[test]
     fn test_switch_nbit() {
         let builder = CircuitBuilder::new();
@@ -0,0 +1,167 @@
+use std::ops::Shr;
+
+use crate::{
+    types::{Bit, I128, I16, I32, I64, I8},
+    Tracer,
+};
+
+use super::binary;
+
+macro_rules! impl_shr_int {
+    ($ty:ident, $len:expr) => {
+        impl<'a> Shr<usize> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            /// Arithmetic right shift, which preserves the sign.
+            fn shr(self, rhs: usize) -> Self::Output {
+                assert!(rhs <= $len);
+
+                let mut nodes = self.to_inner().nodes();
+                let sign = nodes[$len - 1];
+                // Bits are LSB0, so we rotate left
+                nodes.rotate_left(rhs);
+                // Replace the msbs with the sign bit
+                nodes[$len - rhs..].iter_mut().for_each(|node| *node = sign);
+
+                let value = <$ty>::new(nodes);
+
+                Tracer::new(self.state, value)
+            }
+        }
+    };
+}
+
+impl_shr_int!(I8, 8);
+impl_shr_int!(I16, 16);
+impl_shr_int!(I32, 32);
+impl_shr_int!(I64, 64);
+impl_shr_int!(I128, 128);
+
+macro_rules! impl_compare_int {
+    ($ty:ident, $len:expr) => {
+        // The comparisons take their arguments by reference, like `PartialEq` and `PartialOrd`,
+        // so that eg. `a.lt(&b)` can be traced.
+        impl<'a> Tracer<'a, $ty> {
+            /// Returns whether `self` is equal to `rhs`.
+            pub fn eq(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                let node = binary::eq_nbit(
+                    &mut self.state.borrow_mut(),
+                    &self.value.nodes(),
+                    &rhs.value.nodes(),
+                );
+
+                Tracer::new(self.state, Bit::new([node]))
+            }
+
+            /// Returns whether `self` is not equal to `rhs`.
+            pub fn ne(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                !self.eq(rhs)
+            }
+
+            /// Returns whether `self` is less than `rhs`.
+            pub fn lt(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                let mut state = self.state.borrow_mut();
+
+                let mut a = self.value.nodes();
+                let mut b = rhs.value.nodes();
+
+                // Flipping the sign bits maps the signed order onto the unsigned order.
+                a[$len - 1] = state.add_inv_gate(a[$len - 1]);
+                b[$len - 1] = state.add_inv_gate(b[$len - 1]);
+
+                let node = binary::lt_nbit(&mut state, &a, &b);
+
+                drop(state);
+
+                Tracer::new(self.state, Bit::new([node]))
+            }
+
+            /// Returns whether `self` is less than or equal to `rhs`.
+            pub fn le(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                !rhs.lt(self)
+            }
+
+            /// Returns whether `self` is greater than `rhs`.
+            pub fn gt(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                rhs.lt(self)
+            }
+
+            /// Returns whether `self` is greater than or equal to `rhs`.
+            pub fn ge(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                !self.lt(rhs)
+            }
+        }
+    };
+}
+
+impl_compare_int!(I8, 8);
+impl_compare_int!(I16, 16);
+impl_compare_int!(I32, 32);
+impl_compare_int!(I64, 64);
+impl_compare_int!(I128, 128);
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits_macros::{test_circ, trace};
+    use rand::{thread_rng, Rng};
+
+    use crate::{
+        ops::{WrappingAdd, WrappingMul, WrappingSub},
+        CircuitBuilder,
+    };
+
+    #[trace]
+    fn arithmetic(a: i32, b: i32) -> [i32; 4] {
+        [
+            a.wrapping_add(b),
+            a.wrapping_sub(b),
+            a.wrapping_mul(b),
+            a >> 5,
+        ]
+    }
+
+    #[trace]
+    fn compare(a: i8, b: i8) -> [bool; 6] {
+        [a.eq(&b), a.ne(&b), a.lt(&b), a.le(&b), a.gt(&b), a.ge(&b)]
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i32>();
+        let b = builder.add_input::<i32>();
+
+        let out = arithmetic_trace(builder.state(), a, b);
+        builder.add_output(out);
+
+        let circ = builder.build().unwrap();
+
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let a: i32 = rng.gen();
+            let b: i32 = rng.gen();
+
+            test_circ!(circ, arithmetic, fn(a, b) -> [i32; 4]);
+        }
+
+        test_circ!(circ, arithmetic, fn(-1i32, i32::MIN) -> [i32; 4]);
+    }
+
+    #[test]
+    fn test_compare() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i8>();
+        let b = builder.add_input::<i8>();
+
+        let out = compare_trace(builder.state(), a, b);
+        builder.add_output(out);
+
+        let circ = builder.build().unwrap();
+
+        for a in i8::MIN..=i8::MAX {
+            for b in i8::MIN..=i8::MAX {
+                test_circ!(circ, compare, fn(a, b) -> [bool; 6]);
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Operations on binary encoded types.
 
 pub(crate) mod binary;
+mod int;
+mod select;
 mod uint;
 
 /// Addition of two integers using so called "wrapping addition", which
@@ -34,3 +36,35 @@ pub trait WrappingSub<Rhs> {
     /// ```
     fn wrapping_sub(self, rhs: Rhs) -> Self::Output;
 }
+
+/// Multiplication of two integers using so called "wrapping multiplication", which
+/// allows bit overflow.
+pub trait WrappingMul<Rhs> {
+    /// The result type after wrapping multiplication.
+    type Output;
+
+    /// Multiplies two integers with wrapping multiplication.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert_eq!(16u8.wrapping_mul(17u8), 16u8);
+    /// ```
+    fn wrapping_mul(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Selection between two values, ie. a multiplexer.
+///
+/// This is implemented for [`Tracer<Bit>`](crate::Tracer) over traced values, in which case both
+/// values are evaluated, and for `bool` so that [`trace`](crate::trace) can translate `if`
+/// expressions regardless of whether the condition is traced.
+pub trait Select<T> {
+    /// Returns `a` if `self` is set, otherwise `b`.
+    fn select(self, a: T, b: T) -> T;
+}
+
+/// A condition of an `if` expression in a traced function.
+pub trait Condition {
+    /// Returns the value of the condition if it is known while tracing.
+    fn to_const(&self) -> Option<bool>;
+}
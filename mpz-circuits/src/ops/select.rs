@@ -0,0 +1,276 @@
+use crate::{
+    types::{BinaryRepr, Bit, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8},
+    Tracer,
+};
+
+use super::{binary, Condition, Select};
+
+impl<T> Select<T> for bool {
+    fn select(self, a: T, b: T) -> T {
+        if self {
+            a
+        } else {
+            b
+        }
+    }
+}
+
+impl Condition for bool {
+    fn to_const(&self) -> Option<bool> {
+        Some(*self)
+    }
+}
+
+impl<'a> Condition for Tracer<'a, Bit> {
+    fn to_const(&self) -> Option<bool> {
+        let state = self.state.borrow();
+        let node = self.node();
+
+        if node == state.get_const_zero() {
+            Some(false)
+        } else if node == state.get_const_one() {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_select {
+    ($ty:ident) => {
+        impl<'a> Select<Tracer<'a, $ty>> for Tracer<'a, Bit> {
+            fn select(self, a: Tracer<'a, $ty>, b: Tracer<'a, $ty>) -> Tracer<'a, $ty> {
+                let mut state = self.state.borrow_mut();
+
+                let nodes = binary::switch_nbit(
+                    &mut state,
+                    &b.to_inner().nodes(),
+                    &a.to_inner().nodes(),
+                    self.node(),
+                );
+
+                let value = <$ty>::new(nodes.try_into().expect("value should have same length"));
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+    };
+}
+
+impl_select!(Bit);
+impl_select!(U8);
+impl_select!(U16);
+impl_select!(U32);
+impl_select!(U64);
+impl_select!(U128);
+impl_select!(I8);
+impl_select!(I16);
+impl_select!(I32);
+impl_select!(I64);
+impl_select!(I128);
+
+impl<'a> Select<Tracer<'a, BinaryRepr>> for Tracer<'a, Bit> {
+    fn select(
+        self,
+        a: Tracer<'a, BinaryRepr>,
+        b: Tracer<'a, BinaryRepr>,
+    ) -> Tracer<'a, BinaryRepr> {
+        let (a, b) = (a.to_inner(), b.to_inner());
+
+        if a.value_type() != b.value_type() {
+            panic!("types {:?} and {:?} are not compatible", a, b)
+        }
+
+        let nodes = binary::switch_nbit(
+            &mut self.state.borrow_mut(),
+            &b.iter().copied().collect::<Vec<_>>(),
+            &a.iter().copied().collect::<Vec<_>>(),
+            self.node(),
+        );
+
+        let mut value = a;
+        value
+            .iter_mut()
+            .zip(nodes)
+            .for_each(|(node, selected)| *node = selected);
+
+        Tracer::new(self.state, value)
+    }
+}
+
+impl<'a, T, const N: usize> Select<[T; N]> for Tracer<'a, Bit>
+where
+    Tracer<'a, Bit>: Select<T>,
+{
+    fn select(self, a: [T; N], b: [T; N]) -> [T; N] {
+        let mut b = b.into_iter();
+        a.map(|a| self.select(a, b.next().expect("arrays should have same length")))
+    }
+}
+
+impl<'a, T> Select<Vec<T>> for Tracer<'a, Bit>
+where
+    Tracer<'a, Bit>: Select<T>,
+{
+    fn select(self, a: Vec<T>, b: Vec<T>) -> Vec<T> {
+        assert_eq!(a.len(), b.len(), "vectors should have same length");
+
+        a.into_iter()
+            .zip(b)
+            .map(|(a, b)| self.select(a, b))
+            .collect()
+    }
+}
+
+macro_rules! impl_select_tuple {
+    ($($ty:ident: $idx:tt),+) => {
+        impl<'a, $($ty),+> Select<($($ty,)+)> for Tracer<'a, Bit>
+        where
+            $(Tracer<'a, Bit>: Select<$ty>),+
+        {
+            fn select(self, a: ($($ty,)+), b: ($($ty,)+)) -> ($($ty,)+) {
+                ($(self.select(a.$idx, b.$idx),)+)
+            }
+        }
+    };
+}
+
+impl_select_tuple!(A: 0, B: 1);
+impl_select_tuple!(A: 0, B: 1, C: 2);
+impl_select_tuple!(A: 0, B: 1, C: 2, D: 3);
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits_macros::{evaluate, test_circ, trace};
+
+    use crate::{ops::WrappingSub, CircuitBuilder};
+
+    #[trace]
+    fn abs_diff(a: u32, b: u32) -> u32 {
+        if a.lt(&b) {
+            b.wrapping_sub(a)
+        } else {
+            a.wrapping_sub(b)
+        }
+    }
+
+    #[trace]
+    fn sort(a: u8, b: u8) -> (u8, u8) {
+        if a.gt(&b) {
+            (b, a)
+        } else {
+            (a, b)
+        }
+    }
+
+    #[trace]
+    fn clamp(a: u8, lo: u8, hi: u8) -> u8 {
+        if a.lt(&lo) {
+            lo
+        } else if a.gt(&hi) {
+            hi
+        } else {
+            a
+        }
+    }
+
+    #[trace]
+    fn choose(toggle: bool, a: [u16; 4], b: [u16; 4]) -> [u16; 4] {
+        // Conditions which are not traced are evaluated while tracing.
+        let a = if a.len() > 4 { b } else { a };
+
+        if toggle {
+            a
+        } else {
+            b
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let builder = CircuitBuilder::new();
+        let toggle = builder.add_input::<bool>();
+        let a = builder.add_array_input::<u8, 4>();
+        let b = builder.add_array_input::<u8, 4>();
+
+        let out = toggle.select(a, b);
+        builder.add_output(out);
+
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.and_count(), 32);
+
+        let a = [1u8, 2, 3, 4];
+        let b = [5u8, 6, 7, 8];
+
+        let out: [u8; 4] = evaluate!(circ, fn(true, a, b) -> [u8; 4]).unwrap();
+        assert_eq!(out, a);
+
+        let out: [u8; 4] = evaluate!(circ, fn(false, a, b) -> [u8; 4]).unwrap();
+        assert_eq!(out, b);
+    }
+
+    #[test]
+    fn test_select_constant() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u64>();
+        let b = builder.add_input::<u64>();
+
+        let toggle = builder.get_constant(true);
+        let out = toggle.select(a, b);
+        builder.add_output(out);
+
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.and_count(), 0);
+    }
+
+    #[test]
+    fn test_trace_if() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u32>();
+        let b = builder.add_input::<u32>();
+        let out = abs_diff_trace(builder.state(), a, b);
+        builder.add_output(out);
+        let circ = builder.build().unwrap();
+
+        test_circ!(circ, abs_diff, fn(42u32, 69u32) -> u32);
+        test_circ!(circ, abs_diff, fn(69u32, 42u32) -> u32);
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let (min, max) = sort_trace(builder.state(), a, b);
+        builder.add_output(min);
+        builder.add_output(max);
+        let circ = builder.build().unwrap();
+
+        test_circ!(circ, sort, fn(42u8, 69u8) -> (u8, u8));
+        test_circ!(circ, sort, fn(69u8, 42u8) -> (u8, u8));
+
+        let builder = CircuitBuilder::new();
+        let toggle = builder.add_input::<bool>();
+        let a = builder.add_array_input::<u16, 4>();
+        let b = builder.add_array_input::<u16, 4>();
+        let out = choose_trace(builder.state(), toggle, a, b);
+        builder.add_output(out);
+        let circ = builder.build().unwrap();
+
+        test_circ!(circ, choose, fn(true, [1u16; 4], [2u16; 4]) -> [u16; 4]);
+        test_circ!(circ, choose, fn(false, [1u16; 4], [2u16; 4]) -> [u16; 4]);
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let lo = builder.add_input::<u8>();
+        let hi = builder.add_input::<u8>();
+        let out = clamp_trace(builder.state(), a, lo, hi);
+        builder.add_output(out);
+        let circ = builder.build().unwrap();
+
+        test_circ!(circ, clamp, fn(1u8, 42u8, 69u8) -> u8);
+        test_circ!(circ, clamp, fn(50u8, 42u8, 69u8) -> u8);
+        test_circ!(circ, clamp, fn(99u8, 42u8, 69u8) -> u8);
+    }
+}
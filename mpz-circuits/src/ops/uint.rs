@@ -1,11 +1,14 @@
-use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+use std::ops::{BitAnd, BitOr, BitXor, Div, Not, Rem, Shl, Shr};
 
 use crate::{
-    types::{BinaryRepr, U128, U16, U32, U64, U8},
+    types::{BinaryRepr, Bit, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8},
     Tracer,
 };
 
-use super::{binary, WrappingAdd, WrappingSub};
+use super::{binary, WrappingAdd, WrappingMul, WrappingSub};
+
+// Signed integers are encoded in two's complement, so the operations which do not depend on the
+// sign are implemented for them here as well, see `int` for the others.
 
 macro_rules! impl_wrapping_add_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -56,6 +59,11 @@ impl_wrapping_add_uint!(U16, u16, 16);
 impl_wrapping_add_uint!(U32, u32, 32);
 impl_wrapping_add_uint!(U64, u64, 64);
 impl_wrapping_add_uint!(U128, u128, 128);
+impl_wrapping_add_uint!(I8, i8, 8);
+impl_wrapping_add_uint!(I16, i16, 16);
+impl_wrapping_add_uint!(I32, i32, 32);
+impl_wrapping_add_uint!(I64, i64, 64);
+impl_wrapping_add_uint!(I128, i128, 128);
 
 macro_rules! impl_wrapping_sub_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -108,6 +116,230 @@ impl_wrapping_sub_uint!(U16, u16, 16);
 impl_wrapping_sub_uint!(U32, u32, 32);
 impl_wrapping_sub_uint!(U64, u64, 64);
 impl_wrapping_sub_uint!(U128, u128, 128);
+impl_wrapping_sub_uint!(I8, i8, 8);
+impl_wrapping_sub_uint!(I16, i16, 16);
+impl_wrapping_sub_uint!(I32, i32, 32);
+impl_wrapping_sub_uint!(I64, i64, 64);
+impl_wrapping_sub_uint!(I128, i128, 128);
+
+macro_rules! impl_wrapping_mul_uint {
+    ($ty:ident, $const_ty:ident) => {
+        impl<'a> WrappingMul<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn wrapping_mul(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let nodes = binary::wrapping_mul_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                );
+
+                let value = <$ty>::new(nodes.try_into().expect("product should have same length"));
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+
+        impl<'a> WrappingMul<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn wrapping_mul(self, rhs: $const_ty) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let rhs = state.get_constant::<$const_ty>(rhs);
+
+                let nodes =
+                    binary::wrapping_mul_nbit(&mut state, &self.to_inner().nodes(), &rhs.nodes());
+
+                let value = <$ty>::new(nodes.try_into().expect("product should have same length"));
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+    };
+}
+
+impl_wrapping_mul_uint!(U8, u8);
+impl_wrapping_mul_uint!(U16, u16);
+impl_wrapping_mul_uint!(U32, u32);
+impl_wrapping_mul_uint!(U64, u64);
+impl_wrapping_mul_uint!(U128, u128);
+impl_wrapping_mul_uint!(I8, i8);
+impl_wrapping_mul_uint!(I16, i16);
+impl_wrapping_mul_uint!(I32, i32);
+impl_wrapping_mul_uint!(I64, i64);
+impl_wrapping_mul_uint!(I128, i128);
+
+macro_rules! impl_div_rem_uint {
+    ($ty:ident, $const_ty:ident) => {
+        impl<'a> Div<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            /// Divides two integers, rounding towards zero.
+            ///
+            /// Division by zero does not panic, instead the quotient is the maximum value.
+            fn div(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let (quotient, _) = binary::div_rem_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                );
+
+                let value = <$ty>::new(
+                    quotient
+                        .try_into()
+                        .expect("quotient should have same length"),
+                );
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+
+        impl<'a> Div<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn div(self, rhs: $const_ty) -> Self::Output {
+                assert_ne!(rhs, 0, "attempt to divide by zero");
+
+                let mut state = self.state.borrow_mut();
+
+                let rhs = state.get_constant::<$const_ty>(rhs);
+
+                let (quotient, _) =
+                    binary::div_rem_nbit(&mut state, &self.to_inner().nodes(), &rhs.nodes());
+
+                let value = <$ty>::new(
+                    quotient
+                        .try_into()
+                        .expect("quotient should have same length"),
+                );
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+
+        impl<'a> Rem<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            /// Returns the remainder of the division of two integers.
+            ///
+            /// Division by zero does not panic, instead the remainder is the dividend.
+            fn rem(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let (_, rem) = binary::div_rem_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                );
+
+                let value = <$ty>::new(rem.try_into().expect("remainder should have same length"));
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+
+        impl<'a> Rem<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn rem(self, rhs: $const_ty) -> Self::Output {
+                assert_ne!(
+                    rhs, 0,
+                    "attempt to calculate the remainder with a divisor of zero"
+                );
+
+                let mut state = self.state.borrow_mut();
+
+                let rhs = state.get_constant::<$const_ty>(rhs);
+
+                let (_, rem) =
+                    binary::div_rem_nbit(&mut state, &self.to_inner().nodes(), &rhs.nodes());
+
+                let value = <$ty>::new(rem.try_into().expect("remainder should have same length"));
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+    };
+}
+
+impl_div_rem_uint!(U8, u8);
+impl_div_rem_uint!(U16, u16);
+impl_div_rem_uint!(U32, u32);
+impl_div_rem_uint!(U64, u64);
+impl_div_rem_uint!(U128, u128);
+
+macro_rules! impl_compare_uint {
+    ($ty:ident) => {
+        // The comparisons take their arguments by reference, like `PartialEq` and `PartialOrd`,
+        // so that eg. `a.lt(&b)` can be traced.
+        impl<'a> Tracer<'a, $ty> {
+            /// Returns whether `self` is equal to `rhs`.
+            pub fn eq(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                let node = binary::eq_nbit(
+                    &mut self.state.borrow_mut(),
+                    &self.value.nodes(),
+                    &rhs.value.nodes(),
+                );
+
+                Tracer::new(self.state, Bit::new([node]))
+            }
+
+            /// Returns whether `self` is not equal to `rhs`.
+            pub fn ne(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                !self.eq(rhs)
+            }
+
+            /// Returns whether `self` is less than `rhs`.
+            pub fn lt(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                let node = binary::lt_nbit(
+                    &mut self.state.borrow_mut(),
+                    &self.value.nodes(),
+                    &rhs.value.nodes(),
+                );
+
+                Tracer::new(self.state, Bit::new([node]))
+            }
+
+            /// Returns whether `self` is less than or equal to `rhs`.
+            pub fn le(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                !rhs.lt(self)
+            }
+
+            /// Returns whether `self` is greater than `rhs`.
+            pub fn gt(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                rhs.lt(self)
+            }
+
+            /// Returns whether `self` is greater than or equal to `rhs`.
+            pub fn ge(&self, rhs: &Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                !self.lt(rhs)
+            }
+        }
+    };
+}
+
+impl_compare_uint!(U8);
+impl_compare_uint!(U16);
+impl_compare_uint!(U32);
+impl_compare_uint!(U64);
+impl_compare_uint!(U128);
 
 impl<'a> BitXor for Tracer<'a, BinaryRepr> {
     type Output = Tracer<'a, BinaryRepr>;
@@ -138,6 +370,26 @@ impl<'a> BitXor for Tracer<'a, BinaryRepr> {
                 let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
                 Tracer::new(self.state, c.into())
             }
+            (BinaryRepr::I8(a), BinaryRepr::I8(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I16(a), BinaryRepr::I16(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I32(a), BinaryRepr::I32(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I64(a), BinaryRepr::I64(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I128(a), BinaryRepr::I128(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
             (BinaryRepr::Array(a), BinaryRepr::Array(b)) => Tracer::new(
                 self.state,
                 BinaryRepr::Array(
@@ -202,6 +454,11 @@ impl_bitxor_uint!(U16, u16, 16);
 impl_bitxor_uint!(U32, u32, 32);
 impl_bitxor_uint!(U64, u64, 64);
 impl_bitxor_uint!(U128, u128, 128);
+impl_bitxor_uint!(I8, i8, 8);
+impl_bitxor_uint!(I16, i16, 16);
+impl_bitxor_uint!(I32, i32, 32);
+impl_bitxor_uint!(I64, i64, 64);
+impl_bitxor_uint!(I128, i128, 128);
 
 macro_rules! impl_bit_and_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -250,6 +507,11 @@ impl_bit_and_uint!(U16, u16, 16);
 impl_bit_and_uint!(U32, u32, 32);
 impl_bit_and_uint!(U64, u64, 64);
 impl_bit_and_uint!(U128, u128, 128);
+impl_bit_and_uint!(I8, i8, 8);
+impl_bit_and_uint!(I16, i16, 16);
+impl_bit_and_uint!(I32, i32, 32);
+impl_bit_and_uint!(I64, i64, 64);
+impl_bit_and_uint!(I128, i128, 128);
 
 macro_rules! impl_bit_or_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -298,6 +560,11 @@ impl_bit_or_uint!(U16, u16, 16);
 impl_bit_or_uint!(U32, u32, 32);
 impl_bit_or_uint!(U64, u64, 64);
 impl_bit_or_uint!(U128, u128, 128);
+impl_bit_or_uint!(I8, i8, 8);
+impl_bit_or_uint!(I16, i16, 16);
+impl_bit_or_uint!(I32, i32, 32);
+impl_bit_or_uint!(I64, i64, 64);
+impl_bit_or_uint!(I128, i128, 128);
 
 macro_rules! impl_shl_uint {
     ($ty:ident, $len:expr) => {
@@ -330,6 +597,11 @@ impl_shl_uint!(U16, 16);
 impl_shl_uint!(U32, 32);
 impl_shl_uint!(U64, 64);
 impl_shl_uint!(U128, 128);
+impl_shl_uint!(I8, 8);
+impl_shl_uint!(I16, 16);
+impl_shl_uint!(I32, 32);
+impl_shl_uint!(I64, 64);
+impl_shl_uint!(I128, 128);
 
 macro_rules! impl_shr_uint {
     ($ty:ident, $len:expr) => {
@@ -347,7 +619,7 @@ macro_rules! impl_shr_uint {
                 // Bits are LSB0, so we rotate left
                 nodes.rotate_left(rhs);
                 // Replace the msbs with 0s
-                nodes[$len - rhs - 1..]
+                nodes[$len - rhs..]
                     .iter_mut()
                     .for_each(|node| *node = const_zero);
 
@@ -386,6 +658,11 @@ impl_neg_uint!(U16);
 impl_neg_uint!(U32);
 impl_neg_uint!(U64);
 impl_neg_uint!(U128);
+impl_neg_uint!(I8);
+impl_neg_uint!(I16);
+impl_neg_uint!(I32);
+impl_neg_uint!(I64);
+impl_neg_uint!(I128);
 
 macro_rules! impl_convert_bytes {
     ($ty:ident, $len:expr) => {
@@ -424,3 +701,94 @@ impl_convert_bytes!(U16, 2);
 impl_convert_bytes!(U32, 4);
 impl_convert_bytes!(U64, 8);
 impl_convert_bytes!(U128, 16);
+impl_convert_bytes!(I8, 1);
+impl_convert_bytes!(I16, 2);
+impl_convert_bytes!(I32, 4);
+impl_convert_bytes!(I64, 8);
+impl_convert_bytes!(I128, 16);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits_macros::{test_circ, trace};
+    use rand::{thread_rng, Rng};
+
+    use crate::CircuitBuilder;
+
+    #[trace]
+    fn arithmetic(a: u32, b: u32) -> (u32, u32, u32) {
+        (a.wrapping_mul(b), a / b, a % b)
+    }
+
+    #[trace]
+    fn compare(a: u64, b: u64) -> [bool; 6] {
+        [a.eq(&b), a.ne(&b), a.lt(&b), a.le(&b), a.gt(&b), a.ge(&b)]
+    }
+
+    // The shifted values are masked as constant outputs are not supported.
+    #[trace]
+    fn shift(a: u16, b: u16) -> [u16; 4] {
+        [(a >> 3) ^ b, (a << 3) ^ b, (a >> 1) ^ b, (a >> 15) ^ b]
+    }
+
+    #[test]
+    fn test_shift() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u16>();
+        let b = builder.add_input::<u16>();
+
+        let out = shift_trace(builder.state(), a, b);
+        builder.add_output(out);
+
+        let circ = builder.build().unwrap();
+
+        test_circ!(circ, shift, fn(0xabcdu16, 0x1234u16) -> [u16; 4]);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u32>();
+        let b = builder.add_input::<u32>();
+
+        let (product, quotient, rem) = arithmetic_trace(builder.state(), a, b);
+
+        builder.add_output(product);
+        builder.add_output(quotient);
+        builder.add_output(rem);
+
+        let circ = builder.build().unwrap();
+
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let a: u32 = rng.gen();
+            // Keep the divisor small so the quotient is not trivially zero.
+            let b: u32 = rng.gen_range(1..=u16::MAX as u32);
+
+            test_circ!(circ, arithmetic, fn(a, b) -> (u32, u32, u32));
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u64>();
+        let b = builder.add_input::<u64>();
+
+        let comparisons = compare_trace(builder.state(), a, b);
+
+        builder.add_output(comparisons);
+
+        let circ = builder.build().unwrap();
+
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let a: u64 = rng.gen();
+            let b: u64 = rng.gen();
+
+            test_circ!(circ, compare, fn(a, b) -> [bool; 6]);
+            test_circ!(circ, compare, fn(a, a) -> [bool; 6]);
+        }
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+
+use crate::{
+    components::{Feed, Gate, Node},
+    Circuit,
+};
+
+impl Circuit {
+    /// Optimizes the circuit, returning an equivalent circuit with fewer gates.
+    ///
+    /// The following passes are applied:
+    ///
+    /// * Constants, eg. `x ^ x` or `x & !x`, are propagated and inverters are absorbed into
+    ///   the gates which use them.
+    /// * Structurally identical gates are deduplicated.
+    /// * `(a & b) ^ (a & c)` is rewritten to `a & (b ^ c)` if the AND gates are not used
+    ///   elsewhere, which removes an AND gate.
+    /// * Gates which do not contribute to the outputs are removed.
+    ///
+    /// The rewrites are repeated until the number of AND gates stops decreasing. The input and
+    /// output types are preserved, but the nodes are renumbered.
+    pub fn optimize(self) -> Circuit {
+        let mut circ = eliminate_dead_gates(fold(&self, false));
+        loop {
+            let next = eliminate_dead_gates(fold(&circ, true));
+
+            if (next.and_count, next.gates.len()) < (circ.and_count, circ.gates.len()) {
+                circ = next;
+            } else {
+                return circ;
+            }
+        }
+    }
+}
+
+/// The value of a node, either a constant or a possibly inverted node of the folded circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Literal {
+    Const(bool),
+    Node { id: usize, inv: bool },
+}
+
+impl Literal {
+    fn invert(self) -> Self {
+        match self {
+            Literal::Const(value) => Literal::Const(!value),
+            Literal::Node { id, inv } => Literal::Node { id, inv: !inv },
+        }
+    }
+}
+
+/// The structure of a gate, with its inputs in ascending order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GateKey {
+    Xor(usize, usize),
+    And(usize, usize),
+    Inv(usize),
+}
+
+/// Builds a circuit while folding constants and deduplicating gates.
+#[derive(Default)]
+struct Folder {
+    gates: Vec<Gate>,
+    feed_count: usize,
+    keys: HashMap<GateKey, usize>,
+}
+
+impl Folder {
+    fn add_feed(&mut self) -> Node<Feed> {
+        let feed = Node::new(self.feed_count);
+        self.feed_count += 1;
+        feed
+    }
+
+    /// Adds a gate unless an identical gate already exists, returning its output.
+    fn add_gate(&mut self, key: GateKey) -> usize {
+        if let Some(id) = self.keys.get(&key) {
+            return *id;
+        }
+
+        let z = self.add_feed();
+        self.gates.push(match key {
+            GateKey::Xor(x, y) => Gate::Xor {
+                x: Node::new(x),
+                y: Node::new(y),
+                z,
+            },
+            GateKey::And(x, y) => Gate::And {
+                x: Node::new(x),
+                y: Node::new(y),
+                z,
+            },
+            GateKey::Inv(x) => Gate::Inv { x: Node::new(x), z },
+        });
+        self.keys.insert(key, z.id);
+
+        z.id
+    }
+
+    /// Returns a node carrying the value of the literal, adding gates if necessary.
+    ///
+    /// Constants are derived from `any`, which can be any node of the circuit.
+    fn materialize(&mut self, literal: Literal, any: usize) -> usize {
+        match literal {
+            Literal::Node { id, inv: false } => id,
+            Literal::Node { id, inv: true } => self.add_gate(GateKey::Inv(id)),
+            Literal::Const(false) => self.add_gate(GateKey::Xor(any, any)),
+            Literal::Const(true) => {
+                let zero = self.add_gate(GateKey::Xor(any, any));
+                self.add_gate(GateKey::Inv(zero))
+            }
+        }
+    }
+
+    fn xor(&mut self, a: Literal, b: Literal) -> Literal {
+        match (a, b) {
+            (Literal::Const(a), Literal::Const(b)) => Literal::Const(a ^ b),
+            (Literal::Const(value), other) | (other, Literal::Const(value)) => {
+                if value {
+                    other.invert()
+                } else {
+                    other
+                }
+            }
+            (Literal::Node { id: x, inv: x_inv }, Literal::Node { id: y, inv: y_inv }) => {
+                // Inverters commute with XOR, so they are moved to the output.
+                let inv = x_inv ^ y_inv;
+                if x == y {
+                    Literal::Const(inv)
+                } else {
+                    let id = self.add_gate(GateKey::Xor(x.min(y), x.max(y)));
+                    Literal::Node { id, inv }
+                }
+            }
+        }
+    }
+
+    fn and(&mut self, a: Literal, b: Literal) -> Literal {
+        match (a, b) {
+            (Literal::Const(a), Literal::Const(b)) => Literal::Const(a & b),
+            (Literal::Const(value), other) | (other, Literal::Const(value)) => {
+                if value {
+                    other
+                } else {
+                    Literal::Const(false)
+                }
+            }
+            (Literal::Node { id: x, inv: x_inv }, Literal::Node { id: y, inv: y_inv })
+                if x == y =>
+            {
+                if x_inv == y_inv {
+                    a
+                } else {
+                    Literal::Const(false)
+                }
+            }
+            (Literal::Node { id: x, .. }, Literal::Node { .. }) => {
+                let x = self.materialize(a, x);
+                let y = self.materialize(b, x);
+                let id = self.add_gate(GateKey::And(x.min(y), x.max(y)));
+                Literal::Node { id, inv: false }
+            }
+        }
+    }
+}
+
+/// Folds the constants and deduplicates the gates of a circuit.
+///
+/// If `distribute` is set, `(a & b) ^ (a & c)` is rewritten to `a & (b ^ c)` when the AND
+/// gates are only used by the XOR gate.
+fn fold(circ: &Circuit, distribute: bool) -> Circuit {
+    let mut folder = Folder::default();
+    let mut literals: Vec<Option<Literal>> = vec![None; circ.feed_count];
+
+    let mut inputs = circ.inputs.clone();
+    for node in inputs.iter_mut().flat_map(|input| input.iter_mut()) {
+        let feed = folder.add_feed();
+        literals[node.id] = Some(Literal::Node {
+            id: feed.id,
+            inv: false,
+        });
+        *node = feed;
+    }
+
+    // The AND gates of the original circuit which are used exactly once, by their output.
+    let mut single_use_ands = HashMap::new();
+    if distribute {
+        let uses = use_counts(circ);
+        for gate in &circ.gates {
+            if let Gate::And { x, y, z } = gate {
+                if uses[z.id] == 1 {
+                    single_use_ands.insert(z.id, (x.id, y.id));
+                }
+            }
+        }
+    }
+
+    let literal = |literals: &[Option<Literal>], id: usize| {
+        literals[id].expect("gates should be topologically sorted")
+    };
+
+    for gate in &circ.gates {
+        let z = match gate {
+            Gate::Xor { x, y, .. } => {
+                let factors = single_use_ands
+                    .get(&x.id)
+                    .zip(single_use_ands.get(&y.id))
+                    .and_then(|(&(a, b), &(c, d))| common_factor((a, b), (c, d)));
+
+                if let Some((a, b, c)) = factors {
+                    let sum = folder.xor(literal(&literals, b), literal(&literals, c));
+                    folder.and(literal(&literals, a), sum)
+                } else {
+                    folder.xor(literal(&literals, x.id), literal(&literals, y.id))
+                }
+            }
+            Gate::And { x, y, .. } => {
+                folder.and(literal(&literals, x.id), literal(&literals, y.id))
+            }
+            Gate::Inv { x, .. } => literal(&literals, x.id).invert(),
+        };
+
+        literals[gate.z().id] = Some(z);
+    }
+
+    // Constant outputs are derived from the first input, a circuit without inputs can not
+    // have any gates.
+    let any = 0;
+    let mut outputs = circ.outputs.clone();
+    for node in outputs.iter_mut().flat_map(|output| output.iter_mut()) {
+        *node = Node::new(folder.materialize(literal(&literals, node.id), any));
+    }
+
+    let and_count = count_ands(&folder.gates);
+    let xor_count = count_xors(&folder.gates);
+
+    Circuit {
+        inputs,
+        outputs,
+        gates: folder.gates,
+        feed_count: folder.feed_count,
+        and_count,
+        xor_count,
+    }
+}
+
+/// Returns `(a, b, c)` if the products `x` and `y` are `a & b` and `a & c`.
+fn common_factor(x: (usize, usize), y: (usize, usize)) -> Option<(usize, usize, usize)> {
+    let (x0, x1) = x;
+    let (y0, y1) = y;
+
+    if x0 == y0 {
+        Some((x0, x1, y1))
+    } else if x0 == y1 {
+        Some((x0, x1, y0))
+    } else if x1 == y0 {
+        Some((x1, x0, y1))
+    } else if x1 == y1 {
+        Some((x1, x0, y0))
+    } else {
+        None
+    }
+}
+
+/// Returns the number of times each node is used by a gate or an output.
+fn use_counts(circ: &Circuit) -> Vec<usize> {
+    let mut uses = vec![0; circ.feed_count];
+    for gate in &circ.gates {
+        uses[gate.x().id] += 1;
+        if let Some(y) = gate.y() {
+            uses[y.id] += 1;
+        }
+    }
+
+    for node in circ.outputs.iter().flat_map(|output| output.iter()) {
+        uses[node.id] += 1;
+    }
+
+    uses
+}
+
+/// Removes the gates which do not contribute to the outputs, renumbering the nodes.
+fn eliminate_dead_gates(mut circ: Circuit) -> Circuit {
+    let mut live = vec![false; circ.feed_count];
+    for node in circ.outputs.iter().flat_map(|output| output.iter()) {
+        live[node.id] = true;
+    }
+
+    for gate in circ.gates.iter().rev() {
+        if live[gate.z().id] {
+            live[gate.x().id] = true;
+            if let Some(y) = gate.y() {
+                live[y.id] = true;
+            }
+        }
+    }
+
+    let mut ids: Vec<Option<usize>> = vec![None; circ.feed_count];
+    let mut feed_count = 0;
+    let mut renumber = |ids: &mut [Option<usize>], node: &mut Node<Feed>| {
+        ids[node.id] = Some(feed_count);
+        node.id = feed_count;
+        feed_count += 1;
+    };
+
+    for node in circ.inputs.iter_mut().flat_map(|input| input.iter_mut()) {
+        renumber(&mut ids, node);
+    }
+
+    let mut gates = Vec::with_capacity(circ.gates.len());
+    for mut gate in circ.gates.into_iter().filter(|gate| live[gate.z().id]) {
+        match &mut gate {
+            Gate::Xor { x, y, z } | Gate::And { x, y, z } => {
+                x.id = ids[x.id].expect("input should be live");
+                y.id = ids[y.id].expect("input should be live");
+                renumber(&mut ids, z);
+            }
+            Gate::Inv { x, z } => {
+                x.id = ids[x.id].expect("input should be live");
+                renumber(&mut ids, z);
+            }
+        }
+        gates.push(gate);
+    }
+    for node in circ.outputs.iter_mut().flat_map(|output| output.iter_mut()) {
+        node.id = ids[node.id].expect("output should be live");
+    }
+
+    Circuit {
+        inputs: circ.inputs,
+        outputs: circ.outputs,
+        and_count: count_ands(&gates),
+        xor_count: count_xors(&gates),
+        gates,
+        feed_count,
+    }
+}
+
+fn count_ands(gates: &[Gate]) -> usize {
+    gates
+        .iter()
+        .filter(|gate| matches!(gate, Gate::And { .. }))
+        .count()
+}
+
+fn count_xors(gates: &[Gate]) -> usize {
+    gates
+        .iter()
+        .filter(|gate| matches!(gate, Gate::Xor { .. }))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits_macros::evaluate;
+    use rand::{thread_rng, Rng};
+
+    use crate::{
+        circuits::{AES128, SHA256_COMPRESS},
+        types::Value,
+        CircuitBuilder,
+    };
+
+    #[test]
+    fn test_optimize_prebuilt() {
+        let mut rng = thread_rng();
+        for (circ, inputs) in [
+            (
+                AES128.as_ref().clone(),
+                vec![
+                    Value::from(rng.gen::<[u8; 16]>()),
+                    rng.gen::<[u8; 16]>().into(),
+                ],
+            ),
+            (
+                SHA256_COMPRESS.as_ref().clone(),
+                vec![
+                    Value::from(rng.gen::<[u32; 8]>()),
+                    [rng.gen::<u8>(); 64].into(),
+                ],
+            ),
+        ] {
+            let optimized = circ.clone().optimize();
+
+            assert!(optimized.and_count() <= circ.and_count());
+            assert_eq!(
+                optimized.evaluate(&inputs).unwrap(),
+                circ.evaluate(&inputs).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_optimize_dead_gates() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        // Never used.
+        let _ = a & b;
+
+        builder.add_output(a ^ b);
+
+        let circ = builder.build().unwrap().optimize();
+
+        assert_eq!(circ.and_count(), 0);
+        assert_eq!(circ.xor_count(), 8);
+        assert_eq!(circ.feed_count(), 24);
+
+        let out = evaluate!(circ, fn(0x0fu8, 0x35u8) -> u8).unwrap();
+        assert_eq!(out, 0x0f ^ 0x35);
+    }
+
+    #[test]
+    fn test_optimize_duplicates() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        builder.add_output(a & b);
+        builder.add_output(b & a);
+
+        let circ = builder.build().unwrap();
+        assert_eq!(circ.and_count(), 16);
+
+        let circ = circ.optimize();
+        assert_eq!(circ.and_count(), 8);
+
+        let out = evaluate!(circ, fn(0x0fu8, 0x35u8) -> (u8, u8)).unwrap();
+        assert_eq!(out, (0x0f & 0x35, 0x0f & 0x35));
+    }
+
+    #[test]
+    fn test_optimize_constants() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        // (a ^ a) & b == 0 and (a & !a) ^ b == b
+        builder.add_output((a ^ a) & b);
+        builder.add_output((a & !a) ^ b);
+
+        let circ = builder.build().unwrap();
+        assert_eq!(circ.and_count(), 16);
+
+        let circ = circ.optimize();
+        assert_eq!(circ.and_count(), 0);
+
+        for (a, b) in [(0x0fu8, 0x35u8), (0xff, 0xff), (0, 0)] {
+            let out = evaluate!(circ, fn(a, b) -> (u8, u8)).unwrap();
+            assert_eq!(out, (0, b));
+        }
+    }
+
+    #[test]
+    fn test_optimize_distribute() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let c = builder.add_input::<u8>();
+        let d = builder.add_input::<u8>();
+
+        builder.add_output((a & b) ^ (c & a) ^ (a & d));
+
+        let circ = builder.build().unwrap();
+        assert_eq!(circ.and_count(), 24);
+
+        let circ = circ.optimize();
+        assert_eq!(circ.and_count(), 8);
+
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let (a, b, c, d): (u8, u8, u8, u8) = rng.gen();
+            let out = evaluate!(circ, fn(a, b, c, d) -> u8).unwrap();
+            assert_eq!(out, a & (b ^ c ^ d));
+        }
+    }
+}
@@ -1,23 +1,29 @@
 use crate::{
-    components::{Feed, GateType, Node},
+    components::{Feed, Node},
     types::ValueType,
-    Circuit, CircuitBuilder,
+    BuilderState, Circuit, CircuitBuilder,
 };
-use regex::{Captures, Regex};
-use std::collections::HashMap;
-
-static GATE_PATTERN: &str = r"(?P<input_count>\d+)\s(?P<output_count>\d+)\s(?P<xref>\d+)\s(?:(?P<yref>\d+)\s)?(?P<zref>\d+)\s(?P<gate>INV|AND|XOR)";
 
+/// An error that can occur when parsing a Bristol circuit.
 #[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
 pub enum ParseError {
     #[error(transparent)]
     IOError(#[from] std::io::Error),
     #[error(transparent)]
     ParseIntError(#[from] std::num::ParseIntError),
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("invalid gate: {0}")]
+    InvalidGate(String),
+    #[error("invalid wire: {0}")]
+    InvalidWire(usize),
     #[error("uninitialized feed: {0}")]
     UninitializedFeed(usize),
     #[error("unsupported gate type: {0}")]
     UnsupportedGateType(String),
+    #[error("circuit has constant outputs but no wire to derive them from")]
+    ConstantOutput,
     #[error(transparent)]
     BuilderError(#[from] crate::BuilderError),
 }
@@ -43,121 +49,319 @@ impl Circuit {
     ) -> Result<Self, ParseError> {
         let file = std::fs::read_to_string(filename)?;
 
-        let builder = CircuitBuilder::new();
+        parse(&file, Some((inputs, outputs)))
+    }
+
+    /// Parses a circuit in Bristol-fashion format from a file, inferring the types of the inputs
+    /// and outputs from the header.
+    ///
+    /// See [`Circuit::from_bristol`] for how the types are inferred.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - The path to the file to parse.
+    pub fn parse_bristol(filename: &str) -> Result<Self, ParseError> {
+        let file = std::fs::read_to_string(filename)?;
+
+        parse(&file, None)
+    }
+
+    /// Parses a circuit in Bristol-fashion format, inferring the types of the inputs and outputs
+    /// from the header.
+    ///
+    /// A value of 1 wire is a bit, values of 8, 16, 32, 64 or 128 wires are unsigned integers
+    /// with the least significant bit first, and any other value is an array of bits.
+    ///
+    /// Besides `XOR`, `AND` and `INV`, the `EQ`, `EQW` and `MAND` gates are supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The circuit in Bristol-fashion format.
+    pub fn from_bristol(source: &str) -> Result<Self, ParseError> {
+        parse(source, None)
+    }
+}
+
+/// The header of a Bristol-fashion circuit.
+struct Header {
+    gate_count: usize,
+    wire_count: usize,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
 
-        let mut feed_ids: Vec<usize> = Vec::new();
-        let mut feed_map: HashMap<usize, Node<Feed>> = HashMap::default();
+impl Header {
+    fn parse<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+        let mut next_line = |name: &str| {
+            let line = lines
+                .next()
+                .ok_or_else(|| ParseError::InvalidHeader(format!("missing {name}")))?;
 
-        let mut input_len = 0;
-        for input in inputs {
-            let input = builder.add_input_by_type(input.clone());
-            for (node, old_id) in input.iter().zip(input_len..input_len + input.len()) {
-                feed_map.insert(old_id, *node);
+            line.split_whitespace()
+                .map(|n| n.parse::<usize>().map_err(ParseError::from))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let counts = next_line("gate and wire counts")?;
+        let [gate_count, wire_count] = counts[..] else {
+            return Err(ParseError::InvalidHeader(format!(
+                "expected gate and wire counts, got {counts:?}"
+            )));
+        };
+
+        let mut values = |name: &str| {
+            let line = next_line(name)?;
+            match line.split_first() {
+                Some((count, lens)) if *count == lens.len() => Ok(lens.to_vec()),
+                _ => Err(ParseError::InvalidHeader(format!(
+                    "expected {name} count followed by their lengths, got {line:?}"
+                ))),
             }
-            input_len += input.len();
+        };
+
+        let inputs = values("inputs")?;
+        let outputs = values("outputs")?;
+
+        if inputs.iter().chain(&outputs).sum::<usize>() > wire_count {
+            return Err(ParseError::InvalidHeader(
+                "inputs and outputs exceed the wire count".to_string(),
+            ));
         }
 
-        let mut state = builder.state().borrow_mut();
-        let pattern = Regex::new(GATE_PATTERN).unwrap();
-        for cap in pattern.captures_iter(&file) {
-            let UncheckedGate {
-                xref,
-                yref,
-                zref,
-                gate_type,
-            } = UncheckedGate::parse(cap)?;
-            feed_ids.push(zref);
-
-            match gate_type {
-                GateType::Xor => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_y = feed_map
-                        .get(&yref.unwrap())
-                        .ok_or(ParseError::UninitializedFeed(yref.unwrap()))?;
-                    let new_z = state.add_xor_gate(*new_x, *new_y);
-                    feed_map.insert(zref, new_z);
-                }
-                GateType::And => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_y = feed_map
-                        .get(&yref.unwrap())
-                        .ok_or(ParseError::UninitializedFeed(yref.unwrap()))?;
-                    let new_z = state.add_and_gate(*new_x, *new_y);
-                    feed_map.insert(zref, new_z);
-                }
-                GateType::Inv => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_z = state.add_inv_gate(*new_x);
-                    feed_map.insert(zref, new_z);
-                }
-            }
+        Ok(Self {
+            gate_count,
+            wire_count,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+/// Infers the type of a value from its number of wires.
+fn infer_type(len: usize) -> ValueType {
+    match len {
+        1 => ValueType::Bit,
+        8 => ValueType::U8,
+        16 => ValueType::U16,
+        32 => ValueType::U32,
+        64 => ValueType::U64,
+        128 => ValueType::U128,
+        _ => ValueType::Array(Box::new(ValueType::Bit), len),
+    }
+}
+
+/// Checks that the types match the lengths in the header, or infers them if not provided.
+fn check_types(
+    name: &str,
+    types: Option<&[ValueType]>,
+    lens: &[usize],
+) -> Result<Vec<ValueType>, ParseError> {
+    let Some(types) = types else {
+        return Ok(lens.iter().copied().map(infer_type).collect());
+    };
+
+    let type_lens = types.iter().map(|ty| ty.len()).collect::<Vec<_>>();
+    if type_lens != lens {
+        return Err(ParseError::InvalidHeader(format!(
+            "expected {name} of lengths {type_lens:?}, got {lens:?}"
+        )));
+    }
+
+    Ok(types.to_vec())
+}
+
+fn parse(source: &str, types: Option<(&[ValueType], &[ValueType])>) -> Result<Circuit, ParseError> {
+    let mut lines = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+
+    let header = Header::parse(&mut lines)?;
+    let inputs = check_types("inputs", types.map(|(inputs, _)| inputs), &header.inputs)?;
+    let outputs = check_types(
+        "outputs",
+        types.map(|(_, outputs)| outputs),
+        &header.outputs,
+    )?;
+
+    let builder = CircuitBuilder::new();
+
+    let mut feeds: Vec<Option<Node<Feed>>> = vec![None; header.wire_count];
+    let mut wire_id = 0;
+    for input in inputs {
+        let input = builder.add_input_by_type(input);
+        for node in input.iter() {
+            feeds[wire_id] = Some(*node);
+            wire_id += 1;
         }
-        drop(state);
-        feed_ids.sort();
-
-        for output in outputs.iter().rev() {
-            let feeds = feed_ids
-                .drain(feed_ids.len() - output.len()..)
-                .map(|id| {
-                    *feed_map
-                        .get(&id)
-                        .expect("Old feed should be mapped to new feed")
-                })
-                .collect::<Vec<Node<Feed>>>();
-
-            let output = output.to_bin_repr(&feeds).unwrap();
-            builder.add_output(output);
+    }
+
+    let mut state = builder.state().borrow_mut();
+    let mut gate_count = 0;
+    for line in lines {
+        parse_gate(&mut state, &mut feeds, line)?;
+        gate_count += 1;
+    }
+
+    if gate_count != header.gate_count {
+        return Err(ParseError::InvalidHeader(format!(
+            "expected {} gates, got {}",
+            header.gate_count, gate_count
+        )));
+    }
+
+    // The outputs are the last wires of the circuit.
+    let mut wire_id = header.wire_count - header.outputs.iter().sum::<usize>();
+    let mut output_reprs = Vec::with_capacity(outputs.len());
+    for output in outputs {
+        let mut nodes = Vec::with_capacity(output.len());
+        for _ in 0..output.len() {
+            let node = feeds[wire_id].ok_or(ParseError::UninitializedFeed(wire_id))?;
+            nodes.push(materialize_constant(&mut state, feeds[0], node)?);
+            wire_id += 1;
         }
 
-        Ok(builder.build()?)
+        output_reprs.push(output.to_bin_repr(&nodes).expect("length should match"));
     }
+
+    drop(state);
+
+    for output in output_reprs {
+        builder.add_output(output);
+    }
+
+    Ok(builder.build()?)
 }
 
-struct UncheckedGate {
-    xref: usize,
-    yref: Option<usize>,
-    zref: usize,
-    gate_type: GateType,
+/// Parses a gate, adding it to the builder state.
+fn parse_gate(
+    state: &mut BuilderState,
+    feeds: &mut [Option<Node<Feed>>],
+    line: &str,
+) -> Result<(), ParseError> {
+    let tokens = line.split_whitespace().collect::<Vec<_>>();
+    let invalid = || ParseError::InvalidGate(line.to_string());
+
+    let (&op, tokens) = tokens.split_last().ok_or_else(invalid)?;
+    let numbers = tokens
+        .iter()
+        .map(|n| n.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let [input_count, output_count, ref wires @ ..] = numbers[..] else {
+        return Err(invalid());
+    };
+
+    if wires.len() != input_count + output_count {
+        return Err(invalid());
+    }
+
+    let (xs, zs) = wires.split_at(input_count);
+    if let Some(z) = zs.iter().find(|z| **z >= feeds.len()) {
+        return Err(ParseError::InvalidWire(*z));
+    }
+
+    let feed = |feeds: &[Option<Node<Feed>>], id: usize| {
+        feeds
+            .get(id)
+            .ok_or(ParseError::InvalidWire(id))?
+            .ok_or(ParseError::UninitializedFeed(id))
+    };
+
+    match (op, input_count, output_count) {
+        ("XOR", 2, 1) => {
+            let (x, y) = (feed(feeds, xs[0])?, feed(feeds, xs[1])?);
+            feeds[zs[0]] = Some(state.add_xor_gate(x, y));
+        }
+        ("AND", 2, 1) => {
+            let (x, y) = (feed(feeds, xs[0])?, feed(feeds, xs[1])?);
+            feeds[zs[0]] = Some(state.add_and_gate(x, y));
+        }
+        ("INV", 1, 1) => {
+            let x = feed(feeds, xs[0])?;
+            feeds[zs[0]] = Some(state.add_inv_gate(x));
+        }
+        ("EQW", 1, 1) => {
+            feeds[zs[0]] = Some(feed(feeds, xs[0])?);
+        }
+        // The input of an EQ gate is a constant rather than a wire.
+        ("EQ", 1, 1) => {
+            feeds[zs[0]] = Some(match xs[0] {
+                0 => state.get_const_zero(),
+                1 => state.get_const_one(),
+                _ => return Err(invalid()),
+            });
+        }
+        ("MAND", _, _) if input_count == 2 * output_count => {
+            let (xs, ys) = xs.split_at(output_count);
+            for ((x, y), z) in xs.iter().zip(ys).zip(zs) {
+                let (x, y) = (feed(feeds, *x)?, feed(feeds, *y)?);
+                feeds[*z] = Some(state.add_and_gate(x, y));
+            }
+        }
+        ("XOR" | "AND" | "INV" | "EQW" | "EQ" | "MAND", _, _) => return Err(invalid()),
+        _ => return Err(ParseError::UnsupportedGateType(op.to_string())),
+    }
+
+    Ok(())
 }
 
-impl UncheckedGate {
-    fn parse(captures: Captures) -> Result<Self, ParseError> {
-        let xref: usize = captures.name("xref").unwrap().as_str().parse()?;
-        let yref: Option<usize> = captures
-            .name("yref")
-            .map(|yref| yref.as_str().parse())
-            .transpose()?;
-        let zref: usize = captures.name("zref").unwrap().as_str().parse()?;
-        let gate_type = captures.name("gate").unwrap().as_str();
-
-        let gate_type = match gate_type {
-            "XOR" => GateType::Xor,
-            "AND" => GateType::And,
-            "INV" => GateType::Inv,
-            _ => return Err(ParseError::UnsupportedGateType(gate_type.to_string())),
-        };
+/// Returns a node carrying the value of a constant node, as constants can not be outputs.
+///
+/// The constant is derived from the first wire, other nodes are returned as is.
+fn materialize_constant(
+    state: &mut BuilderState,
+    first: Option<Node<Feed>>,
+    node: Node<Feed>,
+) -> Result<Node<Feed>, ParseError> {
+    if node.id() > 1 {
+        return Ok(node);
+    }
 
-        Ok(Self {
-            xref,
-            yref,
-            zref,
-            gate_type,
-        })
+    let first = first
+        .filter(|first| first.id() > 1)
+        .ok_or(ParseError::ConstantOutput)?;
+
+    let zero = state.add_xor_gate(first, first);
+    if node.id() == 0 {
+        Ok(zero)
+    } else {
+        Ok(state.add_inv_gate(zero))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use mpz_circuits_macros::evaluate;
+    use rand::thread_rng;
+
+    use crate::{types::Value, CircuitBuilder};
 
     use super::*;
 
+    /// Evaluates both circuits on the same random inputs, asserting the outputs are equal.
+    fn assert_equivalent(a: &Circuit, b: &Circuit) {
+        assert_eq!(
+            a.inputs()
+                .iter()
+                .map(|i| i.value_type())
+                .collect::<Vec<_>>(),
+            b.inputs()
+                .iter()
+                .map(|i| i.value_type())
+                .collect::<Vec<_>>()
+        );
+
+        let mut rng = thread_rng();
+        let inputs = a
+            .inputs()
+            .iter()
+            .map(|input| Value::random(&mut rng, &input.value_type()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(a.evaluate(&inputs).unwrap(), b.evaluate(&inputs).unwrap());
+    }
+
     #[test]
     fn test_parse_adder_64() {
         let circ = Circuit::parse(
@@ -172,6 +376,125 @@ mod tests {
         assert_eq!(output, 3);
     }
 
+    #[test]
+    fn test_parse_infer_types() {
+        let circ = Circuit::parse_bristol("circuits/bristol/adder64_reverse.txt").unwrap();
+
+        let output: u64 = evaluate!(circ, fn(1u64, 2u64) -> u64).unwrap();
+
+        assert_eq!(output, 3);
+
+        let circ = Circuit::parse_bristol("circuits/bristol/aes_128_reverse.txt").unwrap();
+
+        assert_eq!(
+            circ.inputs()
+                .iter()
+                .map(|i| i.value_type())
+                .collect::<Vec<_>>(),
+            [ValueType::U128, ValueType::U128]
+        );
+    }
+
+    #[test]
+    fn test_parse_type_mismatch() {
+        let err = Circuit::parse(
+            "circuits/bristol/adder64_reverse.txt",
+            &[ValueType::U64, ValueType::U32],
+            &[ValueType::U64],
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, ParseError::InvalidHeader(_)));
+    }
+
+    #[test]
+    fn test_parse_extended_gates() {
+        // out_0 = a & b, out_1 = a & c, out_2 = a, out_3 = 1
+        let source = "
+            4 9
+            3 1 1 1
+            1 4
+
+            4 2 0 0 1 2 5 6 MAND
+            1 1 0 7 EQW
+            1 1 1 8 EQ
+            1 1 5 4 EQW
+        ";
+
+        let circ = Circuit::from_bristol(source).unwrap();
+
+        for (a, b, c) in [
+            (true, true, false),
+            (true, false, true),
+            (false, true, true),
+        ] {
+            let output = evaluate!(circ, fn(a, b, c) -> [bool; 4]).unwrap();
+            assert_eq!(output, [a & b, a & c, a, true]);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_gates() {
+        for (gate, expected) in [
+            ("2 1 0 1 2 NAND", "unsupported"),
+            ("1 1 0 1 2 XOR", "invalid gate"),
+            ("2 1 0 1 AND", "invalid gate"),
+            ("1 1 2 2 EQ", "invalid gate"),
+            ("2 1 0 2 2 XOR", "uninitialized"),
+            ("2 1 0 1 5 XOR", "invalid wire"),
+        ] {
+            let source = format!("1 3\n2 1 1\n1 1\n\n{gate}");
+            let err = Circuit::from_bristol(&source).unwrap_err();
+
+            assert!(err.to_string().contains(expected), "{gate}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_bristol_round_trip() {
+        for filename in [
+            "circuits/bristol/adder64_reverse.txt",
+            "circuits/bristol/aes_128_reverse.txt",
+            "circuits/bristol/sha256_reverse.txt",
+        ] {
+            let circ = Circuit::parse_bristol(filename).unwrap();
+
+            let bristol = circ.to_bristol();
+            let parsed = Circuit::from_bristol(&bristol).unwrap();
+
+            assert_eq!(parsed.and_count(), circ.and_count());
+            assert_equivalent(&circ, &parsed);
+
+            // Writing is deterministic, so the output is a fixed point.
+            assert_eq!(parsed.to_bristol(), bristol);
+        }
+    }
+
+    #[test]
+    fn test_to_bristol_copies() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let c = a & b;
+
+        builder.add_output(a);
+        builder.add_output(c);
+        builder.add_output(c);
+
+        let circ = builder.build().unwrap();
+
+        let bristol = circ.to_bristol();
+        assert_eq!(
+            bristol.lines().filter(|line| line.ends_with("EQW")).count(),
+            16
+        );
+
+        let parsed = Circuit::from_bristol(&bristol).unwrap();
+        assert_equivalent(&circ, &parsed);
+    }
+
     #[test]
     #[cfg(feature = "aes")]
     #[ignore = "expensive"]
@@ -0,0 +1,174 @@
+use crate::{components::Gate, Circuit};
+
+impl Circuit {
+    /// Returns the depth of the circuit, ie. the length of the longest path of gates.
+    ///
+    /// This is equal to the number of layers returned by [`Circuit::layers`].
+    pub fn depth(&self) -> usize {
+        self.gate_depths().into_iter().max().unwrap_or_default()
+    }
+
+    /// Returns the AND depth of the circuit, ie. the largest number of AND gates on any path.
+    ///
+    /// This is also known as the multiplicative depth of the circuit, and is equal to the
+    /// number of layers returned by [`Circuit::and_layers`] minus one.
+    pub fn and_depth(&self) -> usize {
+        self.gate_and_depths().into_iter().max().unwrap_or_default()
+    }
+
+    /// Returns the indices of the gates grouped into layers.
+    ///
+    /// The gates of a layer only depend on the inputs and on gates of previous layers, so they
+    /// are independent of each other. Within a layer the gates are in ascending order.
+    pub fn layers(&self) -> Vec<Vec<usize>> {
+        let mut layers = group(self.gate_depths());
+        // Depths start at 1, so the first group is always empty.
+        layers.remove(0);
+        layers
+    }
+
+    /// Returns the indices of the gates grouped into layers by AND depth.
+    ///
+    /// Layer `k` contains the AND gates at AND depth `k`, which only depend on previous layers
+    /// and are independent of each other, followed by the free gates at AND depth `k`, which
+    /// may depend on any gate of the layer before them. Within a layer the gates are in
+    /// ascending order, so a layer can be processed by first computing its AND gates in
+    /// parallel and then its free gates in order.
+    ///
+    /// The first layer contains no AND gates.
+    pub fn and_layers(&self) -> Vec<Vec<usize>> {
+        group(self.gate_and_depths())
+    }
+
+    /// Returns the depth of each gate, starting at 1.
+    fn gate_depths(&self) -> Vec<usize> {
+        self.depths(|_| 1)
+    }
+
+    /// Returns the AND depth of each gate.
+    fn gate_and_depths(&self) -> Vec<usize> {
+        self.depths(|gate| matches!(gate, Gate::And { .. }) as usize)
+    }
+
+    /// Returns the depth of each gate, where `weight` is the depth added by a gate.
+    fn depths(&self, weight: impl Fn(&Gate) -> usize) -> Vec<usize> {
+        let mut feed_depths = vec![0; self.feed_count];
+
+        self.gates
+            .iter()
+            .map(|gate| {
+                let x = feed_depths[gate.x().id];
+                let y = gate.y().map(|y| feed_depths[y.id]).unwrap_or_default();
+
+                let depth = x.max(y) + weight(gate);
+                feed_depths[gate.z().id] = depth;
+
+                depth
+            })
+            .collect()
+    }
+}
+
+/// Groups gate indices by their depth.
+fn group(depths: Vec<usize>) -> Vec<Vec<usize>> {
+    let max_depth = depths.iter().copied().max().unwrap_or_default();
+
+    let mut layers = vec![Vec::new(); max_depth + 1];
+    for (idx, depth) in depths.into_iter().enumerate() {
+        layers[depth].push(idx);
+    }
+
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
+
+    use super::*;
+
+    fn adder() -> Circuit {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        builder.add_output(a.wrapping_add(b));
+
+        builder.build().unwrap()
+    }
+
+    /// Asserts that the layers cover every gate once and respect the dependencies.
+    fn assert_schedule(circ: &Circuit, layers: &[Vec<usize>], free_in_layer: bool) {
+        let mut layer_of = vec![None; circ.feed_count()];
+        let mut count = 0;
+        for (k, layer) in layers.iter().enumerate() {
+            assert!(layer.windows(2).all(|w| w[0] < w[1]));
+            for &idx in layer {
+                let gate = &circ.gates()[idx];
+                let is_and = matches!(gate, Gate::And { .. });
+                for input in [Some(gate.x()), gate.y()].into_iter().flatten() {
+                    if let Some(input_layer) = layer_of[input.id()] {
+                        if is_and || !free_in_layer {
+                            assert!(input_layer < k);
+                        } else {
+                            assert!(input_layer <= k);
+                        }
+                    }
+                }
+                layer_of[gate.z().id()] = Some(k);
+                count += 1;
+            }
+        }
+
+        assert_eq!(count, circ.gates().len());
+    }
+
+    #[test]
+    fn test_adder_depth() {
+        let circ = adder();
+
+        assert_eq!(circ.and_depth(), 8);
+        assert_eq!(circ.depth(), circ.layers().len());
+        assert_eq!(circ.and_layers().len(), 9);
+    }
+
+    #[test]
+    fn test_layers() {
+        for circ in [adder(), AES128.as_ref().clone()] {
+            assert_schedule(&circ, &circ.layers(), false);
+            assert_schedule(&circ, &circ.and_layers(), true);
+        }
+    }
+
+    #[test]
+    fn test_and_layers() {
+        let layers = AES128.and_layers();
+
+        assert_eq!(layers.len(), AES128.and_depth() + 1);
+        assert!(layers[0]
+            .iter()
+            .all(|&idx| !matches!(AES128.gates()[idx], Gate::And { .. })));
+        assert_eq!(
+            layers
+                .iter()
+                .flatten()
+                .filter(|&&idx| matches!(AES128.gates()[idx], Gate::And { .. }))
+                .count(),
+            AES128.and_count()
+        );
+    }
+
+    #[test]
+    fn test_empty_circuit() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        builder.add_output(a);
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.depth(), 0);
+        assert_eq!(circ.and_depth(), 0);
+        assert!(circ.layers().is_empty());
+        assert_eq!(circ.and_layers(), vec![Vec::<usize>::new()]);
+    }
+}
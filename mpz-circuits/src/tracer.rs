@@ -2,6 +2,7 @@ use std::cell::RefCell;
 
 use crate::{
     builder::BuilderState,
+    ops::Select,
     types::{BinaryRepr, Bit},
     Feed, Node,
 };
@@ -60,4 +61,15 @@ impl<'a> Tracer<'a, Bit> {
     pub fn node(&self) -> Node<Feed> {
         self.to_inner().nodes()[0]
     }
+
+    /// Returns `a` if the bit is set, otherwise `b`.
+    ///
+    /// This is a multiplexer over any traced value, including arrays, vectors and tuples of
+    /// traced values. It costs a single AND gate per bit.
+    pub fn select<T>(self, a: T, b: T) -> T
+    where
+        Self: Select<T>,
+    {
+        Select::select(self, a, b)
+    }
 }
@@ -65,6 +65,11 @@ pub enum BinaryRepr {
     U64(U64),
     U128(U128),
     Array(Vec<BinaryRepr>),
+    I8(I8),
+    I16(I16),
+    I32(I32),
+    I64(I64),
+    I128(I128),
 }
 
 impl BinaryRepr {
@@ -77,6 +82,11 @@ impl BinaryRepr {
             BinaryRepr::U32(_) => ValueType::U32,
             BinaryRepr::U64(_) => ValueType::U64,
             BinaryRepr::U128(_) => ValueType::U128,
+            BinaryRepr::I8(_) => ValueType::I8,
+            BinaryRepr::I16(_) => ValueType::I16,
+            BinaryRepr::I32(_) => ValueType::I32,
+            BinaryRepr::I64(_) => ValueType::I64,
+            BinaryRepr::I128(_) => ValueType::I128,
             BinaryRepr::Array(v) => ValueType::Array(Box::new(v[0].value_type()), v.len()),
         }
     }
@@ -91,6 +101,11 @@ impl BinaryRepr {
             BinaryRepr::U32(U32 { .. }) => 32,
             BinaryRepr::U64(U64 { .. }) => 64,
             BinaryRepr::U128(U128 { .. }) => 128,
+            BinaryRepr::I8(I8 { .. }) => 8,
+            BinaryRepr::I16(I16 { .. }) => 16,
+            BinaryRepr::I32(I32 { .. }) => 32,
+            BinaryRepr::I64(I64 { .. }) => 64,
+            BinaryRepr::I128(I128 { .. }) => 128,
             BinaryRepr::Array(v) => v.iter().map(|v| v.len()).sum(),
         }
     }
@@ -104,6 +119,11 @@ impl BinaryRepr {
             BinaryRepr::U32(v) => Box::new(v.0.iter()),
             BinaryRepr::U64(v) => Box::new(v.0.iter()),
             BinaryRepr::U128(v) => Box::new(v.0.iter()),
+            BinaryRepr::I8(v) => Box::new(v.0.iter()),
+            BinaryRepr::I16(v) => Box::new(v.0.iter()),
+            BinaryRepr::I32(v) => Box::new(v.0.iter()),
+            BinaryRepr::I64(v) => Box::new(v.0.iter()),
+            BinaryRepr::I128(v) => Box::new(v.0.iter()),
             BinaryRepr::Array(v) => Box::new(v.iter().flat_map(|v| v.iter())),
         }
     }
@@ -117,6 +137,11 @@ impl BinaryRepr {
             BinaryRepr::U32(v) => Box::new(v.0.iter_mut()),
             BinaryRepr::U64(v) => Box::new(v.0.iter_mut()),
             BinaryRepr::U128(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I8(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I16(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I32(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I64(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I128(v) => Box::new(v.0.iter_mut()),
             BinaryRepr::Array(v) => Box::new(v.iter_mut().flat_map(|v| v.iter_mut())),
         }
     }
@@ -130,6 +155,11 @@ impl BinaryRepr {
             BinaryRepr::U32(v) => v.shift_left(offset),
             BinaryRepr::U64(v) => v.shift_left(offset),
             BinaryRepr::U128(v) => v.shift_left(offset),
+            BinaryRepr::I8(v) => v.shift_left(offset),
+            BinaryRepr::I16(v) => v.shift_left(offset),
+            BinaryRepr::I32(v) => v.shift_left(offset),
+            BinaryRepr::I64(v) => v.shift_left(offset),
+            BinaryRepr::I128(v) => v.shift_left(offset),
             BinaryRepr::Array(v) => v.iter_mut().for_each(|v| v.shift_left(offset)),
         }
     }
@@ -157,6 +187,13 @@ impl BinaryRepr {
             BinaryRepr::U32(_) => Ok(Value::U32(u32::from_lsb0_iter(bits.iter().copied()))),
             BinaryRepr::U64(_) => Ok(Value::U64(u64::from_lsb0_iter(bits.iter().copied()))),
             BinaryRepr::U128(_) => Ok(Value::U128(u128::from_lsb0_iter(bits.iter().copied()))),
+            BinaryRepr::I8(_) => Ok(Value::I8(u8::from_lsb0_iter(bits.iter().copied()) as i8)),
+            BinaryRepr::I16(_) => Ok(Value::I16(u16::from_lsb0_iter(bits.iter().copied()) as i16)),
+            BinaryRepr::I32(_) => Ok(Value::I32(u32::from_lsb0_iter(bits.iter().copied()) as i32)),
+            BinaryRepr::I64(_) => Ok(Value::I64(u64::from_lsb0_iter(bits.iter().copied()) as i64)),
+            BinaryRepr::I128(_) => Ok(Value::I128(
+                u128::from_lsb0_iter(bits.iter().copied()) as i128
+            )),
             BinaryRepr::Array(v) => Ok(Value::Array(
                 v.iter()
                     .zip(bits.chunks(v[0].len()))
@@ -176,6 +213,11 @@ impl Display for BinaryRepr {
             BinaryRepr::U32(v) => write!(f, "U32({:?})", v.0),
             BinaryRepr::U64(v) => write!(f, "U64({:?})", v.0),
             BinaryRepr::U128(v) => write!(f, "U128({:?})", v.0),
+            BinaryRepr::I8(v) => write!(f, "I8({:?})", v.0),
+            BinaryRepr::I16(v) => write!(f, "I16({:?})", v.0),
+            BinaryRepr::I32(v) => write!(f, "I32({:?})", v.0),
+            BinaryRepr::I64(v) => write!(f, "I64({:?})", v.0),
+            BinaryRepr::I128(v) => write!(f, "I128({:?})", v.0),
             BinaryRepr::Array(v) => write!(f, "Array({:?})", v),
         }
     }
@@ -426,6 +468,11 @@ define_binary_value!(u16, U16, 16);
 define_binary_value!(u32, U32, 32);
 define_binary_value!(u64, U64, 64);
 define_binary_value!(u128, U128, 128);
+define_binary_value!(i8, I8, 8);
+define_binary_value!(i16, I16, 16);
+define_binary_value!(i32, I32, 32);
+define_binary_value!(i64, I64, 64);
+define_binary_value!(i128, I128, 128);
 
 /// A value type that can be encoded into a binary representation.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -439,6 +486,11 @@ pub enum ValueType {
     U64,
     U128,
     Array(Box<ValueType>, usize),
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
 }
 
 impl ValueType {
@@ -462,6 +514,11 @@ impl ValueType {
             ValueType::U32 => 32,
             ValueType::U64 => 64,
             ValueType::U128 => 128,
+            ValueType::I8 => 8,
+            ValueType::I16 => 16,
+            ValueType::I32 => 32,
+            ValueType::I64 => 64,
+            ValueType::I128 => 128,
             ValueType::Array(ty, len) => ty.len() * len,
         }
     }
@@ -485,6 +542,11 @@ impl ValueType {
             ValueType::U32 => BinaryRepr::U32(U32::new(nodes.try_into().unwrap())),
             ValueType::U64 => BinaryRepr::U64(U64::new(nodes.try_into().unwrap())),
             ValueType::U128 => BinaryRepr::U128(U128::new(nodes.try_into().unwrap())),
+            ValueType::I8 => BinaryRepr::I8(I8::new(nodes.try_into().unwrap())),
+            ValueType::I16 => BinaryRepr::I16(I16::new(nodes.try_into().unwrap())),
+            ValueType::I32 => BinaryRepr::I32(I32::new(nodes.try_into().unwrap())),
+            ValueType::I64 => BinaryRepr::I64(I64::new(nodes.try_into().unwrap())),
+            ValueType::I128 => BinaryRepr::I128(I128::new(nodes.try_into().unwrap())),
             ValueType::Array(ty, _) => BinaryRepr::Array(
                 nodes
                     .chunks(ty.len())
@@ -506,6 +568,11 @@ impl Display for ValueType {
             ValueType::U32 => write!(f, "U32"),
             ValueType::U64 => write!(f, "U64"),
             ValueType::U128 => write!(f, "U128"),
+            ValueType::I8 => write!(f, "I8"),
+            ValueType::I16 => write!(f, "I16"),
+            ValueType::I32 => write!(f, "I32"),
+            ValueType::I64 => write!(f, "I64"),
+            ValueType::I128 => write!(f, "I128"),
             ValueType::Array(ty, len) => write!(f, "Array<{}, {}>", ty, len),
         }
     }
@@ -533,6 +600,11 @@ impl_value_type!(u16, U16);
 impl_value_type!(u32, U32);
 impl_value_type!(u64, U64);
 impl_value_type!(u128, U128);
+impl_value_type!(i8, I8);
+impl_value_type!(i16, I16);
+impl_value_type!(i32, I32);
+impl_value_type!(i64, I64);
+impl_value_type!(i128, I128);
 
 /// A value that can be encoded into a binary representation.
 #[derive(Debug, Clone, PartialEq)]
@@ -546,6 +618,11 @@ pub enum Value {
     U64(u64),
     U128(u128),
     Array(Vec<Value>),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
 }
 
 impl Value {
@@ -558,6 +635,11 @@ impl Value {
             ValueType::U32 => Value::U32(rng.gen()),
             ValueType::U64 => Value::U64(rng.gen()),
             ValueType::U128 => Value::U128(rng.gen()),
+            ValueType::I8 => Value::I8(rng.gen()),
+            ValueType::I16 => Value::I16(rng.gen()),
+            ValueType::I32 => Value::I32(rng.gen()),
+            ValueType::I64 => Value::I64(rng.gen()),
+            ValueType::I128 => Value::I128(rng.gen()),
             ValueType::Array(ty, len) => Value::Array(
                 (0..*len)
                     .map(|_| Value::random(rng, ty))
@@ -575,6 +657,11 @@ impl Value {
             Value::U32(_) => ValueType::U32,
             Value::U64(_) => ValueType::U64,
             Value::U128(_) => ValueType::U128,
+            Value::I8(_) => ValueType::I8,
+            Value::I16(_) => ValueType::I16,
+            Value::I32(_) => ValueType::I32,
+            Value::I64(_) => ValueType::I64,
+            Value::I128(_) => ValueType::I128,
             Value::Array(v) => ValueType::Array(Box::new(v[0].value_type()), v.len()),
         }
     }
@@ -592,6 +679,11 @@ impl IntoBits for Value {
             Value::U32(v) => v.into_lsb0_vec(),
             Value::U64(v) => v.into_lsb0_vec(),
             Value::U128(v) => v.into_lsb0_vec(),
+            Value::I8(v) => (v as u8).into_lsb0_vec(),
+            Value::I16(v) => (v as u16).into_lsb0_vec(),
+            Value::I32(v) => (v as u32).into_lsb0_vec(),
+            Value::I64(v) => (v as u64).into_lsb0_vec(),
+            Value::I128(v) => (v as u128).into_lsb0_vec(),
             Value::Array(v) => v.into_iter().flat_map(|v| v.into_iter_lsb0()).collect(),
         }
         .into_iter()
@@ -605,6 +697,11 @@ impl IntoBits for Value {
             Value::U32(v) => v.into_msb0_vec(),
             Value::U64(v) => v.into_msb0_vec(),
             Value::U128(v) => v.into_msb0_vec(),
+            Value::I8(v) => (v as u8).into_msb0_vec(),
+            Value::I16(v) => (v as u16).into_msb0_vec(),
+            Value::I32(v) => (v as u32).into_msb0_vec(),
+            Value::I64(v) => (v as u64).into_msb0_vec(),
+            Value::I128(v) => (v as u128).into_msb0_vec(),
             Value::Array(v) => v.into_iter().flat_map(|v| v.into_iter_msb0()).collect(),
         }
         .into_iter()
@@ -620,6 +717,11 @@ impl Display for Value {
             Value::U32(v) => write!(f, "U32({})", v),
             Value::U64(v) => write!(f, "U64({})", v),
             Value::U128(v) => write!(f, "U128({})", v),
+            Value::I8(v) => write!(f, "I8({})", v),
+            Value::I16(v) => write!(f, "I16({})", v),
+            Value::I32(v) => write!(f, "I32({})", v),
+            Value::I64(v) => write!(f, "I64({})", v),
+            Value::I128(v) => write!(f, "I128({})", v),
             Value::Array(v) => write!(f, "Array({:?})", v),
         }
     }
@@ -636,6 +738,11 @@ impl BitXor for Value {
             (Value::U32(a), Value::U32(b)) => Value::U32(a ^ b),
             (Value::U64(a), Value::U64(b)) => Value::U64(a ^ b),
             (Value::U128(a), Value::U128(b)) => Value::U128(a ^ b),
+            (Value::I8(a), Value::I8(b)) => Value::I8(a ^ b),
+            (Value::I16(a), Value::I16(b)) => Value::I16(a ^ b),
+            (Value::I32(a), Value::I32(b)) => Value::I32(a ^ b),
+            (Value::I64(a), Value::I64(b)) => Value::I64(a ^ b),
+            (Value::I128(a), Value::I128(b)) => Value::I128(a ^ b),
             (Value::Array(a), Value::Array(b)) => Value::Array(
                 a.iter()
                     .zip(b.iter())
@@ -663,6 +770,11 @@ impl BitXor<&Value> for &Value {
             (Value::U32(a), Value::U32(b)) => Value::U32(a ^ b),
             (Value::U64(a), Value::U64(b)) => Value::U64(a ^ b),
             (Value::U128(a), Value::U128(b)) => Value::U128(a ^ b),
+            (Value::I8(a), Value::I8(b)) => Value::I8(a ^ b),
+            (Value::I16(a), Value::I16(b)) => Value::I16(a ^ b),
+            (Value::I32(a), Value::I32(b)) => Value::I32(a ^ b),
+            (Value::I64(a), Value::I64(b)) => Value::I64(a ^ b),
+            (Value::I128(a), Value::I128(b)) => Value::I128(a ^ b),
             (Value::Array(a), Value::Array(b)) => Value::Array(
                 a.iter()
                     .zip(b.iter())
@@ -690,6 +802,11 @@ impl BitXor<&Value> for Value {
             (Value::U32(a), Value::U32(b)) => Value::U32(a ^ b),
             (Value::U64(a), Value::U64(b)) => Value::U64(a ^ b),
             (Value::U128(a), Value::U128(b)) => Value::U128(a ^ b),
+            (Value::I8(a), Value::I8(b)) => Value::I8(a ^ b),
+            (Value::I16(a), Value::I16(b)) => Value::I16(a ^ b),
+            (Value::I32(a), Value::I32(b)) => Value::I32(a ^ b),
+            (Value::I64(a), Value::I64(b)) => Value::I64(a ^ b),
+            (Value::I128(a), Value::I128(b)) => Value::I128(a ^ b),
             (Value::Array(a), Value::Array(b)) => Value::Array(
                 a.iter()
                     .zip(b.iter())
@@ -717,6 +834,11 @@ impl BitXor<Value> for &Value {
             (Value::U32(a), Value::U32(b)) => Value::U32(a ^ b),
             (Value::U64(a), Value::U64(b)) => Value::U64(a ^ b),
             (Value::U128(a), Value::U128(b)) => Value::U128(a ^ b),
+            (Value::I8(a), Value::I8(b)) => Value::I8(a ^ b),
+            (Value::I16(a), Value::I16(b)) => Value::I16(a ^ b),
+            (Value::I32(a), Value::I32(b)) => Value::I32(a ^ b),
+            (Value::I64(a), Value::I64(b)) => Value::I64(a ^ b),
+            (Value::I128(a), Value::I128(b)) => Value::I128(a ^ b),
             (Value::Array(a), Value::Array(b)) => Value::Array(
                 a.iter()
                     .zip(b.iter())
@@ -764,6 +886,11 @@ impl_convert_bytes!(U16, 2);
 impl_convert_bytes!(U32, 4);
 impl_convert_bytes!(U64, 8);
 impl_convert_bytes!(U128, 16);
+impl_convert_bytes!(I8, 1);
+impl_convert_bytes!(I16, 2);
+impl_convert_bytes!(I32, 4);
+impl_convert_bytes!(I64, 8);
+impl_convert_bytes!(I128, 16);
 
 #[cfg(test)]
 mod tests {
@@ -41,7 +41,14 @@ impl<const D: usize> LpnEncoder<D> {
 
     /// Compute 4 rows as a batch, this is for the `compute` function.
     #[inline]
-    fn compute_four_rows_indep(&self, y: &mut [Block], x: &[Block], pos: usize, prp: &Prp) {
+    fn compute_four_rows_indep<T: Copy>(
+        &self,
+        y: &mut [T],
+        x: &[T],
+        pos: usize,
+        prp: &Prp,
+        add: &impl Fn(T, T) -> T,
+    ) {
         let mut cnt = 0u64;
         let mut index: [Block; D] = std::array::from_fn(|_| {
             let i = cnt;
@@ -57,13 +64,20 @@ impl<const D: usize> LpnEncoder<D> {
                 *ind &= self.mask;
                 *ind = if *ind >= self.k { *ind - self.k } else { *ind };
 
-                *y ^= x[*ind as usize];
+                *y = add(*y, x[*ind as usize]);
             }
         }
     }
 
     #[inline]
-    fn compute_one_row(&self, y: &mut [Block], x: &[Block], pos: usize, prp: &Prp) {
+    fn compute_one_row<T: Copy>(
+        &self,
+        y: &mut [T],
+        x: &[T],
+        pos: usize,
+        prp: &Prp,
+        add: &impl Fn(T, T) -> T,
+    ) {
         let block_size = (D + 4 - 1) / 4;
         let mut index = (0..block_size)
             .map(|i| Block::from(bytemuck::cast::<_, [u8; 16]>([pos as u64, i as u64])))
@@ -74,7 +88,7 @@ impl<const D: usize> LpnEncoder<D> {
         for ind in index.iter_mut().take(D) {
             *ind &= self.mask;
             *ind = if *ind >= self.k { *ind - self.k } else { *ind };
-            y[pos] ^= x[*ind as usize];
+            y[pos] = add(y[pos], x[*ind as usize]);
         }
     }
 
@@ -89,6 +103,28 @@ impl<const D: usize> LpnEncoder<D> {
     ///
     /// Panics if `x.len() !=k` or `y.len() != n`.
     pub fn compute(&self, y: &mut [Block], x: &[Block]) {
+        self.compute_with(y, x, |a, b| a ^ b)
+    }
+
+    /// Compute `Ax + e` over an additive group, writing the result in-place into `y`.
+    ///
+    /// This is the same as [`LpnEncoder::compute`], except that the binary matrix `A` is applied
+    /// using the provided addition, eg. the addition of a finite field.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Secret vector with length `k`.
+    /// * `y` - Error vector with length `n`, this is actually `e` in LPN.
+    /// * `add` - The addition of the group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len() !=k` or `y.len() != n`.
+    pub fn compute_with<T, F>(&self, y: &mut [T], x: &[T], add: F)
+    where
+        T: Copy + Send + Sync,
+        F: Fn(T, T) -> T + Sync,
+    {
         assert_eq!(x.len() as u32, self.k);
         assert!(x.len() >= D);
         let prp = Prp::new(self.seed);
@@ -103,11 +139,11 @@ impl<const D: usize> LpnEncoder<D> {
         }
 
         iter.for_each(|(i, y)| {
-            self.compute_four_rows_indep(y, x, i * 4, &prp);
+            self.compute_four_rows_indep(y, x, i * 4, &prp, &add);
         });
 
         for i in size..y.len() {
-            self.compute_one_row(y, x, i, &prp);
+            self.compute_one_row(y, x, i, &prp, &add);
         }
     }
 }
@@ -125,6 +161,11 @@ pub struct LpnParameters {
 
 impl LpnParameters {
     /// Create a new LpnParameters instance.
+    ///
+    /// Only checks that `t <= n`, the parameters are not validated for any LPN type.
+    #[deprecated(
+        note = "use `mpz_ot_core::ferret::params::new_lpn_parameters`, which validates the parameters"
+    )]
     pub fn new(n: usize, k: usize, t: usize) -> Self {
         assert!(t <= n);
         LpnParameters { n, k, t }
@@ -195,7 +236,7 @@ mod tests {
             }
 
             for i in batch_size * 4..y.len() {
-                self.compute_one_row(y, x, i, &prp);
+                self.compute_one_row(y, x, i, &prp, &|a, b| a ^ b);
             }
         }
     }
@@ -0,0 +1,82 @@
+//! Private set intersection cardinality.
+//!
+//! The receiver learns the size of the intersection and nothing else, using a Diffie-Hellman
+//! based OPRF over the Ristretto group:
+//!
+//! 1. The receiver sends its hashed items, masked with its secret `a`.
+//! 2. The sender masks them with its secret `b` and shuffles them, and sends them back along
+//!    with its own hashed items masked with `b`, also shuffled.
+//! 3. The receiver removes its mask `a`, and counts the items which are in both sets.
+//!
+//! Contrary to the KKRT based PSI, the receiver can not link its own items to their OPRF
+//! outputs, as they are shuffled by the sender.
+
+pub mod msgs;
+mod receiver;
+mod sender;
+
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+use curve25519_dalek::RistrettoPoint;
+use mpz_core::Block;
+
+/// Hashes an item to a point of the Ristretto group.
+pub(crate) fn hash_to_point(item: Block) -> RistrettoPoint {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&item.to_bytes());
+
+    let mut bytes = [0u8; 64];
+    hasher.finalize_xof().fill(&mut bytes);
+
+    RistrettoPoint::from_uniform_bytes(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use futures_util::StreamExt;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+    use utils_aio::duplex::MemoryDuplex;
+
+    #[rstest]
+    #[case::small(10, 10, 5)]
+    #[case::unbalanced(200, 20, 10)]
+    #[case::disjoint(20, 20, 0)]
+    #[case::empty(0, 20, 0)]
+    #[tokio::test]
+    async fn test_psi_cardinality(
+        #[case] sender_count: usize,
+        #[case] receiver_count: usize,
+        #[case] common: usize,
+    ) {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let common_items = (0..common).map(|_| rng.gen()).collect::<Vec<Block>>();
+
+        let mut sender_items = common_items.clone();
+        sender_items.extend((common..sender_count).map(|_| rng.gen::<Block>()));
+
+        let mut receiver_items = common_items;
+        receiver_items.extend((common..receiver_count).map(|_| rng.gen::<Block>()));
+
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let mut sender = Sender::new();
+        let mut receiver = Receiver::new();
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &sender_items),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &receiver_items)
+        );
+
+        sender_res.unwrap();
+
+        assert_eq!(receiver_res.unwrap(), common);
+    }
+}
@@ -0,0 +1,36 @@
+//! Messages for the PSI cardinality protocol.
+
+use curve25519_dalek::RistrettoPoint;
+use enum_try_as_inner::EnumTryAsInner;
+use serde::{Deserialize, Serialize};
+
+/// A PSI cardinality protocol message.
+#[derive(Debug, Clone, EnumTryAsInner, Serialize, Deserialize)]
+#[derive_err(Debug)]
+#[allow(missing_docs)]
+pub enum Message {
+    ReceiverItems(ReceiverItems),
+    SenderPayload(SenderPayload),
+}
+
+impl From<MessageError> for std::io::Error {
+    fn from(err: MessageError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+/// The receiver's items, masked with the receiver's secret.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiverItems {
+    /// The masked items.
+    pub items: Vec<RistrettoPoint>,
+}
+
+/// The sender's payload.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SenderPayload {
+    /// The receiver's items, additionally masked with the sender's secret, in a random order.
+    pub receiver_items: Vec<RistrettoPoint>,
+    /// The sender's items, masked with the sender's secret, in a random order.
+    pub sender_items: Vec<RistrettoPoint>,
+}
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use curve25519_dalek::Scalar;
+use futures_util::SinkExt;
+use rand::thread_rng;
+use utils_aio::{
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use crate::{
+    cardinality::{
+        hash_to_point,
+        msgs::{Message, ReceiverItems, SenderPayload},
+    },
+    hashing::dedup,
+    Item, ReceiverError,
+};
+
+/// PSI cardinality receiver.
+///
+/// The receiver learns the size of the intersection.
+#[derive(Debug, Default)]
+pub struct Receiver {}
+
+impl Receiver {
+    /// Creates a new receiver.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Runs the protocol with the receiver's set, returning the size of the intersection.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender.
+    /// * `stream` - The stream to receive messages from the sender.
+    /// * `items` - The receiver's set.
+    pub async fn receive<
+        T: Item + Sync,
+        Si: IoSink<Message> + Send + Unpin,
+        St: IoStream<Message> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        items: &[T],
+    ) -> Result<usize, ReceiverError> {
+        let secret = Scalar::random(&mut thread_rng());
+
+        let mut items = items.iter().map(Item::to_block).collect::<Vec<_>>();
+        dedup(&mut items);
+
+        let count = items.len();
+        let masked_items = items
+            .into_iter()
+            .map(|item| hash_to_point(item) * secret)
+            .collect();
+
+        sink.send(Message::ReceiverItems(ReceiverItems {
+            items: masked_items,
+        }))
+        .await?;
+
+        let SenderPayload {
+            receiver_items,
+            sender_items,
+        } = stream.expect_next().await?.try_into_sender_payload()?;
+
+        if receiver_items.len() != count {
+            return Err(ReceiverError::InvalidPayload(format!(
+                "expected {} items, got {}",
+                count,
+                receiver_items.len()
+            )));
+        }
+
+        let sender_items = sender_items
+            .into_iter()
+            .map(|point| point.compress().to_bytes())
+            .collect::<HashSet<_>>();
+
+        // Remove the receiver's mask, leaving its items masked with the sender's secret only.
+        let inverse = secret.invert();
+
+        Ok(receiver_items
+            .into_iter()
+            .filter(|point| sender_items.contains(&(point * inverse).compress().to_bytes()))
+            .count())
+    }
+}
@@ -0,0 +1,77 @@
+use curve25519_dalek::Scalar;
+use futures_util::SinkExt;
+use rand::{seq::SliceRandom, thread_rng};
+use utils_aio::{
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use crate::{
+    cardinality::{
+        hash_to_point,
+        msgs::{Message, ReceiverItems, SenderPayload},
+    },
+    hashing::dedup,
+    Item, SenderError,
+};
+
+/// PSI cardinality sender.
+///
+/// The sender learns nothing about the intersection.
+#[derive(Debug, Default)]
+pub struct Sender {}
+
+impl Sender {
+    /// Creates a new sender.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Runs the protocol with the sender's set.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    /// * `items` - The sender's set.
+    pub async fn send<
+        T: Item + Sync,
+        Si: IoSink<Message> + Send + Unpin,
+        St: IoStream<Message> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        items: &[T],
+    ) -> Result<(), SenderError> {
+        let ReceiverItems {
+            items: receiver_items,
+        } = stream.expect_next().await?.try_into_receiver_items()?;
+
+        let secret = Scalar::random(&mut thread_rng());
+
+        let mut items = items.iter().map(Item::to_block).collect::<Vec<_>>();
+        dedup(&mut items);
+
+        let mut receiver_items = receiver_items
+            .into_iter()
+            .map(|point| point * secret)
+            .collect::<Vec<_>>();
+        let mut sender_items = items
+            .into_iter()
+            .map(|item| hash_to_point(item) * secret)
+            .collect::<Vec<_>>();
+
+        // Shuffle both sets so the receiver can not link the intersection to its items.
+        receiver_items.shuffle(&mut thread_rng());
+        sender_items.shuffle(&mut thread_rng());
+
+        sink.send(Message::SenderPayload(SenderPayload {
+            receiver_items,
+            sender_items,
+        }))
+        .await?;
+
+        Ok(())
+    }
+}
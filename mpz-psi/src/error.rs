@@ -0,0 +1,55 @@
+use crate::msgs::MessageError;
+
+/// A PSI sender error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+    #[error(transparent)]
+    OprfError(#[from] mpz_ot::kkrt::SenderError),
+    #[error("invalid setup: {0}")]
+    InvalidSetup(String),
+}
+
+impl<T> From<MessageError<T>> for SenderError {
+    fn from(err: MessageError<T>) -> Self {
+        SenderError::from(std::io::Error::from(err))
+    }
+}
+
+/// A PSI receiver error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+    #[error(transparent)]
+    OprfError(#[from] mpz_ot::kkrt::ReceiverError),
+    #[error("cuckoo hashing failed after {0} attempts")]
+    CuckooHashError(usize),
+    #[error("invalid payload: {0}")]
+    InvalidPayload(String),
+}
+
+impl<T> From<MessageError<T>> for ReceiverError {
+    fn from(err: MessageError<T>) -> Self {
+        ReceiverError::from(std::io::Error::from(err))
+    }
+}
+
+impl From<crate::cardinality::msgs::MessageError> for SenderError {
+    fn from(err: crate::cardinality::msgs::MessageError) -> Self {
+        SenderError::from(std::io::Error::from(err))
+    }
+}
+
+impl From<crate::cardinality::msgs::MessageError> for ReceiverError {
+    fn from(err: crate::cardinality::msgs::MessageError) -> Self {
+        ReceiverError::from(std::io::Error::from(err))
+    }
+}
@@ -0,0 +1,118 @@
+//! Cuckoo and simple hashing of items into bins.
+
+use mpz_core::{
+    aes::{AesEncryptor, FIXED_KEY_AES},
+    prg::Prg,
+    Block,
+};
+use mpz_ot_core::ferret::{CUCKOO_HASH_NUM, CUCKOO_TRIAL_NUM};
+use rand::SeedableRng;
+
+/// Returns the number of bins for a cuckoo table with `count` items.
+pub(crate) fn table_length(count: usize) -> usize {
+    // Same expansion factor as the cuckoo hashing of MPCOT, m = 1.5 * t.
+    (3 * count).div_ceil(2).max(1)
+}
+
+/// Encodes an item with the index of the hash function which assigned it to its bin.
+///
+/// This prevents an item assigned with one hash function from matching a different item
+/// assigned to the same bin with another hash function.
+pub(crate) fn encode(item: Block, hash_index: usize) -> Block {
+    FIXED_KEY_AES.tccr(Block::new((hash_index as u128).to_le_bytes()), item)
+}
+
+/// Removes duplicate items, which would otherwise reveal their multiplicity.
+pub(crate) fn dedup(items: &mut Vec<Block>) {
+    let mut seen = std::collections::HashSet::with_capacity(items.len());
+    items.retain(|item| seen.insert(item.to_bytes()));
+}
+
+/// The hash functions which assign items to bins.
+pub(crate) struct BinHasher {
+    hashes: [AesEncryptor; CUCKOO_HASH_NUM],
+    bins: usize,
+}
+
+impl BinHasher {
+    /// Creates new hash functions from a seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the hash functions.
+    /// * `bins` - The number of bins.
+    pub(crate) fn new(seed: Block, bins: usize) -> Self {
+        let mut prg = Prg::from_seed(seed);
+        Self {
+            hashes: std::array::from_fn(|_| AesEncryptor::new(prg.random_block())),
+            bins,
+        }
+    }
+
+    /// Returns the bin of an item for the given hash function.
+    pub(crate) fn bin(&self, hash_index: usize, item: Block) -> usize {
+        let hash = self.hashes[hash_index].encrypt_block(item);
+        (u128::from_le_bytes(hash.to_bytes()) % self.bins as u128) as usize
+    }
+
+    /// Returns the candidate bins of an item, one for each hash function (simple hashing).
+    pub(crate) fn bins(&self, item: Block) -> [usize; CUCKOO_HASH_NUM] {
+        std::array::from_fn(|hash_index| self.bin(hash_index, item))
+    }
+
+    /// Inserts distinct items into a cuckoo table, returning for each bin the index of the item
+    /// and the index of the hash function which assigned it, if any.
+    ///
+    /// Returns `None` if the insertion fails, in which case new hash functions should be sampled.
+    pub(crate) fn cuckoo(&self, items: &[Block]) -> Option<Vec<Option<(usize, usize)>>> {
+        let mut table = vec![None; self.bins];
+
+        'items: for index in 0..items.len() {
+            let mut entry = (index, 0);
+            for _ in 0..CUCKOO_TRIAL_NUM {
+                let pos = self.bin(entry.1, items[entry.0]);
+
+                match table[pos].replace(entry) {
+                    Some((evicted, hash_index)) => {
+                        entry = (evicted, (hash_index + 1) % CUCKOO_HASH_NUM);
+                    }
+                    None => continue 'items,
+                }
+            }
+
+            return None;
+        }
+
+        Some(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cuckoo() {
+        let mut prg = Prg::from_seed(Block::ZERO);
+        let items = (0..1000).map(|_| prg.random_block()).collect::<Vec<_>>();
+
+        let hasher = BinHasher::new(Block::ONES, table_length(items.len()));
+        let table = hasher.cuckoo(&items).unwrap();
+
+        let mut inserted = table
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, entry)| {
+                entry.map(|(index, hash_index)| {
+                    // Each item is in one of its candidate bins.
+                    assert_eq!(hasher.bin(hash_index, items[index]), pos);
+                    assert!(hasher.bins(items[index]).contains(&pos));
+                    index
+                })
+            })
+            .collect::<Vec<_>>();
+        inserted.sort();
+
+        assert_eq!(inserted, (0..items.len()).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,47 @@
+use mpz_core::Block;
+
+/// An item of a set.
+pub trait Item {
+    /// Returns the block which represents the item in the protocols.
+    ///
+    /// Distinct items must be represented by distinct blocks.
+    fn to_block(&self) -> Block;
+}
+
+impl Item for Block {
+    fn to_block(&self) -> Block {
+        *self
+    }
+}
+
+impl Item for [u8] {
+    fn to_block(&self) -> Block {
+        // Byte strings are compressed with a collision resistant hash.
+        let hash = mpz_core::utils::blake3(self);
+        Block::try_from(&hash[..Block::LEN]).expect("slice should be 16 bytes")
+    }
+}
+
+impl Item for Vec<u8> {
+    fn to_block(&self) -> Block {
+        self.as_slice().to_block()
+    }
+}
+
+impl Item for str {
+    fn to_block(&self) -> Block {
+        self.as_bytes().to_block()
+    }
+}
+
+impl Item for String {
+    fn to_block(&self) -> Block {
+        self.as_bytes().to_block()
+    }
+}
+
+impl<T: Item + ?Sized> Item for &T {
+    fn to_block(&self) -> Block {
+        (**self).to_block()
+    }
+}
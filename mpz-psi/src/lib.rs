@@ -0,0 +1,190 @@
+//! Two-party private set intersection.
+//!
+//! Provides two protocols, both only secure against semi-honest adversaries:
+//!
+//! - [`Sender`] and [`Receiver`] compute the intersection, which is learned by the receiver.
+//!   The receiver assigns its items to bins with cuckoo hashing, and evaluates a
+//!   [`KKRT`](mpz_ot::kkrt) OPRF on the item of each bin. The sender assigns its items to every
+//!   candidate bin (simple hashing), and sends the OPRF evaluations of its items.
+//! - [`cardinality::Sender`] and [`cardinality::Receiver`] compute the size of the intersection,
+//!   which is learned by the receiver, with a Diffie-Hellman based OPRF.
+//!
+//! In both protocols, the size of each set is revealed to the other party.
+
+#![deny(missing_docs, unreachable_pub, unused_must_use)]
+#![deny(unsafe_code)]
+#![deny(clippy::all)]
+
+pub mod cardinality;
+mod error;
+mod hashing;
+mod item;
+pub mod msgs;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+pub use item::Item;
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+use futures_util::{SinkExt, StreamExt};
+use mpz_ot::kkrt::msgs::Message as OprfMessage;
+use utils_aio::{sink::IoSink, stream::IoStream};
+
+/// Converts a sink of PSI messages into a sink of OPRF messages.
+pub(crate) fn into_oprf_sink<'a, Si: IoSink<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    sink: &'a mut Si,
+) -> impl IoSink<OprfMessage<T>> + Send + Unpin + 'a {
+    Box::pin(SinkExt::with(sink, |msg| async move {
+        Ok(msgs::Message::Oprf(msg))
+    }))
+}
+
+/// Converts a stream of PSI messages into a stream of OPRF messages.
+pub(crate) fn into_oprf_stream<'a, St: IoStream<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    stream: &'a mut St,
+) -> impl IoStream<OprfMessage<T>> + Send + Unpin + 'a {
+    StreamExt::map(stream, |msg| match msg {
+        Ok(msg) => msg.try_into_oprf().map_err(From::from),
+        Err(err) => Err(err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use mpz_core::Block;
+    use mpz_ot::{
+        ideal::{ideal_ot_pair, IdealOTReceiver, IdealOTSender},
+        kkrt,
+    };
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+    use utils_aio::duplex::MemoryDuplex;
+
+    /// Returns two sets of `sender_count` and `receiver_count` items, with `common` items in
+    /// common.
+    fn sets(sender_count: usize, receiver_count: usize, common: usize) -> (Vec<Block>, Vec<Block>) {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let common_items = (0..common).map(|_| rng.gen()).collect::<Vec<Block>>();
+
+        let mut sender_items = common_items.clone();
+        sender_items.extend((common..sender_count).map(|_| rng.gen::<Block>()));
+
+        let mut receiver_items = (common..receiver_count)
+            .map(|_| rng.gen())
+            .collect::<Vec<Block>>();
+        receiver_items.extend(common_items);
+
+        (sender_items, receiver_items)
+    }
+
+    async fn run<T: Item + Clone + Send + Sync>(
+        sender_items: &[T],
+        receiver_items: &[T],
+    ) -> Vec<T> {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (base_sender, base_receiver) = ideal_ot_pair::<Block>();
+
+        let mut sender: Sender<IdealOTReceiver<Block>> =
+            Sender::new(kkrt::Sender::new(base_receiver));
+        let mut receiver: Receiver<IdealOTSender<Block>> =
+            Receiver::new(kkrt::Receiver::new(base_sender));
+
+        let (sender_res, receiver_res) = tokio::join!(
+            async {
+                sender.setup(&mut sender_sink, &mut sender_stream).await?;
+                sender
+                    .send(&mut sender_sink, &mut sender_stream, sender_items)
+                    .await
+            },
+            async {
+                receiver
+                    .setup(&mut receiver_sink, &mut receiver_stream)
+                    .await?;
+                receiver
+                    .receive(&mut receiver_sink, &mut receiver_stream, receiver_items)
+                    .await
+            }
+        );
+
+        sender_res.unwrap();
+        receiver_res.unwrap()
+    }
+
+    #[rstest]
+    #[case::small(10, 10, 5)]
+    #[case::unbalanced(1000, 100, 50)]
+    #[case::disjoint(100, 100, 0)]
+    #[case::subset(100, 1000, 100)]
+    #[case::empty(0, 100, 0)]
+    #[tokio::test]
+    async fn test_psi(
+        #[case] sender_count: usize,
+        #[case] receiver_count: usize,
+        #[case] common: usize,
+    ) {
+        let (sender_items, receiver_items) = sets(sender_count, receiver_count, common);
+
+        let intersection = run(&sender_items, &receiver_items).await;
+
+        assert_eq!(intersection, receiver_items[receiver_count - common..]);
+    }
+
+    #[tokio::test]
+    async fn test_psi_bytes() {
+        let sender_items = ["alice", "bob", "carol", "dave"]
+            .map(|item| item.as_bytes().to_vec())
+            .to_vec();
+        let receiver_items = ["erin", "bob", "frank", "alice", "bob"]
+            .map(|item| item.as_bytes().to_vec())
+            .to_vec();
+
+        let intersection = run(&sender_items, &receiver_items).await;
+
+        assert_eq!(
+            intersection,
+            ["bob", "alice", "bob"].map(|item| item.as_bytes().to_vec())
+        );
+    }
+
+    #[rstest]
+    #[case::no_bins(0)]
+    #[case::too_many_bins(usize::MAX)]
+    #[tokio::test]
+    async fn test_psi_invalid_bins(#[case] bins: usize) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, _receiver_stream) = receiver_channel.split();
+
+        let (_, base_receiver) = ideal_ot_pair::<Block>();
+
+        let mut sender: Sender<IdealOTReceiver<Block>> =
+            Sender::new(kkrt::Sender::new(base_receiver));
+
+        receiver_sink
+            .send(msgs::Message::Hashing(msgs::Hashing {
+                seed: Block::ZERO,
+                bins,
+            }))
+            .await
+            .unwrap();
+
+        let (sender_items, _) = sets(10, 0, 0);
+
+        assert!(matches!(
+            sender
+                .send(&mut sender_sink, &mut sender_stream, &sender_items)
+                .await,
+            Err(SenderError::InvalidSetup(_))
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+//! Messages for the PSI protocol.
+
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_core::Block;
+use mpz_ot::kkrt::msgs::Message as OprfMessage;
+use serde::{Deserialize, Serialize};
+
+/// A PSI protocol message.
+#[derive(Debug, Clone, EnumTryAsInner, Serialize, Deserialize)]
+#[derive_err(Debug)]
+#[allow(missing_docs)]
+pub enum Message<BaseMsg> {
+    Oprf(OprfMessage<BaseMsg>),
+    Hashing(Hashing),
+    Evaluations(Evaluations),
+}
+
+impl<BaseMsg> From<MessageError<BaseMsg>> for std::io::Error {
+    fn from(err: MessageError<BaseMsg>) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+/// The hashing parameters chosen by the receiver.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hashing {
+    /// The seed of the hash functions.
+    pub seed: Block,
+    /// The number of bins.
+    pub bins: usize,
+}
+
+/// The OPRF evaluations of the sender's items, in a random order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evaluations {
+    /// The evaluations, one per item and hash function.
+    pub evaluations: Vec<Block>,
+}
@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+
+use futures_util::SinkExt;
+use mpz_core::{Block, ProtocolMessage};
+use mpz_ot::{kkrt, OTSender, OTSetup};
+use rand::{thread_rng, Rng};
+use utils_aio::{
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use crate::{
+    hashing::{dedup, encode, table_length, BinHasher},
+    into_oprf_sink, into_oprf_stream,
+    msgs::{Evaluations, Hashing, Message},
+    Item, ReceiverError,
+};
+
+/// The number of times the receiver samples new hash functions if the cuckoo hashing fails.
+const CUCKOO_ATTEMPTS: usize = 10;
+
+/// PSI receiver.
+///
+/// The receiver learns which of its items are in the intersection.
+#[derive(Debug)]
+pub struct Receiver<BaseOT> {
+    oprf: kkrt::Receiver<BaseOT>,
+}
+
+impl<BaseOT> Receiver<BaseOT>
+where
+    BaseOT: OTSetup + OTSender<[Block; 2]> + Send,
+{
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `oprf` - The KKRT OPRF receiver.
+    pub fn new(oprf: kkrt::Receiver<BaseOT>) -> Self {
+        Self { oprf }
+    }
+
+    /// Performs the setup of the OPRF.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender.
+    /// * `stream` - The stream to receive messages from the sender.
+    pub async fn setup<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), ReceiverError> {
+        self.oprf
+            .setup(&mut into_oprf_sink(sink), &mut into_oprf_stream(stream))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Computes the intersection with the sender's set, returning the receiver's items which are
+    /// in the intersection, in their original order.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender.
+    /// * `stream` - The stream to receive messages from the sender.
+    /// * `items` - The receiver's set.
+    pub async fn receive<
+        T: Item + Clone + Sync,
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        items: &[T],
+    ) -> Result<Vec<T>, ReceiverError> {
+        let blocks = items.iter().map(Item::to_block).collect::<Vec<_>>();
+
+        let mut distinct = blocks.clone();
+        dedup(&mut distinct);
+
+        // Sample hash functions until the cuckoo hashing succeeds.
+        let bins = table_length(distinct.len());
+        let (seed, table) = (0..CUCKOO_ATTEMPTS)
+            .find_map(|_| {
+                let seed: Block = thread_rng().gen();
+                BinHasher::new(seed, bins)
+                    .cuckoo(&distinct)
+                    .map(|table| (seed, table))
+            })
+            .ok_or(ReceiverError::CuckooHashError(CUCKOO_ATTEMPTS))?;
+
+        sink.send(Message::Hashing(Hashing { seed, bins })).await?;
+
+        // Empty bins are filled with random inputs.
+        let inputs = table
+            .iter()
+            .map(|entry| match entry {
+                Some((index, hash_index)) => encode(distinct[*index], *hash_index),
+                None => thread_rng().gen(),
+            })
+            .collect::<Vec<_>>();
+
+        let keys = self
+            .oprf
+            .extend(
+                &mut into_oprf_sink(sink),
+                &mut into_oprf_stream(stream),
+                &inputs,
+            )
+            .await?;
+
+        let Evaluations { evaluations } = stream.expect_next().await?.try_into_evaluations()?;
+
+        let evaluations = evaluations
+            .into_iter()
+            .map(|evaluation| evaluation.to_bytes())
+            .collect::<HashSet<_>>();
+
+        let intersection = table
+            .iter()
+            .zip(keys.outputs())
+            .filter_map(|(entry, output)| {
+                entry
+                    .filter(|_| evaluations.contains(&output.to_bytes()))
+                    .map(|(index, _)| distinct[index].to_bytes())
+            })
+            .collect::<HashSet<_>>();
+
+        Ok(items
+            .iter()
+            .zip(blocks)
+            .filter(|(_, block)| intersection.contains(&block.to_bytes()))
+            .map(|(item, _)| item.clone())
+            .collect())
+    }
+}
+
+impl<BaseOT> ProtocolMessage for Receiver<BaseOT>
+where
+    BaseOT: ProtocolMessage,
+{
+    type Msg = Message<BaseOT::Msg>;
+}
@@ -0,0 +1,142 @@
+use futures_util::SinkExt;
+use mpz_core::{Block, ProtocolMessage};
+use mpz_ot::{kkrt, OTReceiver, OTSetup};
+use rand::{seq::SliceRandom, thread_rng};
+use utils_aio::{
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use crate::{
+    hashing::{dedup, encode, table_length, BinHasher},
+    into_oprf_sink, into_oprf_stream,
+    msgs::{Evaluations, Hashing, Message},
+    Item, SenderError,
+};
+
+/// The maximum ratio between the size of the receiver's set and the size of the sender's set.
+const MAX_SET_SIZE_RATIO: usize = 1 << 10;
+
+/// PSI sender.
+///
+/// The sender evaluates the OPRF on its items, and learns nothing about the intersection.
+///
+/// The sender rejects receivers whose set is more than 1024 times larger than its own.
+#[derive(Debug)]
+pub struct Sender<BaseOT> {
+    oprf: kkrt::Sender<BaseOT>,
+}
+
+impl<BaseOT> Sender<BaseOT>
+where
+    BaseOT: OTSetup + OTReceiver<bool, Block> + Send,
+{
+    /// Creates a new sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `oprf` - The KKRT OPRF sender.
+    pub fn new(oprf: kkrt::Sender<BaseOT>) -> Self {
+        Self { oprf }
+    }
+
+    /// Performs the setup of the OPRF.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    pub async fn setup<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), SenderError> {
+        self.oprf
+            .setup(&mut into_oprf_sink(sink), &mut into_oprf_stream(stream))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sends the OPRF evaluations of the sender's items to the receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    /// * `items` - The sender's set.
+    pub async fn send<
+        T: Item + Sync,
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        items: &[T],
+    ) -> Result<(), SenderError> {
+        let Hashing { seed, bins } = stream.expect_next().await?.try_into_hashing()?;
+
+        let mut items = items.iter().map(Item::to_block).collect::<Vec<_>>();
+        dedup(&mut items);
+
+        if bins == 0 {
+            return Err(SenderError::InvalidSetup(
+                "the number of bins must be non-zero".to_string(),
+            ));
+        }
+
+        let max_bins = table_length(items.len().max(1).saturating_mul(MAX_SET_SIZE_RATIO));
+        if bins > max_bins {
+            return Err(SenderError::InvalidSetup(format!(
+                "expected at most {} bins, got {}",
+                max_bins, bins
+            )));
+        }
+
+        let keys = self
+            .oprf
+            .extend(&mut into_oprf_sink(sink), &mut into_oprf_stream(stream))
+            .await?;
+
+        if keys.len() != bins {
+            return Err(SenderError::InvalidSetup(format!(
+                "expected {} OPRF instances, got {}",
+                bins,
+                keys.len()
+            )));
+        }
+
+        // Evaluate the OPRF of each candidate bin of each item.
+        let hasher = BinHasher::new(seed, bins);
+        let mut evaluations = items
+            .into_iter()
+            .flat_map(|item| {
+                hasher
+                    .bins(item)
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(hash_index, bin)| (bin, encode(item, hash_index)))
+            })
+            .map(|(bin, input)| keys.eval(bin, input))
+            .collect::<Vec<_>>();
+
+        // Shuffle the evaluations so they can not be linked to the items.
+        evaluations.shuffle(&mut thread_rng());
+
+        sink.send(Message::Evaluations(Evaluations { evaluations }))
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<BaseOT> ProtocolMessage for Sender<BaseOT>
+where
+    BaseOT: ProtocolMessage,
+{
+    type Msg = Message<BaseOT::Msg>;
+}
@@ -0,0 +1,87 @@
+use derive_builder::Builder;
+use mpz_core::lpn::LpnParameters;
+
+use crate::ferret::{params, LpnType};
+
+/// Ferret configuration.
+///
+/// The sender and receiver must use the same configuration.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct FerretConfig {
+    /// The LPN parameters.
+    lpn_parameters: LpnParameters,
+    /// The LPN type.
+    #[builder(default = "LpnType::Regular")]
+    lpn_type: LpnType,
+    /// Whether to skip the SPCOT consistency check.
+    #[builder(setter(custom), default = "false")]
+    semi_honest: bool,
+}
+
+impl FerretConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(lpn_parameters) = &self.lpn_parameters {
+            params::validate(lpn_parameters, self.lpn_type.unwrap_or(LpnType::Regular))
+                .map_err(|err| err.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Skips the SPCOT consistency check, which is only secure against a semi-honest sender.
+    pub fn semi_honest(&mut self) -> &mut Self {
+        self.semi_honest = Some(true);
+        self
+    }
+}
+
+impl FerretConfig {
+    /// Creates a new builder for FerretConfig.
+    pub fn builder() -> FerretConfigBuilder {
+        FerretConfigBuilder::default()
+    }
+
+    /// Returns the LPN parameters.
+    pub fn lpn_parameters(&self) -> LpnParameters {
+        self.lpn_parameters
+    }
+
+    /// Returns the LPN type.
+    pub fn lpn_type(&self) -> LpnType {
+        self.lpn_type
+    }
+
+    /// Returns whether the SPCOT consistency check is skipped.
+    pub fn semi_honest(&self) -> bool {
+        self.semi_honest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_core::lpn::LpnParameters;
+
+    use super::*;
+
+    #[test]
+    fn test_config_validates_lpn_parameters() {
+        let lpn_parameters = LpnParameters {
+            n: 9600,
+            k: 1220,
+            t: 700,
+        };
+
+        assert!(FerretConfig::builder()
+            .lpn_parameters(lpn_parameters)
+            .lpn_type(LpnType::Regular)
+            .build()
+            .is_err());
+
+        assert!(FerretConfig::builder()
+            .lpn_parameters(lpn_parameters)
+            .lpn_type(LpnType::Uniform)
+            .build()
+            .is_ok());
+    }
+}
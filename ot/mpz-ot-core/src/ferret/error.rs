@@ -9,3 +9,15 @@ pub struct SenderError(pub String);
 #[derive(Debug, thiserror::Error)]
 #[error("invalid input: expected {0}")]
 pub struct ReceiverError(pub String);
+
+/// Errors that can occur when validating LPN parameters.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum LpnParametersError {
+    #[error("invalid LPN parameters: t = {t} should be in 1..={n}")]
+    InvalidWeight { n: usize, t: usize },
+    #[error("invalid LPN parameters: k = {k} should be in 1..{n}")]
+    InvalidDimension { n: usize, k: usize },
+    #[error("invalid LPN parameters: n = {n} should be divisible by t = {t} for regular noise")]
+    NotRegular { n: usize, t: usize },
+}
@@ -1,15 +1,18 @@
 //! An implementation of the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) protocol.
 
-use mpz_core::lpn::LpnParameters;
-
+mod config;
 pub mod cuckoo;
 pub mod error;
 pub mod mpcot;
 pub mod msgs;
+pub mod params;
 pub mod receiver;
 pub mod sender;
 pub mod spcot;
 
+pub use config::{FerretConfig, FerretConfigBuilder, FerretConfigBuilderError};
+pub use params::{LPN_PARAMETERS_REGULAR, LPN_PARAMETERS_UNIFORM};
+
 /// Computational security parameter
 pub const CSP: usize = 128;
 
@@ -19,23 +22,8 @@ pub const CUCKOO_HASH_NUM: usize = 3;
 /// Trial numbers in Cuckoo hash insertion.
 pub const CUCKOO_TRIAL_NUM: usize = 100;
 
-/// LPN parameters with regular noise.
-/// Derived from https://github.com/emp-toolkit/emp-ot/blob/master/emp-ot/ferret/constants.h
-pub const LPN_PARAMETERS_REGULAR: LpnParameters = LpnParameters {
-    n: 10180608,
-    k: 124000,
-    t: 4971,
-};
-
-/// LPN parameters with uniform noise.
-/// Derived from Table 2.
-pub const LPN_PARAMETERS_UNIFORM: LpnParameters = LpnParameters {
-    n: 10616092,
-    k: 588160,
-    t: 1324,
-};
-
 /// The type of Lpn parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LpnType {
     /// Uniform error distribution.
     Uniform,
@@ -46,20 +34,15 @@ pub enum LpnType {
 #[cfg(test)]
 mod tests {
     use super::{
-        msgs::LpnMatrixSeed, receiver::Receiver as FerretReceiver, sender::Sender as FerretSender,
-        LpnType,
+        mpcot::sender_regular::Sender as RegularSender, msgs::LpnMatrixSeed,
+        params::LPN_PARAMETERS_TEST, receiver::Receiver as FerretReceiver,
+        sender::Sender as FerretSender, FerretConfig, LpnType, CSP,
     };
     use crate::ideal::{
         ideal_cot::{CotMsgForReceiver, CotMsgForSender, IdealCOT},
         ideal_mpcot::{IdealMpcot, MpcotMsgForReceiver, MpcotMsgForSender},
     };
-    use mpz_core::{lpn::LpnParameters, prg::Prg};
-
-    const LPN_PARAMETERS_TEST: LpnParameters = LpnParameters {
-        n: 9600,
-        k: 1220,
-        t: 600,
-    };
+    use mpz_core::prg::Prg;
 
     #[test]
     fn ferret_test() {
@@ -71,6 +54,14 @@ mod tests {
         let sender = FerretSender::new();
         let receiver = FerretReceiver::new();
 
+        // The ideal MPCOT consumes no COTs, so nothing is reserved for it.
+        let config = FerretConfig::builder()
+            .lpn_parameters(LPN_PARAMETERS_TEST)
+            .lpn_type(LpnType::Regular)
+            .semi_honest()
+            .build()
+            .unwrap();
+
         // Invoke Ideal COT to init the Ferret setup phase.
         let (sender_cot, receiver_cot) = ideal_cot.extend(LPN_PARAMETERS_TEST.k);
 
@@ -81,29 +72,13 @@ mod tests {
         let lpn_matrix_seed = prg.random_block();
 
         // init the setup of sender and receiver.
-        let (mut receiver, seed) = receiver
-            .setup(
-                LPN_PARAMETERS_TEST,
-                LpnType::Regular,
-                lpn_matrix_seed,
-                &u,
-                &w,
-            )
-            .unwrap();
+        let (mut receiver, seed) = receiver.setup(&config, lpn_matrix_seed, &u, &w).unwrap();
 
         let LpnMatrixSeed {
             seed: lpn_matrix_seed,
         } = seed;
 
-        let mut sender = sender
-            .setup(
-                delta,
-                LPN_PARAMETERS_TEST,
-                LpnType::Regular,
-                lpn_matrix_seed,
-                &v,
-            )
-            .unwrap();
+        let mut sender = sender.setup(delta, &config, lpn_matrix_seed, &v).unwrap();
 
         // extend once
         let _ = sender.get_mpcot_query();
@@ -114,8 +89,8 @@ mod tests {
         let MpcotMsgForSender { s } = sender_mpcot;
         let MpcotMsgForReceiver { r } = receiver_mpcot;
 
-        let sender_out = sender.extend(&s).unwrap();
-        let receiver_out = receiver.extend(&r).unwrap();
+        let sender_out = sender.extend(&[], &s).unwrap();
+        let receiver_out = receiver.extend(&[], &r).unwrap();
 
         assert!(ideal_cot.check(
             CotMsgForSender { qs: sender_out },
@@ -134,8 +109,8 @@ mod tests {
         let MpcotMsgForSender { s } = sender_mpcot;
         let MpcotMsgForReceiver { r } = receiver_mpcot;
 
-        let sender_out = sender.extend(&s).unwrap();
-        let receiver_out = receiver.extend(&r).unwrap();
+        let sender_out = sender.extend(&[], &s).unwrap();
+        let receiver_out = receiver.extend(&[], &r).unwrap();
 
         assert!(ideal_cot.check(
             CotMsgForSender { qs: sender_out },
@@ -145,4 +120,80 @@ mod tests {
             },
         ));
     }
+
+    #[test]
+    fn ferret_bootstrap_test() {
+        let mut prg = Prg::new();
+        let delta = prg.random_block();
+        let mut ideal_cot = IdealCOT::new_with_delta(delta);
+        let mut ideal_mpcot = IdealMpcot::init_with_delta(delta);
+
+        let config = FerretConfig::builder()
+            .lpn_parameters(LPN_PARAMETERS_TEST)
+            .lpn_type(LpnType::Regular)
+            .build()
+            .unwrap();
+
+        let (sender_cot, receiver_cot) = ideal_cot.extend(LPN_PARAMETERS_TEST.k);
+
+        let CotMsgForSender { qs: v } = sender_cot;
+        let CotMsgForReceiver { rs: u, ts: w } = receiver_cot;
+
+        let (mut receiver, LpnMatrixSeed { seed }) = FerretReceiver::new()
+            .setup(&config, prg.random_block(), &u, &w)
+            .unwrap();
+
+        let mut sender = FerretSender::new().setup(delta, &config, seed, &v).unwrap();
+
+        // The depths of the GGM trees of the regular MPCOT.
+        let (t, n) = sender.get_mpcot_query();
+        let (_, hs) = RegularSender::new().setup(delta).pre_extend(t, n).unwrap();
+        let mpcot_cost = hs.iter().sum::<usize>() + CSP;
+
+        // Only the MPCOT of the first extension is bootstrapped.
+        let (mut sender_reserved, mut receiver_reserved) = {
+            let (sender_cot, receiver_cot) = ideal_cot.extend(mpcot_cost);
+            (sender_cot.qs, (receiver_cot.rs, receiver_cot.ts))
+        };
+
+        for _ in 0..3 {
+            assert_eq!(sender_reserved.len(), mpcot_cost);
+            assert!(ideal_cot.check(
+                CotMsgForSender {
+                    qs: sender_reserved
+                },
+                CotMsgForReceiver {
+                    rs: receiver_reserved.0,
+                    ts: receiver_reserved.1,
+                },
+            ));
+
+            let query = receiver.get_mpcot_query();
+
+            let (sender_mpcot, receiver_mpcot) = ideal_mpcot.extend(&query.0, query.1, query.2);
+
+            let MpcotMsgForSender { s } = sender_mpcot;
+            let MpcotMsgForReceiver { r } = receiver_mpcot;
+
+            let sender_out = sender.extend(&hs, &s).unwrap();
+            let receiver_out = receiver.extend(&hs, &r).unwrap();
+
+            assert_eq!(
+                sender_out.len(),
+                LPN_PARAMETERS_TEST.n - LPN_PARAMETERS_TEST.k - mpcot_cost
+            );
+            assert!(ideal_cot.check(
+                CotMsgForSender { qs: sender_out },
+                CotMsgForReceiver {
+                    rs: receiver_out.0,
+                    ts: receiver_out.1,
+                },
+            ));
+
+            sender_reserved = sender.take_reserved();
+            receiver_reserved = receiver.take_reserved();
+        }
+
+        assert!(sender.take_reserved().is_empty());
+    }
 }
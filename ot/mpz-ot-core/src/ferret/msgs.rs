@@ -1,10 +1,46 @@
 //! Messages for the Ferret protocol.
+use enum_try_as_inner::EnumTryAsInner;
 use mpz_core::Block;
 use serde::{Deserialize, Serialize};
 
+use crate::ferret::{
+    mpcot::msgs::HashSeed,
+    spcot::msgs::{CheckFromReceiver, CheckFromSender, ExtendFromSender, MaskBits},
+};
+
+/// A Ferret protocol message.
+#[derive(Debug, Clone, EnumTryAsInner, Serialize, Deserialize)]
+#[derive_err(Debug)]
+#[allow(missing_docs)]
+pub enum Message<BaseMsg> {
+    BaseMsg(BaseMsg),
+    LpnMatrixSeed(LpnMatrixSeed),
+    HashSeed(HashSeed),
+    MaskBits(Vec<MaskBits>),
+    ExtendFromSender(Vec<ExtendFromSender>),
+    CheckFromReceiver(CheckFromReceiver),
+    CheckFromSender(CheckFromSender),
+    Reserve(Reserve),
+}
+
+impl<BaseMsg> From<MessageError<BaseMsg>> for std::io::Error {
+    fn from(err: MessageError<BaseMsg>) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// The seed to generate Lpn matrix.
 pub struct LpnMatrixSeed {
     /// The seed.
     pub seed: Block,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Message sent by the receiver to reserve COTs for the transfer with the provided id.
+pub struct Reserve {
+    /// The id of the transfer.
+    pub id: String,
+    /// The number of COTs to reserve.
+    pub count: usize,
+}
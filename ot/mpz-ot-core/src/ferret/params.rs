@@ -0,0 +1,346 @@
+//! LPN parameters for Ferret.
+//!
+//! [`LPN_PARAMETERS_REGULAR`] and [`LPN_PARAMETERS_UNIFORM`] are the parameter sets of
+//! [emp-ot](https://github.com/emp-toolkit/emp-ot/blob/master/emp-ot/ferret/constants.h) and of
+//! the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) paper, each claimed to provide 128 bits of
+//! computational security.
+//!
+//! The other presets extend `n = 2^20`, `2^22` or `2^24` COTs at a time, for both LPN types, so
+//! that the batch size can be picked according to the memory budget. They minimize the COTs kept
+//! for the next extension, ie. `k` plus the cost of the MPCOT, under an [`estimate_security`] of
+//! at least 128 bits, with `k` rounded up to a multiple of 1000.
+
+use std::f64::consts::{LN_2, PI};
+
+use mpz_core::lpn::LpnParameters;
+
+use crate::ferret::{error::LpnParametersError, LpnType};
+
+/// LPN parameters with regular noise.
+/// Taken from `ferret_b11` in https://github.com/emp-toolkit/emp-ot/blob/master/emp-ot/ferret/constants.h
+pub const LPN_PARAMETERS_REGULAR: LpnParameters = LpnParameters {
+    n: 10180608,
+    k: 124000,
+    t: 4971,
+};
+
+/// LPN parameters with uniform noise.
+/// Derived from Table 2 of the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) paper.
+pub const LPN_PARAMETERS_UNIFORM: LpnParameters = LpnParameters {
+    n: 10616092,
+    k: 588160,
+    t: 1324,
+};
+
+/// LPN parameters with regular noise and `n = 2^20`, ie. `t = 2^12` intervals of `2^8` entries.
+pub const LPN_PARAMETERS_REGULAR_2_20: LpnParameters = LpnParameters {
+    n: 1 << 20,
+    k: 25000,
+    t: 1 << 12,
+};
+
+/// LPN parameters with regular noise and `n = 2^22`, ie. `t = 2^13` intervals of `2^9` entries.
+pub const LPN_PARAMETERS_REGULAR_2_22: LpnParameters = LpnParameters {
+    n: 1 << 22,
+    k: 48000,
+    t: 1 << 13,
+};
+
+/// LPN parameters with regular noise and `n = 2^24`, ie. `t = 2^13` intervals of `2^11` entries.
+pub const LPN_PARAMETERS_REGULAR_2_24: LpnParameters = LpnParameters {
+    n: 1 << 24,
+    k: 158000,
+    t: 1 << 13,
+};
+
+/// LPN parameters with uniform noise and `n = 2^20`.
+pub const LPN_PARAMETERS_UNIFORM_2_20: LpnParameters = LpnParameters {
+    n: 1 << 20,
+    k: 39000,
+    t: 2048,
+};
+
+/// LPN parameters with uniform noise and `n = 2^22`.
+pub const LPN_PARAMETERS_UNIFORM_2_22: LpnParameters = LpnParameters {
+    n: 1 << 22,
+    k: 77000,
+    t: 4096,
+};
+
+/// LPN parameters with uniform noise and `n = 2^24`.
+pub const LPN_PARAMETERS_UNIFORM_2_24: LpnParameters = LpnParameters {
+    n: 1 << 24,
+    k: 150000,
+    t: 8192,
+};
+
+/// Small LPN parameters with regular noise, to keep the tests fast.
+///
+/// These are NOT secure.
+#[cfg(any(test, feature = "test-utils"))]
+pub const LPN_PARAMETERS_TEST: LpnParameters = LpnParameters {
+    n: 9600,
+    k: 1220,
+    t: 600,
+};
+
+/// Creates new LPN parameters, validating them for the given LPN type.
+///
+/// # Arguments
+///
+/// * `n` - The length of the output vectors.
+/// * `k` - The length of the secret vector.
+/// * `t` - The Hamming weight of the error vectors.
+/// * `lpn_type` - The LPN type.
+pub fn new_lpn_parameters(
+    n: usize,
+    k: usize,
+    t: usize,
+    lpn_type: LpnType,
+) -> Result<LpnParameters, LpnParametersError> {
+    let lpn_parameters = LpnParameters { n, k, t };
+
+    validate(&lpn_parameters, lpn_type)?;
+
+    Ok(lpn_parameters)
+}
+
+/// Validates the LPN parameters for the given LPN type.
+///
+/// Regular noise requires `n` to be divisible by `t`, as the error vector is sampled with a
+/// single non-zero value in each of the `t` intervals.
+pub fn validate(
+    lpn_parameters: &LpnParameters,
+    lpn_type: LpnType,
+) -> Result<(), LpnParametersError> {
+    let LpnParameters { n, k, t } = *lpn_parameters;
+
+    if t == 0 || t > n {
+        return Err(LpnParametersError::InvalidWeight { n, t });
+    }
+
+    if k == 0 || k >= n {
+        return Err(LpnParametersError::InvalidDimension { n, k });
+    }
+
+    if lpn_type == LpnType::Regular && n % t != 0 {
+        return Err(LpnParametersError::NotRegular { n, t });
+    }
+
+    Ok(())
+}
+
+/// Exponent of the cost of the Gaussian elimination.
+const OMEGA: f64 = 2.8;
+
+/// Upper bound on the number of errors in the information set of the ISD attack.
+const ISD_MAX_P: usize = 16;
+
+/// Upper bound on the size of the window of the ISD attack.
+const ISD_MAX_L: usize = 256;
+
+/// Returns a conservative estimate of the bit security of the LPN parameters.
+///
+/// This is the cost of the cheapest of the following attacks, see
+/// [Liu et al.](https://eprint.iacr.org/2022/712) for their analysis:
+///
+/// * Pooled Gauss, which solves random systems of `k` samples until one is noise-free.
+/// * Stern-Dumer information set decoding, which allows `p` errors in `k + l` samples and finds
+///   them with a meet-in-the-middle on the `l` extra samples.
+///
+/// For regular noise, the `t` intervals each contain exactly one error, so summing the samples of
+/// an interval gives a noise-free equation. The attacks are applied to the resulting instance
+/// with `n - t` samples, a secret of length `k - t` and `t` errors.
+///
+/// The estimate is a lower bound on the cost of these attacks, it ignores their memory cost and
+/// uses the cheapest Gaussian elimination, `k^2.8`. It does not account for the algebraic attacks
+/// on regular noise.
+pub fn estimate_security(lpn_parameters: &LpnParameters, lpn_type: LpnType) -> f64 {
+    let LpnParameters { n, k, t } = *lpn_parameters;
+
+    let (n, k) = match lpn_type {
+        LpnType::Uniform => (n, k),
+        LpnType::Regular => (n.saturating_sub(t), k.saturating_sub(t)),
+    };
+
+    if k == 0 || t == 0 || t > n {
+        return 0.0;
+    }
+
+    pooled_gauss(n, k, t).min(stern_dumer(n, k, t))
+}
+
+/// Returns the bit cost of the Pooled Gauss attack, ie. `k^2.8 · C(n, t) / C(n - k, t)`.
+fn pooled_gauss(n: usize, k: usize, t: usize) -> f64 {
+    OMEGA * (k as f64).log2() + log2_binomial(n, t) - log2_binomial(n - k, t)
+}
+
+/// Returns the bit cost of the Stern-Dumer ISD attack, minimized over `p` and `l`.
+///
+/// Each iteration succeeds if `p` errors are split evenly between the two halves of `k + l`
+/// samples, and costs a Gaussian elimination plus the merge of two lists of size
+/// `L = C((k + l) / 2, p / 2)`, ie. `k^2.8 + L + L^2 / 2^l`.
+fn stern_dumer(n: usize, k: usize, t: usize) -> f64 {
+    let gauss = OMEGA * (k as f64).log2();
+    let total = log2_binomial(n, t);
+
+    let mut cost = f64::INFINITY;
+    for p in (2..=t.min(ISD_MAX_P)).step_by(2) {
+        for l in 0..=ISD_MAX_L.min((n - k).saturating_sub(t - p)) {
+            let list = log2_binomial((k + l) / 2, p / 2);
+            let success = 2.0 * list + log2_binomial(n - k - l, t - p) - total;
+            let iteration = log2_add(log2_add(gauss, list), 2.0 * list - l as f64);
+
+            cost = cost.min(iteration - success);
+        }
+    }
+
+    cost
+}
+
+/// Returns `log2(C(n, k))`.
+fn log2_binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+
+    log2_factorial(n) - log2_factorial(k) - log2_factorial(n - k)
+}
+
+/// Returns `log2(n!)`, using Stirling's series for large `n`.
+fn log2_factorial(n: usize) -> f64 {
+    if n < 16 {
+        return (2..=n).map(|i| (i as f64).log2()).sum();
+    }
+
+    let n = n as f64;
+    (n * n.ln() - n + 0.5 * (2.0 * PI * n).ln() + 1.0 / (12.0 * n) - 1.0 / (360.0 * n.powi(3)))
+        / LN_2
+}
+
+/// Returns `log2(2^a + 2^b)`.
+fn log2_add(a: f64, b: f64) -> f64 {
+    let (max, min) = if a > b { (a, b) } else { (b, a) };
+    max + (1.0 + (min - max).exp2()).log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The presets of this module, targeting 128 bits of security.
+    const PRESETS: [(LpnParameters, LpnType); 6] = [
+        (LPN_PARAMETERS_REGULAR_2_20, LpnType::Regular),
+        (LPN_PARAMETERS_REGULAR_2_22, LpnType::Regular),
+        (LPN_PARAMETERS_REGULAR_2_24, LpnType::Regular),
+        (LPN_PARAMETERS_UNIFORM_2_20, LpnType::Uniform),
+        (LPN_PARAMETERS_UNIFORM_2_22, LpnType::Uniform),
+        (LPN_PARAMETERS_UNIFORM_2_24, LpnType::Uniform),
+    ];
+
+    /// The published presets, along with their claimed security.
+    const PUBLISHED: [(LpnParameters, LpnType, f64); 2] = [
+        (LPN_PARAMETERS_REGULAR, LpnType::Regular, 128.0),
+        (LPN_PARAMETERS_UNIFORM, LpnType::Uniform, 128.0),
+    ];
+
+    #[test]
+    fn test_presets() {
+        for (lpn_parameters, lpn_type, _) in PUBLISHED {
+            validate(&lpn_parameters, lpn_type).unwrap();
+        }
+
+        for (lpn_parameters, lpn_type) in PRESETS {
+            validate(&lpn_parameters, lpn_type).unwrap();
+        }
+
+        for (lpn_parameters, log_n) in [
+            (LPN_PARAMETERS_REGULAR_2_20, 20),
+            (LPN_PARAMETERS_REGULAR_2_22, 22),
+            (LPN_PARAMETERS_REGULAR_2_24, 24),
+            (LPN_PARAMETERS_UNIFORM_2_20, 20),
+            (LPN_PARAMETERS_UNIFORM_2_22, 22),
+            (LPN_PARAMETERS_UNIFORM_2_24, 24),
+        ] {
+            assert_eq!(lpn_parameters.n, 1 << log_n);
+        }
+    }
+
+    #[test]
+    fn test_estimate_security_presets() {
+        // The presets reach 128 bits, without wasting more than a few bits of security.
+        for (lpn_parameters, lpn_type) in PRESETS {
+            let security = estimate_security(&lpn_parameters, lpn_type);
+            assert!((128.0..134.0).contains(&security), "{security}");
+        }
+    }
+
+    #[test]
+    fn test_estimate_security_published() {
+        // The published estimates account for the memory cost of the attacks, so the conservative
+        // estimate may fall below the claimed security, but should stay close to it.
+        for (lpn_parameters, lpn_type, claimed) in PUBLISHED {
+            let security = estimate_security(&lpn_parameters, lpn_type);
+            assert!(
+                (claimed - 24.0..claimed + 12.0).contains(&security),
+                "{security}"
+            );
+        }
+
+        // The test parameters are not secure.
+        assert!(estimate_security(&LPN_PARAMETERS_TEST, LpnType::Regular) < 80.0);
+    }
+
+    #[test]
+    fn test_estimate_security_attacks() {
+        // ISD is cheaper than Pooled Gauss on the presets, as several errors are expected in any
+        // set of k samples.
+        let LpnParameters { n, k, t } = LPN_PARAMETERS_UNIFORM;
+        assert!(stern_dumer(n, k, t) < pooled_gauss(n, k, t));
+
+        // Without errors in the samples, both attacks are a single Gaussian elimination.
+        assert!((pooled_gauss(1000, 100, 0) - OMEGA * 100f64.log2()).abs() < 1e-6);
+        assert!(log2_binomial(10, 11).is_infinite());
+        assert!((log2_binomial(20, 10) - 184756f64.log2()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(new_lpn_parameters(9600, 1220, 600, LpnType::Regular).is_ok());
+        assert!(matches!(
+            new_lpn_parameters(9600, 1220, 700, LpnType::Regular),
+            Err(LpnParametersError::NotRegular { .. })
+        ));
+        assert!(new_lpn_parameters(9600, 1220, 700, LpnType::Uniform).is_ok());
+        assert!(matches!(
+            new_lpn_parameters(9600, 1220, 0, LpnType::Uniform),
+            Err(LpnParametersError::InvalidWeight { .. })
+        ));
+        assert!(matches!(
+            new_lpn_parameters(9600, 9600, 600, LpnType::Uniform),
+            Err(LpnParametersError::InvalidDimension { .. })
+        ));
+    }
+
+    #[test]
+    fn test_estimate_security() {
+        // More noise or a larger secret increase the security.
+        let lpn_parameters = LPN_PARAMETERS_REGULAR_2_20;
+        let security = estimate_security(&lpn_parameters, LpnType::Regular);
+
+        let more_noise = LpnParameters {
+            t: 2 * lpn_parameters.t,
+            ..lpn_parameters
+        };
+        let larger_secret = LpnParameters {
+            k: 2 * lpn_parameters.k,
+            ..lpn_parameters
+        };
+
+        assert!(estimate_security(&more_noise, LpnType::Regular) > security);
+        assert!(estimate_security(&larger_secret, LpnType::Regular) > security);
+
+        // The structure of regular noise reduces the security.
+        assert!(estimate_security(&lpn_parameters, LpnType::Uniform) > security);
+    }
+}
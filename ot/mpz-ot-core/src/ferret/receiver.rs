@@ -4,7 +4,7 @@ use mpz_core::{
     Block,
 };
 
-use crate::ferret::{error::ReceiverError, LpnType};
+use crate::ferret::{error::ReceiverError, FerretConfig, LpnType, CSP};
 
 use super::msgs::LpnMatrixSeed;
 
@@ -28,19 +28,18 @@ impl Receiver {
     ///
     /// # Arguments
     ///
-    /// * `lpn_parameters` - The lpn parameters.
+    /// * `config` - The Ferret configuration.
     /// * `seed` - The seed to generate lpn matrix.
-    /// * `lpn_type` - The lpn type.
     /// * `u` - The bits received from the COT ideal functionality.
     /// * `w` - The vector received from the COT ideal functionality.
     pub fn setup(
         self,
-        lpn_parameters: LpnParameters,
-        lpn_type: LpnType,
+        config: &FerretConfig,
         seed: Block,
         u: &[bool],
         w: &[Block],
     ) -> Result<(Receiver<state::Extension>, LpnMatrixSeed), ReceiverError> {
+        let lpn_parameters = config.lpn_parameters();
         if u.len() != lpn_parameters.k || w.len() != lpn_parameters.k {
             return Err(ReceiverError(
                 "the length of u and w should be k".to_string(),
@@ -55,10 +54,12 @@ impl Receiver {
                     counter: 0,
                     lpn_parameters,
                     lpn_encoder,
-                    lpn_type,
+                    lpn_type: config.lpn_type(),
+                    semi_honest: config.semi_honest(),
                     u: u.to_vec(),
                     w: w.to_vec(),
                     e: Vec::default(),
+                    reserved: (Vec::new(), Vec::new()),
                 },
             },
             LpnMatrixSeed { seed },
@@ -96,19 +97,44 @@ impl Receiver<state::Extension> {
         )
     }
 
+    /// Takes the choice bits and COTs reserved by the last extension for the MPCOT of the next
+    /// one.
+    ///
+    /// Returns empty vectors before the first extension, in which case the COTs have to be
+    /// bootstrapped from a base COT.
+    pub fn take_reserved(&mut self) -> (Vec<bool>, Vec<Block>) {
+        std::mem::take(&mut self.state.reserved)
+    }
+
     /// Performs the Ferret extension.
-    /// Outputs exactly l = n - t COTs.
+    ///
+    /// The first `k` COTs are kept as the next LPN secret, and the next `mpcot_cost` COTs are
+    /// reserved for the MPCOT of the next extension, where `mpcot_cost` is the sum of `hs` plus
+    /// the COTs of the consistency check. Outputs exactly l = n - k - mpcot_cost COTs.
     ///
     /// See step 5 and 6.
     ///
     /// # Arguments.
     ///
+    /// * `hs` - The depths of the GGM trees output by the MPCOT.
     /// * `r` - The vector received from the MPCOT protocol.
-    pub fn extend(&mut self, r: &[Block]) -> Result<(Vec<bool>, Vec<Block>), ReceiverError> {
+    pub fn extend(
+        &mut self,
+        hs: &[usize],
+        r: &[Block],
+    ) -> Result<(Vec<bool>, Vec<Block>), ReceiverError> {
         if r.len() != self.state.lpn_parameters.n {
             return Err(ReceiverError("the length of r should be n".to_string()));
         }
 
+        let check_count = if self.state.semi_honest { 0 } else { CSP };
+        let reserve = hs.iter().sum::<usize>() + check_count;
+        if self.state.lpn_parameters.k + reserve >= self.state.lpn_parameters.n {
+            return Err(ReceiverError(
+                "the MPCOT cost should be less than n - k".to_string(),
+            ));
+        }
+
         // Compute z = A * w + r.
         let mut z = r.to_vec();
         self.state.lpn_encoder.compute(&mut z, &self.state.w);
@@ -125,13 +151,18 @@ impl Receiver<state::Extension> {
 
         let mut x = x.iter().map(|a| a.lsb() == 1).collect::<Vec<bool>>();
 
-        let x_ = x.split_off(self.state.lpn_parameters.k);
-        let z_ = z.split_off(self.state.lpn_parameters.k);
+        let mut reserved_x = x.split_off(self.state.lpn_parameters.k);
+        let mut reserved_z = z.split_off(self.state.lpn_parameters.k);
+        let x_ = reserved_x.split_off(reserve);
+        let z_ = reserved_z.split_off(reserve);
 
         // Update u, w
         self.state.u = x;
         self.state.w = z;
 
+        // Keep x[k..k + reserve], z[k..k + reserve] for the MPCOT of the next extension.
+        self.state.reserved = (reserved_x, reserved_z);
+
         // Update counter
         self.state.counter += 1;
 
@@ -173,6 +204,8 @@ pub mod state {
         pub(super) lpn_encoder: LpnEncoder<10>,
         /// Lpn type.
         pub(super) lpn_type: LpnType,
+        /// Whether the SPCOT consistency check is skipped.
+        pub(super) semi_honest: bool,
 
         /// Receiver's COT messages in the setup phase.
         pub(super) u: Vec<bool>,
@@ -180,6 +213,9 @@ pub mod state {
 
         /// Receiver's lpn error vector.
         pub(super) e: Vec<Block>,
+
+        /// Choice bits and COTs reserved by the last extension.
+        pub(super) reserved: (Vec<bool>, Vec<Block>),
     }
 
     impl State for Extension {}
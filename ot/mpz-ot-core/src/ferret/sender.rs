@@ -4,7 +4,7 @@ use mpz_core::{
     Block,
 };
 
-use crate::ferret::{error::SenderError, LpnType};
+use crate::ferret::{error::SenderError, FerretConfig, LpnType, CSP};
 
 /// Ferret sender.
 #[derive(Debug, Default)]
@@ -27,18 +27,17 @@ impl Sender {
     /// # Arguments
     ///
     /// * `delta` - The sender's global secret.
-    /// * `lpn_parameters` - The lpn parameters.
-    /// * `lpn_type` - The lpn type.
+    /// * `config` - The Ferret configuration.
     /// * `seed` - The seed received from receiver to generate lpn matrix.
     /// * `v` - The vector received from the COT ideal functionality.
     pub fn setup(
         self,
         delta: Block,
-        lpn_parameters: LpnParameters,
-        lpn_type: LpnType,
+        config: &FerretConfig,
         seed: Block,
         v: &[Block],
     ) -> Result<Sender<state::Extension>, SenderError> {
+        let lpn_parameters = config.lpn_parameters();
         if v.len() != lpn_parameters.k {
             return Err(SenderError(
                 "the length of v should be equal to k".to_string(),
@@ -51,9 +50,11 @@ impl Sender {
                 delta,
                 counter: 0,
                 lpn_parameters,
-                lpn_type,
+                lpn_type: config.lpn_type(),
                 lpn_encoder,
+                semi_honest: config.semi_honest(),
                 v: v.to_vec(),
+                reserved: Vec::new(),
             },
         })
     }
@@ -70,28 +71,52 @@ impl Sender<state::Extension> {
         )
     }
 
+    /// Takes the COTs reserved by the last extension for the MPCOT of the next one.
+    ///
+    /// Returns an empty vector before the first extension, in which case the COTs have to be
+    /// bootstrapped from a base COT.
+    pub fn take_reserved(&mut self) -> Vec<Block> {
+        std::mem::take(&mut self.state.reserved)
+    }
+
     /// Performs the Ferret extension.
-    /// Outputs exactly l = n-t COTs.
+    ///
+    /// The first `k` COTs are kept as the next LPN secret, and the next `mpcot_cost` COTs are
+    /// reserved for the MPCOT of the next extension, where `mpcot_cost` is the sum of `hs` plus
+    /// the COTs of the consistency check. Outputs exactly l = n - k - mpcot_cost COTs.
     ///
     /// See step 5 and 6.
     ///
     /// # Arguments.
     ///
+    /// * `hs` - The depths of the GGM trees output by the MPCOT.
     /// * `s` - The vector received from the MPCOT protocol.
-    pub fn extend(&mut self, s: &[Block]) -> Result<Vec<Block>, SenderError> {
+    pub fn extend(&mut self, hs: &[usize], s: &[Block]) -> Result<Vec<Block>, SenderError> {
         if s.len() != self.state.lpn_parameters.n {
             return Err(SenderError("the length of s should be n".to_string()));
         }
 
+        let check_count = if self.state.semi_honest { 0 } else { CSP };
+        let reserve = hs.iter().sum::<usize>() + check_count;
+        if self.state.lpn_parameters.k + reserve >= self.state.lpn_parameters.n {
+            return Err(SenderError(
+                "the MPCOT cost should be less than n - k".to_string(),
+            ));
+        }
+
         // Compute y = A * v + s
         let mut y = s.to_vec();
         self.state.lpn_encoder.compute(&mut y, &self.state.v);
 
-        let y_ = y.split_off(self.state.lpn_parameters.k);
+        let mut reserved = y.split_off(self.state.lpn_parameters.k);
+        let y_ = reserved.split_off(reserve);
 
         // Update v to y[0..k]
         self.state.v = y;
 
+        // Keep y[k..k + reserve] for the MPCOT of the next extension.
+        self.state.reserved = reserved;
+
         // Update counter
         self.state.counter += 1;
 
@@ -139,8 +164,14 @@ pub mod state {
         /// Lpn encoder.
         pub(super) lpn_encoder: LpnEncoder<10>,
 
+        /// Whether the SPCOT consistency check is skipped.
+        pub(super) semi_honest: bool,
+
         /// Sender's COT message in the setup phase.
         pub(super) v: Vec<Block>,
+
+        /// COTs reserved by the last extension.
+        pub(super) reserved: Vec<Block>,
     }
 
     impl State for Extension {}
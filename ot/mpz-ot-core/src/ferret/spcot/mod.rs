@@ -9,7 +9,9 @@ pub mod sender;
 mod tests {
     use mpz_core::prg::Prg;
 
-    use super::{receiver::Receiver as SpcotReceiver, sender::Sender as SpcotSender};
+    use super::{
+        error::ReceiverError, receiver::Receiver as SpcotReceiver, sender::Sender as SpcotSender,
+    };
     use crate::{
         ferret::CSP,
         ideal::ideal_cot::{CotMsgForReceiver, CotMsgForSender, IdealCOT},
@@ -81,4 +83,82 @@ mod tests {
                 vs == ws
             }));
     }
+
+    #[test]
+    fn spcot_finalize_test() {
+        let mut ideal_cot = IdealCOT::new();
+        let delta = ideal_cot.delta();
+
+        let mut sender = SpcotSender::new().setup(delta, Prg::new().random_block());
+        let mut receiver = SpcotReceiver::new().setup();
+
+        let h = 8;
+        let alpha = 5;
+
+        let (msg_for_sender, msg_for_receiver) = ideal_cot.extend(h);
+
+        let CotMsgForReceiver { rs, ts } = msg_for_receiver;
+        let CotMsgForSender { qs } = msg_for_sender;
+
+        let maskbits = receiver.extend_mask_bits(h, alpha, &rs).unwrap();
+        let msg_from_sender = sender.extend(h, &qs, maskbits).unwrap();
+        receiver.extend(h, alpha, &ts, msg_from_sender).unwrap();
+
+        let mut output_sender = sender.finalize().unwrap();
+        let output_receiver = receiver.finalize().unwrap();
+
+        assert!(output_sender
+            .iter_mut()
+            .zip(output_receiver.iter())
+            .all(|(vs, (ws, alpha))| {
+                vs[*alpha as usize] ^= delta;
+                vs == ws
+            }));
+
+        // The outputs can only be taken once.
+        assert!(sender.finalize().is_err());
+        assert!(receiver.finalize().is_err());
+    }
+
+    #[test]
+    fn spcot_malicious_sender_test() {
+        let mut ideal_cot = IdealCOT::new();
+        let delta = ideal_cot.delta();
+
+        let mut sender = SpcotSender::new().setup(delta, Prg::new().random_block());
+        let mut receiver = SpcotReceiver::new().setup();
+
+        let h = 8;
+        let alpha = 3;
+
+        let (msg_for_sender, msg_for_receiver) = ideal_cot.extend(h);
+
+        let CotMsgForReceiver { rs, ts } = msg_for_receiver;
+        let CotMsgForSender { qs } = msg_for_sender;
+
+        let maskbits = receiver.extend_mask_bits(h, alpha, &rs).unwrap();
+        let mut msg_from_sender = sender.extend(h, &qs, maskbits).unwrap();
+
+        // The sender tampers with the sum of the leaves, which corrupts the receiver's
+        // output at the punctured position.
+        msg_from_sender.sum ^= Prg::new().random_block();
+
+        receiver.extend(h, alpha, &ts, msg_from_sender).unwrap();
+
+        let (msg_for_sender, msg_for_receiver) = ideal_cot.extend(CSP);
+
+        let CotMsgForReceiver {
+            rs: x_star,
+            ts: z_star,
+        } = msg_for_receiver;
+        let CotMsgForSender { qs: y_star } = msg_for_sender;
+
+        let check_from_receiver = receiver.check_pre(&x_star).unwrap();
+        let (_, check) = sender.check(&y_star, check_from_receiver).unwrap();
+
+        assert!(matches!(
+            receiver.check(&z_star, check),
+            Err(ReceiverError::ConsistencyCheckFailed)
+        ));
+    }
 }
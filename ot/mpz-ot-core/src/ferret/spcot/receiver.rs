@@ -1,4 +1,6 @@
 //! SPCOT receiver
+use std::collections::VecDeque;
+
 use crate::ferret::{spcot::error::ReceiverError, CSP};
 use itybity::ToBits;
 use mpz_core::{
@@ -31,6 +33,7 @@ impl Receiver {
         Receiver {
             state: state::Extension {
                 unchecked_ws: Vec::default(),
+                mask_bits: VecDeque::default(),
                 chis: Vec::default(),
                 alphas_and_length: Vec::default(),
                 cot_counter: 0,
@@ -45,6 +48,9 @@ impl Receiver {
 impl Receiver<state::Extension> {
     /// Performs the mask bit step in extension.
     ///
+    /// This function can be called multiple times before extending, the calls to
+    /// [`Receiver::extend`] must then be made in the same order.
+    ///
     /// See step 4 in Figure 6.
     ///
     /// # Arguments
@@ -86,8 +92,9 @@ impl Receiver<state::Extension> {
             .map(|(alpha, &r)| alpha == r)
             .collect();
 
-        // Updates hasher.
-        self.state.hasher.update(&bs.to_bytes());
+        // Stores the mask bits, the hasher is updated when the tree is reconstructed so that
+        // multiple mask bits can be computed before extending.
+        self.state.mask_bits.push_back(bs.clone());
 
         Ok(MaskBits { bs })
     }
@@ -134,7 +141,14 @@ impl Receiver<state::Extension> {
             ));
         }
 
+        let Some(bs) = self.state.mask_bits.pop_front() else {
+            return Err(ReceiverError::InvalidState(
+                "mask bits should be computed before extension".to_string(),
+            ));
+        };
+
         // Updates hasher
+        self.state.hasher.update(&bs.to_bytes());
         self.state.hasher.update(&ms.to_bytes());
         self.state.hasher.update(&sum.to_bytes());
 
@@ -247,6 +261,26 @@ impl Receiver<state::Extension> {
             return Err(ReceiverError::ConsistencyCheckFailed);
         }
 
+        Ok(self.take_outputs())
+    }
+
+    /// Outputs the resulting COTs without the consistency check.
+    ///
+    /// # Warning
+    ///
+    /// Without the check, a malicious sender can send inconsistent GGM trees and learn
+    /// information about the chosen positions from the receiver's subsequent behavior.
+    pub fn finalize(&mut self) -> Result<Vec<(Vec<Block>, u32)>, ReceiverError> {
+        if self.state.extended {
+            return Err(ReceiverError::InvalidState(
+                "extension is not allowed".to_string(),
+            ));
+        }
+
+        Ok(self.take_outputs())
+    }
+
+    fn take_outputs(&mut self) -> Vec<(Vec<Block>, u32)> {
         self.state.cot_counter += self.state.unchecked_ws.len();
         self.state.extended = true;
 
@@ -256,7 +290,7 @@ impl Receiver<state::Extension> {
             res.push((tmp, *alpha));
         }
 
-        Ok(res)
+        res
     }
 }
 
@@ -288,6 +322,8 @@ pub mod state {
     pub struct Extension {
         /// Receiver's output blocks.
         pub(super) unchecked_ws: Vec<Block>,
+        /// Mask bits which have not been used for extension yet.
+        pub(super) mask_bits: VecDeque<Vec<bool>>,
         /// Receiver's random challenges chis.
         pub(super) chis: Vec<Block>,
         /// Stores the alpha and the length in each extend phase.
@@ -185,6 +185,24 @@ impl Sender<state::Extension> {
         // Computes H'(V)
         let hashed_v = Hash::from(blake3(&v.to_bytes()));
 
+        Ok((self.take_outputs(), CheckFromSender { hashed_v }))
+    }
+
+    /// Outputs the resulting COTs without the consistency check.
+    ///
+    /// The check protects the receiver against a malicious sender, so this must only be used
+    /// if the receiver also skips the check.
+    pub fn finalize(&mut self) -> Result<Vec<Vec<Block>>, SenderError> {
+        if self.state.extended {
+            return Err(SenderError::InvalidState(
+                "extension is not allowed".to_string(),
+            ));
+        }
+
+        Ok(self.take_outputs())
+    }
+
+    fn take_outputs(&mut self) -> Vec<Vec<Block>> {
         self.state.cot_counter += self.state.unchecked_vs.len();
 
         let mut res = Vec::new();
@@ -195,7 +213,7 @@ impl Sender<state::Extension> {
 
         self.state.extended = true;
 
-        Ok((res, CheckFromSender { hashed_v }))
+        res
     }
 }
 
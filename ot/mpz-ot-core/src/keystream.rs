@@ -0,0 +1,93 @@
+//! Stream cipher used to encrypt variable-length messages with OT keys.
+
+use mpz_core::{prg::Prg, Block};
+use rand::{RngCore, SeedableRng};
+
+/// Applies the keystream derived from `key` to `data`.
+///
+/// The keystream is the output of a PRG seeded with `key`, so a key must never be used to
+/// encrypt more than one message.
+pub(crate) fn apply_keystream(key: Block, data: &mut [u8]) {
+    let mut keystream = vec![0u8; data.len()];
+    Prg::from_seed(key).fill_bytes(&mut keystream);
+
+    data.iter_mut()
+        .zip(keystream)
+        .for_each(|(byte, key)| *byte ^= key);
+}
+
+/// Encrypts pairs of variable-length messages, returning the concatenated ciphertexts and
+/// the lengths of the messages.
+///
+/// # Arguments
+///
+/// * `keys` - The sender's keys.
+/// * `flip` - The derandomization bits, which swap the keys of a pair.
+/// * `msgs` - The messages to encrypt.
+pub(crate) fn encrypt_vec(
+    keys: Vec<[Block; 2]>,
+    flip: impl Iterator<Item = bool>,
+    msgs: &[[Vec<u8>; 2]],
+) -> (Vec<u8>, Vec<[usize; 2]>) {
+    let mut ciphertexts = Vec::with_capacity(msgs.iter().map(|[m0, m1]| m0.len() + m1.len()).sum());
+    let mut lengths = Vec::with_capacity(msgs.len());
+
+    for (([k0, k1], [m0, m1]), flip) in keys.into_iter().zip(msgs).zip(flip) {
+        let (k0, k1) = if flip { (k1, k0) } else { (k0, k1) };
+
+        let offset = ciphertexts.len();
+        ciphertexts.extend_from_slice(m0);
+        apply_keystream(k0, &mut ciphertexts[offset..]);
+
+        let offset = ciphertexts.len();
+        ciphertexts.extend_from_slice(m1);
+        apply_keystream(k1, &mut ciphertexts[offset..]);
+
+        lengths.push([m0.len(), m1.len()]);
+    }
+
+    (ciphertexts, lengths)
+}
+
+/// Decrypts the chosen messages from the output of [`encrypt_vec`].
+///
+/// Returns `None` if the lengths do not match the ciphertexts.
+///
+/// # Arguments
+///
+/// * `keys` - The receiver's keys.
+/// * `choices` - The receiver's choices.
+/// * `ciphertexts` - The concatenated ciphertexts.
+/// * `lengths` - The lengths of the messages.
+pub(crate) fn decrypt_vec(
+    keys: Vec<Block>,
+    choices: Vec<bool>,
+    ciphertexts: &[u8],
+    lengths: &[[usize; 2]],
+) -> Option<Vec<Vec<u8>>> {
+    let total = lengths.iter().try_fold(0usize, |acc, [l0, l1]| {
+        acc.checked_add(*l0)?.checked_add(*l1)
+    })?;
+
+    if total != ciphertexts.len() {
+        return None;
+    }
+
+    let mut offset = 0;
+    let msgs = keys
+        .into_iter()
+        .zip(choices)
+        .zip(lengths)
+        .map(|((key, c), [l0, l1])| {
+            let (start, len) = if c { (offset + l0, *l1) } else { (offset, *l0) };
+            offset += l0 + l1;
+
+            let mut msg = ciphertexts[start..start + len].to_vec();
+            apply_keystream(key, &mut msg);
+
+            msg
+        })
+        .collect();
+
+    Some(msgs)
+}
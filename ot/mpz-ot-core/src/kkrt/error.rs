@@ -0,0 +1,23 @@
+/// Errors that can occur when using the KKRT sender.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+    #[error("invalid extend")]
+    InvalidExtend,
+}
+
+/// Errors that can occur when using the KKRT receiver.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+    #[error("id mismatch: expected {0}, got {1}")]
+    IdMismatch(u32, u32),
+    #[error("invalid choice: {0}")]
+    InvalidChoice(usize),
+    #[error("invalid payload")]
+    InvalidPayload(String),
+}
@@ -0,0 +1,252 @@
+//! An implementation of the [`KKRT16`](https://eprint.iacr.org/2016/799.pdf) batched oblivious
+//! PRF, which yields a 1-out-of-N oblivious transfer extension.
+//!
+//! The receiver plays the role of the base OT sender, and the sender the role of the base OT
+//! receiver, as in IKNP. Instead of a repetition code, each input of the receiver is encoded with
+//! a pseudorandom code, which allows the sender to evaluate the PRF on arbitrary inputs.
+//!
+//! # ⚠️ Warning ⚠️
+//!
+//! This protocol is only secure against semi-honest adversaries.
+
+mod error;
+pub mod msgs;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+pub use receiver::{state as receiver_state, Receiver, ReceiverKeys};
+pub use sender::{state as sender_state, Sender, SenderKeys};
+
+use mpz_core::{aes::FIXED_KEY_AES, Block};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+
+/// The length of the pseudorandom code in bits, which is also the number of base OTs.
+pub const CODE_LEN: usize = 512;
+/// The length of the pseudorandom code in blocks.
+pub(crate) const CODE_BLOCKS: usize = CODE_LEN / 128;
+/// Rng to use for expanding the base OT seeds.
+pub(crate) type Rng = ChaCha20Rng;
+/// Rng seed type
+pub(crate) type RngSeed = <Rng as SeedableRng>::Seed;
+
+/// Pads the number of OPRF instances to accommodate for the extension matrix transpose
+/// optimization.
+pub fn pad_ot_count(count: usize) -> usize {
+    // Round up the OTs to extend to the nearest multiple of 64.
+    (count + 63) & !63
+}
+
+/// Returns the size in bytes of the extension matrix for a given number of OPRF instances.
+pub fn extension_matrix_size(count: usize) -> usize {
+    count * CODE_LEN / 8
+}
+
+/// Returns the PRF input which corresponds to the `index`-th message of a 1-out-of-N OT.
+pub fn input_from_index(index: usize) -> Block {
+    Block::new((index as u128).to_le_bytes())
+}
+
+/// Stretches a Block-sized seed to a 32-byte seed.
+pub(crate) fn rng_from_seed(seed: Block) -> Rng {
+    let mut seed_ = RngSeed::default();
+    seed_
+        .iter_mut()
+        .zip(seed.to_bytes().into_iter().cycle())
+        .for_each(|(s, c)| *s = c);
+    Rng::from_seed(seed_)
+}
+
+/// Encodes an input with the pseudorandom code, instantiated using fixed-key AES.
+pub(crate) fn encode(input: Block) -> [Block; CODE_BLOCKS] {
+    let cipher = &(*FIXED_KEY_AES);
+    std::array::from_fn(|i| cipher.tccr(Block::new((i as u128).to_le_bytes()), input))
+}
+
+/// Hashes a row of the extension matrix, tweaked with the global index of the OPRF instance.
+pub(crate) fn hash(index: usize, row: &[Block; CODE_BLOCKS]) -> Block {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&(index as u64).to_le_bytes());
+    row.iter().for_each(|block| {
+        hasher.update(&block.to_bytes());
+    });
+
+    let hash: [u8; 32] = hasher.finalize().into();
+    Block::try_from(&hash[..16]).expect("slice should be 16 bytes")
+}
+
+/// Converts the rows of an extension matrix into blocks.
+pub(crate) fn into_rows(matrix: &[u8]) -> impl Iterator<Item = [Block; CODE_BLOCKS]> + '_ {
+    matrix.chunks_exact(CODE_LEN / 8).map(|row| {
+        std::array::from_fn(|i| {
+            Block::try_from(&row[i * Block::LEN..(i + 1) * Block::LEN])
+                .expect("slice should be 16 bytes")
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itybity::ToBits;
+    use rstest::*;
+
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+
+    #[fixture]
+    fn delta() -> [Block; CODE_BLOCKS] {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        std::array::from_fn(|_| rng.gen())
+    }
+
+    #[fixture]
+    fn receiver_seeds() -> [[Block; 2]; CODE_LEN] {
+        let mut rng = ChaCha12Rng::seed_from_u64(1);
+        std::array::from_fn(|_| [rng.gen(), rng.gen()])
+    }
+
+    #[fixture]
+    fn sender_seeds(
+        delta: [Block; CODE_BLOCKS],
+        receiver_seeds: [[Block; 2]; CODE_LEN],
+    ) -> [Block; CODE_LEN] {
+        delta
+            .iter_lsb0()
+            .zip(receiver_seeds)
+            .map(|(b, seeds)| if b { seeds[1] } else { seeds[0] })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap()
+    }
+
+    #[fixture]
+    fn inputs() -> Vec<Block> {
+        let mut rng = ChaCha12Rng::seed_from_u64(2);
+        (0..100).map(|_| rng.gen()).collect()
+    }
+
+    fn setup(
+        delta: [Block; CODE_BLOCKS],
+        sender_seeds: [Block; CODE_LEN],
+        receiver_seeds: [[Block; 2]; CODE_LEN],
+    ) -> (
+        Sender<sender_state::Extension>,
+        Receiver<receiver_state::Extension>,
+    ) {
+        (
+            Sender::new().setup(delta, sender_seeds),
+            Receiver::new().setup(receiver_seeds),
+        )
+    }
+
+    #[rstest]
+    fn test_kkrt_oprf(
+        delta: [Block; CODE_BLOCKS],
+        sender_seeds: [Block; CODE_LEN],
+        receiver_seeds: [[Block; 2]; CODE_LEN],
+        inputs: Vec<Block>,
+    ) {
+        let (mut sender, mut receiver) = setup(delta, sender_seeds, receiver_seeds);
+
+        // Run two transfers to make sure the outputs are not reused.
+        let mut outputs = Vec::new();
+        for _ in 0..2 {
+            let (extend, receiver_keys) = receiver.extend(&inputs);
+            let sender_keys = sender.extend(inputs.len(), extend).unwrap();
+
+            for (j, (input, output)) in inputs.iter().zip(receiver_keys.outputs()).enumerate() {
+                assert_eq!(sender_keys.eval(j, *input), *output);
+                assert_ne!(sender_keys.eval(j, *input ^ Block::ONE), *output);
+            }
+
+            outputs.push(receiver_keys.take_outputs());
+        }
+
+        assert_ne!(outputs[0], outputs[1]);
+    }
+
+    #[rstest]
+    fn test_kkrt_ot(
+        delta: [Block; CODE_BLOCKS],
+        sender_seeds: [Block; CODE_LEN],
+        receiver_seeds: [[Block; 2]; CODE_LEN],
+    ) {
+        const N: usize = 8;
+
+        let (mut sender, mut receiver) = setup(delta, sender_seeds, receiver_seeds);
+
+        let mut rng = ChaCha12Rng::seed_from_u64(3);
+        let choices = (0..100).map(|_| rng.gen_range(0..N)).collect::<Vec<_>>();
+        let data = (0..choices.len())
+            .map(|_| std::array::from_fn::<Block, N, _>(|_| rng.gen()))
+            .collect::<Vec<_>>();
+
+        let inputs = choices
+            .iter()
+            .copied()
+            .map(input_from_index)
+            .collect::<Vec<_>>();
+
+        let (extend, receiver_keys) = receiver.extend(&inputs);
+        let sender_keys = sender.extend(inputs.len(), extend).unwrap();
+
+        let payload = sender_keys.encrypt_blocks(&data).unwrap();
+        let received = receiver_keys.decrypt_blocks(&choices, payload).unwrap();
+
+        let expected = data
+            .iter()
+            .zip(&choices)
+            .map(|(msgs, choice)| msgs[*choice])
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
+    #[rstest]
+    fn test_kkrt_invalid_extend(
+        delta: [Block; CODE_BLOCKS],
+        sender_seeds: [Block; CODE_LEN],
+        receiver_seeds: [[Block; 2]; CODE_LEN],
+        inputs: Vec<Block>,
+    ) {
+        let (mut sender, mut receiver) = setup(delta, sender_seeds, receiver_seeds);
+
+        let (extend, _) = receiver.extend(&inputs);
+
+        assert!(matches!(
+            sender.extend(inputs.len() + 64, extend),
+            Err(SenderError::InvalidExtend)
+        ));
+    }
+
+    #[rstest]
+    fn test_kkrt_empty_payload(
+        delta: [Block; CODE_BLOCKS],
+        sender_seeds: [Block; CODE_LEN],
+        receiver_seeds: [[Block; 2]; CODE_LEN],
+    ) {
+        let (mut sender, mut receiver) = setup(delta, sender_seeds, receiver_seeds);
+
+        let choices = vec![0usize; 8];
+        let inputs = choices
+            .iter()
+            .copied()
+            .map(input_from_index)
+            .collect::<Vec<_>>();
+
+        let (extend, receiver_keys) = receiver.extend(&inputs);
+        let sender_keys = sender.extend(inputs.len(), extend).unwrap();
+
+        let mut payload = sender_keys
+            .encrypt_blocks(&vec![[Block::ZERO; 2]; 8])
+            .unwrap();
+        payload.ciphertexts.clear();
+
+        assert!(matches!(
+            receiver_keys.decrypt_blocks(&choices, payload),
+            Err(ReceiverError::InvalidPayload(_))
+        ));
+    }
+}
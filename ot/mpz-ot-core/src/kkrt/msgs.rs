@@ -0,0 +1,75 @@
+//! Messages for the KKRT protocol.
+
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_core::Block;
+use serde::{Deserialize, Serialize};
+
+/// A KKRT protocol message.
+#[derive(Debug, Clone, EnumTryAsInner, Serialize, Deserialize)]
+#[derive_err(Debug)]
+#[allow(missing_docs)]
+pub enum Message<BaseMsg> {
+    BaseMsg(BaseMsg),
+    StartExtend(StartExtend),
+    Extend(Extend),
+    SenderPayload(SenderPayload),
+}
+
+impl<BaseMsg> From<MessageError<BaseMsg>> for std::io::Error {
+    fn from(err: MessageError<BaseMsg>) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+/// Extension message sent by the receiver to agree upon the number of OPRF instances.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StartExtend {
+    /// The number of OPRF instances.
+    pub count: usize,
+}
+
+/// Extension message sent by the receiver.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Extend {
+    /// The rows of the receiver's extension matrix.
+    pub us: Vec<u8>,
+}
+
+impl Extend {
+    /// Returns an iterator over the chunks of the message.
+    pub fn into_chunks(self, chunk_size: usize) -> ExtendChunks {
+        ExtendChunks {
+            chunk_size,
+            us: self.us.into_iter(),
+        }
+    }
+}
+
+/// Iterator over the chunks of an extension message.
+pub struct ExtendChunks {
+    chunk_size: usize,
+    us: <Vec<u8> as IntoIterator>::IntoIter,
+}
+
+impl Iterator for ExtendChunks {
+    type Item = Extend;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.us.len() == 0 {
+            None
+        } else {
+            Some(Extend {
+                us: self.us.by_ref().take(self.chunk_size).collect::<Vec<_>>(),
+            })
+        }
+    }
+}
+
+/// Sender payload message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SenderPayload {
+    /// Transfer ID
+    pub id: u32,
+    /// Sender's ciphertexts, `N` consecutive ciphertexts per OT.
+    pub ciphertexts: Vec<Block>,
+}
@@ -0,0 +1,228 @@
+use crate::kkrt::{
+    encode, hash, into_rows,
+    msgs::{Extend, SenderPayload},
+    pad_ot_count, rng_from_seed, ReceiverError, Rng, CODE_LEN,
+};
+
+use mpz_core::Block;
+use rand_core::RngCore;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        use rayon::prelude::*;
+    }
+}
+
+/// KKRT receiver.
+#[derive(Debug, Default)]
+pub struct Receiver<T: state::State = state::Initialized> {
+    state: T,
+}
+
+impl Receiver {
+    /// Creates a new Receiver
+    pub fn new() -> Self {
+        Receiver {
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Complete the setup phase of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - The receiver's rng seeds
+    pub fn setup(self, seeds: [[Block; 2]; CODE_LEN]) -> Receiver<state::Extension> {
+        Receiver {
+            state: state::Extension {
+                rngs: seeds
+                    .into_iter()
+                    .map(|seeds| seeds.map(rng_from_seed))
+                    .collect(),
+                transfer_id: 0,
+                counter: 0,
+            },
+        }
+    }
+}
+
+impl Receiver<state::Extension> {
+    /// Performs the extension for the provided inputs, returning the extension message for the
+    /// sender and the OPRF outputs of the receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The receiver's OPRF inputs.
+    pub fn extend(&mut self, inputs: &[Block]) -> (Extend, ReceiverKeys) {
+        let count = inputs.len();
+        let row_width = pad_ot_count(count) / 8;
+
+        // 𝐭₀ⁱ and 𝐭₀ⁱ ⊕ 𝐭₁ⁱ, column-wise.
+        let mut ts = vec![0u8; CODE_LEN * row_width];
+        let mut us = vec![0u8; CODE_LEN * row_width];
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = self.state.rngs
+                    .par_iter_mut()
+                    .zip(ts.par_chunks_exact_mut(row_width))
+                    .zip(us.par_chunks_exact_mut(row_width));
+            } else {
+                let iter = self.state.rngs
+                    .iter_mut()
+                    .zip(ts.chunks_exact_mut(row_width))
+                    .zip(us.chunks_exact_mut(row_width));
+            }
+        }
+
+        iter.for_each(|((rngs, t_0), u)| {
+            rngs[0].fill_bytes(t_0);
+            // reuse u to avoid memory allocation for 𝐭₁ⁱ
+            rngs[1].fill_bytes(u);
+            u.iter_mut().zip(t_0.iter()).for_each(|(u, t_0)| *u ^= *t_0);
+        });
+
+        matrix_transpose::transpose_bits(&mut ts, CODE_LEN).expect("matrix is rectangular");
+        matrix_transpose::transpose_bits(&mut us, CODE_LEN).expect("matrix is rectangular");
+
+        // `uⱼ = t₀ⱼ ⊕ t₁ⱼ ⊕ C(rⱼ)`, row-wise.
+        us.chunks_exact_mut(CODE_LEN / 8)
+            .zip(inputs)
+            .for_each(|(u, input)| {
+                u.iter_mut()
+                    .zip(encode(*input).iter().flat_map(|block| block.to_bytes()))
+                    .for_each(|(u, c)| *u ^= c);
+            });
+
+        let offset = self.state.counter;
+        let outputs = into_rows(&ts)
+            .take(count)
+            .enumerate()
+            .map(|(j, t)| hash(offset + j, &t))
+            .collect();
+
+        let id = self.state.transfer_id;
+        self.state.transfer_id += 1;
+        self.state.counter += count;
+
+        (Extend { us }, ReceiverKeys { id, outputs })
+    }
+}
+
+/// KKRT receiver's OPRF outputs for a single transfer.
+pub struct ReceiverKeys {
+    /// Transfer ID
+    id: u32,
+    /// OPRF outputs
+    outputs: Vec<Block>,
+}
+
+opaque_debug::implement!(ReceiverKeys);
+
+impl ReceiverKeys {
+    /// Returns the transfer ID.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the OPRF outputs.
+    pub fn outputs(&self) -> &[Block] {
+        &self.outputs
+    }
+
+    /// Returns the OPRF outputs.
+    pub fn take_outputs(self) -> Vec<Block> {
+        self.outputs
+    }
+
+    /// Decrypts the sender's payload.
+    ///
+    /// The choices must be the indices which were used as inputs of the extension,
+    /// see [`input_from_index`](crate::kkrt::input_from_index).
+    ///
+    /// # Arguments
+    ///
+    /// * `choices` - The receiver's choices.
+    /// * `payload` - The sender's payload.
+    pub fn decrypt_blocks(
+        self,
+        choices: &[usize],
+        payload: SenderPayload,
+    ) -> Result<Vec<Block>, ReceiverError> {
+        let SenderPayload { id, ciphertexts } = payload;
+
+        if id != self.id {
+            return Err(ReceiverError::IdMismatch(self.id, id));
+        }
+
+        if choices.len() != self.outputs.len() {
+            return Err(ReceiverError::CountMismatch(
+                self.outputs.len(),
+                choices.len(),
+            ));
+        }
+
+        if self.outputs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if ciphertexts.is_empty() || ciphertexts.len() % self.outputs.len() != 0 {
+            return Err(ReceiverError::InvalidPayload(
+                "ciphertext count is not a non-zero multiple of the OT count".to_string(),
+            ));
+        }
+
+        let n = ciphertexts.len() / self.outputs.len();
+
+        ciphertexts
+            .chunks_exact(n)
+            .zip(self.outputs)
+            .zip(choices)
+            .map(|((ciphertexts, key), choice)| {
+                ciphertexts
+                    .get(*choice)
+                    .map(|ciphertext| *ciphertext ^ key)
+                    .ok_or(ReceiverError::InvalidChoice(*choice))
+            })
+            .collect()
+    }
+}
+
+/// The receiver's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Extension {}
+    }
+
+    /// The receiver's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The receiver's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The receiver's state after the setup phase.
+    ///
+    /// In this state the receiver performs the extension for each transfer.
+    pub struct Extension {
+        /// Rngs seeded from the base OT seeds
+        pub(super) rngs: Vec<[Rng; 2]>,
+
+        /// Current transfer id
+        pub(super) transfer_id: u32,
+        /// Current OPRF counter
+        pub(super) counter: usize,
+    }
+
+    impl State for Extension {}
+
+    opaque_debug::implement!(Extension);
+}
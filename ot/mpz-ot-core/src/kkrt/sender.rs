@@ -0,0 +1,235 @@
+use crate::kkrt::{
+    encode, extension_matrix_size, hash, input_from_index, into_rows,
+    msgs::{Extend, SenderPayload},
+    pad_ot_count, rng_from_seed, Rng, SenderError, CODE_BLOCKS, CODE_LEN,
+};
+
+use mpz_core::Block;
+use rand_core::RngCore;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        use rayon::prelude::*;
+    }
+}
+
+/// KKRT sender.
+#[derive(Debug, Default)]
+pub struct Sender<T: state::State = state::Initialized> {
+    state: T,
+}
+
+impl Sender {
+    /// Creates a new Sender
+    pub fn new() -> Self {
+        Sender {
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Complete the setup phase of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The sender's base OT choice bits
+    /// * `seeds` - The rng seeds chosen during base OT
+    pub fn setup(
+        self,
+        delta: [Block; CODE_BLOCKS],
+        seeds: [Block; CODE_LEN],
+    ) -> Sender<state::Extension> {
+        Sender {
+            state: state::Extension {
+                delta,
+                rngs: seeds.into_iter().map(rng_from_seed).collect(),
+                transfer_id: 0,
+                counter: 0,
+            },
+        }
+    }
+}
+
+impl Sender<state::Extension> {
+    /// Performs the extension for `count` OPRF instances, returning the keys of the sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of OPRF instances.
+    /// * `extend` - The receiver's extension message.
+    pub fn extend(&mut self, count: usize, extend: Extend) -> Result<SenderKeys, SenderError> {
+        let padded_count = pad_ot_count(count);
+        let row_width = padded_count / 8;
+
+        let Extend { us } = extend;
+
+        if us.len() != extension_matrix_size(padded_count) {
+            return Err(SenderError::InvalidExtend);
+        }
+
+        // Expand the columns of the matrix from the seeds chosen by `delta`.
+        let mut qs = vec![0u8; CODE_LEN * row_width];
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = self.state.rngs
+                    .par_iter_mut()
+                    .zip(qs.par_chunks_exact_mut(row_width));
+            } else {
+                let iter = self.state.rngs
+                    .iter_mut()
+                    .zip(qs.chunks_exact_mut(row_width));
+            }
+        }
+
+        iter.for_each(|(rng, q)| rng.fill_bytes(q));
+
+        matrix_transpose::transpose_bits(&mut qs, CODE_LEN).expect("matrix is rectangular");
+
+        // `qⱼ = tⱼ ⊕ (uⱼ ∧ ∆)`, which is equal to `t₀ⱼ ⊕ (C(rⱼ) ∧ ∆)`.
+        let delta = self.state.delta;
+        let qs = into_rows(&qs)
+            .zip(into_rows(&us))
+            .take(count)
+            .map(|(q, u)| std::array::from_fn(|i| q[i] ^ (u[i] & delta[i])))
+            .collect();
+
+        let id = self.state.transfer_id;
+        self.state.transfer_id += 1;
+
+        let offset = self.state.counter;
+        self.state.counter += count;
+
+        Ok(SenderKeys {
+            id,
+            offset,
+            delta,
+            qs,
+        })
+    }
+}
+
+/// KKRT sender's keys for a single transfer.
+///
+/// Returned by the [`Sender::extend`] method, the keys can be used to evaluate the OPRF
+/// on any input.
+pub struct SenderKeys {
+    /// Transfer ID
+    id: u32,
+    /// Global index of the first OPRF instance
+    offset: usize,
+    /// Sender's base OT choice bits
+    delta: [Block; CODE_BLOCKS],
+    /// Rows of the extension matrix
+    qs: Vec<[Block; CODE_BLOCKS]>,
+}
+
+opaque_debug::implement!(SenderKeys);
+
+impl SenderKeys {
+    /// Returns the transfer ID.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the number of OPRF instances.
+    pub fn len(&self) -> usize {
+        self.qs.len()
+    }
+
+    /// Returns `true` if there are no OPRF instances.
+    pub fn is_empty(&self) -> bool {
+        self.qs.is_empty()
+    }
+
+    /// Evaluates the `j`-th OPRF instance on the provided input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `j` is out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `j` - The index of the OPRF instance.
+    /// * `input` - The input to evaluate.
+    pub fn eval(&self, j: usize, input: Block) -> Block {
+        let q = &self.qs[j];
+        let code = encode(input);
+        let row = std::array::from_fn(|i| q[i] ^ (code[i] & self.delta[i]));
+
+        hash(self.offset + j, &row)
+    }
+
+    /// Encrypts the provided messages, where the `i`-th message of each OT is encrypted with
+    /// the OPRF evaluated on the `i`-th input, see [`input_from_index`](crate::kkrt::input_from_index).
+    ///
+    /// # Arguments
+    ///
+    /// * `msgs` - The messages to encrypt
+    pub fn encrypt_blocks<const N: usize>(
+        self,
+        msgs: &[[Block; N]],
+    ) -> Result<SenderPayload, SenderError> {
+        if msgs.len() != self.qs.len() {
+            return Err(SenderError::CountMismatch(self.qs.len(), msgs.len()));
+        }
+
+        let keys = &self;
+        let encrypt = |(j, msgs): (usize, &[Block; N])| {
+            std::array::from_fn::<_, N, _>(|i| keys.eval(j, input_from_index(i)) ^ msgs[i])
+        };
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let ciphertexts = msgs.par_iter().enumerate().flat_map_iter(encrypt).collect();
+            } else {
+                let ciphertexts = msgs.iter().enumerate().flat_map(encrypt).collect();
+            }
+        }
+
+        Ok(SenderPayload {
+            id: self.id,
+            ciphertexts,
+        })
+    }
+}
+
+/// The sender's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Extension {}
+    }
+
+    /// The sender's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The sender's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The sender's state after the setup phase.
+    ///
+    /// In this state the sender performs the extension for each transfer.
+    pub struct Extension {
+        /// Sender's base OT choices
+        pub(super) delta: [Block; CODE_BLOCKS],
+        /// Rngs seeded from seeds obliviously received from base OT
+        pub(super) rngs: Vec<Rng>,
+
+        /// Current transfer id
+        pub(super) transfer_id: u32,
+        /// Current OPRF counter
+        pub(super) counter: usize,
+    }
+
+    impl State for Extension {}
+
+    opaque_debug::implement!(Extension);
+}
@@ -179,6 +179,54 @@ mod tests {
         assert_eq!(received, expected);
     }
 
+    #[rstest]
+    fn test_kos_extension_vec(
+        delta: Block,
+        sender_seeds: [Block; CSP],
+        receiver_seeds: [[Block; 2]; CSP],
+        chi_seed: Block,
+        choices: Vec<bool>,
+    ) {
+        let sender = Sender::new(SenderConfig::default());
+        let receiver = Receiver::new(ReceiverConfig::default());
+
+        let mut sender = sender.setup(delta, sender_seeds);
+        let mut receiver = receiver.setup(receiver_seeds);
+
+        let receiver_setup = receiver.extend(choices.len() + 256).unwrap();
+        sender.extend(choices.len() + 256, receiver_setup).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        let mut receiver_keys = receiver.keys(choices.len()).unwrap();
+        let derandomize = receiver_keys.derandomize(&choices).unwrap();
+
+        // Messages of various lengths, up to 1KiB.
+        let mut rng = ChaCha12Rng::seed_from_u64(5);
+        let data: Vec<[Vec<u8>; 2]> = (0..choices.len())
+            .map(|i| {
+                let m0 = (0..i * 8).map(|_| rng.gen()).collect();
+                let m1 = (0..1024 - i * 8).map(|_| rng.gen()).collect();
+                [m0, m1]
+            })
+            .collect();
+
+        let mut sender_keys = sender.keys(data.len()).unwrap();
+        sender_keys.derandomize(derandomize).unwrap();
+        let payload = sender_keys.encrypt_vec(&data).unwrap();
+
+        let received = receiver_keys.decrypt_vec(payload).unwrap();
+
+        let expected = data
+            .into_iter()
+            .zip(choices)
+            .map(|([m0, m1], choice)| if choice { m1 } else { m0 })
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
     #[rstest]
     fn test_kos_extension_stream_extends(
         delta: Block,
@@ -113,4 +113,11 @@ pub enum Ciphertexts {
         /// The length of each message in bytes.
         length: u32,
     },
+    /// Variable-length messages encrypted with a PRG stream cipher
+    VarBytes {
+        /// Sender's ciphertexts
+        ciphertexts: Vec<u8>,
+        /// The length of each pair of messages in bytes.
+        lengths: Vec<[usize; 2]>,
+    },
 }
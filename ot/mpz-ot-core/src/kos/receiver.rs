@@ -4,6 +4,7 @@ use std::{
 };
 
 use crate::{
+    keystream::decrypt_vec,
     kos::{
         error::ReceiverVerifyError,
         msgs::{Check, Ciphertexts, Extend, SenderPayload},
@@ -585,6 +586,37 @@ impl ReceiverKeys {
             .collect())
     }
 
+    /// Decrypts the sender's variable-length payload.
+    ///
+    /// # Verifiable OT
+    ///
+    /// Verifiable OT with KOS does not currently support byte payloads, so no record of this payload
+    /// will be recorded.
+    pub fn decrypt_vec(self, payload: SenderPayload) -> Result<Vec<Vec<u8>>, ReceiverError> {
+        let SenderPayload { id, ciphertexts } = payload;
+
+        let Ciphertexts::VarBytes {
+            ciphertexts,
+            lengths,
+        } = ciphertexts
+        else {
+            return Err(ReceiverError::InvalidPayload(
+                "expected variable-length byte ciphertexts".to_string(),
+            ));
+        };
+
+        if id != self.id {
+            return Err(ReceiverError::IdMismatch(self.id, id));
+        }
+
+        if lengths.len() != self.keys.len() {
+            return Err(ReceiverError::CountMismatch(self.keys.len(), lengths.len()));
+        }
+
+        decrypt_vec(self.keys, self.choices, &ciphertexts, &lengths)
+            .ok_or_else(|| ReceiverError::InvalidPayload("invalid message lengths".to_string()))
+    }
+
     /// Returns the choices and the keys
     pub fn take_choices_and_keys(self) -> (Vec<bool>, Vec<Block>) {
         (self.choices, self.keys)
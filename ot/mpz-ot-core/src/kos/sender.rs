@@ -1,4 +1,5 @@
 use crate::{
+    keystream::encrypt_vec,
     kos::{
         extension_matrix_size,
         msgs::{Check, Ciphertexts, Extend, SenderPayload},
@@ -445,6 +446,36 @@ impl SenderKeys {
         })
     }
 
+    /// Encrypts the provided variable-length messages using the keys.
+    ///
+    /// The lengths of both messages of each pair are revealed to the receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `msgs` - The messages to encrypt
+    pub fn encrypt_vec(self, msgs: &[[Vec<u8>; 2]]) -> Result<SenderPayload, SenderError> {
+        if msgs.len() != self.keys.len() {
+            return Err(SenderError::InsufficientSetup(msgs.len(), self.keys.len()));
+        }
+
+        // If we have derandomization, use it to correct the receiver's choices, else we use
+        // default
+        let flip = self
+            .derandomize
+            .map(|x| x.flip)
+            .unwrap_or_else(|| vec![0; self.keys.len() / 8 + 1]);
+
+        let (ciphertexts, lengths) = encrypt_vec(self.keys, flip.iter_lsb0(), msgs);
+
+        Ok(SenderPayload {
+            id: self.id,
+            ciphertexts: Ciphertexts::VarBytes {
+                ciphertexts,
+                lengths,
+            },
+        })
+    }
+
     /// Returns the keys
     pub fn take_keys(self) -> Vec<[Block; 2]> {
         self.keys
@@ -17,5 +17,9 @@
 pub mod chou_orlandi;
 pub mod ferret;
 pub mod ideal;
+mod keystream;
+pub mod kkrt;
 pub mod kos;
 pub mod msgs;
+pub mod rot;
+pub mod vole;
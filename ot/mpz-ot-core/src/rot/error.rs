@@ -0,0 +1,21 @@
+/// Errors that can occur when using the random OT sender.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+    #[error("id mismatch: expected {0}, got {1}")]
+    IdMismatch(u32, u32),
+}
+
+/// Errors that can occur when using the random OT receiver.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+    #[error("id mismatch: expected {0}, got {1}")]
+    IdMismatch(u32, u32),
+    #[error("invalid payload")]
+    InvalidPayload(String),
+}
@@ -0,0 +1,144 @@
+//! Random OT derived from random correlated OT.
+//!
+//! The correlation of the COTs is broken by hashing each message with a tweakable circular
+//! correlation-robust hash function, tweaked by the index of the OT. Chosen-message OT is
+//! then obtained using Beaver derandomization.
+
+mod error;
+pub mod msgs;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+pub use receiver::{Receiver, ReceiverKeys};
+pub use sender::{Sender, SenderKeys};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpz_core::Block;
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::ideal::ideal_cot::{CotMsgForReceiver, CotMsgForSender, IdealCOT};
+
+    fn setup(count: usize) -> (SenderKeys, ReceiverKeys) {
+        let mut ideal_cot = IdealCOT::new();
+        let mut sender = Sender::new(ideal_cot.delta());
+        let mut receiver = Receiver::new();
+
+        let (CotMsgForSender { qs }, CotMsgForReceiver { rs, ts }) = ideal_cot.extend(count);
+
+        let sender_keys = sender.keys(&qs);
+        let receiver_keys = receiver.keys(rs, &ts).unwrap();
+
+        (sender_keys, receiver_keys)
+    }
+
+    #[test]
+    fn test_rot() {
+        let (sender_keys, receiver_keys) = setup(128);
+
+        let keys = sender_keys.take_keys();
+        let (choices, received) = receiver_keys.take_choices_and_keys();
+
+        let expected = keys
+            .iter()
+            .zip(&choices)
+            .map(|([k0, k1], c)| if *c { *k1 } else { *k0 })
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+
+        // The correlation is broken.
+        let delta = keys[0][0] ^ keys[0][1];
+        assert!(keys.iter().skip(1).all(|[k0, k1]| *k0 ^ *k1 != delta));
+    }
+
+    #[test]
+    fn test_rot_derandomize() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let choices = (0..128).map(|_| rng.gen()).collect::<Vec<bool>>();
+        let data = (0..128)
+            .map(|_| [rng.gen::<Block>(), rng.gen::<Block>()])
+            .collect::<Vec<_>>();
+
+        let (mut sender_keys, mut receiver_keys) = setup(data.len());
+
+        let derandomize = receiver_keys.derandomize(&choices).unwrap();
+        sender_keys.derandomize(derandomize).unwrap();
+
+        let payload = sender_keys.encrypt_blocks(&data).unwrap();
+        let received = receiver_keys.decrypt_blocks(payload).unwrap();
+
+        let expected = data
+            .iter()
+            .zip(&choices)
+            .map(|([m0, m1], c)| if *c { *m1 } else { *m0 })
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn test_rot_vec() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let choices = (0..128).map(|_| rng.gen()).collect::<Vec<bool>>();
+        let data = (0..128)
+            .map(|i| {
+                let m0 = (0..i).map(|_| rng.gen()).collect::<Vec<u8>>();
+                let m1 = (0..2 * i).map(|_| rng.gen()).collect::<Vec<u8>>();
+                [m0, m1]
+            })
+            .collect::<Vec<_>>();
+
+        let (mut sender_keys, mut receiver_keys) = setup(data.len());
+
+        let derandomize = receiver_keys.derandomize(&choices).unwrap();
+        sender_keys.derandomize(derandomize).unwrap();
+
+        let mut payload = sender_keys.encrypt_vec(&data).unwrap();
+        let received = receiver_keys.decrypt_vec(payload.clone()).unwrap();
+
+        let expected = data
+            .iter()
+            .zip(&choices)
+            .map(|([m0, m1], c)| if *c { m1.clone() } else { m0.clone() })
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+
+        // The lengths must match the ciphertexts.
+        let msgs::Ciphertexts::VarBytes { lengths, .. } = &mut payload.ciphertexts else {
+            unreachable!()
+        };
+        lengths[0][0] += 1;
+
+        let (_, receiver_keys) = setup(data.len());
+        assert!(matches!(
+            receiver_keys.decrypt_vec(payload),
+            Err(ReceiverError::InvalidPayload(_))
+        ));
+    }
+
+    #[test]
+    fn test_rot_id_mismatch() {
+        let mut ideal_cot = IdealCOT::new();
+        let mut sender = Sender::new(ideal_cot.delta());
+        let mut receiver = Receiver::new();
+
+        let (CotMsgForSender { qs }, CotMsgForReceiver { rs, ts }) = ideal_cot.extend(8);
+
+        let _ = sender.keys(&qs);
+        let mut sender_keys = sender.keys(&qs);
+        let mut receiver_keys = receiver.keys(rs, &ts).unwrap();
+
+        let derandomize = receiver_keys.derandomize(&[false; 8]).unwrap();
+
+        assert!(matches!(
+            sender_keys.derandomize(derandomize),
+            Err(SenderError::IdMismatch(1, 0))
+        ));
+    }
+}
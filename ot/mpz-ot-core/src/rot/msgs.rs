@@ -0,0 +1,49 @@
+//! Messages for the random OT adapter.
+
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_core::Block;
+use serde::{Deserialize, Serialize};
+
+use crate::msgs::Derandomize;
+
+/// A random OT adapter message.
+#[derive(Debug, Clone, EnumTryAsInner, Serialize, Deserialize)]
+#[derive_err(Debug)]
+#[allow(missing_docs)]
+pub enum Message<CotMsg> {
+    CotMsg(CotMsg),
+    Derandomize(Derandomize),
+    SenderPayload(SenderPayload),
+}
+
+impl<CotMsg> From<MessageError<CotMsg>> for std::io::Error {
+    fn from(err: MessageError<CotMsg>) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+/// Sender payload message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SenderPayload {
+    /// Transfer ID
+    pub id: u32,
+    /// Sender's ciphertexts
+    pub ciphertexts: Ciphertexts,
+}
+
+/// OT ciphertexts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Ciphertexts {
+    /// Messages encrypted with XOR
+    Blocks {
+        /// Sender's ciphertexts
+        ciphertexts: Vec<Block>,
+    },
+    /// Variable-length messages encrypted with a PRG stream cipher
+    VarBytes {
+        /// Sender's ciphertexts
+        ciphertexts: Vec<u8>,
+        /// The length of each pair of messages in bytes.
+        lengths: Vec<[usize; 2]>,
+    },
+}
@@ -0,0 +1,174 @@
+use itybity::FromBitIterator;
+use mpz_core::{aes::FIXED_KEY_AES, Block};
+
+use crate::{
+    keystream::decrypt_vec,
+    msgs::Derandomize,
+    rot::{
+        error::ReceiverError,
+        msgs::{Ciphertexts, SenderPayload},
+    },
+};
+
+/// Random OT receiver.
+///
+/// Derives random OTs from random correlated OTs.
+#[derive(Debug, Default)]
+pub struct Receiver {
+    /// The index of the next OT, used as the tweak of the hash.
+    index: usize,
+    /// Current transfer id.
+    transfer_id: u32,
+}
+
+impl Receiver {
+    /// Creates a new Receiver.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Breaks the correlation of the provided COTs, returning the keys of a single transfer.
+    ///
+    /// Each key is computed as `H(i, t)` where `H` is a tweakable circular correlation-robust
+    /// hash function and `i` the index of the OT.
+    ///
+    /// # Arguments
+    ///
+    /// * `choices` - The choices of the COTs.
+    /// * `ts` - The messages of the COTs.
+    pub fn keys(
+        &mut self,
+        choices: Vec<bool>,
+        ts: &[Block],
+    ) -> Result<ReceiverKeys, ReceiverError> {
+        if choices.len() != ts.len() {
+            return Err(ReceiverError::CountMismatch(ts.len(), choices.len()));
+        }
+
+        let cipher = &(*FIXED_KEY_AES);
+        let keys = ts
+            .iter()
+            .enumerate()
+            .map(|(j, t)| {
+                let j = Block::new(((self.index + j) as u128).to_be_bytes());
+
+                cipher.tccr(j, *t)
+            })
+            .collect::<Vec<_>>();
+
+        self.index += keys.len();
+
+        let id = self.transfer_id;
+        self.transfer_id += 1;
+
+        Ok(ReceiverKeys { id, keys, choices })
+    }
+}
+
+/// Random OT receiver's keys for a single transfer.
+///
+/// Returned by the [`Receiver::keys`] method.
+pub struct ReceiverKeys {
+    /// Transfer ID
+    id: u32,
+    /// Decryption keys
+    keys: Vec<Block>,
+    /// The Receiver's choices. If derandomization is performed, these are the overwritten
+    /// with the derandomized choices.
+    choices: Vec<bool>,
+}
+
+opaque_debug::implement!(ReceiverKeys);
+
+impl ReceiverKeys {
+    /// Returns the transfer ID.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Derandomizes the receiver's choices.
+    pub fn derandomize(&mut self, choices: &[bool]) -> Result<Derandomize, ReceiverError> {
+        if choices.len() != self.choices.len() {
+            return Err(ReceiverError::CountMismatch(
+                self.choices.len(),
+                choices.len(),
+            ));
+        }
+
+        let derandomize = Derandomize {
+            id: self.id,
+            count: self.choices.len() as u32,
+            flip: Vec::<u8>::from_lsb0_iter(
+                self.choices
+                    .iter()
+                    .zip(choices)
+                    .map(|(setup_choice, new_choice)| setup_choice ^ new_choice),
+            ),
+        };
+
+        self.choices.copy_from_slice(choices);
+
+        Ok(derandomize)
+    }
+
+    /// Decrypts the sender's payload.
+    pub fn decrypt_blocks(self, payload: SenderPayload) -> Result<Vec<Block>, ReceiverError> {
+        let SenderPayload { id, ciphertexts } = payload;
+
+        let Ciphertexts::Blocks { ciphertexts } = ciphertexts else {
+            return Err(ReceiverError::InvalidPayload(
+                "expected block ciphertexts".to_string(),
+            ));
+        };
+
+        if id != self.id {
+            return Err(ReceiverError::IdMismatch(self.id, id));
+        }
+
+        if ciphertexts.len() != 2 * self.keys.len() {
+            return Err(ReceiverError::CountMismatch(
+                self.keys.len(),
+                ciphertexts.len() / 2,
+            ));
+        }
+
+        Ok(self
+            .keys
+            .into_iter()
+            .zip(self.choices)
+            .zip(ciphertexts.chunks(2))
+            .map(|((key, c), ct)| if c { key ^ ct[1] } else { key ^ ct[0] })
+            .collect())
+    }
+
+    /// Decrypts the sender's variable-length payload.
+    pub fn decrypt_vec(self, payload: SenderPayload) -> Result<Vec<Vec<u8>>, ReceiverError> {
+        let SenderPayload { id, ciphertexts } = payload;
+
+        let Ciphertexts::VarBytes {
+            ciphertexts,
+            lengths,
+        } = ciphertexts
+        else {
+            return Err(ReceiverError::InvalidPayload(
+                "expected variable-length byte ciphertexts".to_string(),
+            ));
+        };
+
+        if id != self.id {
+            return Err(ReceiverError::IdMismatch(self.id, id));
+        }
+
+        if lengths.len() != self.keys.len() {
+            return Err(ReceiverError::CountMismatch(self.keys.len(), lengths.len()));
+        }
+
+        decrypt_vec(self.keys, self.choices, &ciphertexts, &lengths)
+            .ok_or_else(|| ReceiverError::InvalidPayload("invalid message lengths".to_string()))
+    }
+
+    /// Returns the choices and the keys.
+    pub fn take_choices_and_keys(self) -> (Vec<bool>, Vec<Block>) {
+        (self.choices, self.keys)
+    }
+}
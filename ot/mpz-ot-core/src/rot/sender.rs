@@ -0,0 +1,187 @@
+use itybity::ToBits;
+use mpz_core::{aes::FIXED_KEY_AES, Block};
+
+use crate::{
+    keystream::encrypt_vec,
+    msgs::Derandomize,
+    rot::{
+        error::SenderError,
+        msgs::{Ciphertexts, SenderPayload},
+    },
+};
+
+/// Random OT sender.
+///
+/// Derives random OTs from random correlated OTs with global correlation `delta`.
+#[derive(Debug)]
+pub struct Sender {
+    /// The global correlation of the COTs.
+    delta: Block,
+    /// The index of the next OT, used as the tweak of the hash.
+    index: usize,
+    /// Current transfer id.
+    transfer_id: u32,
+}
+
+impl Sender {
+    /// Creates a new Sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The global correlation of the COTs.
+    pub fn new(delta: Block) -> Self {
+        Self {
+            delta,
+            index: 0,
+            transfer_id: 0,
+        }
+    }
+
+    /// Returns the global correlation of the COTs.
+    pub fn delta(&self) -> Block {
+        self.delta
+    }
+
+    /// Breaks the correlation of the provided COTs, returning the keys of a single transfer.
+    ///
+    /// Each pair of keys is computed as `(H(i, q), H(i, q ^ delta))` where `H` is a tweakable
+    /// circular correlation-robust hash function and `i` the index of the OT.
+    ///
+    /// # Arguments
+    ///
+    /// * `qs` - The `0`-bit messages of the COTs.
+    pub fn keys(&mut self, qs: &[Block]) -> SenderKeys {
+        let cipher = &(*FIXED_KEY_AES);
+        let keys = qs
+            .iter()
+            .enumerate()
+            .map(|(j, q)| {
+                let j = Block::new(((self.index + j) as u128).to_be_bytes());
+
+                [cipher.tccr(j, *q), cipher.tccr(j, *q ^ self.delta)]
+            })
+            .collect::<Vec<_>>();
+
+        self.index += keys.len();
+
+        let id = self.transfer_id;
+        self.transfer_id += 1;
+
+        SenderKeys {
+            id,
+            keys,
+            derandomize: None,
+        }
+    }
+}
+
+/// Random OT sender's keys for a single transfer.
+///
+/// Returned by the [`Sender::keys`] method.
+pub struct SenderKeys {
+    /// Transfer ID
+    id: u32,
+    /// Encryption keys
+    keys: Vec<[Block; 2]>,
+    /// Derandomization
+    derandomize: Option<Derandomize>,
+}
+
+opaque_debug::implement!(SenderKeys);
+
+impl SenderKeys {
+    /// Returns the transfer ID.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Applies Beaver derandomization to correct the receiver's random choices.
+    pub fn derandomize(&mut self, derandomize: Derandomize) -> Result<(), SenderError> {
+        if derandomize.id != self.id {
+            return Err(SenderError::IdMismatch(self.id, derandomize.id));
+        }
+
+        if derandomize.count as usize != self.keys.len() {
+            return Err(SenderError::CountMismatch(
+                self.keys.len(),
+                derandomize.count as usize,
+            ));
+        }
+
+        self.derandomize = Some(derandomize);
+
+        Ok(())
+    }
+
+    /// Encrypts the provided messages using the keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `msgs` - The messages to encrypt
+    pub fn encrypt_blocks(self, msgs: &[[Block; 2]]) -> Result<SenderPayload, SenderError> {
+        if msgs.len() != self.keys.len() {
+            return Err(SenderError::CountMismatch(self.keys.len(), msgs.len()));
+        }
+
+        // If we have derandomization, use it to correct the receiver's choices, else we use
+        // default
+        let flip = self
+            .derandomize
+            .map(|x| x.flip)
+            .unwrap_or_else(|| vec![0; self.keys.len() / 8 + 1]);
+
+        let ciphertexts = self
+            .keys
+            .into_iter()
+            .zip(msgs)
+            .zip(flip.iter_lsb0())
+            .flat_map(|(([k0, k1], [m0, m1]), flip)| {
+                if flip {
+                    [k1 ^ *m0, k0 ^ *m1]
+                } else {
+                    [k0 ^ *m0, k1 ^ *m1]
+                }
+            })
+            .collect();
+
+        Ok(SenderPayload {
+            id: self.id,
+            ciphertexts: Ciphertexts::Blocks { ciphertexts },
+        })
+    }
+
+    /// Encrypts the provided variable-length messages using the keys.
+    ///
+    /// The lengths of both messages of each pair are revealed to the receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `msgs` - The messages to encrypt
+    pub fn encrypt_vec(self, msgs: &[[Vec<u8>; 2]]) -> Result<SenderPayload, SenderError> {
+        if msgs.len() != self.keys.len() {
+            return Err(SenderError::CountMismatch(self.keys.len(), msgs.len()));
+        }
+
+        // If we have derandomization, use it to correct the receiver's choices, else we use
+        // default
+        let flip = self
+            .derandomize
+            .map(|x| x.flip)
+            .unwrap_or_else(|| vec![0; self.keys.len() / 8 + 1]);
+
+        let (ciphertexts, lengths) = encrypt_vec(self.keys, flip.iter_lsb0(), msgs);
+
+        Ok(SenderPayload {
+            id: self.id,
+            ciphertexts: Ciphertexts::VarBytes {
+                ciphertexts,
+                lengths,
+            },
+        })
+    }
+
+    /// Returns the keys
+    pub fn take_keys(self) -> Vec<[Block; 2]> {
+        self.keys
+    }
+}
@@ -0,0 +1,113 @@
+use derive_builder::Builder;
+use mpz_core::lpn::LpnParameters;
+
+use crate::ferret::{params, LpnType};
+
+/// VOLE configuration.
+///
+/// The sender and receiver must use the same configuration.
+///
+/// The LPN parameters, including the default, are those of LPN over GF(2) as used by Ferret.
+/// They are applied as-is over the field of the VOLE, their security has not been analysed for
+/// other fields.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct VoleConfig {
+    /// The LPN parameters, the noise is always regular.
+    #[builder(default = "params::LPN_PARAMETERS_REGULAR_2_20")]
+    lpn_parameters: LpnParameters,
+}
+
+impl VoleConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(lpn_parameters) = &self.lpn_parameters {
+            params::validate(lpn_parameters, LpnType::Regular).map_err(|err| err.to_string())?;
+
+            let LpnParameters { n, k, t } = *lpn_parameters;
+
+            let interval = n / t;
+            if !interval.is_power_of_two() || interval < 4 {
+                return Err(format!(
+                    "invalid LPN parameters: n / t = {interval} should be a power of two of at least 4"
+                ));
+            }
+
+            if n <= k + t {
+                return Err(format!(
+                    "invalid LPN parameters: n = {n} should be greater than k + t = {}",
+                    k + t
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl VoleConfig {
+    /// Creates a new builder for VoleConfig.
+    pub fn builder() -> VoleConfigBuilder {
+        VoleConfigBuilder::default()
+    }
+
+    /// Returns the LPN parameters.
+    pub fn lpn_parameters(&self) -> LpnParameters {
+        self.lpn_parameters
+    }
+
+    /// Returns the number of VOLEs which are reserved from each extension as the base VOLEs of
+    /// the next one, ie. `k + t`.
+    pub fn reserved(&self) -> usize {
+        self.lpn_parameters.k + self.lpn_parameters.t
+    }
+
+    /// Returns the number of VOLEs output by each extension, ie. `n - k - t`.
+    pub fn output_len(&self) -> usize {
+        self.lpn_parameters.n - self.reserved()
+    }
+
+    /// Returns the depth of the GGM trees, ie. `log2(n / t)`.
+    pub(crate) fn tree_depth(&self) -> usize {
+        (self.lpn_parameters.n / self.lpn_parameters.t).trailing_zeros() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_validates_lpn_parameters() {
+        assert!(VoleConfig::builder().build().is_ok());
+
+        // Not divisible by t.
+        assert!(VoleConfig::builder()
+            .lpn_parameters(LpnParameters {
+                n: 9600,
+                k: 1220,
+                t: 700,
+            })
+            .build()
+            .is_err());
+
+        // n / t is not a power of two.
+        assert!(VoleConfig::builder()
+            .lpn_parameters(LpnParameters {
+                n: 9000,
+                k: 1220,
+                t: 600,
+            })
+            .build()
+            .is_err());
+
+        // No VOLEs left after reserving the base VOLEs.
+        assert!(VoleConfig::builder()
+            .lpn_parameters(LpnParameters {
+                n: 1024,
+                k: 1000,
+                t: 64,
+            })
+            .build()
+            .is_err());
+    }
+}
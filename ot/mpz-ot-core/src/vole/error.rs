@@ -0,0 +1,17 @@
+/// Errors that can occur when using the VOLE sender.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+}
+
+/// Errors that can occur when using the VOLE receiver.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+    #[error("no extension in progress")]
+    NotExtending,
+}
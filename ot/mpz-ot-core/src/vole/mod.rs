@@ -0,0 +1,133 @@
+//! An implementation of random vector oblivious linear evaluation (VOLE) over any field, using
+//! the LPN-based extension of [`Ferret`](https://eprint.iacr.org/2020/924.pdf) generalized as in
+//! [`Wolverine`](https://eprint.iacr.org/2020/925.pdf).
+//!
+//! The sender holds a global correlation `Δ` and receives `v`, the receiver receives `u` and
+//! `w = u·Δ + v`, where all of `Δ`, `u`, `v` and `w` are elements of the same field.
+//!
+//! The `k + t` base VOLEs of the first extension are bootstrapped with Gilboa multiplication
+//! over a chosen-message OT, each subsequent extension reserves the base VOLEs of the next one.
+//! The single-point VOLEs are built from GGM trees, whose keys are also transferred using the
+//! base OT.
+//!
+//! # ⚠️ Warning ⚠️
+//!
+//! This implementation is only secure against semi-honest adversaries.
+
+mod config;
+mod error;
+pub mod msgs;
+mod receiver;
+mod sender;
+
+pub use config::{VoleConfig, VoleConfigBuilder, VoleConfigBuilderError};
+pub use error::{ReceiverError, SenderError};
+pub use receiver::{state as receiver_state, Receiver};
+pub use sender::{state as sender_state, Sender};
+
+use mpz_core::{lpn::LpnEncoder, prg::Prg, Block};
+use mpz_fields::Field;
+use rand_core::SeedableRng;
+
+/// The number of non-zero entries in each row of the LPN matrix.
+const LPN_D: usize = 10;
+
+/// Creates the LPN encoder from the seed sampled by the receiver.
+fn lpn_encoder(seed: Block, k: usize) -> LpnEncoder<LPN_D> {
+    LpnEncoder::new(seed, k as u32)
+}
+
+/// Maps a leaf of a GGM tree to a field element.
+#[inline]
+fn leaf_to_field<T: Field>(leaf: Block) -> T {
+    T::rand(&mut Prg::from_seed(leaf))
+}
+
+/// Computes `Ax + y` over the field, writing the result in-place into `y`.
+fn lpn_compute<T: Field>(encoder: &LpnEncoder<LPN_D>, y: &mut [T], x: &[T]) {
+    encoder.compute_with(y, x, |a, b| a + b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256};
+    use rand::thread_rng;
+    use rstest::*;
+
+    use crate::ferret::params::LPN_PARAMETERS_TEST;
+
+    fn config() -> VoleConfig {
+        VoleConfig::builder()
+            .lpn_parameters(LPN_PARAMETERS_TEST)
+            .build()
+            .unwrap()
+    }
+
+    fn choose<T: Copy>(msgs: &[[T; 2]], choices: &[bool]) -> Vec<T> {
+        msgs.iter()
+            .zip(choices)
+            .map(|(msg, choice)| msg[*choice as usize])
+            .collect()
+    }
+
+    fn check<T: Field>(delta: T, v: &[T], u: &[T], w: &[T]) {
+        assert!(!v.is_empty());
+        assert_eq!(v.len(), u.len());
+        assert_eq!(v.len(), w.len());
+        assert!(v
+            .iter()
+            .zip(u)
+            .zip(w)
+            .all(|((v, u), w)| *w == *u * delta + *v));
+    }
+
+    #[rstest]
+    #[case::gf2_128(Gf2_128::zero())]
+    #[case::p256(P256::zero())]
+    fn test_vole<T: Field>(#[case] _field: T) {
+        let mut rng = thread_rng();
+        let config = config();
+
+        let delta = T::rand(&mut rng);
+        let seed = Block::random(&mut rng);
+
+        let sender = Sender::new(config.clone());
+        let receiver = Receiver::new(config.clone());
+
+        let (v, base_msgs) = sender.base_vole(&mut rng, delta);
+        let (u, choices) = receiver.base_vole_choices::<T, _>(&mut rng);
+        let received = choose(&base_msgs, &choices);
+
+        let mut sender = sender.setup(delta, seed, v).unwrap();
+        let mut receiver = receiver.setup(seed, u, &received).unwrap();
+
+        for _ in 0..2 {
+            let (extend, choices) = receiver.start_extend(&mut rng);
+            let (keys, extend, v) = sender.extend(extend).unwrap();
+            let keys = choose(&keys, &choices);
+            let (u, w) = receiver.extend(&keys, extend).unwrap();
+
+            assert_eq!(v.len(), config.output_len());
+            check(delta, &v, &u, &w);
+        }
+    }
+
+    #[test]
+    fn test_vole_extend_without_start() {
+        let mut rng = thread_rng();
+        let config = config();
+
+        let receiver = Receiver::new(config.clone());
+        let (u, _) = receiver.base_vole_choices::<Gf2_128, _>(&mut rng);
+        let received = vec![Gf2_128::zero(); u.len() * Gf2_128::BIT_SIZE as usize];
+        let mut receiver = receiver.setup(Block::ZERO, u, &received).unwrap();
+
+        let err = receiver
+            .extend(&[], msgs::ExtendFromSender { sums: Vec::new() })
+            .unwrap_err();
+
+        assert!(matches!(err, ReceiverError::NotExtending));
+    }
+}
@@ -0,0 +1,43 @@
+//! Messages for the VOLE protocol.
+
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_core::Block;
+use serde::{Deserialize, Serialize};
+
+/// A VOLE protocol message.
+#[derive(Debug, Clone, EnumTryAsInner, Serialize, Deserialize)]
+#[derive_err(Debug)]
+#[allow(missing_docs)]
+pub enum Message<T, BaseMsg> {
+    BaseMsg(BaseMsg),
+    LpnMatrixSeed(LpnMatrixSeed),
+    ExtendFromReceiver(ExtendFromReceiver<T>),
+    ExtendFromSender(ExtendFromSender<T>),
+}
+
+impl<T, BaseMsg> From<MessageError<T, BaseMsg>> for std::io::Error {
+    fn from(err: MessageError<T, BaseMsg>) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+/// The seed of the LPN matrix, sampled by the receiver during the setup.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LpnMatrixSeed {
+    /// The seed.
+    pub seed: Block,
+}
+
+/// Extension message sent by the receiver.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtendFromReceiver<T> {
+    /// The noise values of each interval, masked with the receiver's base VOLEs.
+    pub betas: Vec<T>,
+}
+
+/// Extension message sent by the sender.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtendFromSender<T> {
+    /// The correction of the punctured leaf of each GGM tree.
+    pub sums: Vec<T>,
+}
@@ -0,0 +1,275 @@
+use crate::vole::{
+    leaf_to_field, lpn_compute, lpn_encoder,
+    msgs::{ExtendFromReceiver, ExtendFromSender},
+    ReceiverError, VoleConfig, LPN_D,
+};
+
+use itybity::{GetBit, Lsb0};
+use mpz_core::{ggm_tree::GgmTree, lpn::LpnEncoder, Block};
+use mpz_fields::Field;
+use rand::{CryptoRng, Rng};
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        use rayon::prelude::*;
+    }
+}
+
+/// VOLE receiver.
+#[derive(Debug)]
+pub struct Receiver<T: state::State = state::Initialized> {
+    config: VoleConfig,
+    state: T,
+}
+
+impl Receiver {
+    /// Creates a new Receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The VOLE configuration.
+    pub fn new(config: VoleConfig) -> Self {
+        Receiver {
+            config,
+            state: state::Initialized {},
+        }
+    }
+
+    /// Samples the receiver's values `u` of the base VOLEs of the first extension, returning
+    /// them along with the choice bits of the chosen-message OTs which bootstrap them.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator.
+    pub fn base_vole_choices<T: Field, R: Rng + CryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> (Vec<T>, Vec<bool>) {
+        let u = (0..self.config.reserved())
+            .map(|_| T::rand(rng))
+            .collect::<Vec<_>>();
+
+        let choices = u
+            .iter()
+            .flat_map(|u| (0..T::BIT_SIZE as usize).map(|i| GetBit::<Lsb0>::get_bit(u, i)))
+            .collect();
+
+        (u, choices)
+    }
+
+    /// Completes the setup.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the LPN matrix.
+    /// * `u` - The receiver's values of the base VOLEs.
+    /// * `received` - The messages received from the bootstrapping OTs.
+    pub fn setup<T: Field>(
+        self,
+        seed: Block,
+        u: Vec<T>,
+        received: &[T],
+    ) -> Result<Receiver<state::Extension<T>>, ReceiverError> {
+        let count = self.config.reserved();
+        if u.len() != count {
+            return Err(ReceiverError::CountMismatch(count, u.len()));
+        }
+
+        let bits = T::BIT_SIZE as usize;
+        if received.len() != count * bits {
+            return Err(ReceiverError::CountMismatch(count * bits, received.len()));
+        }
+
+        let w = received
+            .chunks_exact(bits)
+            .map(|chunk| chunk.iter().fold(T::zero(), |acc, m| acc + *m))
+            .collect();
+
+        let lpn_encoder = lpn_encoder(seed, self.config.lpn_parameters().k);
+
+        Ok(Receiver {
+            config: self.config,
+            state: state::Extension {
+                lpn_encoder,
+                u,
+                w,
+                noise: None,
+            },
+        })
+    }
+}
+
+impl<T: Field> Receiver<state::Extension<T>> {
+    /// Starts an extension by sampling the noise, returning the extension message and the
+    /// choice bits of the OTs which transfer the GGM tree keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator.
+    pub fn start_extend<R: Rng + CryptoRng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> (ExtendFromReceiver<T>, Vec<bool>) {
+        let lpn_parameters = self.config.lpn_parameters();
+        let (k, t) = (lpn_parameters.k, lpn_parameters.t);
+        let depth = self.config.tree_depth();
+
+        let mut alphas = Vec::with_capacity(t);
+        let mut betas = Vec::with_capacity(t);
+        let mut masked_betas = Vec::with_capacity(t);
+        for u in &self.state.u[k..] {
+            let beta = loop {
+                let beta = T::rand(rng);
+                if beta != T::zero() {
+                    break beta;
+                }
+            };
+
+            alphas.push(rng.gen_range(0..1 << depth));
+            betas.push(beta);
+            masked_betas.push(beta + -*u);
+        }
+
+        let choices = alphas
+            .iter()
+            .flat_map(|alpha| tree_choices(*alpha, depth))
+            .collect();
+
+        self.state.noise = Some((alphas, betas));
+
+        (
+            ExtendFromReceiver {
+                betas: masked_betas,
+            },
+            choices,
+        )
+    }
+
+    /// Completes the extension, returning the receiver's outputs `u` and `w`.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The GGM tree keys received using the choices of [`Receiver::start_extend`].
+    /// * `extend` - The sender's extension message.
+    pub fn extend(
+        &mut self,
+        keys: &[Block],
+        extend: ExtendFromSender<T>,
+    ) -> Result<(Vec<T>, Vec<T>), ReceiverError> {
+        let (alphas, betas) = self.state.noise.take().ok_or(ReceiverError::NotExtending)?;
+
+        let lpn_parameters = self.config.lpn_parameters();
+        let (k, t) = (lpn_parameters.k, lpn_parameters.t);
+        let depth = self.config.tree_depth();
+
+        let ExtendFromSender { sums } = extend;
+        if sums.len() != t {
+            return Err(ReceiverError::CountMismatch(t, sums.len()));
+        }
+
+        if keys.len() != t * depth {
+            return Err(ReceiverError::CountMismatch(t * depth, keys.len()));
+        }
+
+        let (u_lpn, _) = self.state.u.split_at(k);
+        let (w_lpn, w_noise) = self.state.w.split_at(k);
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = keys
+                    .par_chunks_exact(depth)
+                    .zip(alphas.par_iter().copied().zip(sums))
+                    .zip(w_noise.par_iter());
+            } else {
+                let iter = keys
+                    .chunks_exact(depth)
+                    .zip(alphas.iter().copied().zip(sums))
+                    .zip(w_noise.iter());
+            }
+        }
+
+        let trees = iter
+            .map(|((keys, (alpha, sum)), w)| {
+                let ggm = GgmTree::new(depth);
+                let mut tree = vec![Block::ZERO; 1 << depth];
+                ggm.reconstruct(&mut tree, keys, &tree_choices(alpha, depth));
+
+                let mut leaves = tree.into_iter().map(leaf_to_field).collect::<Vec<T>>();
+                leaves[alpha] = T::zero();
+
+                // The punctured leaf is shifted by `beta·Δ` from the sender's leaf.
+                let punctured = leaves.iter().fold(*w + sum, |acc, leaf| acc + -*leaf);
+                leaves[alpha] = punctured;
+
+                leaves
+            })
+            .collect::<Vec<_>>();
+
+        let n = lpn_parameters.n;
+        let interval = n / t;
+        let mut u = vec![T::zero(); n];
+        for (i, (alpha, beta)) in alphas.into_iter().zip(betas).enumerate() {
+            u[i * interval + alpha] = beta;
+        }
+        let mut w = trees.into_iter().flatten().collect::<Vec<_>>();
+
+        lpn_compute(&self.state.lpn_encoder, &mut u, u_lpn);
+        lpn_compute(&self.state.lpn_encoder, &mut w, w_lpn);
+
+        // Reserve the base VOLEs of the next extension.
+        let reserved = self.config.reserved();
+        let u_out = u.split_off(reserved);
+        let w_out = w.split_off(reserved);
+        self.state.u = u;
+        self.state.w = w;
+
+        Ok((u_out, w_out))
+    }
+}
+
+/// Returns the OT choices of the GGM tree keys for the punctured index `alpha`, ie. the
+/// complement of the bits of `alpha`, most significant first.
+fn tree_choices(alpha: usize, depth: usize) -> Vec<bool> {
+    (0..depth).rev().map(|i| (alpha >> i) & 1 == 0).collect()
+}
+
+/// The receiver's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl<T> Sealed for super::Extension<T> {}
+    }
+
+    /// The receiver's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The receiver's initial state.
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The receiver's state after the setup phase.
+    ///
+    /// In this state the receiver performs the extensions.
+    pub struct Extension<T> {
+        /// The LPN encoder.
+        pub(super) lpn_encoder: LpnEncoder<LPN_D>,
+        /// The receiver's values `u` of the base VOLEs of the next extension.
+        pub(super) u: Vec<T>,
+        /// The receiver's values `w` of the base VOLEs of the next extension.
+        pub(super) w: Vec<T>,
+        /// The punctured indices and noise values of the extension in progress.
+        #[allow(clippy::type_complexity)]
+        pub(super) noise: Option<(Vec<usize>, Vec<T>)>,
+    }
+
+    impl<T> State for Extension<T> {}
+
+    opaque_debug::implement!(Extension<T>);
+}
@@ -0,0 +1,218 @@
+use crate::vole::{
+    leaf_to_field, lpn_compute, lpn_encoder,
+    msgs::{ExtendFromReceiver, ExtendFromSender},
+    SenderError, VoleConfig, LPN_D,
+};
+
+use mpz_core::{ggm_tree::GgmTree, lpn::LpnEncoder, prg::Prg, Block};
+use mpz_fields::Field;
+use rand::{CryptoRng, Rng};
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        use rayon::prelude::*;
+    }
+}
+
+/// VOLE sender.
+#[derive(Debug)]
+pub struct Sender<T: state::State = state::Initialized> {
+    config: VoleConfig,
+    state: T,
+}
+
+impl Sender {
+    /// Creates a new Sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The VOLE configuration.
+    pub fn new(config: VoleConfig) -> Self {
+        Sender {
+            config,
+            state: state::Initialized {},
+        }
+    }
+
+    /// Returns the chosen-message OTs which bootstrap the base VOLEs of the first extension,
+    /// along with the sender's values `v` of those VOLEs.
+    ///
+    /// For each base VOLE the sender offers `(r_i, r_i + 2^i·Δ)` for every bit `i` of the
+    /// receiver's `u`, and `v` is the sum of the `r_i`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator.
+    /// * `delta` - The global correlation.
+    pub fn base_vole<T: Field, R: Rng + CryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+        delta: T,
+    ) -> (Vec<T>, Vec<[T; 2]>) {
+        let count = self.config.reserved();
+        let bits = T::BIT_SIZE as usize;
+
+        let mut v = Vec::with_capacity(count);
+        let mut msgs = Vec::with_capacity(count * bits);
+        for _ in 0..count {
+            let mut sum = T::zero();
+            for i in 0..bits {
+                let r = T::rand(rng);
+                msgs.push([r, r + delta * T::two_pow(i as u32)]);
+                sum = sum + r;
+            }
+            v.push(sum);
+        }
+
+        (v, msgs)
+    }
+
+    /// Completes the setup.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The global correlation.
+    /// * `seed` - The seed of the LPN matrix, sampled by the receiver.
+    /// * `v` - The sender's values of the base VOLEs.
+    pub fn setup<T: Field>(
+        self,
+        delta: T,
+        seed: Block,
+        v: Vec<T>,
+    ) -> Result<Sender<state::Extension<T>>, SenderError> {
+        if v.len() != self.config.reserved() {
+            return Err(SenderError::CountMismatch(self.config.reserved(), v.len()));
+        }
+
+        let lpn_encoder = lpn_encoder(seed, self.config.lpn_parameters().k);
+
+        Ok(Sender {
+            config: self.config,
+            state: state::Extension {
+                delta,
+                lpn_encoder,
+                base: v,
+            },
+        })
+    }
+}
+
+impl<T: Field> Sender<state::Extension<T>> {
+    /// Returns the global correlation.
+    pub fn delta(&self) -> T {
+        self.state.delta
+    }
+
+    /// Performs an extension, returning the GGM tree keys which must be transferred to the
+    /// receiver using chosen-message OT, the extension message and the sender's outputs `v`.
+    ///
+    /// The keys are ordered by tree and then by level, for each level the receiver chooses with
+    /// the complement of the corresponding bit of its punctured index, most significant first.
+    ///
+    /// # Arguments
+    ///
+    /// * `extend` - The receiver's extension message.
+    #[allow(clippy::type_complexity)]
+    pub fn extend(
+        &mut self,
+        extend: ExtendFromReceiver<T>,
+    ) -> Result<(Vec<[Block; 2]>, ExtendFromSender<T>, Vec<T>), SenderError> {
+        let lpn_parameters = self.config.lpn_parameters();
+        let (k, t) = (lpn_parameters.k, lpn_parameters.t);
+        let depth = self.config.tree_depth();
+
+        let ExtendFromReceiver { betas } = extend;
+        if betas.len() != t {
+            return Err(SenderError::CountMismatch(t, betas.len()));
+        }
+
+        let delta = self.state.delta;
+        let (v_lpn, v_noise) = self.state.base.split_at(k);
+
+        let mut prg = Prg::new();
+        let seeds = (0..t).map(|_| prg.random_block()).collect::<Vec<_>>();
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = seeds.into_par_iter().zip(v_noise.par_iter().zip(betas));
+            } else {
+                let iter = seeds.into_iter().zip(v_noise.iter().zip(betas));
+            }
+        }
+
+        let trees = iter
+            .map(|(seed, (v, beta))| {
+                let ggm = GgmTree::new(depth);
+                let mut tree = vec![Block::ZERO; 1 << depth];
+                let mut k0 = vec![Block::ZERO; depth];
+                let mut k1 = vec![Block::ZERO; depth];
+                ggm.gen(seed, &mut tree, &mut k0, &mut k1);
+
+                let leaves = tree.into_iter().map(leaf_to_field).collect::<Vec<T>>();
+
+                // The receiver holds `w`, the sender `gamma = w - beta·Δ`.
+                let gamma = *v + -(beta * delta);
+                let sum = leaves.iter().fold(-gamma, |acc, leaf| acc + *leaf);
+
+                let keys = k0.into_iter().zip(k1).map(|(k0, k1)| [k0, k1]);
+
+                (keys.collect::<Vec<_>>(), leaves, sum)
+            })
+            .collect::<Vec<_>>();
+
+        let mut keys = Vec::with_capacity(t * depth);
+        let mut sums = Vec::with_capacity(t);
+        let mut v = Vec::with_capacity(lpn_parameters.n);
+        for (tree_keys, leaves, sum) in trees {
+            keys.extend(tree_keys);
+            v.extend(leaves);
+            sums.push(sum);
+        }
+
+        lpn_compute(&self.state.lpn_encoder, &mut v, v_lpn);
+
+        // Reserve the base VOLEs of the next extension.
+        let output = v.split_off(self.config.reserved());
+        self.state.base = v;
+
+        Ok((keys, ExtendFromSender { sums }, output))
+    }
+}
+
+/// The sender's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl<T> Sealed for super::Extension<T> {}
+    }
+
+    /// The sender's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The sender's initial state.
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The sender's state after the setup phase.
+    ///
+    /// In this state the sender performs the extensions.
+    pub struct Extension<T> {
+        /// The global correlation.
+        pub(super) delta: T,
+        /// The LPN encoder.
+        pub(super) lpn_encoder: LpnEncoder<LPN_D>,
+        /// The base VOLEs of the next extension.
+        pub(super) base: Vec<T>,
+    }
+
+    impl<T> State for Extension<T> {}
+
+    opaque_debug::implement!(Extension<T>);
+}
@@ -0,0 +1,91 @@
+use mpz_ot_core::ferret::msgs::MessageError;
+
+use crate::OTError;
+
+/// A Ferret sender error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::ferret::error::SenderError),
+    #[error(transparent)]
+    SpcotError(#[from] mpz_ot_core::ferret::spcot::error::SenderError),
+    #[error(transparent)]
+    MpcotError(#[from] mpz_ot_core::ferret::mpcot::error::SenderError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<SenderError> for OTError {
+    fn from(err: SenderError) -> Self {
+        match err {
+            SenderError::IOError(e) => e.into(),
+            e => OTError::SenderError(Box::new(e)),
+        }
+    }
+}
+
+impl From<crate::ferret::SenderStateError> for SenderError {
+    fn from(err: crate::ferret::SenderStateError) -> Self {
+        SenderError::StateError(err.to_string())
+    }
+}
+
+impl<BaseMsg> From<MessageError<BaseMsg>> for SenderError {
+    fn from(err: MessageError<BaseMsg>) -> Self {
+        SenderError::from(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            err.to_string(),
+        ))
+    }
+}
+
+/// A Ferret receiver error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::ferret::error::ReceiverError),
+    #[error(transparent)]
+    SpcotError(#[from] mpz_ot_core::ferret::spcot::error::ReceiverError),
+    #[error(transparent)]
+    MpcotError(#[from] mpz_ot_core::ferret::mpcot::error::ReceiverError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<ReceiverError> for OTError {
+    fn from(err: ReceiverError) -> Self {
+        match err {
+            ReceiverError::IOError(e) => e.into(),
+            e => OTError::ReceiverError(Box::new(e)),
+        }
+    }
+}
+
+impl From<crate::ferret::ReceiverStateError> for ReceiverError {
+    fn from(err: crate::ferret::ReceiverStateError) -> Self {
+        ReceiverError::StateError(err.to_string())
+    }
+}
+
+impl<BaseMsg> From<MessageError<BaseMsg>> for ReceiverError {
+    fn from(err: MessageError<BaseMsg>) -> Self {
+        ReceiverError::from(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            err.to_string(),
+        ))
+    }
+}
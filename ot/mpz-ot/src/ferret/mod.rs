@@ -0,0 +1,261 @@
+//! An implementation of the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) correlated oblivious transfer extension protocol.
+//!
+//! The base COTs are bootstrapped from a chosen-message OT, eg. [`KOS`](crate::kos). The base OT
+//! is only used for the setup and the first extension, each extension reserves the COTs required
+//! by the next one.
+
+mod error;
+mod receiver;
+mod sender;
+mod shared;
+
+pub use error::{ReceiverError, SenderError};
+use futures_util::{SinkExt, StreamExt};
+pub use receiver::Receiver;
+pub use sender::Sender;
+pub use shared::{SharedReceiver, SharedSender};
+
+pub(crate) use receiver::StateError as ReceiverStateError;
+pub(crate) use sender::StateError as SenderStateError;
+
+pub use mpz_ot_core::ferret::{
+    msgs, params, FerretConfig, FerretConfigBuilder, FerretConfigBuilderError, LpnType,
+    LPN_PARAMETERS_REGULAR, LPN_PARAMETERS_UNIFORM,
+};
+use utils_aio::{sink::IoSink, stream::IoStream};
+
+/// Converts a sink of Ferret messages into a sink of base OT messages.
+pub(crate) fn into_base_sink<'a, Si: IoSink<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    sink: &'a mut Si,
+) -> impl IoSink<T> + Send + Unpin + 'a {
+    Box::pin(SinkExt::with(sink, |msg| async move {
+        Ok(msgs::Message::BaseMsg(msg))
+    }))
+}
+
+/// Converts a stream of Ferret messages into a stream of base OT messages.
+pub(crate) fn into_base_stream<'a, St: IoStream<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    stream: &'a mut St,
+) -> impl IoStream<T> + Send + Unpin + 'a {
+    StreamExt::map(stream, |msg| match msg {
+        Ok(msg) => msg.try_into_base_msg().map_err(From::from),
+        Err(err) => Err(err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use mpz_core::Block;
+    use mpz_ot_core::ferret::{msgs::Message, params::LPN_PARAMETERS_TEST};
+    use utils_aio::duplex::MemoryDuplex;
+
+    use crate::{
+        ideal::{ideal_ot_pair, IdealOTReceiver, IdealOTSender},
+        kos::{self, ReceiverConfig, SenderConfig},
+        OTSetup, RandomCOTReceiver, RandomCOTReceiverShared, RandomCOTSender,
+        RandomCOTSenderShared,
+    };
+
+    fn config(lpn_type: LpnType, semi_honest: bool) -> FerretConfig {
+        let mut builder = FerretConfig::builder();
+        builder
+            .lpn_parameters(LPN_PARAMETERS_TEST)
+            .lpn_type(lpn_type);
+
+        if semi_honest {
+            builder.semi_honest();
+        }
+
+        builder.build().unwrap()
+    }
+
+    fn assert_cots(delta: Block, sender_output: &[Block], choices: &[bool], received: &[Block]) {
+        let expected = sender_output
+            .iter()
+            .zip(choices)
+            .map(|(&v, &c)| if c { v ^ delta } else { v })
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
+    async fn setup<
+        Si: IoSink<Message<()>> + Send + Unpin,
+        St: IoStream<Message<()>> + Send + Unpin,
+    >(
+        config: FerretConfig,
+        sender_sink: &mut Si,
+        sender_stream: &mut St,
+        receiver_sink: &mut Si,
+        receiver_stream: &mut St,
+    ) -> (
+        Sender<IdealOTSender<Block>>,
+        Receiver<IdealOTReceiver<Block>>,
+    ) {
+        let (base_sender, base_receiver) = ideal_ot_pair();
+
+        let mut sender = Sender::new(config.clone(), base_sender);
+        let mut receiver = Receiver::new(config, base_receiver);
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.setup(sender_sink, sender_stream),
+            receiver.setup(receiver_sink, receiver_stream)
+        );
+
+        sender_res.unwrap();
+        receiver_res.unwrap();
+
+        (sender, receiver)
+    }
+
+    #[rstest]
+    #[case::regular(LpnType::Regular, false)]
+    #[case::uniform(LpnType::Uniform, false)]
+    #[case::regular_semi_honest(LpnType::Regular, true)]
+    #[case::uniform_semi_honest(LpnType::Uniform, true)]
+    #[tokio::test]
+    async fn test_ferret(#[case] lpn_type: LpnType, #[case] semi_honest: bool) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (mut sender, mut receiver) = setup(
+            config(lpn_type, semi_honest),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+        )
+        .await;
+
+        let delta = sender.delta().unwrap();
+
+        // Consume more than a single extension to test the buffering.
+        let count = LPN_PARAMETERS_TEST.n;
+        for _ in 0..2 {
+            let (sender_res, receiver_res) = tokio::join!(
+                sender.send_random_correlated(&mut sender_sink, &mut sender_stream, count),
+                receiver.receive_random_correlated(&mut receiver_sink, &mut receiver_stream, count)
+            );
+
+            let sender_output = sender_res.unwrap();
+            let (choices, received) = receiver_res.unwrap();
+
+            assert_cots(delta, &sender_output, &choices, &received);
+        }
+
+        assert_eq!(sender.remaining().unwrap(), receiver.remaining().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_ferret_shared() {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (sender, receiver) = setup(
+            config(LpnType::Regular, false),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+        )
+        .await;
+
+        let delta = sender.delta().unwrap();
+
+        let sender = SharedSender::new(sender, sender_sink, sender_stream).unwrap();
+        let receiver = SharedReceiver::new(receiver, receiver_sink, receiver_stream);
+
+        assert_eq!(sender.delta(), delta);
+
+        // The sender requests the transfers in a different order than the receiver.
+        let (sender_res, receiver_res) = tokio::join!(
+            async {
+                let b = sender.send_random_correlated("b", 10000).await.unwrap();
+                let a = sender.send_random_correlated("a", 10).await.unwrap();
+                (a, b)
+            },
+            async {
+                let a = receiver.receive_random_correlated("a", 10).await.unwrap();
+                let b = receiver
+                    .receive_random_correlated("b", 10000)
+                    .await
+                    .unwrap();
+                (a, b)
+            }
+        );
+
+        let (sender_a, sender_b) = sender_res;
+        let ((choices_a, received_a), (choices_b, received_b)) = receiver_res;
+
+        assert_cots(delta, &sender_a, &choices_a, &received_a);
+        assert_cots(delta, &sender_b, &choices_b, &received_b);
+    }
+
+    #[tokio::test]
+    async fn test_ferret_kos() {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (base_sender, base_receiver) = ideal_ot_pair();
+
+        let mut kos_sender = kos::Sender::new(SenderConfig::default(), base_receiver);
+        let mut kos_receiver = kos::Receiver::new(ReceiverConfig::default(), base_sender);
+
+        // Setup KOS with enough OTs for the Ferret setup and the first extension only.
+        let kos_count = 4096;
+        tokio::join!(
+            async {
+                let mut sink = into_base_sink(&mut sender_sink);
+                let mut stream = into_base_stream(&mut sender_stream);
+                kos_sender.setup(&mut sink, &mut stream).await.unwrap();
+                kos_sender
+                    .extend(&mut sink, &mut stream, kos_count)
+                    .await
+                    .unwrap();
+            },
+            async {
+                let mut sink = into_base_sink(&mut receiver_sink);
+                let mut stream = into_base_stream(&mut receiver_stream);
+                kos_receiver.setup(&mut sink, &mut stream).await.unwrap();
+                kos_receiver
+                    .extend(&mut sink, &mut stream, kos_count)
+                    .await
+                    .unwrap();
+            }
+        );
+
+        let mut sender = Sender::new(config(LpnType::Regular, false), kos_sender);
+        let mut receiver = Receiver::new(config(LpnType::Regular, false), kos_receiver);
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.setup(&mut sender_sink, &mut sender_stream),
+            receiver.setup(&mut receiver_sink, &mut receiver_stream)
+        );
+
+        sender_res.unwrap();
+        receiver_res.unwrap();
+
+        let delta = sender.delta().unwrap();
+
+        // The subsequent extensions are bootstrapped from the previous ones.
+        let count = 2 * LPN_PARAMETERS_TEST.n;
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send_random_correlated(&mut sender_sink, &mut sender_stream, count),
+            receiver.receive_random_correlated(&mut receiver_sink, &mut receiver_stream, count)
+        );
+
+        let sender_output = sender_res.unwrap();
+        let (choices, received) = receiver_res.unwrap();
+
+        assert_cots(delta, &sender_output, &choices, &received);
+    }
+}
@@ -0,0 +1,370 @@
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use futures_util::SinkExt;
+use mpz_core::{prg::Prg, Block, ProtocolMessage};
+use mpz_ot_core::ferret::{
+    mpcot::{
+        receiver::{state as mpcot_state, Receiver as MpcotReceiver},
+        receiver_regular::{state as regular_state, Receiver as RegularReceiver},
+    },
+    msgs::Message,
+    receiver::{state, Receiver as ReceiverCore},
+    spcot::receiver::Receiver as SpcotReceiver,
+    FerretConfig, LpnType, CSP,
+};
+use rand::{thread_rng, Rng};
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{into_base_sink, into_base_stream};
+use crate::{ferret::ReceiverError, OTError, OTReceiver, OTSetup, RandomCOTReceiver};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(FerretConfig),
+    Extension(Box<Extension>),
+    Error,
+}
+
+/// The state of the receiver after setup.
+#[derive(Debug)]
+pub(crate) struct Extension {
+    core: ReceiverCore<state::Extension>,
+    mpcot: Mpcot,
+    /// Whether the SPCOT consistency check is skipped.
+    semi_honest: bool,
+    /// Extended choices which have not been consumed yet.
+    choices: Vec<bool>,
+    /// Extended COTs which have not been consumed yet.
+    msgs: Vec<Block>,
+}
+
+/// The MPCOT receiver used for the extension, depending on the LPN type.
+#[derive(Debug)]
+enum Mpcot {
+    General(MpcotReceiver<mpcot_state::PreExtension>),
+    Regular(RegularReceiver<regular_state::PreExtension>),
+}
+
+/// The MPCOT receiver during an extension.
+enum MpcotExtension {
+    General(MpcotReceiver<mpcot_state::Extension>),
+    Regular(RegularReceiver<regular_state::Extension>),
+}
+
+/// Ferret receiver.
+#[derive(Debug)]
+pub struct Receiver<BaseOT> {
+    state: State,
+    base: BaseOT,
+}
+
+impl<BaseOT> Receiver<BaseOT>
+where
+    BaseOT: OTReceiver<bool, Block> + Send,
+{
+    /// Creates a new Receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Receiver's configuration
+    /// * `base` - The base OT receiver used to bootstrap the COTs, eg. KOS.
+    pub fn new(config: FerretConfig, base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(config),
+            base,
+        }
+    }
+
+    /// The number of remaining COTs which can be consumed without another extension.
+    pub fn remaining(&self) -> Result<usize, ReceiverError> {
+        Ok(self.state.try_as_extension()?.msgs.len())
+    }
+
+    /// Performs a single Ferret extension, adding `n - k` COTs to the buffer, minus the COTs
+    /// reserved for the MPCOT of the next extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender.
+    /// * `stream` - The stream to receive messages from the sender.
+    pub async fn extend<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), ReceiverError> {
+        let ext = std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+        let Extension {
+            mut core,
+            mpcot,
+            semi_honest,
+            choices,
+            msgs,
+        } = *ext;
+
+        let (alphas, _, n) = core.get_mpcot_query();
+
+        let (mpcot, queries) = match mpcot {
+            Mpcot::General(mpcot) => {
+                let (mpcot, queries) = mpcot.pre_extend(&alphas, n as u32)?;
+                (MpcotExtension::General(mpcot), queries)
+            }
+            Mpcot::Regular(mpcot) => {
+                let (mpcot, queries) = mpcot.pre_extend(&alphas, n as u32)?;
+                (MpcotExtension::Regular(mpcot), queries)
+            }
+        };
+
+        // The COTs for the GGM trees and the consistency check are reserved by the last
+        // extension, only the first extension bootstraps them from the base OT.
+        let hs = queries.iter().map(|(h, _)| *h).collect::<Vec<_>>();
+        let check_count = if semi_honest { 0 } else { CSP };
+        let count = hs.iter().sum::<usize>() + check_count;
+        let (mut rs, mut ts) = core.take_reserved();
+        if rs.is_empty() {
+            (rs, ts) = self.receive_base_cots(sink, stream, count).await?;
+        } else if rs.len() != count {
+            return Err(ReceiverError::Other(format!(
+                "expected {} reserved COTs, got {}",
+                count,
+                rs.len()
+            )));
+        }
+
+        let x_star = rs.split_off(rs.len() - check_count);
+        let z_star = ts.split_off(ts.len() - check_count);
+
+        let mut spcot = SpcotReceiver::new().setup();
+
+        let mut mask_bits = Vec::with_capacity(queries.len());
+        let mut offset = 0;
+        for (h, alpha) in queries.iter().copied() {
+            mask_bits.push(spcot.extend_mask_bits(h, alpha, &rs[offset..offset + h])?);
+            offset += h;
+        }
+
+        sink.send(Message::MaskBits(mask_bits)).await?;
+
+        let extend = stream
+            .expect_next()
+            .await?
+            .try_into_extend_from_sender()
+            .map_err(ReceiverError::from)?;
+
+        if extend.len() != queries.len() {
+            return Err(ReceiverError::Other(format!(
+                "expected {} extend messages, got {}",
+                queries.len(),
+                extend.len()
+            )));
+        }
+
+        let (spcot, check) = Backend::spawn(move || {
+            let mut offset = 0;
+            for ((h, alpha), extend) in queries.into_iter().zip(extend) {
+                spcot.extend(h, alpha, &ts[offset..offset + h], extend)?;
+                offset += h;
+            }
+
+            let check = if semi_honest {
+                None
+            } else {
+                Some(spcot.check_pre(&x_star)?)
+            };
+
+            Ok::<_, ReceiverError>((spcot, check))
+        })
+        .await?;
+
+        let check = if let Some(check) = check {
+            sink.send(Message::CheckFromReceiver(check)).await?;
+
+            Some(
+                stream
+                    .expect_next()
+                    .await?
+                    .try_into_check_from_sender()
+                    .map_err(ReceiverError::from)?,
+            )
+        } else {
+            None
+        };
+
+        let ext = Backend::spawn(move || {
+            let mut spcot = spcot;
+            let rt = match check {
+                Some(check) => spcot.check(&z_star, check)?,
+                None => spcot.finalize()?,
+            }
+            .into_iter()
+            .map(|(w, _)| w)
+            .collect::<Vec<_>>();
+
+            let (mpcot, r) = match mpcot {
+                MpcotExtension::General(mpcot) => {
+                    let (mpcot, r) = mpcot.extend(&rt)?;
+                    (Mpcot::General(mpcot), r)
+                }
+                MpcotExtension::Regular(mpcot) => {
+                    let (mpcot, r) = mpcot.extend(&rt)?;
+                    (Mpcot::Regular(mpcot), r)
+                }
+            };
+
+            let (new_choices, new_msgs) = core.extend(&hs, &r)?;
+
+            let mut choices = choices;
+            let mut msgs = msgs;
+            choices.extend(new_choices);
+            msgs.extend(new_msgs);
+
+            Ok::<_, ReceiverError>(Extension {
+                core,
+                mpcot,
+                semi_honest,
+                choices,
+                msgs,
+            })
+        })
+        .await?;
+
+        self.state = State::Extension(Box::new(ext));
+
+        Ok(())
+    }
+
+    /// Receives `count` random COTs from the sender using the base OT, returning the choices
+    /// and the chosen messages.
+    async fn receive_base_cots<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<(Vec<bool>, Vec<Block>), ReceiverError> {
+        let choices = (0..count)
+            .map(|_| thread_rng().gen())
+            .collect::<Vec<bool>>();
+
+        let msgs = self
+            .base
+            .receive(
+                &mut into_base_sink(sink),
+                &mut into_base_stream(stream),
+                &choices,
+            )
+            .await?;
+
+        Ok((choices, msgs))
+    }
+}
+
+impl<BaseOT> ProtocolMessage for Receiver<BaseOT>
+where
+    BaseOT: ProtocolMessage,
+{
+    type Msg = Message<BaseOT::Msg>;
+}
+
+#[async_trait]
+impl<BaseOT> OTSetup for Receiver<BaseOT>
+where
+    BaseOT: OTSetup + OTReceiver<bool, Block> + Send,
+{
+    async fn setup<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        let config = std::mem::replace(&mut self.state, State::Error)
+            .try_into_initialized()
+            .map_err(ReceiverError::from)?;
+
+        // Set up base OT if not already done
+        self.base
+            .setup(&mut into_base_sink(sink), &mut into_base_stream(stream))
+            .await?;
+
+        let lpn_parameters = config.lpn_parameters();
+        let lpn_type = config.lpn_type();
+
+        // Bootstrap the `k` COTs required by the Ferret setup.
+        let (u, w) = self
+            .receive_base_cots(sink, stream, lpn_parameters.k)
+            .await?;
+
+        let mut prg = Prg::new();
+        let (core, lpn_matrix_seed) = ReceiverCore::new()
+            .setup(&config, prg.random_block(), &u, &w)
+            .map_err(ReceiverError::from)?;
+
+        sink.send(Message::LpnMatrixSeed(lpn_matrix_seed)).await?;
+
+        let mpcot = match lpn_type {
+            LpnType::Uniform => {
+                let (mpcot, hash_seed) = MpcotReceiver::new().setup(prg.random_block());
+                sink.send(Message::HashSeed(hash_seed)).await?;
+
+                Mpcot::General(mpcot)
+            }
+            LpnType::Regular => Mpcot::Regular(RegularReceiver::new().setup()),
+        };
+
+        self.state = State::Extension(Box::new(Extension {
+            core,
+            mpcot,
+            semi_honest: config.semi_honest(),
+            choices: Vec::new(),
+            msgs: Vec::new(),
+        }));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<BaseOT> RandomCOTReceiver<bool, Block> for Receiver<BaseOT>
+where
+    BaseOT: OTReceiver<bool, Block> + Send,
+{
+    async fn receive_random_correlated<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<(Vec<bool>, Vec<Block>), OTError> {
+        // The sender extends in lockstep, as both parties track the same buffer size.
+        while self.remaining()? < count {
+            self.extend(sink, stream).await?;
+        }
+
+        let ext = self
+            .state
+            .try_as_extension_mut()
+            .map_err(ReceiverError::from)?;
+
+        Ok((
+            ext.choices.drain(..count).collect(),
+            ext.msgs.drain(..count).collect(),
+        ))
+    }
+}
@@ -0,0 +1,385 @@
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use futures_util::SinkExt;
+use mpz_core::{prg::Prg, Block, ProtocolMessage};
+use mpz_ot_core::ferret::{
+    mpcot::{
+        sender::{state as mpcot_state, Sender as MpcotSender},
+        sender_regular::{state as regular_state, Sender as RegularSender},
+    },
+    msgs::{LpnMatrixSeed, Message},
+    sender::{state, Sender as SenderCore},
+    spcot::sender::Sender as SpcotSender,
+    FerretConfig, LpnType, CSP,
+};
+use rand::thread_rng;
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{into_base_sink, into_base_stream};
+use crate::{ferret::SenderError, OTError, OTSender, OTSetup, RandomCOTSender};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(FerretConfig),
+    Extension(Box<Extension>),
+    Error,
+}
+
+/// The state of the sender after setup.
+#[derive(Debug)]
+pub(crate) struct Extension {
+    delta: Block,
+    core: SenderCore<state::Extension>,
+    mpcot: Mpcot,
+    /// Whether the SPCOT consistency check is skipped.
+    semi_honest: bool,
+    /// Extended COTs which have not been consumed yet.
+    buffer: Vec<Block>,
+}
+
+/// The MPCOT sender used for the extension, depending on the LPN type.
+#[derive(Debug)]
+enum Mpcot {
+    General(MpcotSender<mpcot_state::PreExtension>),
+    Regular(RegularSender<regular_state::PreExtension>),
+}
+
+/// The MPCOT sender during an extension.
+enum MpcotExtension {
+    General(MpcotSender<mpcot_state::Extension>),
+    Regular(RegularSender<regular_state::Extension>),
+}
+
+/// Ferret sender.
+#[derive(Debug)]
+pub struct Sender<BaseOT> {
+    state: State,
+    base: BaseOT,
+}
+
+impl<BaseOT> Sender<BaseOT>
+where
+    BaseOT: OTSender<[Block; 2]> + Send,
+{
+    /// Creates a new Sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Sender's configuration
+    /// * `base` - The base OT sender used to bootstrap the COTs, eg. KOS.
+    pub fn new(config: FerretConfig, base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(config),
+            base,
+        }
+    }
+
+    /// The number of remaining COTs which can be consumed without another extension.
+    pub fn remaining(&self) -> Result<usize, SenderError> {
+        Ok(self.state.try_as_extension()?.buffer.len())
+    }
+
+    /// Returns the sender's global correlation.
+    pub fn delta(&self) -> Result<Block, SenderError> {
+        Ok(self.state.try_as_extension()?.delta)
+    }
+
+    /// Performs the setup with the provided delta.
+    ///
+    /// The base OT must already be set up.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    /// * `delta` - The global correlation of the COTs.
+    pub async fn setup_with_delta<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        delta: Block,
+    ) -> Result<(), SenderError> {
+        let config = std::mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        let lpn_parameters = config.lpn_parameters();
+        let lpn_type = config.lpn_type();
+
+        // Bootstrap the `k` COTs required by the Ferret setup.
+        let v = self
+            .send_base_cots(sink, stream, delta, lpn_parameters.k)
+            .await?;
+
+        let LpnMatrixSeed { seed } = stream
+            .expect_next()
+            .await?
+            .try_into_lpn_matrix_seed()
+            .map_err(SenderError::from)?;
+
+        let mpcot = match lpn_type {
+            LpnType::Uniform => {
+                let hash_seed = stream
+                    .expect_next()
+                    .await?
+                    .try_into_hash_seed()
+                    .map_err(SenderError::from)?;
+
+                Mpcot::General(MpcotSender::new().setup(delta, hash_seed))
+            }
+            LpnType::Regular => Mpcot::Regular(RegularSender::new().setup(delta)),
+        };
+
+        let core = SenderCore::new().setup(delta, &config, seed, &v)?;
+
+        self.state = State::Extension(Box::new(Extension {
+            delta,
+            core,
+            mpcot,
+            semi_honest: config.semi_honest(),
+            buffer: Vec::new(),
+        }));
+
+        Ok(())
+    }
+
+    /// Performs a single Ferret extension, adding `n - k` COTs to the buffer, minus the COTs
+    /// reserved for the MPCOT of the next extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    pub async fn extend<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), SenderError> {
+        let ext = std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+        let Extension {
+            delta,
+            mut core,
+            mpcot,
+            semi_honest,
+            buffer,
+        } = *ext;
+
+        let (t, n) = core.get_mpcot_query();
+
+        let (mpcot, hs) = match mpcot {
+            Mpcot::General(mpcot) => {
+                let (mpcot, hs) = mpcot.pre_extend(t, n)?;
+                (MpcotExtension::General(mpcot), hs)
+            }
+            Mpcot::Regular(mpcot) => {
+                let (mpcot, hs) = mpcot.pre_extend(t, n)?;
+                (MpcotExtension::Regular(mpcot), hs)
+            }
+        };
+
+        // The COTs for the GGM trees and the consistency check are reserved by the last
+        // extension, only the first extension bootstraps them from the base OT.
+        let check_count = if semi_honest { 0 } else { CSP };
+        let count = hs.iter().sum::<usize>() + check_count;
+        let mut qs = core.take_reserved();
+        if qs.is_empty() {
+            qs = self.send_base_cots(sink, stream, delta, count).await?;
+        } else if qs.len() != count {
+            return Err(SenderError::Other(format!(
+                "expected {} reserved COTs, got {}",
+                count,
+                qs.len()
+            )));
+        }
+
+        let y_star = qs.split_off(qs.len() - check_count);
+
+        let mask_bits = stream
+            .expect_next()
+            .await?
+            .try_into_mask_bits()
+            .map_err(SenderError::from)?;
+
+        if mask_bits.len() != hs.len() {
+            return Err(SenderError::Other(format!(
+                "expected {} mask bits, got {}",
+                hs.len(),
+                mask_bits.len()
+            )));
+        }
+
+        let (spcot, hs, extend) = Backend::spawn(move || {
+            let mut spcot = SpcotSender::new().setup(delta, Prg::new().random_block());
+
+            let mut qs = qs.as_slice();
+            let mut extend = Vec::with_capacity(hs.len());
+            for (&h, mask_bits) in hs.iter().zip(mask_bits) {
+                let (q, rest) = qs.split_at(h);
+                extend.push(spcot.extend(h, q, mask_bits)?);
+                qs = rest;
+            }
+
+            Ok::<_, SenderError>((spcot, hs, extend))
+        })
+        .await?;
+
+        sink.send(Message::ExtendFromSender(extend)).await?;
+
+        let check = if semi_honest {
+            None
+        } else {
+            Some(
+                stream
+                    .expect_next()
+                    .await?
+                    .try_into_check_from_receiver()
+                    .map_err(SenderError::from)?,
+            )
+        };
+
+        let (ext, check) = Backend::spawn(move || {
+            let mut spcot = spcot;
+            let (vs, check) = match check {
+                Some(check) => {
+                    let (vs, check) = spcot.check(&y_star, check)?;
+                    (vs, Some(check))
+                }
+                None => (spcot.finalize()?, None),
+            };
+
+            let (mpcot, s) = match mpcot {
+                MpcotExtension::General(mpcot) => {
+                    let (mpcot, s) = mpcot.extend(&vs)?;
+                    (Mpcot::General(mpcot), s)
+                }
+                MpcotExtension::Regular(mpcot) => {
+                    let (mpcot, s) = mpcot.extend(&vs)?;
+                    (Mpcot::Regular(mpcot), s)
+                }
+            };
+
+            let mut core = core;
+            let mut buffer = buffer;
+            buffer.extend(core.extend(&hs, &s)?);
+
+            Ok::<_, SenderError>((
+                Extension {
+                    delta,
+                    core,
+                    mpcot,
+                    semi_honest,
+                    buffer,
+                },
+                check,
+            ))
+        })
+        .await?;
+
+        if let Some(check) = check {
+            sink.send(Message::CheckFromSender(check)).await?;
+        }
+
+        self.state = State::Extension(Box::new(ext));
+
+        Ok(())
+    }
+
+    /// Sends `count` random COTs to the receiver using the base OT, returning the `0`-bit messages.
+    async fn send_base_cots<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        delta: Block,
+        count: usize,
+    ) -> Result<Vec<Block>, SenderError> {
+        let qs = Block::random_vec(&mut thread_rng(), count);
+        let msgs = qs.iter().map(|q| [*q, *q ^ delta]).collect::<Vec<_>>();
+
+        self.base
+            .send(
+                &mut into_base_sink(sink),
+                &mut into_base_stream(stream),
+                &msgs,
+            )
+            .await?;
+
+        Ok(qs)
+    }
+}
+
+impl<BaseOT> ProtocolMessage for Sender<BaseOT>
+where
+    BaseOT: ProtocolMessage,
+{
+    type Msg = Message<BaseOT::Msg>;
+}
+
+#[async_trait]
+impl<BaseOT> OTSetup for Sender<BaseOT>
+where
+    BaseOT: OTSetup + OTSender<[Block; 2]> + Send,
+{
+    async fn setup<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        // Set up base OT if not already done
+        self.base
+            .setup(&mut into_base_sink(sink), &mut into_base_stream(stream))
+            .await?;
+
+        let delta = Block::random(&mut thread_rng());
+
+        self.setup_with_delta(sink, stream, delta)
+            .await
+            .map_err(OTError::from)
+    }
+}
+
+#[async_trait]
+impl<BaseOT> RandomCOTSender<Block> for Sender<BaseOT>
+where
+    BaseOT: OTSender<[Block; 2]> + Send,
+{
+    async fn send_random_correlated<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<Vec<Block>, OTError> {
+        // The receiver extends in lockstep, as both parties track the same buffer size.
+        while self.remaining()? < count {
+            self.extend(sink, stream).await?;
+        }
+
+        let ext = self
+            .state
+            .try_as_extension_mut()
+            .map_err(SenderError::from)?;
+
+        Ok(ext.buffer.drain(..count).collect())
+    }
+}
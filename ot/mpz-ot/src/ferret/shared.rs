@@ -0,0 +1,192 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use futures_util::SinkExt;
+use mpz_core::Block;
+use mpz_ot_core::ferret::msgs::{Message, Reserve};
+use utils_aio::{
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use crate::{
+    ferret::{Receiver, ReceiverError, Sender, SenderError},
+    OTError, OTReceiver, OTSender, RandomCOTReceiver, RandomCOTReceiverShared, RandomCOTSender,
+    RandomCOTSenderShared,
+};
+
+struct SenderInner<BaseOT, Si, St> {
+    sender: Sender<BaseOT>,
+    sink: Si,
+    stream: St,
+    /// COTs reserved by the receiver which have not been requested yet.
+    reserved: HashMap<String, Vec<Block>>,
+}
+
+/// A Ferret sender which can be used via a shared reference.
+///
+/// The transfers are ordered by the receiver, which reserves COTs for each transfer id.
+pub struct SharedSender<BaseOT, Si, St> {
+    inner: Arc<Mutex<SenderInner<BaseOT, Si, St>>>,
+    delta: Block,
+}
+
+impl<BaseOT, Si, St> SharedSender<BaseOT, Si, St> {
+    /// Creates a new shared sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `sender` - The Ferret sender, which must already be set up.
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    pub fn new(sender: Sender<BaseOT>, sink: Si, stream: St) -> Result<Self, SenderError> {
+        let delta = sender.delta()?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(SenderInner {
+                sender,
+                sink,
+                stream,
+                reserved: HashMap::new(),
+            })),
+            delta,
+        })
+    }
+}
+
+impl<BaseOT, Si, St> Clone for SharedSender<BaseOT, Si, St> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            delta: self.delta,
+        }
+    }
+}
+
+opaque_debug::implement!(SharedSender<BaseOT, Si, St>);
+
+#[async_trait]
+impl<BaseOT, Si, St> RandomCOTSenderShared<Block> for SharedSender<BaseOT, Si, St>
+where
+    BaseOT: OTSender<[Block; 2]> + Send,
+    Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+    St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+{
+    async fn send_random_correlated(&self, id: &str, count: usize) -> Result<Vec<Block>, OTError> {
+        let mut inner = self.inner.lock().await;
+        let SenderInner {
+            sender,
+            sink,
+            stream,
+            reserved,
+        } = &mut *inner;
+
+        // Process the reservations of the receiver, in order, until we find this transfer.
+        while !reserved.contains_key(id) {
+            let Reserve {
+                id: reserved_id,
+                count: reserved_count,
+            } = stream
+                .expect_next()
+                .await?
+                .try_into_reserve()
+                .map_err(SenderError::from)?;
+
+            let cots = sender
+                .send_random_correlated(sink, stream, reserved_count)
+                .await?;
+
+            reserved.insert(reserved_id, cots);
+        }
+
+        let cots = reserved.remove(id).expect("transfer should be reserved");
+
+        if cots.len() != count {
+            return Err(SenderError::Other(format!(
+                "receiver reserved {} COTs for transfer {}, expected {}",
+                cots.len(),
+                id,
+                count
+            )))?;
+        }
+
+        Ok(cots)
+    }
+
+    fn delta(&self) -> Block {
+        self.delta
+    }
+}
+
+struct ReceiverInner<BaseOT, Si, St> {
+    receiver: Receiver<BaseOT>,
+    sink: Si,
+    stream: St,
+}
+
+/// A Ferret receiver which can be used via a shared reference.
+pub struct SharedReceiver<BaseOT, Si, St> {
+    inner: Arc<Mutex<ReceiverInner<BaseOT, Si, St>>>,
+}
+
+impl<BaseOT, Si, St> SharedReceiver<BaseOT, Si, St> {
+    /// Creates a new shared receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - The Ferret receiver, which must already be set up.
+    /// * `sink` - The sink to send messages to the sender.
+    /// * `stream` - The stream to receive messages from the sender.
+    pub fn new(receiver: Receiver<BaseOT>, sink: Si, stream: St) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ReceiverInner {
+                receiver,
+                sink,
+                stream,
+            })),
+        }
+    }
+}
+
+impl<BaseOT, Si, St> Clone for SharedReceiver<BaseOT, Si, St> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+opaque_debug::implement!(SharedReceiver<BaseOT, Si, St>);
+
+#[async_trait]
+impl<BaseOT, Si, St> RandomCOTReceiverShared<bool, Block> for SharedReceiver<BaseOT, Si, St>
+where
+    BaseOT: OTReceiver<bool, Block> + Send,
+    Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+    St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+{
+    async fn receive_random_correlated(
+        &self,
+        id: &str,
+        count: usize,
+    ) -> Result<(Vec<bool>, Vec<Block>), OTError> {
+        let mut inner = self.inner.lock().await;
+        let ReceiverInner {
+            receiver,
+            sink,
+            stream,
+        } = &mut *inner;
+
+        sink.send(Message::Reserve(Reserve {
+            id: id.to_string(),
+            count,
+        }))
+        .await
+        .map_err(ReceiverError::from)?;
+
+        receiver
+            .receive_random_correlated(sink, stream, count)
+            .await
+    }
+}
@@ -71,6 +71,10 @@ impl RandomCOTSenderShared<Block> for IdealSharedRandomCOTSender {
         }
         Ok(low)
     }
+
+    fn delta(&self) -> Block {
+        self.delta
+    }
 }
 
 /// An ideal random correlated oblivious transfer receiver.
@@ -0,0 +1,83 @@
+use mpz_ot_core::kkrt::msgs::MessageError;
+
+use crate::OTError;
+
+/// A KKRT sender error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::kkrt::SenderError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<SenderError> for OTError {
+    fn from(err: SenderError) -> Self {
+        match err {
+            SenderError::IOError(e) => e.into(),
+            e => OTError::SenderError(Box::new(e)),
+        }
+    }
+}
+
+impl From<crate::kkrt::SenderStateError> for SenderError {
+    fn from(err: crate::kkrt::SenderStateError) -> Self {
+        SenderError::StateError(err.to_string())
+    }
+}
+
+impl<BaseMsg> From<MessageError<BaseMsg>> for SenderError {
+    fn from(err: MessageError<BaseMsg>) -> Self {
+        SenderError::from(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            err.to_string(),
+        ))
+    }
+}
+
+/// A KKRT receiver error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::kkrt::ReceiverError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<ReceiverError> for OTError {
+    fn from(err: ReceiverError) -> Self {
+        match err {
+            ReceiverError::IOError(e) => e.into(),
+            e => OTError::ReceiverError(Box::new(e)),
+        }
+    }
+}
+
+impl From<crate::kkrt::ReceiverStateError> for ReceiverError {
+    fn from(err: crate::kkrt::ReceiverStateError) -> Self {
+        ReceiverError::StateError(err.to_string())
+    }
+}
+
+impl<BaseMsg> From<MessageError<BaseMsg>> for ReceiverError {
+    fn from(err: MessageError<BaseMsg>) -> Self {
+        ReceiverError::from(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            err.to_string(),
+        ))
+    }
+}
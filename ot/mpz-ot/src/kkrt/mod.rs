@@ -0,0 +1,168 @@
+//! An implementation of the [`KKRT16`](https://eprint.iacr.org/2016/799.pdf) batched oblivious
+//! PRF, which provides 1-out-of-N oblivious transfer.
+//!
+//! # ⚠️ Warning ⚠️
+//!
+//! This protocol is only secure against semi-honest adversaries.
+
+mod error;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+use futures_util::{SinkExt, StreamExt};
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+pub(crate) use receiver::StateError as ReceiverStateError;
+pub(crate) use sender::StateError as SenderStateError;
+
+pub use mpz_ot_core::kkrt::{input_from_index, msgs, ReceiverKeys, SenderKeys};
+use utils_aio::{sink::IoSink, stream::IoStream};
+
+// If we're testing we use a smaller chunk size to make sure the chunking code paths are tested.
+cfg_if::cfg_if! {
+    if #[cfg(test)] {
+        pub(crate) const EXTEND_CHUNK_SIZE: usize = 1024;
+    } else {
+        /// The size of the chunks used to send the extension matrix, 4MB.
+        pub(crate) const EXTEND_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+    }
+}
+
+/// Converts a sink of KKRT messages into a sink of base OT messages.
+pub(crate) fn into_base_sink<'a, Si: IoSink<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    sink: &'a mut Si,
+) -> impl IoSink<T> + Send + Unpin + 'a {
+    Box::pin(SinkExt::with(sink, |msg| async move {
+        Ok(msgs::Message::BaseMsg(msg))
+    }))
+}
+
+/// Converts a stream of KKRT messages into a stream of base OT messages.
+pub(crate) fn into_base_stream<'a, St: IoStream<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    stream: &'a mut St,
+) -> impl IoStream<T> + Send + Unpin + 'a {
+    StreamExt::map(stream, |msg| match msg {
+        Ok(msg) => msg.try_into_base_msg().map_err(From::from),
+        Err(err) => Err(err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_core::Block;
+    use mpz_ot_core::{chou_orlandi::msgs::Message as BaseMessage, kkrt::msgs::Message};
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+    use utils_aio::duplex::MemoryDuplex;
+
+    use crate::{
+        chou_orlandi::{
+            Receiver as BaseReceiver, ReceiverConfig as BaseReceiverConfig, Sender as BaseSender,
+            SenderConfig as BaseSenderConfig,
+        },
+        OTReceiver, OTSender, OTSetup,
+    };
+
+    async fn setup<
+        Si: IoSink<Message<BaseMessage>> + Send + Unpin,
+        St: IoStream<Message<BaseMessage>> + Send + Unpin,
+    >(
+        sender_sink: &mut Si,
+        sender_stream: &mut St,
+        receiver_sink: &mut Si,
+        receiver_stream: &mut St,
+    ) -> (Sender<BaseReceiver>, Receiver<BaseSender>) {
+        let mut sender = Sender::new(BaseReceiver::new(BaseReceiverConfig::default()));
+        let mut receiver = Receiver::new(BaseSender::new(BaseSenderConfig::default()));
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.setup(sender_sink, sender_stream),
+            receiver.setup(receiver_sink, receiver_stream)
+        );
+
+        sender_res.unwrap();
+        receiver_res.unwrap();
+
+        (sender, receiver)
+    }
+
+    #[tokio::test]
+    async fn test_kkrt_oprf() {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (mut sender, mut receiver) = setup(
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+        )
+        .await;
+
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let inputs = (0..1000).map(|_| rng.gen()).collect::<Vec<Block>>();
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.extend(&mut sender_sink, &mut sender_stream),
+            receiver.extend(&mut receiver_sink, &mut receiver_stream, &inputs)
+        );
+
+        let sender_keys = sender_res.unwrap();
+        let receiver_keys = receiver_res.unwrap();
+
+        let expected = inputs
+            .iter()
+            .enumerate()
+            .map(|(j, input)| sender_keys.eval(j, *input))
+            .collect::<Vec<_>>();
+
+        assert_eq!(receiver_keys.outputs(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_kkrt_ot() {
+        const N: usize = 16;
+
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (mut sender, mut receiver) = setup(
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+        )
+        .await;
+
+        let mut rng = ChaCha12Rng::seed_from_u64(1);
+        let choices = (0..1000).map(|_| rng.gen_range(0..N)).collect::<Vec<_>>();
+        let data = (0..choices.len())
+            .map(|_| std::array::from_fn::<Block, N, _>(|_| rng.gen()))
+            .collect::<Vec<_>>();
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received: Vec<Block> = receiver_res.unwrap();
+
+        let expected = data
+            .iter()
+            .zip(&choices)
+            .map(|(msgs, choice)| msgs[*choice])
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+}
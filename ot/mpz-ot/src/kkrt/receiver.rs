@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use futures::SinkExt;
+use mpz_core::{Block, ProtocolMessage};
+use mpz_ot_core::kkrt::{
+    input_from_index,
+    msgs::{Message, StartExtend},
+    receiver_state as state, Receiver as ReceiverCore, ReceiverKeys, CODE_LEN,
+};
+use rand::{thread_rng, Rng};
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{into_base_sink, into_base_stream, ReceiverError, EXTEND_CHUNK_SIZE};
+use crate::{OTError, OTReceiver, OTSender, OTSetup};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(ReceiverCore<state::Initialized>),
+    Extension(Box<ReceiverCore<state::Extension>>),
+    Error,
+}
+
+/// KKRT receiver.
+#[derive(Debug)]
+pub struct Receiver<BaseOT> {
+    state: State,
+    base: BaseOT,
+}
+
+impl<BaseOT> Receiver<BaseOT>
+where
+    BaseOT: OTSender<[Block; 2]> + Send,
+{
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base OT sender, eg. Chou-Orlandi.
+    pub fn new(base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(ReceiverCore::new()),
+            base,
+        }
+    }
+}
+
+impl<BaseOT> Receiver<BaseOT>
+where
+    BaseOT: ProtocolMessage + Send,
+{
+    /// Performs the extension for the provided inputs, returning the OPRF outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender
+    /// * `stream` - The stream to receive messages from the sender
+    /// * `inputs` - The receiver's OPRF inputs
+    pub async fn extend<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        _stream: &mut St,
+        inputs: &[Block],
+    ) -> Result<ReceiverKeys, ReceiverError> {
+        let mut ext_receiver =
+            std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let count = inputs.len();
+        let inputs = inputs.to_vec();
+        let (ext_receiver, (extend, keys)) = Backend::spawn(move || {
+            let output = ext_receiver.extend(&inputs);
+
+            (ext_receiver, output)
+        })
+        .await;
+
+        self.state = State::Extension(ext_receiver);
+
+        // Send the extension matrix to the sender.
+        sink.feed(Message::StartExtend(StartExtend { count }))
+            .await?;
+        for extend in extend.into_chunks(EXTEND_CHUNK_SIZE) {
+            sink.feed(Message::Extend(extend)).await?;
+        }
+        sink.flush().await?;
+
+        Ok(keys)
+    }
+}
+
+impl<BaseOT> ProtocolMessage for Receiver<BaseOT>
+where
+    BaseOT: ProtocolMessage,
+{
+    type Msg = Message<BaseOT::Msg>;
+}
+
+#[async_trait]
+impl<BaseOT> OTSetup for Receiver<BaseOT>
+where
+    BaseOT: OTSetup + OTSender<[Block; 2]> + Send,
+{
+    async fn setup<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        let ext_receiver = std::mem::replace(&mut self.state, State::Error)
+            .try_into_initialized()
+            .map_err(ReceiverError::from)?;
+
+        // Set up base OT
+        self.base
+            .setup(&mut into_base_sink(sink), &mut into_base_stream(stream))
+            .await?;
+
+        let seeds: [[Block; 2]; CODE_LEN] = std::array::from_fn(|_| thread_rng().gen());
+
+        // Send seeds to sender
+        self.base
+            .send(
+                &mut into_base_sink(sink),
+                &mut into_base_stream(stream),
+                &seeds,
+            )
+            .await?;
+
+        self.state = State::Extension(Box::new(ext_receiver.setup(seeds)));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<BaseOT> OTReceiver<usize, Block> for Receiver<BaseOT>
+where
+    BaseOT: ProtocolMessage + Send,
+{
+    async fn receive<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        choices: &[usize],
+    ) -> Result<Vec<Block>, OTError> {
+        let inputs = choices
+            .iter()
+            .copied()
+            .map(input_from_index)
+            .collect::<Vec<_>>();
+
+        let keys = self.extend(sink, stream, &inputs).await?;
+
+        let payload = stream
+            .expect_next()
+            .await?
+            .try_into_sender_payload()
+            .map_err(ReceiverError::from)?;
+
+        let choices = choices.to_vec();
+        let received = Backend::spawn(move || keys.decrypt_blocks(&choices, payload))
+            .await
+            .map_err(ReceiverError::from)?;
+
+        Ok(received)
+    }
+}
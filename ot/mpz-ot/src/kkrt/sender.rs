@@ -0,0 +1,190 @@
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use futures_util::SinkExt;
+use itybity::ToBits;
+use mpz_core::{Block, ProtocolMessage};
+use mpz_ot_core::kkrt::{
+    extension_matrix_size,
+    msgs::{Extend, Message, StartExtend},
+    pad_ot_count, sender_state as state, Sender as SenderCore, SenderKeys, CODE_LEN,
+};
+use rand::{thread_rng, Rng};
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{into_base_sink, into_base_stream};
+use crate::{kkrt::SenderError, OTError, OTReceiver, OTSender, OTSetup};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(SenderCore<state::Initialized>),
+    Extension(Box<SenderCore<state::Extension>>),
+    Error,
+}
+
+/// KKRT sender.
+#[derive(Debug)]
+pub struct Sender<BaseOT> {
+    state: State,
+    base: BaseOT,
+}
+
+impl<BaseOT> Sender<BaseOT>
+where
+    BaseOT: OTReceiver<bool, Block> + Send,
+{
+    /// Creates a new Sender
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base OT receiver, eg. Chou-Orlandi.
+    pub fn new(base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(SenderCore::new()),
+            base,
+        }
+    }
+}
+
+impl<BaseOT> Sender<BaseOT>
+where
+    BaseOT: ProtocolMessage + Send,
+{
+    /// Performs the extension for the OPRF instances requested by the receiver, returning the
+    /// keys which evaluate the OPRF.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver
+    /// * `stream` - The stream to receive messages from the receiver
+    pub async fn extend<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        _sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<SenderKeys, SenderError> {
+        let mut ext_sender =
+            std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let StartExtend { count } = stream
+            .expect_next()
+            .await?
+            .try_into_start_extend()
+            .map_err(SenderError::from)?;
+
+        let expected_us = extension_matrix_size(pad_ot_count(count));
+        let mut extend = Extend {
+            us: Vec::with_capacity(expected_us),
+        };
+
+        // Receive extension matrix from the receiver.
+        while extend.us.len() < expected_us {
+            let Extend { us: chunk } = stream
+                .expect_next()
+                .await?
+                .try_into_extend()
+                .map_err(SenderError::from)?;
+
+            extend.us.extend(chunk);
+        }
+
+        let (ext_sender, keys) = Backend::spawn(move || {
+            let keys = ext_sender.extend(count, extend);
+
+            (ext_sender, keys)
+        })
+        .await;
+
+        self.state = State::Extension(ext_sender);
+
+        Ok(keys?)
+    }
+}
+
+impl<BaseOT> ProtocolMessage for Sender<BaseOT>
+where
+    BaseOT: ProtocolMessage,
+{
+    type Msg = Message<BaseOT::Msg>;
+}
+
+#[async_trait]
+impl<BaseOT> OTSetup for Sender<BaseOT>
+where
+    BaseOT: OTSetup + OTReceiver<bool, Block> + Send,
+{
+    async fn setup<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        let sender = std::mem::replace(&mut self.state, State::Error)
+            .try_into_initialized()
+            .map_err(SenderError::from)?;
+
+        // Set up base OT if not already done
+        self.base
+            .setup(&mut into_base_sink(sink), &mut into_base_stream(stream))
+            .await?;
+
+        let delta: [Block; CODE_LEN / 128] = std::array::from_fn(|_| thread_rng().gen());
+
+        let choices = delta.iter_lsb0().collect::<Vec<_>>();
+        let seeds = self
+            .base
+            .receive(
+                &mut into_base_sink(sink),
+                &mut into_base_stream(stream),
+                &choices,
+            )
+            .await?;
+
+        let seeds: [Block; CODE_LEN] = seeds.try_into().expect("seeds should be CODE_LEN length");
+
+        self.state = State::Extension(Box::new(sender.setup(delta, seeds)));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<const N: usize, BaseOT> OTSender<[Block; N]> for Sender<BaseOT>
+where
+    BaseOT: ProtocolMessage + Send,
+{
+    async fn send<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        msgs: &[[Block; N]],
+    ) -> Result<(), OTError> {
+        let keys = self.extend(sink, stream).await?;
+
+        let msgs = msgs.to_vec();
+        let payload = Backend::spawn(move || keys.encrypt_blocks(&msgs))
+            .await
+            .map_err(SenderError::from)?;
+
+        sink.send(Message::SenderPayload(payload))
+            .await
+            .map_err(SenderError::from)?;
+
+        Ok(())
+    }
+}
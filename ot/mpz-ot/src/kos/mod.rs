@@ -235,6 +235,49 @@ mod tests {
         assert_eq!(received, expected);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_vec(choices: Vec<bool>) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut sender_sink,
+            &mut sender_stream,
+            &mut receiver_sink,
+            &mut receiver_stream,
+            choices.len(),
+        )
+        .await;
+
+        // 1KiB payloads.
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let data: Vec<[Vec<u8>; 2]> = (0..choices.len())
+            .map(|_| {
+                [
+                    (0..1024).map(|_| rng.gen()).collect(),
+                    (0..1024).map(|_| rng.gen()).collect(),
+                ]
+            })
+            .collect();
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received: Vec<Vec<u8>> = receiver_res.unwrap();
+
+        let expected = choose(data.into_iter(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_kos_committed_sender(data: Vec<[Block; 2]>, choices: Vec<bool>) {
@@ -421,6 +421,49 @@ where
     }
 }
 
+#[async_trait]
+impl<BaseOT> OTSender<[Vec<u8>; 2]> for Sender<BaseOT>
+where
+    BaseOT: ProtocolMessage + Send,
+{
+    async fn send<
+        Si: IoSink<Message<BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        msgs: &[[Vec<u8>; 2]],
+    ) -> Result<(), OTError> {
+        let sender = self
+            .state
+            .try_as_extension_mut()
+            .map_err(SenderError::from)?;
+
+        let derandomize = stream
+            .expect_next()
+            .await?
+            .try_into_derandomize()
+            .map_err(SenderError::from)?;
+
+        let mut sender_keys = sender.keys(msgs.len()).map_err(SenderError::from)?;
+        sender_keys
+            .derandomize(derandomize)
+            .map_err(SenderError::from)?;
+
+        let msgs = msgs.to_vec();
+        let payload =
+            Backend::spawn(move || sender_keys.encrypt_vec(&msgs).map_err(SenderError::from))
+                .await?;
+
+        sink.send(Message::SenderPayload(payload))
+            .await
+            .map_err(SenderError::from)?;
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl<const N: usize, BaseOT> RandomOTSender<[[u8; N]; 2]> for Sender<BaseOT>
 where
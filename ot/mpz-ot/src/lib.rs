@@ -7,9 +7,13 @@
 #[cfg(feature = "actor")]
 pub mod actor;
 pub mod chou_orlandi;
+pub mod ferret;
 #[cfg(feature = "ideal")]
 pub mod ideal;
+pub mod kkrt;
 pub mod kos;
+pub mod rot;
+pub mod vole;
 
 use async_trait::async_trait;
 use mpz_core::ProtocolMessage;
@@ -392,6 +396,10 @@ pub trait RandomCOTSenderShared<T> {
     /// * `id` - The unique identifier for this transfer.
     /// * `count` - The number of correlated messages to obliviously transfer.
     async fn send_random_correlated(&self, id: &str, count: usize) -> Result<Vec<T>, OTError>;
+
+    /// Returns the global correlation of the transferred messages, ie. the `1`-bit message is
+    /// the `0`-bit message xor `delta`.
+    fn delta(&self) -> T;
 }
 
 /// An oblivious transfer receiver that can be used via a shared reference.
@@ -0,0 +1,197 @@
+//! Random OT derived from random correlated OT, eg. [`Ferret`](crate::ferret).
+//!
+//! The correlation of the COTs is broken using a tweakable circular correlation-robust hash
+//! function. Chosen-message OT is provided using Beaver derandomization.
+
+mod error;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+use futures_util::{SinkExt, StreamExt};
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+pub use mpz_ot_core::rot::msgs;
+use utils_aio::{sink::IoSink, stream::IoStream};
+
+/// Converts a sink of random OT messages into a sink of COT messages.
+pub(crate) fn into_cot_sink<'a, Si: IoSink<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    sink: &'a mut Si,
+) -> impl IoSink<T> + Send + Unpin + 'a {
+    Box::pin(SinkExt::with(sink, |msg| async move {
+        Ok(msgs::Message::CotMsg(msg))
+    }))
+}
+
+/// Converts a stream of random OT messages into a stream of COT messages.
+pub(crate) fn into_cot_stream<'a, St: IoStream<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    stream: &'a mut St,
+) -> impl IoStream<T> + Send + Unpin + 'a {
+    StreamExt::map(stream, |msg| match msg {
+        Ok(msg) => msg.try_into_cot_msg().map_err(From::from),
+        Err(err) => Err(err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_core::Block;
+    use mpz_ot_core::ferret::params::LPN_PARAMETERS_TEST;
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+    use utils_aio::duplex::MemoryDuplex;
+
+    use crate::{
+        ferret::{self, FerretConfig, LpnType},
+        ideal::{ideal_ot_pair, ideal_random_cot_pair},
+        OTReceiver, OTSender, OTSetup, RandomOTReceiver, RandomOTSender,
+    };
+
+    fn data(count: usize) -> (Vec<bool>, Vec<[Block; 2]>) {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        let choices = (0..count).map(|_| rng.gen()).collect();
+        let data = (0..count).map(|_| [rng.gen(), rng.gen()]).collect();
+
+        (choices, data)
+    }
+
+    fn expected(choices: &[bool], data: &[[Block; 2]]) -> Vec<Block> {
+        data.iter()
+            .zip(choices)
+            .map(|([m0, m1], c)| if *c { *m1 } else { *m0 })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rot_ideal() {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let delta = Block::random(&mut ChaCha12Rng::seed_from_u64(0));
+        let (rcot_sender, rcot_receiver) = ideal_random_cot_pair::<Block>([0u8; 32], delta);
+
+        let mut sender = Sender::new(rcot_sender, delta);
+        let mut receiver = Receiver::new(rcot_receiver);
+
+        let count = 128;
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send_random(&mut sender_sink, &mut sender_stream, count),
+            receiver.receive_random(&mut receiver_sink, &mut receiver_stream, count)
+        );
+
+        let keys = sender_res.unwrap();
+        let (choices, received) = receiver_res.unwrap();
+
+        assert_eq!(received, expected(&choices, &keys));
+
+        let (choices, data) = data(count);
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received: Vec<Block> = receiver_res.unwrap();
+
+        assert_eq!(received, expected(&choices, &data));
+    }
+
+    #[tokio::test]
+    async fn test_rot_vec() {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let delta = Block::random(&mut ChaCha12Rng::seed_from_u64(0));
+        let (rcot_sender, rcot_receiver) = ideal_random_cot_pair::<Block>([0u8; 32], delta);
+
+        let mut sender = Sender::new(rcot_sender, delta);
+        let mut receiver = Receiver::new(rcot_receiver);
+
+        let mut rng = ChaCha12Rng::seed_from_u64(1);
+        let choices = (0..128).map(|_| rng.gen()).collect::<Vec<bool>>();
+        let data: Vec<[Vec<u8>; 2]> = (0..choices.len())
+            .map(|i| {
+                [
+                    (0..8 * i).map(|_| rng.gen()).collect(),
+                    (0..1024).map(|_| rng.gen()).collect(),
+                ]
+            })
+            .collect();
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received: Vec<Vec<u8>> = receiver_res.unwrap();
+
+        let expected = data
+            .into_iter()
+            .zip(&choices)
+            .map(|([m0, m1], c)| if *c { m1 } else { m0 })
+            .collect::<Vec<_>>();
+
+        assert_eq!(received, expected);
+    }
+
+    #[tokio::test]
+    async fn test_rot_ferret() {
+        let (sender_channel, receiver_channel) = MemoryDuplex::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let (base_sender, base_receiver) = ideal_ot_pair();
+
+        let config = FerretConfig::builder()
+            .lpn_parameters(LPN_PARAMETERS_TEST)
+            .lpn_type(LpnType::Regular)
+            .build()
+            .unwrap();
+
+        let mut rcot_sender = ferret::Sender::new(config.clone(), base_sender);
+        let mut rcot_receiver = ferret::Receiver::new(config, base_receiver);
+
+        tokio::join!(
+            async {
+                let mut sink = into_cot_sink(&mut sender_sink);
+                let mut stream = into_cot_stream(&mut sender_stream);
+                rcot_sender.setup(&mut sink, &mut stream).await.unwrap();
+            },
+            async {
+                let mut sink = into_cot_sink(&mut receiver_sink);
+                let mut stream = into_cot_stream(&mut receiver_stream);
+                rcot_receiver.setup(&mut sink, &mut stream).await.unwrap();
+            }
+        );
+
+        let delta = rcot_sender.delta().unwrap();
+
+        let mut sender = Sender::new(rcot_sender, delta);
+        let mut receiver = Receiver::new(rcot_receiver);
+
+        let (choices, data) = data(1000);
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send(&mut sender_sink, &mut sender_stream, &data),
+            receiver.receive(&mut receiver_sink, &mut receiver_stream, &choices)
+        );
+
+        sender_res.unwrap();
+        let received: Vec<Block> = receiver_res.unwrap();
+
+        assert_eq!(received, expected(&choices, &data));
+    }
+}
@@ -0,0 +1,175 @@
+use async_trait::async_trait;
+use futures_util::SinkExt;
+use mpz_core::{Block, ProtocolMessage};
+use mpz_ot_core::rot::{msgs::Message, Receiver as ReceiverCore, ReceiverKeys};
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{into_cot_sink, into_cot_stream};
+use crate::{rot::ReceiverError, OTError, OTReceiver, RandomCOTReceiver, RandomOTReceiver};
+
+/// Random OT receiver.
+///
+/// Derives random OTs from a random COT receiver, eg. [`Ferret`](crate::ferret).
+#[derive(Debug)]
+pub struct Receiver<RandomCOT> {
+    rcot: RandomCOT,
+    core: ReceiverCore,
+}
+
+impl<RandomCOT> Receiver<RandomCOT>
+where
+    RandomCOT: RandomCOTReceiver<bool, Block> + Send,
+{
+    /// Creates a new Receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `rcot` - The random COT receiver, which must already be set up.
+    pub fn new(rcot: RandomCOT) -> Self {
+        Self {
+            rcot,
+            core: ReceiverCore::new(),
+        }
+    }
+
+    /// Returns the random COT receiver.
+    pub fn into_inner(self) -> RandomCOT {
+        self.rcot
+    }
+
+    /// Obtains `count` random COTs and breaks their correlation.
+    async fn keys<
+        Si: IoSink<Message<RandomCOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<RandomCOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<ReceiverKeys, OTError> {
+        let (choices, ts) = self
+            .rcot
+            .receive_random_correlated(
+                &mut into_cot_sink(sink),
+                &mut into_cot_stream(stream),
+                count,
+            )
+            .await?;
+
+        Ok(self.core.keys(choices, &ts).map_err(ReceiverError::from)?)
+    }
+}
+
+impl<RandomCOT> ProtocolMessage for Receiver<RandomCOT>
+where
+    RandomCOT: ProtocolMessage,
+{
+    type Msg = Message<RandomCOT::Msg>;
+}
+
+#[async_trait]
+impl<RandomCOT> RandomOTReceiver<bool, Block> for Receiver<RandomCOT>
+where
+    RandomCOT: RandomCOTReceiver<bool, Block> + Send,
+{
+    async fn receive_random<
+        Si: IoSink<Message<RandomCOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<RandomCOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<(Vec<bool>, Vec<Block>), OTError> {
+        Ok(self
+            .keys(sink, stream, count)
+            .await?
+            .take_choices_and_keys())
+    }
+}
+
+#[async_trait]
+impl<RandomCOT> OTReceiver<bool, Block> for Receiver<RandomCOT>
+where
+    RandomCOT: RandomCOTReceiver<bool, Block> + Send,
+{
+    async fn receive<
+        Si: IoSink<Message<RandomCOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<RandomCOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        choices: &[bool],
+    ) -> Result<Vec<Block>, OTError> {
+        let mut receiver_keys = self.keys(sink, stream, choices.len()).await?;
+
+        let derandomize = receiver_keys
+            .derandomize(choices)
+            .map_err(ReceiverError::from)?;
+
+        // Send derandomize message
+        sink.send(Message::Derandomize(derandomize)).await?;
+
+        // Receive payload
+        let payload = stream
+            .expect_next()
+            .await?
+            .try_into_sender_payload()
+            .map_err(ReceiverError::from)?;
+
+        let received = Backend::spawn(move || {
+            receiver_keys
+                .decrypt_blocks(payload)
+                .map_err(ReceiverError::from)
+        })
+        .await?;
+
+        Ok(received)
+    }
+}
+
+#[async_trait]
+impl<RandomCOT> OTReceiver<bool, Vec<u8>> for Receiver<RandomCOT>
+where
+    RandomCOT: RandomCOTReceiver<bool, Block> + Send,
+{
+    async fn receive<
+        Si: IoSink<Message<RandomCOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<RandomCOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        choices: &[bool],
+    ) -> Result<Vec<Vec<u8>>, OTError> {
+        let mut receiver_keys = self.keys(sink, stream, choices.len()).await?;
+
+        let derandomize = receiver_keys
+            .derandomize(choices)
+            .map_err(ReceiverError::from)?;
+
+        // Send derandomize message
+        sink.send(Message::Derandomize(derandomize)).await?;
+
+        // Receive payload
+        let payload = stream
+            .expect_next()
+            .await?
+            .try_into_sender_payload()
+            .map_err(ReceiverError::from)?;
+
+        let received = Backend::spawn(move || {
+            receiver_keys
+                .decrypt_vec(payload)
+                .map_err(ReceiverError::from)
+        })
+        .await?;
+
+        Ok(received)
+    }
+}
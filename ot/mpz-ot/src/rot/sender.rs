@@ -0,0 +1,167 @@
+use async_trait::async_trait;
+use futures_util::SinkExt;
+use mpz_core::{Block, ProtocolMessage};
+use mpz_ot_core::rot::{msgs::Message, Sender as SenderCore, SenderKeys};
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{into_cot_sink, into_cot_stream};
+use crate::{rot::SenderError, OTError, OTSender, RandomCOTSender, RandomOTSender};
+
+/// Random OT sender.
+///
+/// Derives random OTs from a random COT sender, eg. [`Ferret`](crate::ferret).
+#[derive(Debug)]
+pub struct Sender<RandomCOT> {
+    rcot: RandomCOT,
+    core: SenderCore,
+}
+
+impl<RandomCOT> Sender<RandomCOT>
+where
+    RandomCOT: RandomCOTSender<Block> + Send,
+{
+    /// Creates a new Sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `rcot` - The random COT sender, which must already be set up.
+    /// * `delta` - The global correlation of the random COT sender.
+    pub fn new(rcot: RandomCOT, delta: Block) -> Self {
+        Self {
+            rcot,
+            core: SenderCore::new(delta),
+        }
+    }
+
+    /// Returns the random COT sender.
+    pub fn into_inner(self) -> RandomCOT {
+        self.rcot
+    }
+
+    /// Obtains `count` random COTs and breaks their correlation.
+    async fn keys<
+        Si: IoSink<Message<RandomCOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<RandomCOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<SenderKeys, OTError> {
+        let qs = self
+            .rcot
+            .send_random_correlated(
+                &mut into_cot_sink(sink),
+                &mut into_cot_stream(stream),
+                count,
+            )
+            .await?;
+
+        Ok(self.core.keys(&qs))
+    }
+}
+
+impl<RandomCOT> ProtocolMessage for Sender<RandomCOT>
+where
+    RandomCOT: ProtocolMessage,
+{
+    type Msg = Message<RandomCOT::Msg>;
+}
+
+#[async_trait]
+impl<RandomCOT> RandomOTSender<[Block; 2]> for Sender<RandomCOT>
+where
+    RandomCOT: RandomCOTSender<Block> + Send,
+{
+    async fn send_random<
+        Si: IoSink<Message<RandomCOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<RandomCOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<Vec<[Block; 2]>, OTError> {
+        Ok(self.keys(sink, stream, count).await?.take_keys())
+    }
+}
+
+#[async_trait]
+impl<RandomCOT> OTSender<[Block; 2]> for Sender<RandomCOT>
+where
+    RandomCOT: RandomCOTSender<Block> + Send,
+{
+    async fn send<
+        Si: IoSink<Message<RandomCOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<RandomCOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        msgs: &[[Block; 2]],
+    ) -> Result<(), OTError> {
+        let mut sender_keys = self.keys(sink, stream, msgs.len()).await?;
+
+        let derandomize = stream
+            .expect_next()
+            .await?
+            .try_into_derandomize()
+            .map_err(SenderError::from)?;
+
+        sender_keys
+            .derandomize(derandomize)
+            .map_err(SenderError::from)?;
+        let payload = sender_keys
+            .encrypt_blocks(msgs)
+            .map_err(SenderError::from)?;
+
+        sink.send(Message::SenderPayload(payload))
+            .await
+            .map_err(SenderError::from)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<RandomCOT> OTSender<[Vec<u8>; 2]> for Sender<RandomCOT>
+where
+    RandomCOT: RandomCOTSender<Block> + Send,
+{
+    async fn send<
+        Si: IoSink<Message<RandomCOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<RandomCOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        msgs: &[[Vec<u8>; 2]],
+    ) -> Result<(), OTError> {
+        let mut sender_keys = self.keys(sink, stream, msgs.len()).await?;
+
+        let derandomize = stream
+            .expect_next()
+            .await?
+            .try_into_derandomize()
+            .map_err(SenderError::from)?;
+
+        sender_keys
+            .derandomize(derandomize)
+            .map_err(SenderError::from)?;
+
+        let msgs = msgs.to_vec();
+        let payload =
+            Backend::spawn(move || sender_keys.encrypt_vec(&msgs).map_err(SenderError::from))
+                .await?;
+
+        sink.send(Message::SenderPayload(payload))
+            .await
+            .map_err(SenderError::from)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use mpz_ot_core::vole::msgs::MessageError;
+
+use crate::OTError;
+
+/// A VOLE sender error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::vole::SenderError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<SenderError> for OTError {
+    fn from(err: SenderError) -> Self {
+        match err {
+            SenderError::IOError(e) => e.into(),
+            e => OTError::SenderError(Box::new(e)),
+        }
+    }
+}
+
+impl<T> From<crate::vole::SenderStateError<T>> for SenderError {
+    fn from(err: crate::vole::SenderStateError<T>) -> Self {
+        SenderError::StateError(err.to_string())
+    }
+}
+
+impl<T, BaseMsg> From<MessageError<T, BaseMsg>> for SenderError {
+    fn from(err: MessageError<T, BaseMsg>) -> Self {
+        SenderError::from(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            err.to_string(),
+        ))
+    }
+}
+
+/// A VOLE receiver error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::vole::ReceiverError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<ReceiverError> for OTError {
+    fn from(err: ReceiverError) -> Self {
+        match err {
+            ReceiverError::IOError(e) => e.into(),
+            e => OTError::ReceiverError(Box::new(e)),
+        }
+    }
+}
+
+impl<T> From<crate::vole::ReceiverStateError<T>> for ReceiverError {
+    fn from(err: crate::vole::ReceiverStateError<T>) -> Self {
+        ReceiverError::StateError(err.to_string())
+    }
+}
+
+impl<T, BaseMsg> From<MessageError<T, BaseMsg>> for ReceiverError {
+    fn from(err: MessageError<T, BaseMsg>) -> Self {
+        ReceiverError::from(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            err.to_string(),
+        ))
+    }
+}
@@ -0,0 +1,114 @@
+//! An implementation of random vector oblivious linear evaluation (VOLE) over any field.
+//!
+//! The sender holds a global correlation `Δ` and receives `v`, the receiver receives `u` and
+//! `w = u·Δ + v`. The VOLEs are extended from LPN as in [`Ferret`](crate::ferret), with the base
+//! VOLEs bootstrapped from a chosen-message OT over bytes, eg. [`KOS`](crate::kos).
+//!
+//! # ⚠️ Warning ⚠️
+//!
+//! This implementation is only secure against semi-honest adversaries.
+
+mod error;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+use futures_util::{SinkExt, StreamExt};
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+pub(crate) use receiver::StateError as ReceiverStateError;
+pub(crate) use sender::StateError as SenderStateError;
+
+pub use mpz_ot_core::vole::{msgs, VoleConfig, VoleConfigBuilder, VoleConfigBuilderError};
+use utils_aio::{sink::IoSink, stream::IoStream};
+
+/// Converts a sink of VOLE messages into a sink of base OT messages.
+pub(crate) fn into_base_sink<
+    'a,
+    Si: IoSink<msgs::Message<T, BaseMsg>> + Send + Unpin,
+    T: Send + 'a,
+    BaseMsg: Send + 'a,
+>(
+    sink: &'a mut Si,
+) -> impl IoSink<BaseMsg> + Send + Unpin + 'a {
+    Box::pin(SinkExt::with(sink, |msg| async move {
+        Ok(msgs::Message::BaseMsg(msg))
+    }))
+}
+
+/// Converts a stream of VOLE messages into a stream of base OT messages.
+pub(crate) fn into_base_stream<
+    'a,
+    St: IoStream<msgs::Message<T, BaseMsg>> + Send + Unpin,
+    T: Send + 'a,
+    BaseMsg: Send + 'a,
+>(
+    stream: &'a mut St,
+) -> impl IoStream<BaseMsg> + Send + Unpin + 'a {
+    StreamExt::map(stream, |msg| match msg {
+        Ok(msg) => msg.try_into_base_msg().map_err(From::from),
+        Err(err) => Err(err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256, Field};
+    use mpz_ot_core::{ferret::params::LPN_PARAMETERS_TEST, vole::msgs::Message};
+    use utils_aio::duplex::MemoryDuplex;
+
+    use crate::{ideal::ideal_ot_pair, OTSetup};
+
+    #[rstest]
+    #[case::gf2_128(Gf2_128::zero())]
+    #[case::p256(P256::zero())]
+    #[tokio::test]
+    async fn test_vole<T: Field>(#[case] _field: T) {
+        let (sender_channel, receiver_channel) = MemoryDuplex::<Message<T, ()>>::new();
+
+        let (mut sender_sink, mut sender_stream) = sender_channel.split();
+        let (mut receiver_sink, mut receiver_stream) = receiver_channel.split();
+
+        let config = VoleConfig::builder()
+            .lpn_parameters(LPN_PARAMETERS_TEST)
+            .build()
+            .unwrap();
+
+        let (base_sender, base_receiver) = ideal_ot_pair::<Vec<u8>>();
+
+        let mut sender = Sender::new(config.clone(), base_sender);
+        let mut receiver = Receiver::new(config, base_receiver);
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.setup(&mut sender_sink, &mut sender_stream),
+            receiver.setup(&mut receiver_sink, &mut receiver_stream)
+        );
+
+        sender_res.unwrap();
+        receiver_res.unwrap();
+
+        // More than a single extension.
+        let count = 2 * (LPN_PARAMETERS_TEST.n - LPN_PARAMETERS_TEST.k - LPN_PARAMETERS_TEST.t);
+
+        let (sender_res, receiver_res) = tokio::join!(
+            sender.send_random(&mut sender_sink, &mut sender_stream, count),
+            receiver.receive_random(&mut receiver_sink, &mut receiver_stream, count)
+        );
+
+        let v = sender_res.unwrap();
+        let (u, w) = receiver_res.unwrap();
+        let delta = sender.delta().unwrap();
+
+        assert_eq!(v.len(), count);
+        assert_eq!(u.len(), count);
+        assert!(v
+            .into_iter()
+            .zip(u)
+            .zip(w)
+            .all(|((v, u), w)| w == u * delta + v));
+    }
+}
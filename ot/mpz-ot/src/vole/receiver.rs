@@ -0,0 +1,242 @@
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use futures_util::SinkExt;
+use itybity::ToBits;
+use mpz_core::{Block, ProtocolMessage};
+use mpz_fields::Field;
+use mpz_ot_core::vole::{
+    msgs::{LpnMatrixSeed, Message},
+    receiver_state as state, Receiver as ReceiverCore, VoleConfig,
+};
+use rand::thread_rng;
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{into_base_sink, into_base_stream, ReceiverError};
+use crate::{OTError, OTReceiver, OTSetup};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State<T> {
+    Initialized(ReceiverCore<state::Initialized>),
+    Extension(Box<Extension<T>>),
+    Error,
+}
+
+/// The state of the receiver after setup.
+#[derive(Debug)]
+pub(crate) struct Extension<T> {
+    core: ReceiverCore<state::Extension<T>>,
+    /// Extended VOLEs which have not been consumed yet, `u` and `w` respectively.
+    buffer: (Vec<T>, Vec<T>),
+}
+
+/// VOLE receiver.
+#[derive(Debug)]
+pub struct Receiver<T, BaseOT> {
+    state: State<T>,
+    base: BaseOT,
+}
+
+impl<T, BaseOT> Receiver<T, BaseOT>
+where
+    T: Field,
+    BaseOT: OTReceiver<bool, Vec<u8>> + Send,
+{
+    /// Creates a new Receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Receiver's configuration
+    /// * `base` - The base OT receiver used to bootstrap the VOLEs, eg. KOS.
+    pub fn new(config: VoleConfig, base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(ReceiverCore::new(config)),
+            base,
+        }
+    }
+
+    /// The number of remaining VOLEs which can be consumed without another extension.
+    pub fn remaining(&self) -> Result<usize, ReceiverError> {
+        Ok(self.state.try_as_extension()?.buffer.0.len())
+    }
+
+    /// Performs the setup.
+    ///
+    /// The base OT must already be set up.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender.
+    /// * `stream` - The stream to receive messages from the sender.
+    async fn setup_extension<
+        Si: IoSink<Message<T, BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<T, BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), ReceiverError> {
+        let core = std::mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        // Bootstrap the base VOLEs of the first extension.
+        let (u, choices) = core.base_vole_choices(&mut thread_rng());
+
+        let received = self
+            .base
+            .receive(
+                &mut into_base_sink(sink),
+                &mut into_base_stream(stream),
+                &choices,
+            )
+            .await?;
+
+        let received = received
+            .into_iter()
+            .map(|bytes| T::from_lsb0_iter(bytes.iter_lsb0()))
+            .collect::<Vec<_>>();
+
+        let seed = Block::random(&mut thread_rng());
+        sink.send(Message::LpnMatrixSeed(LpnMatrixSeed { seed }))
+            .await?;
+
+        let core = core.setup(seed, u, &received)?;
+
+        self.state = State::Extension(Box::new(Extension {
+            core,
+            buffer: (Vec::new(), Vec::new()),
+        }));
+
+        Ok(())
+    }
+
+    /// Performs a single extension, adding `n - k - t` VOLEs to the buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender.
+    /// * `stream` - The stream to receive messages from the sender.
+    pub async fn extend<
+        Si: IoSink<Message<T, BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<T, BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), ReceiverError> {
+        let Extension { mut core, buffer } =
+            *std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let (extend, choices) = core.start_extend(&mut thread_rng());
+
+        sink.send(Message::ExtendFromReceiver(extend)).await?;
+
+        let keys = self
+            .base
+            .receive(
+                &mut into_base_sink(sink),
+                &mut into_base_stream(stream),
+                &choices,
+            )
+            .await?;
+
+        let keys = keys
+            .into_iter()
+            .map(|bytes| {
+                Block::try_from(bytes.as_slice())
+                    .map_err(|_| ReceiverError::Other("invalid GGM tree key".to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let extend = stream
+            .expect_next()
+            .await?
+            .try_into_extend_from_sender()
+            .map_err(ReceiverError::from)?;
+
+        let (core, output) = Backend::spawn(move || {
+            let output = core.extend(&keys, extend);
+
+            (core, output)
+        })
+        .await;
+        let (u, w) = output?;
+
+        let (mut buffer_u, mut buffer_w) = buffer;
+        buffer_u.extend(u);
+        buffer_w.extend(w);
+
+        self.state = State::Extension(Box::new(Extension {
+            core,
+            buffer: (buffer_u, buffer_w),
+        }));
+
+        Ok(())
+    }
+
+    /// Receives `count` random VOLEs, returning the receiver's values `u` and `w`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the sender.
+    /// * `stream` - The stream to receive messages from the sender.
+    /// * `count` - The number of VOLEs.
+    pub async fn receive_random<
+        Si: IoSink<Message<T, BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<T, BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<(Vec<T>, Vec<T>), ReceiverError> {
+        // The sender extends in lockstep, as both parties track the same buffer size.
+        while self.remaining()? < count {
+            self.extend(sink, stream).await?;
+        }
+
+        let (u, w) = &mut self.state.try_as_extension_mut()?.buffer;
+
+        Ok((u.drain(..count).collect(), w.drain(..count).collect()))
+    }
+}
+
+impl<T, BaseOT> ProtocolMessage for Receiver<T, BaseOT>
+where
+    T: Field,
+    BaseOT: ProtocolMessage,
+{
+    type Msg = Message<T, BaseOT::Msg>;
+}
+
+#[async_trait]
+impl<T, BaseOT> OTSetup for Receiver<T, BaseOT>
+where
+    T: Field,
+    BaseOT: OTSetup + OTReceiver<bool, Vec<u8>> + Send,
+{
+    async fn setup<
+        Si: IoSink<Message<T, BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<T, BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        // Set up base OT if not already done
+        self.base
+            .setup(&mut into_base_sink(sink), &mut into_base_stream(stream))
+            .await?;
+
+        self.setup_extension(sink, stream)
+            .await
+            .map_err(OTError::from)
+    }
+}
@@ -0,0 +1,240 @@
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use futures_util::SinkExt;
+use mpz_core::ProtocolMessage;
+use mpz_fields::Field;
+use mpz_ot_core::vole::{
+    msgs::{LpnMatrixSeed, Message},
+    sender_state as state, Sender as SenderCore, VoleConfig,
+};
+use rand::thread_rng;
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use super::{into_base_sink, into_base_stream, SenderError};
+use crate::{OTError, OTSender, OTSetup};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State<T> {
+    Initialized(SenderCore<state::Initialized>),
+    Extension(Box<Extension<T>>),
+    Error,
+}
+
+/// The state of the sender after setup.
+#[derive(Debug)]
+pub(crate) struct Extension<T> {
+    core: SenderCore<state::Extension<T>>,
+    /// Extended VOLEs which have not been consumed yet.
+    buffer: Vec<T>,
+}
+
+/// VOLE sender.
+#[derive(Debug)]
+pub struct Sender<T, BaseOT> {
+    state: State<T>,
+    base: BaseOT,
+}
+
+impl<T, BaseOT> Sender<T, BaseOT>
+where
+    T: Field,
+    BaseOT: OTSender<[Vec<u8>; 2]> + Send,
+{
+    /// Creates a new Sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Sender's configuration
+    /// * `base` - The base OT sender used to bootstrap the VOLEs, eg. KOS.
+    pub fn new(config: VoleConfig, base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(SenderCore::new(config)),
+            base,
+        }
+    }
+
+    /// The number of remaining VOLEs which can be consumed without another extension.
+    pub fn remaining(&self) -> Result<usize, SenderError> {
+        Ok(self.state.try_as_extension()?.buffer.len())
+    }
+
+    /// Returns the sender's global correlation.
+    pub fn delta(&self) -> Result<T, SenderError> {
+        Ok(self.state.try_as_extension()?.core.delta())
+    }
+
+    /// Performs the setup with the provided delta.
+    ///
+    /// The base OT must already be set up.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    /// * `delta` - The global correlation of the VOLEs.
+    pub async fn setup_with_delta<
+        Si: IoSink<Message<T, BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<T, BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        delta: T,
+    ) -> Result<(), SenderError> {
+        let core = std::mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        // Bootstrap the base VOLEs of the first extension.
+        let (v, msgs) = core.base_vole(&mut thread_rng(), delta);
+        let msgs = msgs
+            .into_iter()
+            .map(|[zero, one]| [zero.to_le_bytes(), one.to_le_bytes()])
+            .collect::<Vec<_>>();
+
+        self.base
+            .send(
+                &mut into_base_sink(sink),
+                &mut into_base_stream(stream),
+                &msgs,
+            )
+            .await?;
+
+        let LpnMatrixSeed { seed } = stream
+            .expect_next()
+            .await?
+            .try_into_lpn_matrix_seed()
+            .map_err(SenderError::from)?;
+
+        let core = core.setup(delta, seed, v)?;
+
+        self.state = State::Extension(Box::new(Extension {
+            core,
+            buffer: Vec::new(),
+        }));
+
+        Ok(())
+    }
+
+    /// Performs a single extension, adding `n - k - t` VOLEs to the buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    pub async fn extend<
+        Si: IoSink<Message<T, BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<T, BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), SenderError> {
+        let Extension { mut core, buffer } =
+            *std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let extend = stream
+            .expect_next()
+            .await?
+            .try_into_extend_from_receiver()
+            .map_err(SenderError::from)?;
+
+        let (core, output) = Backend::spawn(move || {
+            let output = core.extend(extend);
+
+            (core, output)
+        })
+        .await;
+        let (keys, extend, v) = output?;
+
+        let keys = keys
+            .into_iter()
+            .map(|[zero, one]| [zero.to_bytes().to_vec(), one.to_bytes().to_vec()])
+            .collect::<Vec<_>>();
+
+        self.base
+            .send(
+                &mut into_base_sink(sink),
+                &mut into_base_stream(stream),
+                &keys,
+            )
+            .await?;
+
+        sink.send(Message::ExtendFromSender(extend)).await?;
+
+        let mut buffer = buffer;
+        buffer.extend(v);
+
+        self.state = State::Extension(Box::new(Extension { core, buffer }));
+
+        Ok(())
+    }
+
+    /// Sends `count` random VOLEs, returning the sender's values `v`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the receiver.
+    /// * `stream` - The stream to receive messages from the receiver.
+    /// * `count` - The number of VOLEs.
+    pub async fn send_random<
+        Si: IoSink<Message<T, BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<T, BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        count: usize,
+    ) -> Result<Vec<T>, SenderError> {
+        // The receiver extends in lockstep, as both parties track the same buffer size.
+        while self.remaining()? < count {
+            self.extend(sink, stream).await?;
+        }
+
+        let ext = self.state.try_as_extension_mut()?;
+
+        Ok(ext.buffer.drain(..count).collect())
+    }
+}
+
+impl<T, BaseOT> ProtocolMessage for Sender<T, BaseOT>
+where
+    T: Field,
+    BaseOT: ProtocolMessage,
+{
+    type Msg = Message<T, BaseOT::Msg>;
+}
+
+#[async_trait]
+impl<T, BaseOT> OTSetup for Sender<T, BaseOT>
+where
+    T: Field,
+    BaseOT: OTSetup + OTSender<[Vec<u8>; 2]> + Send,
+{
+    async fn setup<
+        Si: IoSink<Message<T, BaseOT::Msg>> + Send + Unpin,
+        St: IoStream<Message<T, BaseOT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        // Set up base OT if not already done
+        self.base
+            .setup(&mut into_base_sink(sink), &mut into_base_stream(stream))
+            .await?;
+
+        let delta = T::rand(&mut thread_rng());
+
+        self.setup_with_delta(sink, stream, delta)
+            .await
+            .map_err(OTError::from)
+    }
+}
@@ -0,0 +1,23 @@
+use mpz_circuits::CircuitError;
+
+/// Errors that can occur when using the prover.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ProverError {
+    #[error(transparent)]
+    CircuitError(#[from] CircuitError),
+    #[error("count mismatch: expected {0} COTs, got {1}")]
+    CountMismatch(usize, usize),
+}
+
+/// Errors that can occur when using the verifier.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum VerifierError {
+    #[error("count mismatch: expected {0} COTs, got {1}")]
+    CountMismatch(usize, usize),
+    #[error("invalid commitment: {0}")]
+    InvalidCommitment(String),
+    #[error("invalid proof: {0}")]
+    InvalidProof(String),
+}
@@ -0,0 +1,194 @@
+//! Core components of the [`QuickSilver`](https://eprint.iacr.org/2021/076) zero-knowledge
+//! protocol for boolean circuits.
+//!
+//! The prover and the verifier hold random COTs, which are used as information-theoretic MACs
+//! over GF(2^128): for each bit `x` the prover holds a MAC `M`, the verifier holds a key `K` and
+//! the global correlation `Δ` such that `M = K + x·Δ`.
+//!
+//! The prover commits to its inputs and to the output of every AND gate, XOR and inverter gates
+//! are free. The AND gates are then checked all at once with a random linear combination of the
+//! QuickSilver polynomial check, and finally the outputs are opened to the verifier.
+//!
+//! A proof of a circuit consumes [`cot_count`] random COTs.
+
+#![deny(missing_docs, unreachable_pub, unused_must_use)]
+#![deny(unsafe_code)]
+#![deny(clippy::all)]
+
+mod error;
+pub mod msgs;
+mod prover;
+mod verifier;
+
+pub use error::{ProverError, VerifierError};
+pub use prover::{state as prover_state, Prover};
+pub use verifier::{state as verifier_state, Verifier};
+
+use mpz_circuits::Circuit;
+use mpz_core::{hash::Hash, prg::Prg, Block};
+use rand_core::SeedableRng;
+
+/// The number of COTs used to mask the AND gate check.
+const MASK_COUNT: usize = 128;
+
+/// Returns the number of random COTs consumed by a proof of the circuit.
+pub fn cot_count(circ: &Circuit) -> usize {
+    input_count(circ) + circ.and_count() + MASK_COUNT
+}
+
+/// Returns the number of input bits of the circuit.
+fn input_count(circ: &Circuit) -> usize {
+    circ.inputs().iter().map(|input| input.len()).sum()
+}
+
+/// Returns the coefficients of the random linear combination of the AND gate checks.
+fn challenges(seed: Block, count: usize) -> Vec<Block> {
+    let mut chis = vec![Block::ZERO; count];
+    Prg::from_seed(seed).random_blocks(&mut chis);
+    chis
+}
+
+/// Returns the monomial basis of GF(2^128), which packs the masking COTs into a field element.
+fn mask_basis() -> Vec<Block> {
+    (0..MASK_COUNT)
+        .map(|i| Block::from((1u128 << i).to_le_bytes()))
+        .collect()
+}
+
+/// Hashes the MACs of the output bits.
+fn hash_macs(macs: impl IntoIterator<Item = Block>) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    for mac in macs {
+        hasher.update(&mac.to_bytes());
+    }
+    Hash::from(*hasher.finalize().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::{
+        circuits::{AES128, SHA256_COMPRESS},
+        types::Value,
+        Circuit,
+    };
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+    use rstest::*;
+    use std::sync::Arc;
+
+    use crate::msgs::Proof;
+
+    /// Returns `count` random COTs, the verifier's keys and the prover's choices and MACs.
+    fn cots(
+        rng: &mut ChaCha12Rng,
+        delta: Block,
+        count: usize,
+    ) -> (Vec<Block>, Vec<bool>, Vec<Block>) {
+        let keys = (0..count).map(|_| rng.gen::<Block>()).collect::<Vec<_>>();
+        let choices = (0..count).map(|_| rng.gen::<bool>()).collect::<Vec<_>>();
+        let macs = keys
+            .iter()
+            .zip(&choices)
+            .map(|(key, choice)| if *choice { *key ^ delta } else { *key })
+            .collect();
+
+        (keys, choices, macs)
+    }
+
+    fn aes_inputs() -> Vec<Value> {
+        vec![[42u8; 16].into(), [69u8; 16].into()]
+    }
+
+    fn sha256_inputs() -> Vec<Value> {
+        vec![[2u32; 8].into(), [1u8; 64].into()]
+    }
+
+    /// Runs the protocol, allowing the proof to be tampered with before it is verified.
+    fn prove_and_verify(
+        circ: Arc<Circuit>,
+        inputs: &[Value],
+        tamper: impl FnOnce(&mut Proof),
+    ) -> Result<Vec<Value>, VerifierError> {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let delta = rng.gen::<Block>();
+        let (keys, choices, macs) = cots(&mut rng, delta, cot_count(&circ));
+
+        let prover = Prover::new(circ.clone());
+        let verifier = Verifier::new(circ, delta);
+
+        let (prover, commitment) = prover.commit(inputs, &choices, &macs).unwrap();
+        let (verifier, challenge) = verifier
+            .commit(&keys, commitment, rng.gen::<Block>())
+            .unwrap();
+
+        let (_, mut proof) = prover.prove(challenge);
+        tamper(&mut proof);
+
+        verifier.verify(proof)
+    }
+
+    #[rstest]
+    #[case::aes(AES128.clone(), aes_inputs())]
+    #[case::sha256(SHA256_COMPRESS.clone(), sha256_inputs())]
+    fn test_quicksilver(#[case] circ: Arc<Circuit>, #[case] inputs: Vec<Value>) {
+        let expected = circ.evaluate(&inputs).unwrap();
+
+        let outputs = prove_and_verify(circ, &inputs, |_| {}).unwrap();
+
+        assert_eq!(outputs, expected);
+    }
+
+    #[test]
+    fn test_quicksilver_invalid_output() {
+        let err = prove_and_verify(AES128.clone(), &aes_inputs(), |proof| {
+            proof.outputs[0] = !proof.outputs[0]
+        })
+        .unwrap_err();
+
+        assert!(matches!(err, VerifierError::InvalidProof(_)));
+    }
+
+    #[test]
+    fn test_quicksilver_invalid_and_gate() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let delta = rng.gen::<Block>();
+        let circ = AES128.clone();
+        let (keys, choices, macs) = cots(&mut rng, delta, cot_count(&circ));
+
+        let prover = Prover::new(circ.clone());
+        let verifier = Verifier::new(circ, delta);
+
+        let (prover, mut commitment) = prover.commit(&aes_inputs(), &choices, &macs).unwrap();
+
+        // The prover lies about the output of an AND gate.
+        commitment.ands[0] = !commitment.ands[0];
+
+        let (verifier, challenge) = verifier
+            .commit(&keys, commitment, rng.gen::<Block>())
+            .unwrap();
+
+        let (_, proof) = prover.prove(challenge);
+
+        let err = verifier.verify(proof).unwrap_err();
+
+        assert!(matches!(err, VerifierError::InvalidProof(_)));
+    }
+
+    #[test]
+    fn test_quicksilver_cot_count_mismatch() {
+        let circ = AES128.clone();
+        let count = cot_count(&circ);
+
+        let err = Prover::new(circ.clone())
+            .commit(
+                &aes_inputs(),
+                &vec![false; count - 1],
+                &vec![Block::ZERO; count - 1],
+            )
+            .unwrap_err();
+
+        assert!(matches!(err, ProverError::CountMismatch(_, _)));
+    }
+}
@@ -0,0 +1,53 @@
+//! Messages for the QuickSilver protocol.
+
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_core::{hash::Hash, Block};
+use serde::{Deserialize, Serialize};
+
+/// A QuickSilver protocol message.
+#[derive(Debug, Clone, EnumTryAsInner, Serialize, Deserialize)]
+#[derive_err(Debug)]
+#[allow(missing_docs)]
+pub enum Message<CotMsg> {
+    CotMsg(CotMsg),
+    Commitment(Commitment),
+    Challenge(Challenge),
+    Proof(Proof),
+}
+
+impl<CotMsg> From<MessageError<CotMsg>> for std::io::Error {
+    fn from(err: MessageError<CotMsg>) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+/// The prover's commitment to the circuit inputs and to the outputs of the AND gates.
+///
+/// Each bit is masked with the choice bit of a random COT.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Commitment {
+    /// The masked input bits.
+    pub inputs: Vec<bool>,
+    /// The masked outputs of the AND gates.
+    pub ands: Vec<bool>,
+}
+
+/// The verifier's challenge, sent after receiving the commitment.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Challenge {
+    /// The seed of the random linear combination of the AND gate checks.
+    pub seed: Block,
+}
+
+/// The prover's proof.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Proof {
+    /// The constant coefficient of the combined AND gate check.
+    pub u: Block,
+    /// The linear coefficient of the combined AND gate check.
+    pub v: Block,
+    /// The output bits of the circuit.
+    pub outputs: Vec<bool>,
+    /// The hash of the MACs of the output bits.
+    pub output_macs: Hash,
+}
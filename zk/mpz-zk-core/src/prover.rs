@@ -0,0 +1,260 @@
+use std::sync::Arc;
+
+use itybity::IntoBits;
+use mpz_circuits::{
+    types::{TypeError, Value},
+    Circuit, CircuitError, Gate,
+};
+use mpz_core::Block;
+
+use crate::{
+    challenges, cot_count, hash_macs, mask_basis,
+    msgs::{Challenge, Commitment, Proof},
+    ProverError, MASK_COUNT,
+};
+
+/// QuickSilver prover.
+#[derive(Debug)]
+pub struct Prover<S: state::State = state::Initialized> {
+    circ: Arc<Circuit>,
+    state: S,
+}
+
+impl Prover {
+    /// Creates a new prover.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to prove.
+    pub fn new(circ: Arc<Circuit>) -> Self {
+        Self {
+            circ,
+            state: state::Initialized {},
+        }
+    }
+
+    /// Evaluates the circuit, committing to the inputs and to the outputs of the AND gates.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The inputs of the circuit.
+    /// * `choices` - The choice bits of the random COTs, see [`cot_count`].
+    /// * `macs` - The messages received with the random COTs.
+    pub fn commit(
+        self,
+        inputs: &[Value],
+        choices: &[bool],
+        macs: &[Block],
+    ) -> Result<(Prover<state::Committed>, Commitment), ProverError> {
+        let circ = self.circ;
+
+        let count = cot_count(&circ);
+        if choices.len() != count || macs.len() != count {
+            return Err(ProverError::CountMismatch(
+                count,
+                choices.len().min(macs.len()),
+            ));
+        }
+
+        if inputs.len() != circ.inputs().len() {
+            return Err(CircuitError::InvalidInputCount(
+                circ.inputs().len(),
+                inputs.len(),
+            ))?;
+        }
+
+        let mut cots = choices.iter().copied().zip(macs.iter().copied());
+        let mut bits = vec![false; circ.feed_count()];
+        let mut wire_macs = vec![Block::ZERO; circ.feed_count()];
+
+        let mut masked_inputs = Vec::with_capacity(count);
+        for (input, value) in circ.inputs().iter().zip(inputs) {
+            if input.value_type() != value.value_type() {
+                return Err(CircuitError::from(TypeError::UnexpectedType {
+                    expected: input.value_type(),
+                    actual: value.value_type(),
+                }))?;
+            }
+
+            for (node, bit) in input.iter().zip(value.clone().into_iter_lsb0()) {
+                let (choice, mac) = cots.next().expect("COT count was checked");
+                bits[node.id()] = bit;
+                wire_macs[node.id()] = mac;
+                masked_inputs.push(bit ^ choice);
+            }
+        }
+
+        let and_count = circ.and_count();
+        let mut masked_ands = Vec::with_capacity(and_count);
+        let mut a0 = Vec::with_capacity(and_count);
+        let mut a1 = Vec::with_capacity(and_count);
+        for gate in circ.gates() {
+            match gate {
+                Gate::Xor { x, y, z } => {
+                    bits[z.id()] = bits[x.id()] ^ bits[y.id()];
+                    wire_macs[z.id()] = wire_macs[x.id()] ^ wire_macs[y.id()];
+                }
+                Gate::And { x, y, z } => {
+                    let (x, mx) = (bits[x.id()], wire_macs[x.id()]);
+                    let (y, my) = (bits[y.id()], wire_macs[y.id()]);
+                    let (choice, mz) = cots.next().expect("COT count was checked");
+
+                    let bit = x & y;
+                    bits[z.id()] = bit;
+                    wire_macs[z.id()] = mz;
+                    masked_ands.push(bit ^ choice);
+
+                    // K_x·K_y + K_z·Δ = M_x·M_y + (x·M_y + y·M_x + M_z)·Δ
+                    a0.push(mx.gfmul(my));
+                    a1.push(select(y, mx) ^ select(x, my) ^ mz);
+                }
+                Gate::Inv { x, z } => {
+                    bits[z.id()] = !bits[x.id()];
+                    wire_macs[z.id()] = wire_macs[x.id()];
+                }
+            }
+        }
+
+        // Masks the AND gate check with a random field element committed bit by bit.
+        let (mask_choices, mask_macs): (Vec<bool>, Vec<Block>) = cots.unzip();
+        debug_assert_eq!(mask_choices.len(), MASK_COUNT);
+
+        let basis = mask_basis();
+        let mask = (
+            Block::inn_prdt_red(&mask_macs, &basis),
+            mask_choices
+                .into_iter()
+                .zip(&basis)
+                .fold(Block::ZERO, |acc, (choice, b)| acc ^ select(choice, *b)),
+        );
+
+        Ok((
+            Prover {
+                circ,
+                state: state::Committed {
+                    bits,
+                    macs: wire_macs,
+                    a0,
+                    a1,
+                    mask,
+                },
+            },
+            Commitment {
+                inputs: masked_inputs,
+                ands: masked_ands,
+            },
+        ))
+    }
+}
+
+impl Prover<state::Committed> {
+    /// Proves the AND gates and opens the outputs, returning the outputs of the circuit and the
+    /// proof.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - The verifier's challenge.
+    pub fn prove(self, challenge: Challenge) -> (Vec<Value>, Proof) {
+        let state::Committed {
+            bits,
+            macs,
+            a0,
+            a1,
+            mask,
+        } = self.state;
+
+        let chis = challenges(challenge.seed, a0.len());
+        let u = Block::inn_prdt_red(&chis, &a0) ^ mask.0;
+        let v = Block::inn_prdt_red(&chis, &a1) ^ mask.1;
+
+        let outputs = self
+            .circ
+            .outputs()
+            .iter()
+            .map(|output| {
+                let bits = output
+                    .iter()
+                    .map(|node| bits[node.id()])
+                    .collect::<Vec<_>>();
+
+                output
+                    .from_bin_repr(&bits)
+                    .expect("Output should be decodable")
+            })
+            .collect();
+
+        let output_bits = self
+            .circ
+            .outputs()
+            .iter()
+            .flat_map(|output| output.iter().map(|node| bits[node.id()]))
+            .collect();
+
+        let output_macs = hash_macs(
+            self.circ
+                .outputs()
+                .iter()
+                .flat_map(|output| output.iter().map(|node| macs[node.id()])),
+        );
+
+        (
+            outputs,
+            Proof {
+                u,
+                v,
+                outputs: output_bits,
+                output_macs,
+            },
+        )
+    }
+}
+
+/// Returns `block` if `bit` is set, otherwise zero.
+#[inline]
+fn select(bit: bool, block: Block) -> Block {
+    if bit {
+        block
+    } else {
+        Block::ZERO
+    }
+}
+
+/// The prover's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Committed {}
+    }
+
+    /// The prover's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The prover's initial state.
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The prover's state after committing to the circuit.
+    pub struct Committed {
+        /// The value of each wire.
+        pub(super) bits: Vec<bool>,
+        /// The MAC of each wire.
+        pub(super) macs: Vec<Block>,
+        /// The constant coefficients of the AND gate checks.
+        pub(super) a0: Vec<Block>,
+        /// The linear coefficients of the AND gate checks.
+        pub(super) a1: Vec<Block>,
+        /// The constant and linear coefficients of the mask.
+        pub(super) mask: (Block, Block),
+    }
+
+    impl State for Committed {}
+
+    opaque_debug::implement!(Committed);
+}
@@ -0,0 +1,250 @@
+use std::sync::Arc;
+
+use mpz_circuits::{types::Value, Circuit, Gate};
+use mpz_core::Block;
+
+use crate::{
+    challenges, cot_count, hash_macs, input_count, mask_basis,
+    msgs::{Challenge, Commitment, Proof},
+    VerifierError, MASK_COUNT,
+};
+
+/// QuickSilver verifier.
+#[derive(Debug)]
+pub struct Verifier<S: state::State = state::Initialized> {
+    circ: Arc<Circuit>,
+    delta: Block,
+    state: S,
+}
+
+impl Verifier {
+    /// Creates a new verifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to verify.
+    /// * `delta` - The global correlation of the random COTs.
+    pub fn new(circ: Arc<Circuit>, delta: Block) -> Self {
+        Self {
+            circ,
+            delta,
+            state: state::Initialized {},
+        }
+    }
+
+    /// Receives the prover's commitment, returning the challenge.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The `0`-bit messages of the random COTs, see [`cot_count`].
+    /// * `commitment` - The prover's commitment.
+    /// * `seed` - A random seed from which the challenge is derived.
+    pub fn commit(
+        self,
+        keys: &[Block],
+        commitment: Commitment,
+        seed: Block,
+    ) -> Result<(Verifier<state::Committed>, Challenge), VerifierError> {
+        let Self { circ, delta, .. } = self;
+
+        let count = cot_count(&circ);
+        if keys.len() != count {
+            return Err(VerifierError::CountMismatch(count, keys.len()));
+        }
+
+        let Commitment {
+            inputs: masked_inputs,
+            ands: masked_ands,
+        } = commitment;
+
+        if masked_inputs.len() != input_count(&circ) {
+            return Err(VerifierError::InvalidCommitment(format!(
+                "expected {} input bits, got {}",
+                input_count(&circ),
+                masked_inputs.len()
+            )));
+        }
+
+        if masked_ands.len() != circ.and_count() {
+            return Err(VerifierError::InvalidCommitment(format!(
+                "expected {} AND gates, got {}",
+                circ.and_count(),
+                masked_ands.len()
+            )));
+        }
+
+        let correct = |key: Block, masked: bool| if masked { key ^ delta } else { key };
+
+        let mut cot_keys = keys.iter().copied();
+        let mut wire_keys = vec![Block::ZERO; circ.feed_count()];
+
+        let mut masked_inputs = masked_inputs.into_iter();
+        for node in circ.inputs().iter().flat_map(|input| input.iter()) {
+            let key = cot_keys.next().expect("COT count was checked");
+            let masked = masked_inputs.next().expect("input count was checked");
+            wire_keys[node.id()] = correct(key, masked);
+        }
+
+        let and_count = circ.and_count();
+        let mut masked_ands = masked_ands.into_iter();
+        let mut b0 = Vec::with_capacity(and_count);
+        let mut b1 = Vec::with_capacity(and_count);
+        for gate in circ.gates() {
+            match gate {
+                Gate::Xor { x, y, z } => {
+                    wire_keys[z.id()] = wire_keys[x.id()] ^ wire_keys[y.id()];
+                }
+                Gate::And { x, y, z } => {
+                    let key = cot_keys.next().expect("COT count was checked");
+                    let masked = masked_ands.next().expect("AND gate count was checked");
+                    let kz = correct(key, masked);
+                    wire_keys[z.id()] = kz;
+
+                    b0.push(wire_keys[x.id()].gfmul(wire_keys[y.id()]));
+                    b1.push(kz);
+                }
+                Gate::Inv { x, z } => {
+                    wire_keys[z.id()] = wire_keys[x.id()] ^ delta;
+                }
+            }
+        }
+
+        let mask_keys = cot_keys.collect::<Vec<_>>();
+        debug_assert_eq!(mask_keys.len(), MASK_COUNT);
+
+        let mask = Block::inn_prdt_red(&mask_keys, &mask_basis());
+
+        Ok((
+            Verifier {
+                circ,
+                delta,
+                state: state::Committed {
+                    keys: wire_keys,
+                    b0,
+                    b1,
+                    mask,
+                    seed,
+                },
+            },
+            Challenge { seed },
+        ))
+    }
+}
+
+impl Verifier<state::Committed> {
+    /// Verifies the proof, returning the outputs of the circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `proof` - The prover's proof.
+    pub fn verify(self, proof: Proof) -> Result<Vec<Value>, VerifierError> {
+        let Self { circ, delta, state } = self;
+        let state::Committed {
+            keys,
+            b0,
+            b1,
+            mask,
+            seed,
+        } = state;
+
+        let Proof {
+            u,
+            v,
+            outputs,
+            output_macs,
+        } = proof;
+
+        // Σ χ_i·(K_x·K_y + K_z·Δ) + K* = U + V·Δ
+        let chis = challenges(seed, b0.len());
+        let w =
+            Block::inn_prdt_red(&chis, &b0) ^ Block::inn_prdt_red(&chis, &b1).gfmul(delta) ^ mask;
+
+        if w != u ^ v.gfmul(delta) {
+            return Err(VerifierError::InvalidProof(
+                "AND gate check failed".to_string(),
+            ));
+        }
+
+        let output_count = circ.outputs().iter().map(|output| output.len()).sum();
+        if outputs.len() != output_count {
+            return Err(VerifierError::InvalidProof(format!(
+                "expected {} output bits, got {}",
+                output_count,
+                outputs.len()
+            )));
+        }
+
+        let expected_macs = circ
+            .outputs()
+            .iter()
+            .flat_map(|output| output.iter())
+            .zip(&outputs)
+            .map(|(node, bit)| {
+                let key = keys[node.id()];
+                if *bit {
+                    key ^ delta
+                } else {
+                    key
+                }
+            });
+
+        if hash_macs(expected_macs) != output_macs {
+            return Err(VerifierError::InvalidProof(
+                "output MACs are invalid".to_string(),
+            ));
+        }
+
+        let mut outputs = outputs.as_slice();
+        circ.outputs()
+            .iter()
+            .map(|output| {
+                let (bits, rest) = outputs.split_at(output.len());
+                outputs = rest;
+
+                output
+                    .from_bin_repr(bits)
+                    .map_err(|err| VerifierError::InvalidProof(err.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// The verifier's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Committed {}
+    }
+
+    /// The verifier's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The verifier's initial state.
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The verifier's state after receiving the commitment.
+    pub struct Committed {
+        /// The key of each wire.
+        pub(super) keys: Vec<Block>,
+        /// The products of the input keys of each AND gate.
+        pub(super) b0: Vec<Block>,
+        /// The output key of each AND gate.
+        pub(super) b1: Vec<Block>,
+        /// The key of the mask.
+        pub(super) mask: Block,
+        /// The seed of the challenge.
+        pub(super) seed: Block,
+    }
+
+    impl State for Committed {}
+
+    opaque_debug::implement!(Committed);
+}
@@ -0,0 +1,37 @@
+use mpz_zk_core::msgs::MessageError;
+
+/// A QuickSilver prover error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ProverError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_zk_core::ProverError),
+    #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+}
+
+impl<T> From<MessageError<T>> for ProverError {
+    fn from(err: MessageError<T>) -> Self {
+        ProverError::from(std::io::Error::from(err))
+    }
+}
+
+/// A QuickSilver verifier error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum VerifierError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_zk_core::VerifierError),
+    #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+}
+
+impl<T> From<MessageError<T>> for VerifierError {
+    fn from(err: MessageError<T>) -> Self {
+        VerifierError::from(std::io::Error::from(err))
+    }
+}
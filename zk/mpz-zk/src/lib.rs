@@ -0,0 +1,126 @@
+//! An implementation of the [`QuickSilver`](https://eprint.iacr.org/2021/076) zero-knowledge
+//! protocol for boolean circuits.
+//!
+//! The [`Prover`] convinces the [`Verifier`] that it knows inputs which evaluate a circuit to the
+//! revealed outputs. The bits of the prover are authenticated with random COTs, eg. from
+//! [`Ferret`](mpz_ot::ferret) or [`KOS`](mpz_ot::kos), which costs roughly one bit of
+//! communication per input bit and AND gate instead of a garbled table.
+//!
+//! The verifier must know the global correlation of its random COT sender.
+
+#![deny(missing_docs, unreachable_pub, unused_must_use)]
+#![deny(unsafe_code)]
+#![deny(clippy::all)]
+
+mod error;
+mod prover;
+mod verifier;
+
+pub use error::{ProverError, VerifierError};
+pub use mpz_zk_core::{cot_count, msgs};
+pub use prover::Prover;
+pub use verifier::Verifier;
+
+use futures_util::{SinkExt, StreamExt};
+use utils_aio::{sink::IoSink, stream::IoStream};
+
+/// Converts a sink of QuickSilver messages into a sink of COT messages.
+pub(crate) fn into_cot_sink<'a, Si: IoSink<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    sink: &'a mut Si,
+) -> impl IoSink<T> + Send + Unpin + 'a {
+    Box::pin(SinkExt::with(sink, |msg| async move {
+        Ok(msgs::Message::CotMsg(msg))
+    }))
+}
+
+/// Converts a stream of QuickSilver messages into a stream of COT messages.
+pub(crate) fn into_cot_stream<'a, St: IoStream<msgs::Message<T>> + Send + Unpin, T: Send + 'a>(
+    stream: &'a mut St,
+) -> impl IoStream<T> + Send + Unpin + 'a {
+    StreamExt::map(stream, |msg| match msg {
+        Ok(msg) => msg.try_into_cot_msg().map_err(From::from),
+        Err(err) => Err(err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use mpz_circuits::{
+        circuits::{AES128, SHA256_COMPRESS},
+        types::Value,
+        Circuit,
+    };
+    use mpz_core::Block;
+    use mpz_ot::ideal::ideal_random_cot_pair;
+    use std::sync::Arc;
+    use utils_aio::duplex::MemoryDuplex;
+
+    #[rstest]
+    #[case::aes(AES128.clone(), vec![[42u8; 16].into(), [69u8; 16].into()])]
+    #[case::sha256(SHA256_COMPRESS.clone(), vec![[2u32; 8].into(), [1u8; 64].into()])]
+    #[tokio::test]
+    async fn test_quicksilver(#[case] circ: Arc<Circuit>, #[case] inputs: Vec<Value>) {
+        let (prover_channel, verifier_channel) = MemoryDuplex::new();
+
+        let (mut prover_sink, mut prover_stream) = prover_channel.split();
+        let (mut verifier_sink, mut verifier_stream) = verifier_channel.split();
+
+        let delta = Block::random(&mut rand::thread_rng());
+        let (cot_sender, cot_receiver) = ideal_random_cot_pair::<Block>([0u8; 32], delta);
+
+        let mut prover = Prover::new(cot_receiver);
+        let mut verifier = Verifier::new(cot_sender, delta);
+
+        let (prover_res, verifier_res) = tokio::join!(
+            prover.setup(&mut prover_sink, &mut prover_stream),
+            verifier.setup(&mut verifier_sink, &mut verifier_stream)
+        );
+
+        prover_res.unwrap();
+        verifier_res.unwrap();
+
+        let expected = circ.evaluate(&inputs).unwrap();
+
+        let (prover_res, verifier_res) = tokio::join!(
+            prover.prove(&mut prover_sink, &mut prover_stream, circ.clone(), &inputs),
+            verifier.verify(&mut verifier_sink, &mut verifier_stream, circ)
+        );
+
+        assert_eq!(prover_res.unwrap(), expected);
+        assert_eq!(verifier_res.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_quicksilver_wrong_delta() {
+        let (prover_channel, verifier_channel) = MemoryDuplex::new();
+
+        let (mut prover_sink, mut prover_stream) = prover_channel.split();
+        let (mut verifier_sink, mut verifier_stream) = verifier_channel.split();
+
+        let delta = Block::random(&mut rand::thread_rng());
+        let (cot_sender, cot_receiver) = ideal_random_cot_pair::<Block>([0u8; 32], delta);
+
+        let mut prover = Prover::new(cot_receiver);
+        let mut verifier = Verifier::new(cot_sender, delta ^ Block::ONES);
+
+        let inputs: Vec<Value> = vec![[42u8; 16].into(), [69u8; 16].into()];
+        let (prover_res, verifier_res) = tokio::join!(
+            prover.prove(
+                &mut prover_sink,
+                &mut prover_stream,
+                AES128.clone(),
+                &inputs
+            ),
+            verifier.verify(&mut verifier_sink, &mut verifier_stream, AES128.clone())
+        );
+
+        prover_res.unwrap();
+        assert!(matches!(
+            verifier_res.unwrap_err(),
+            VerifierError::CoreError(mpz_zk_core::VerifierError::InvalidProof(_))
+        ));
+    }
+}
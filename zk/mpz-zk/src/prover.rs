@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use futures_util::SinkExt;
+use mpz_circuits::{types::Value, Circuit};
+use mpz_core::Block;
+use mpz_ot::{OTSetup, RandomCOTReceiver};
+use mpz_zk_core::{cot_count, msgs::Message, Prover as ProverCore};
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use crate::{into_cot_sink, into_cot_stream, ProverError};
+
+/// QuickSilver prover.
+#[derive(Debug)]
+pub struct Prover<COT> {
+    cot: COT,
+}
+
+impl<COT> Prover<COT>
+where
+    COT: RandomCOTReceiver<bool, Block> + Send,
+{
+    /// Creates a new prover.
+    ///
+    /// # Arguments
+    ///
+    /// * `cot` - The random COT receiver used to authenticate the prover's bits, eg. Ferret.
+    pub fn new(cot: COT) -> Self {
+        Self { cot }
+    }
+
+    /// Sets up the random COT receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the verifier.
+    /// * `stream` - The stream to receive messages from the verifier.
+    pub async fn setup<
+        Si: IoSink<Message<COT::Msg>> + Send + Unpin,
+        St: IoStream<Message<COT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), ProverError>
+    where
+        COT: OTSetup,
+    {
+        self.cot
+            .setup(&mut into_cot_sink(sink), &mut into_cot_stream(stream))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Proves the evaluation of the circuit on the provided inputs, returning the outputs which
+    /// are revealed to the verifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the verifier.
+    /// * `stream` - The stream to receive messages from the verifier.
+    /// * `circ` - The circuit to prove.
+    /// * `inputs` - The inputs of the circuit, which remain private.
+    pub async fn prove<
+        Si: IoSink<Message<COT::Msg>> + Send + Unpin,
+        St: IoStream<Message<COT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        circ: Arc<Circuit>,
+        inputs: &[Value],
+    ) -> Result<Vec<Value>, ProverError> {
+        let (choices, macs) = self
+            .cot
+            .receive_random_correlated(
+                &mut into_cot_sink(sink),
+                &mut into_cot_stream(stream),
+                cot_count(&circ),
+            )
+            .await?;
+
+        let inputs = inputs.to_vec();
+        let (prover, commitment) =
+            Backend::spawn(move || ProverCore::new(circ).commit(&inputs, &choices, &macs)).await?;
+
+        sink.send(Message::Commitment(commitment)).await?;
+
+        let challenge = stream
+            .expect_next()
+            .await?
+            .try_into_challenge()
+            .map_err(ProverError::from)?;
+
+        let (outputs, proof) = Backend::spawn(move || prover.prove(challenge)).await;
+
+        sink.send(Message::Proof(proof)).await?;
+
+        Ok(outputs)
+    }
+}
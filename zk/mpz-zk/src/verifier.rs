@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use futures_util::SinkExt;
+use mpz_circuits::{types::Value, Circuit};
+use mpz_core::Block;
+use mpz_ot::{OTSetup, RandomCOTSender};
+use mpz_zk_core::{cot_count, msgs::Message, Verifier as VerifierCore};
+use rand::thread_rng;
+use utils_aio::{
+    non_blocking_backend::{Backend, NonBlockingBackend},
+    sink::IoSink,
+    stream::{ExpectStreamExt, IoStream},
+};
+
+use crate::{into_cot_sink, into_cot_stream, VerifierError};
+
+/// QuickSilver verifier.
+#[derive(Debug)]
+pub struct Verifier<COT> {
+    cot: COT,
+    delta: Block,
+}
+
+impl<COT> Verifier<COT>
+where
+    COT: RandomCOTSender<Block> + Send,
+{
+    /// Creates a new verifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `cot` - The random COT sender used to authenticate the prover's bits, eg. Ferret.
+    /// * `delta` - The global correlation of the random COT sender.
+    pub fn new(cot: COT, delta: Block) -> Self {
+        Self { cot, delta }
+    }
+
+    /// Sets up the random COT sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the prover.
+    /// * `stream` - The stream to receive messages from the prover.
+    pub async fn setup<
+        Si: IoSink<Message<COT::Msg>> + Send + Unpin,
+        St: IoStream<Message<COT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+    ) -> Result<(), VerifierError>
+    where
+        COT: OTSetup,
+    {
+        self.cot
+            .setup(&mut into_cot_sink(sink), &mut into_cot_stream(stream))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Verifies the evaluation of the circuit by the prover, returning the outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to send messages to the prover.
+    /// * `stream` - The stream to receive messages from the prover.
+    /// * `circ` - The circuit to verify.
+    pub async fn verify<
+        Si: IoSink<Message<COT::Msg>> + Send + Unpin,
+        St: IoStream<Message<COT::Msg>> + Send + Unpin,
+    >(
+        &mut self,
+        sink: &mut Si,
+        stream: &mut St,
+        circ: Arc<Circuit>,
+    ) -> Result<Vec<Value>, VerifierError> {
+        let keys = self
+            .cot
+            .send_random_correlated(
+                &mut into_cot_sink(sink),
+                &mut into_cot_stream(stream),
+                cot_count(&circ),
+            )
+            .await?;
+
+        let commitment = stream
+            .expect_next()
+            .await?
+            .try_into_commitment()
+            .map_err(VerifierError::from)?;
+
+        let delta = self.delta;
+        let seed = Block::random(&mut thread_rng());
+        let (verifier, challenge) =
+            Backend::spawn(move || VerifierCore::new(circ, delta).commit(&keys, commitment, seed))
+                .await?;
+
+        sink.send(Message::Challenge(challenge)).await?;
+
+        let proof = stream
+            .expect_next()
+            .await?
+            .try_into_proof()
+            .map_err(VerifierError::from)?;
+
+        let outputs = Backend::spawn(move || verifier.verify(proof)).await?;
+
+        Ok(outputs)
+    }
+}